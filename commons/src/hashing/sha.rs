@@ -1,5 +1,7 @@
+use std::fmt::Write;
+
 use hmac::{Hmac, Mac};
-use sha2::{Digest, Sha512};
+use sha2::{Digest, Sha256, Sha512};
 
 use crate::errors::RustyError;
 
@@ -45,3 +47,81 @@ pub fn sha512(text: &str) -> String {
         acc
     })
 }
+
+/// Compute the SHA-256 hash of a text.
+///
+/// # Arguments
+///
+/// * `text` - The text to be hashed.
+///
+/// # Returns
+///
+/// A hexadecimal string representation of the SHA-256 hash.
+#[must_use]
+pub fn sha256(text: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(text);
+    to_hex(&hasher.finalize())
+}
+
+/// Compute the HMAC-SHA256 signature of a payload.
+///
+/// # Arguments
+///
+/// * `key` - The secret key used for signing.
+/// * `payload` - The payload to be signed.
+///
+/// # Returns
+///
+/// A hexadecimal string representation of the HMAC-SHA256 signature.
+///
+/// # Errors
+///
+/// This function can generate the following errors:
+///
+/// * `RustyError` - If the key could not be used for signing.
+pub fn hmac256(key: &str, payload: &[u8]) -> Result<String, RustyError> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes())
+        .map_err(|err| RustyError::HashingError(err.to_string()))?;
+    mac.update(payload);
+    Ok(to_hex(&mac.finalize().into_bytes()))
+}
+
+/// Verify the HMAC-SHA256 signature of a payload in constant time.
+///
+/// # Arguments
+///
+/// * `key` - The secret key used for signing.
+/// * `payload` - The signed payload.
+/// * `signature` - A hexadecimal string representation of the expected signature.
+///
+/// # Returns
+///
+/// `true` if the signature matches the payload, `false` otherwise.
+#[must_use]
+pub fn verify_hmac256(key: &str, payload: &[u8], signature: &str) -> bool {
+    let Some(signature) = from_hex(signature) else {
+        return false;
+    };
+    Hmac::<Sha256>::new_from_slice(key.as_bytes()).is_ok_and(|mut mac| {
+        mac.update(payload);
+        mac.verify_slice(&signature).is_ok()
+    })
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut acc, &value| {
+        let _ = write!(acc, "{value:02x}");
+        acc
+    })
+}
+
+fn from_hex(text: &str) -> Option<Vec<u8>> {
+    if text.len() % 2 != 0 || !text.is_ascii() {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).ok())
+        .collect()
+}
@@ -0,0 +1,71 @@
+use async_graphql::{InputObject, SimpleObject};
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+
+use crate::RustyDomainItem;
+
+/// A struct representing the badge settings of a job.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct BadgeSettings {
+    /// settings id
+    pub id: String,
+    /// badges served without authentication
+    pub public: bool,
+    /// settings job id
+    #[serde(rename(deserialize = "jobId", deserialize = "job_id"))]
+    pub job_id: String,
+    /// job project id
+    #[serde(rename(deserialize = "projectId", deserialize = "project_id"))]
+    pub project_id: String,
+}
+
+/// A struct representing the registration of job badge settings.
+#[derive(Clone, Debug, InputObject, Serialize, Deserialize, Validate)]
+pub struct RegisterBadgeSettings {
+    /// badges served without authentication
+    pub public: bool,
+    /// settings job id
+    #[serde(rename(deserialize = "jobId", deserialize = "job_id"))]
+    #[validate(min_length = 36)]
+    #[validate(max_length = 36)]
+    pub job_id: String,
+}
+
+impl RegisterBadgeSettings {
+    /// constructor
+    #[must_use]
+    pub fn new(job_id: &str, public: bool) -> Self {
+        Self {
+            public,
+            job_id: job_id.to_string(),
+        }
+    }
+}
+
+impl BadgeSettings {
+    /// Badge settings of a job of a project
+    #[must_use]
+    pub fn new(value: &RegisterBadgeSettings, project_id: &str) -> Self {
+        Self {
+            id: Self::generate_id(),
+            public: value.public,
+            job_id: value.job_id.clone(),
+            project_id: project_id.to_string(),
+        }
+    }
+}
+
+impl RustyDomainItem for BadgeSettings {}
+
+/// A struct representing a paged result Badge Settings.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct PagedBadgeSettings {
+    /// total amount of entries found
+    pub total: usize,
+    /// current page
+    pub page: usize,
+    /// size of a page
+    pub page_size: usize,
+    /// data returned by query
+    pub entries: Vec<BadgeSettings>,
+}
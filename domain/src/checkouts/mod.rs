@@ -0,0 +1,170 @@
+use async_graphql::{Enum, InputObject, SimpleObject};
+use serde::{Deserialize, Serialize};
+use serde_valid::{validation, Validate};
+
+use crate::RustyDomainItem;
+
+/// Git submodules checkout mode
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Enum, Serialize, Deserialize)]
+#[graphql(rename_items = "UPPERCASE")]
+#[serde(rename_all = "lowercase")]
+pub enum Submodules {
+    /// submodules are not checked out
+    #[default]
+    #[serde(alias = "NONE")]
+    None,
+    /// submodules are checked out recursively
+    #[serde(alias = "RECURSIVE")]
+    Recursive,
+}
+
+/// A struct representing the repository checkout settings of a project.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct CheckoutSettings {
+    /// settings id
+    pub id: String,
+    /// clone depth - full history if missing
+    pub depth: Option<u32>,
+    /// submodules checkout mode
+    pub submodules: Option<Submodules>,
+    /// fetch git lfs objects
+    pub lfs: Option<bool>,
+    /// paths checked out - the whole repository if missing
+    #[serde(rename(deserialize = "sparsePaths", deserialize = "sparse_paths"))]
+    pub sparse_paths: Option<Vec<String>>,
+    /// private ssh deploy key
+    #[graphql(skip)]
+    #[serde(rename(deserialize = "sshKey", deserialize = "ssh_key"))]
+    pub ssh_key: Option<String>,
+    /// https username used with the token
+    pub username: Option<String>,
+    /// https access token
+    #[graphql(skip)]
+    pub token: Option<String>,
+    /// settings project id
+    #[serde(rename(deserialize = "projectId", deserialize = "project_id"))]
+    pub project_id: String,
+}
+
+/// A struct representing the registration of project checkout settings.
+#[derive(Clone, Debug, Default, InputObject, Serialize, Deserialize, Validate)]
+pub struct RegisterCheckoutSettings {
+    /// clone depth - full history if missing
+    #[validate(minimum = 1)]
+    pub depth: Option<u32>,
+    /// submodules checkout mode
+    pub submodules: Option<Submodules>,
+    /// fetch git lfs objects
+    pub lfs: Option<bool>,
+    /// paths checked out - the whole repository if missing
+    #[serde(rename(deserialize = "sparsePaths", deserialize = "sparse_paths"))]
+    #[validate(custom(validate_sparse_paths))]
+    pub sparse_paths: Option<Vec<String>>,
+    /// private ssh deploy key
+    #[serde(rename(deserialize = "sshKey", deserialize = "ssh_key"))]
+    #[validate(min_length = 1)]
+    #[validate(max_length = 16384)]
+    pub ssh_key: Option<String>,
+    /// https username used with the token - defaults to `oauth2`
+    #[validate(min_length = 1)]
+    #[validate(max_length = 256)]
+    pub username: Option<String>,
+    /// https access token
+    #[validate(min_length = 1)]
+    #[validate(max_length = 4096)]
+    pub token: Option<String>,
+    /// settings project id
+    #[serde(rename(deserialize = "projectId", deserialize = "project_id"))]
+    #[validate(min_length = 36)]
+    #[validate(max_length = 36)]
+    pub project_id: String,
+}
+
+fn validate_sparse_paths(paths: &Option<Vec<String>>) -> Result<(), validation::Error> {
+    let invalid = |path: &String| {
+        path.trim().is_empty() || path.starts_with('/') || path.split('/').any(|p| p == "..")
+    };
+    match paths {
+        Some(paths) if paths.is_empty() || paths.iter().any(invalid) => Err(
+            validation::Error::Custom("Invalid sparse checkout paths".to_owned()),
+        ),
+        _ => Ok(()),
+    }
+}
+
+impl RegisterCheckoutSettings {
+    /// constructor
+    #[must_use]
+    pub fn new(project_id: &str) -> Self {
+        Self {
+            project_id: project_id.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&RegisterCheckoutSettings> for CheckoutSettings {
+    fn from(value: &RegisterCheckoutSettings) -> Self {
+        Self {
+            id: Self::generate_id(),
+            depth: value.depth,
+            submodules: value.submodules,
+            lfs: value.lfs,
+            sparse_paths: value.clone().sparse_paths,
+            ssh_key: value.clone().ssh_key,
+            username: value.clone().username,
+            token: value.clone().token,
+            project_id: value.clone().project_id,
+        }
+    }
+}
+
+impl RustyDomainItem for CheckoutSettings {}
+
+/// A struct representing a paged result Checkout Settings.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct PagedCheckoutSettings {
+    /// total amount of entries found
+    pub total: usize,
+    /// current page
+    pub page: usize,
+    /// size of a page
+    pub page_size: usize,
+    /// data returned by query
+    pub entries: Vec<CheckoutSettings>,
+}
+
+/// A struct representing the checkout settings handed to an agent cloning a repository.
+#[derive(Clone, Debug, Default, SimpleObject, Serialize, Deserialize)]
+pub struct Checkout {
+    /// clone depth
+    pub depth: Option<u32>,
+    /// submodules checkout mode
+    pub submodules: Option<Submodules>,
+    /// fetch git lfs objects
+    pub lfs: Option<bool>,
+    /// paths checked out
+    #[serde(rename(deserialize = "sparsePaths", deserialize = "sparse_paths"))]
+    pub sparse_paths: Option<Vec<String>>,
+    /// private ssh deploy key
+    #[serde(rename(deserialize = "sshKey", deserialize = "ssh_key"))]
+    pub ssh_key: Option<String>,
+    /// https username used with the token
+    pub username: Option<String>,
+    /// https access token
+    pub token: Option<String>,
+}
+
+impl From<&CheckoutSettings> for Checkout {
+    fn from(value: &CheckoutSettings) -> Self {
+        Self {
+            depth: value.depth,
+            submodules: value.submodules,
+            lfs: value.lfs,
+            sparse_paths: value.clone().sparse_paths,
+            ssh_key: value.clone().ssh_key,
+            username: value.clone().username,
+            token: value.clone().token,
+        }
+    }
+}
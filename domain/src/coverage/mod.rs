@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use async_graphql::{InputObject, SimpleObject};
+use serde::{Deserialize, Serialize};
+
+use crate::pipelines::Pipeline;
+use crate::RustyDomainItem;
+
+/// A struct representing the line coverage of a source file.
+#[derive(Clone, Debug, Eq, PartialEq, SimpleObject, InputObject, Serialize, Deserialize)]
+#[graphql(input_name = "FileCoverageInput")]
+pub struct FileCoverage {
+    /// file path, as written in the report
+    pub path: String,
+    /// amount of instrumented lines
+    pub lines: u64,
+    /// amount of lines hit at least once
+    pub covered: u64,
+}
+
+/// A struct representing the coverage report of a pipeline stage.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct CoverageReport {
+    /// report id
+    pub id: String,
+    /// report upload date
+    pub date: String,
+    /// stage producing the report
+    pub stage: String,
+    /// pipeline branch
+    pub branch: String,
+    /// pipeline commit
+    pub commit: Option<String>,
+    /// total amount of instrumented lines
+    pub lines: u64,
+    /// total amount of covered lines
+    pub covered: u64,
+    /// covered lines percentage
+    pub percent: f64,
+    /// per file line coverage
+    pub files: Vec<FileCoverage>,
+    /// pipeline id
+    #[serde(rename(deserialize = "pipelineId", deserialize = "pipeline_id"))]
+    pub pipeline_id: String,
+    /// pipeline order number
+    #[serde(rename(deserialize = "pipelineNumber", deserialize = "pipeline_number"))]
+    pub pipeline_number: u64,
+    /// job id
+    #[serde(rename(deserialize = "jobId", deserialize = "job_id"))]
+    pub job_id: String,
+    /// project id
+    #[serde(rename(deserialize = "projectId", deserialize = "project_id"))]
+    pub project_id: String,
+}
+
+impl RustyDomainItem for CoverageReport {}
+
+/// A struct representing a paged result Coverage Reports.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct PagedCoverageReports {
+    /// total amount of entries found
+    pub total: usize,
+    /// current page
+    pub page: usize,
+    /// size of a page
+    pub page_size: usize,
+    /// data returned by query
+    pub entries: Vec<CoverageReport>,
+}
+
+/// A struct representing the coverage of a pipeline - all its reports combined.
+#[derive(Clone, Debug, PartialEq, SimpleObject, Serialize, Deserialize)]
+pub struct PipelineCoverage {
+    /// pipeline id
+    pub pipeline_id: String,
+    /// pipeline order number
+    pub pipeline_number: u64,
+    /// pipeline branch
+    pub branch: String,
+    /// pipeline commit
+    pub commit: Option<String>,
+    /// latest report upload date
+    pub date: String,
+    /// total amount of instrumented lines
+    pub lines: u64,
+    /// total amount of covered lines
+    pub covered: u64,
+    /// covered lines percentage
+    pub percent: f64,
+}
+
+/// A struct representing the coverage change of a pipeline against a base pipeline.
+#[derive(Clone, Debug, PartialEq, SimpleObject, Serialize, Deserialize)]
+pub struct CoverageDelta {
+    /// compared pipeline coverage
+    pub coverage: PipelineCoverage,
+    /// base pipeline coverage - none when no base pipeline reported coverage
+    pub base: Option<PipelineCoverage>,
+    /// covered lines percentage points gained - negative when coverage dropped
+    pub delta: Option<f64>,
+}
+
+/// Covered lines percentage - 0 when no line is instrumented
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn percent(covered: u64, lines: u64) -> f64 {
+    if lines == 0 {
+        0.0
+    } else {
+        covered as f64 * 100.0 / lines as f64
+    }
+}
+
+/// Build a coverage report from the uploaded files, with its totals
+#[must_use]
+pub fn new_report(
+    id: &str,
+    stage: &str,
+    files: Vec<FileCoverage>,
+    pipeline: &Pipeline,
+    project_id: &str,
+) -> CoverageReport {
+    let lines = files.iter().map(|file| file.lines).sum();
+    let covered = files.iter().map(|file| file.covered).sum();
+    CoverageReport {
+        id: id.to_string(),
+        date: chrono::Utc::now().to_rfc3339(),
+        stage: stage.to_string(),
+        branch: pipeline.branch.clone(),
+        commit: pipeline.commit.clone(),
+        lines,
+        covered,
+        percent: percent(covered, lines),
+        files,
+        pipeline_id: pipeline.id.clone(),
+        pipeline_number: pipeline.number,
+        job_id: pipeline.job_id.clone(),
+        project_id: project_id.to_string(),
+    }
+}
+
+/// Coverage of the pipelines of the reports, ordered by pipeline number
+///
+/// A file reported by several stages of a pipeline counts once, with its best coverage.
+#[must_use]
+pub fn pipeline_coverage(reports: &[CoverageReport]) -> Vec<PipelineCoverage> {
+    let mut pipelines: HashMap<&str, Vec<&CoverageReport>> = HashMap::new();
+    for report in reports {
+        pipelines
+            .entry(report.pipeline_id.as_str())
+            .or_default()
+            .push(report);
+    }
+    let mut coverage = pipelines
+        .into_values()
+        .filter_map(|reports| {
+            let latest = reports.iter().max_by(|a, b| a.date.cmp(&b.date))?;
+            let mut files: HashMap<&str, &FileCoverage> = HashMap::new();
+            for file in reports.iter().flat_map(|report| &report.files) {
+                let best = files.entry(file.path.as_str()).or_insert(file);
+                if file.covered > best.covered {
+                    *best = file;
+                }
+            }
+            let lines = files.values().map(|file| file.lines).sum();
+            let covered = files.values().map(|file| file.covered).sum();
+            Some(PipelineCoverage {
+                pipeline_id: latest.pipeline_id.clone(),
+                pipeline_number: latest.pipeline_number,
+                branch: latest.branch.clone(),
+                commit: latest.commit.clone(),
+                date: latest.date.clone(),
+                lines,
+                covered,
+                percent: percent(covered, lines),
+            })
+        })
+        .collect::<Vec<PipelineCoverage>>();
+    coverage.sort_by_key(|pipeline| pipeline.pipeline_number);
+    coverage
+}
+
+/// Coverage change of a pipeline against a base pipeline
+#[must_use]
+pub fn delta(coverage: PipelineCoverage, base: Option<PipelineCoverage>) -> CoverageDelta {
+    let delta = base.as_ref().map(|base| coverage.percent - base.percent);
+    CoverageDelta {
+        coverage,
+        base,
+        delta,
+    }
+}
@@ -0,0 +1,94 @@
+use async_graphql::SimpleObject;
+use serde::{Deserialize, Serialize};
+
+use crate::RustyDomainItem;
+
+/// A struct representing a deployment - a successful pipeline stage with an environment.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct Deployment {
+    /// deployment id
+    pub id: String,
+    /// environment name
+    pub environment: String,
+    /// environment url
+    pub url: Option<String>,
+    /// deployment date
+    pub date: String,
+    /// deployed branch
+    pub branch: String,
+    /// deployed commit sha
+    pub commit: Option<String>,
+    /// deploying stage
+    pub stage: String,
+    /// username of the user who registered the pipeline
+    #[serde(rename(deserialize = "deployedBy", deserialize = "deployed_by"))]
+    pub deployed_by: Option<String>,
+    /// deploying pipeline id
+    #[serde(rename(deserialize = "pipelineId", deserialize = "pipeline_id"))]
+    pub pipeline_id: String,
+    /// deploying pipeline order number
+    #[serde(rename(deserialize = "pipelineNumber", deserialize = "pipeline_number"))]
+    pub pipeline_number: u64,
+    /// deploying job id
+    #[serde(rename(deserialize = "jobId", deserialize = "job_id"))]
+    pub job_id: String,
+    /// deployed project id
+    #[serde(rename(deserialize = "projectId", deserialize = "project_id"))]
+    pub project_id: String,
+}
+
+impl RustyDomainItem for Deployment {}
+
+/// A struct representing a paged result Deployments.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct PagedDeployments {
+    /// total amount of entries found
+    pub total: usize,
+    /// current page
+    pub page: usize,
+    /// size of a page
+    pub page_size: usize,
+    /// data returned by query
+    pub entries: Vec<Deployment>,
+}
+
+/// A struct representing a deployment environment of a project.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct ProjectEnvironment {
+    /// environment name
+    pub name: String,
+    /// environment url - from the current deployment
+    pub url: Option<String>,
+    /// latest deployment
+    pub current: Deployment,
+    /// earlier deployments, latest first
+    pub previous: Vec<Deployment>,
+}
+
+/// Group the deployments of a project by environment
+///
+/// Environments are sorted by name, and their deployments by date, latest first.
+#[must_use]
+pub fn environments(deployments: &[Deployment]) -> Vec<ProjectEnvironment> {
+    let mut deployments = deployments.to_vec();
+    deployments.sort_by(|a, b| {
+        a.environment
+            .cmp(&b.environment)
+            .then_with(|| b.date.cmp(&a.date))
+    });
+    let mut environments: Vec<ProjectEnvironment> = vec![];
+    for deployment in deployments {
+        match environments.last_mut() {
+            Some(environment) if environment.name == deployment.environment => {
+                environment.previous.push(deployment);
+            }
+            _ => environments.push(ProjectEnvironment {
+                name: deployment.environment.clone(),
+                url: deployment.url.clone(),
+                current: deployment,
+                previous: vec![],
+            }),
+        }
+    }
+    environments
+}
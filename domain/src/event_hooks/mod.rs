@@ -0,0 +1,223 @@
+use async_graphql::{Enum, InputObject, SimpleObject};
+use serde::{Deserialize, Serialize};
+use serde_valid::{validation, Validate};
+
+use crate::RustyDomainItem;
+
+/// Operations broadcast for domain changes.
+pub const EVENT_OPERATIONS: [&str; 2] = ["create", "update"];
+
+/// A struct representing an outgoing webhook subscribed to domain events.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct EventHook {
+    /// hook id
+    pub id: String,
+    /// hook target url
+    pub url: String,
+    /// hook secret used for payload signatures
+    #[graphql(skip)]
+    pub secret: String,
+    /// hook index filter - empty for all indexes
+    pub indexes: Vec<String>,
+    /// hook operation filter - empty for all operations
+    pub operations: Vec<String>,
+    /// hook project filter
+    #[serde(rename(deserialize = "projectId", deserialize = "project_id"))]
+    pub project_id: Option<String>,
+    /// hook owner username
+    pub owner: String,
+}
+
+impl EventHook {
+    /// Check if an event matches the hook filters
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the changed item.
+    /// * `op` - The operation performed on the item.
+    /// * `project_id` - The project the item belongs to, if any.
+    #[must_use]
+    pub fn matches(&self, index: &str, op: &str, project_id: Option<&str>) -> bool {
+        (self.indexes.is_empty() || self.indexes.iter().any(|i| i == index))
+            && (self.operations.is_empty() || self.operations.iter().any(|o| o == op))
+            && self
+                .project_id
+                .as_ref()
+                .map_or(true, |id| project_id == Some(id.as_str()))
+    }
+}
+
+/// A struct representing the registration of an outgoing webhook.
+#[derive(Clone, Debug, InputObject, Serialize, Deserialize, Validate)]
+pub struct RegisterEventHook {
+    /// hook target url
+    #[validate(custom(validate_url))]
+    pub url: String,
+    /// hook secret used for payload signatures
+    #[validate(min_length = 16)]
+    #[validate(max_length = 256)]
+    pub secret: String,
+    /// hook index filter - empty for all indexes
+    #[validate(max_items = 32)]
+    pub indexes: Vec<String>,
+    /// hook operation filter - empty for all operations
+    #[validate(custom(validate_operations))]
+    pub operations: Vec<String>,
+    /// hook project filter
+    #[serde(rename(deserialize = "projectId", deserialize = "project_id"))]
+    #[validate(custom(validate_project_id))]
+    pub project_id: Option<String>,
+}
+
+fn validate_url(url: &str) -> Result<(), validation::Error> {
+    match url::Url::parse(url) {
+        Ok(url) if ["http", "https"].contains(&url.scheme()) => Ok(()),
+        _ => Err(validation::Error::Custom("Invalid url".to_owned())),
+    }
+}
+
+fn validate_operations(operations: &[String]) -> Result<(), validation::Error> {
+    if operations
+        .iter()
+        .all(|op| EVENT_OPERATIONS.contains(&op.as_str()))
+    {
+        Ok(())
+    } else {
+        Err(validation::Error::Custom(format!(
+            "operations must be one of: {EVENT_OPERATIONS:?}"
+        )))
+    }
+}
+
+fn validate_project_id(id: &Option<String>) -> Result<(), validation::Error> {
+    match id {
+        Some(id) if id.len() != 36 => {
+            Err(validation::Error::Custom("Invalid project id".to_owned()))
+        }
+        _ => Ok(()),
+    }
+}
+
+impl RegisterEventHook {
+    /// constructor
+    #[must_use]
+    pub fn new(url: &str, secret: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            secret: secret.to_string(),
+            indexes: vec![],
+            operations: vec![],
+            project_id: None,
+        }
+    }
+}
+
+impl From<&RegisterEventHook> for EventHook {
+    fn from(value: &RegisterEventHook) -> Self {
+        Self {
+            id: Self::generate_id(),
+            url: value.clone().url,
+            secret: value.clone().secret,
+            indexes: value.clone().indexes,
+            operations: value.clone().operations,
+            project_id: value.clone().project_id,
+            owner: String::new(),
+        }
+    }
+}
+
+impl RustyDomainItem for EventHook {}
+
+/// A struct representing a paged result Event Hooks.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct PagedEventHooks {
+    /// total amount of entries found
+    pub total: usize,
+    /// current page
+    pub page: usize,
+    /// size of a page
+    pub page_size: usize,
+    /// data returned by query
+    pub entries: Vec<EventHook>,
+}
+
+/// An enum representing an event delivery status.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Enum, Serialize, Deserialize)]
+pub enum DeliveryStatus {
+    /// Delivery registered, not yet acknowledged by the target.
+    #[serde(rename(deserialize = "PENDING", deserialize = "Pending"))]
+    Pending,
+    /// Delivery acknowledged by the target.
+    #[serde(rename(deserialize = "DELIVERED", deserialize = "Delivered"))]
+    Delivered,
+    /// Delivery failed after all retries.
+    #[serde(rename(deserialize = "FAILED", deserialize = "Failed"))]
+    Failed,
+}
+
+/// A struct representing a delivery of a domain event to an outgoing webhook.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct EventHookDelivery {
+    /// delivery id
+    pub id: String,
+    /// delivery register date
+    pub date: String,
+    /// delivery event index
+    pub index: String,
+    /// delivery event operation
+    pub op: String,
+    /// delivery payload - signed JSON body
+    pub payload: String,
+    /// delivery status
+    pub status: DeliveryStatus,
+    /// delivery attempts
+    pub attempts: u32,
+    /// delivery last error
+    pub error: Option<String>,
+    /// delivery hook id
+    #[serde(rename(deserialize = "hookId", deserialize = "hook_id"))]
+    pub hook_id: String,
+}
+
+impl EventHookDelivery {
+    /// constructor
+    #[must_use]
+    pub fn new(hook_id: &str, index: &str, op: &str, item: &serde_json::Value) -> Self {
+        let id = Self::generate_id();
+        let date = chrono::Utc::now().to_rfc3339();
+        let payload = serde_json::json!({
+            "id": id,
+            "date": date,
+            "index": index,
+            "op": op,
+            "item": item,
+        })
+        .to_string();
+        Self {
+            id,
+            date,
+            index: index.to_string(),
+            op: op.to_string(),
+            payload,
+            status: DeliveryStatus::Pending,
+            attempts: 0,
+            error: None,
+            hook_id: hook_id.to_string(),
+        }
+    }
+}
+
+impl RustyDomainItem for EventHookDelivery {}
+
+/// A struct representing a paged result Event Hook Deliveries.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct PagedEventHookDeliveries {
+    /// total amount of entries found
+    pub total: usize,
+    /// current page
+    pub page: usize,
+    /// size of a page
+    pub page_size: usize,
+    /// data returned by query
+    pub entries: Vec<EventHookDelivery>,
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use async_graphql::{Enum, InputObject, SimpleObject};
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+
+use crate::pipelines::{Pipeline, PipelineStatus};
+use crate::templates::pipeline::matches_branch;
+use crate::RustyDomainItem;
+
+/// An enum representing the upstream pipeline result starting a downstream pipeline.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Enum, Serialize, Deserialize)]
+pub enum TriggerCondition {
+    /// Trigger when the upstream pipeline succeeds
+    #[serde(rename(deserialize = "ON_SUCCESS", deserialize = "OnSuccess"))]
+    OnSuccess,
+    /// Trigger when the upstream pipeline fails
+    #[serde(rename(deserialize = "ON_FAILURE", deserialize = "OnFailure"))]
+    OnFailure,
+    /// Trigger on every finished upstream pipeline
+    #[serde(rename(deserialize = "ALWAYS", deserialize = "Always"))]
+    Always,
+}
+
+impl TriggerCondition {
+    /// Check if a finished pipeline status matches the condition
+    #[must_use]
+    pub fn matches(self, status: PipelineStatus) -> bool {
+        match self {
+            Self::OnSuccess => status == PipelineStatus::Success,
+            Self::OnFailure => {
+                [PipelineStatus::Failure, PipelineStatus::Unstable].contains(&status)
+            }
+            Self::Always => true,
+        }
+    }
+}
+
+/// A struct representing a trigger starting a downstream job after an upstream job.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct JobTrigger {
+    /// trigger id
+    pub id: String,
+    /// upstream pipeline result starting the downstream pipeline
+    pub condition: TriggerCondition,
+    /// upstream pipeline branch filter - any branch if missing
+    pub branch: Option<String>,
+    /// variables passed to the downstream pipeline
+    pub variables: Option<HashMap<String, String>>,
+    /// upstream job id
+    #[serde(rename(deserialize = "jobId", deserialize = "job_id"))]
+    pub job_id: String,
+    /// downstream job id
+    #[serde(rename(deserialize = "downstreamJobId", deserialize = "downstream_job_id"))]
+    pub downstream_job_id: String,
+    /// upstream job project id
+    #[serde(rename(deserialize = "projectId", deserialize = "project_id"))]
+    pub project_id: String,
+}
+
+impl JobTrigger {
+    /// Build a trigger of a job of a project
+    #[must_use]
+    pub fn new(value: &RegisterJobTrigger, project_id: &str) -> Self {
+        Self {
+            id: Self::generate_id(),
+            condition: value.condition,
+            branch: value.branch.clone(),
+            variables: value.variables.clone(),
+            job_id: value.job_id.clone(),
+            downstream_job_id: value.downstream_job_id.clone(),
+            project_id: project_id.to_string(),
+        }
+    }
+
+    /// Check if a finished upstream pipeline starts the downstream job
+    ///
+    /// Branch filters support `*` wildcards, e.g. `release/*`.
+    #[must_use]
+    pub fn matches(&self, pipeline: &Pipeline) -> bool {
+        pipeline.job_id == self.job_id
+            && self.condition.matches(pipeline.status)
+            && self
+                .branch
+                .as_ref()
+                .map_or(true, |filter| matches_branch(filter, &pipeline.branch))
+    }
+}
+
+/// A struct representing the registration of a job trigger.
+#[derive(Clone, Debug, InputObject, Serialize, Deserialize, Validate)]
+pub struct RegisterJobTrigger {
+    /// upstream pipeline result starting the downstream pipeline
+    pub condition: TriggerCondition,
+    /// upstream pipeline branch filter - any branch if missing
+    #[validate(min_length = 1)]
+    #[validate(max_length = 256)]
+    pub branch: Option<String>,
+    /// variables passed to the downstream pipeline
+    pub variables: Option<HashMap<String, String>>,
+    /// upstream job id
+    #[serde(rename(deserialize = "jobId", deserialize = "job_id"))]
+    #[validate(min_length = 36)]
+    #[validate(max_length = 36)]
+    pub job_id: String,
+    /// downstream job id
+    #[serde(rename(deserialize = "downstreamJobId", deserialize = "downstream_job_id"))]
+    #[validate(min_length = 36)]
+    #[validate(max_length = 36)]
+    pub downstream_job_id: String,
+}
+
+impl RegisterJobTrigger {
+    /// constructor
+    #[must_use]
+    pub fn new(job_id: &str, downstream_job_id: &str, condition: TriggerCondition) -> Self {
+        Self {
+            condition,
+            branch: None,
+            variables: None,
+            job_id: job_id.to_string(),
+            downstream_job_id: downstream_job_id.to_string(),
+        }
+    }
+}
+
+impl RustyDomainItem for JobTrigger {}
+
+/// A struct representing a paged result Job Triggers.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct PagedJobTriggers {
+    /// total amount of entries found
+    pub total: usize,
+    /// current page
+    pub page: usize,
+    /// size of a page
+    pub page_size: usize,
+    /// data returned by query
+    pub entries: Vec<JobTrigger>,
+}
+
+/// Check if a new trigger would start its own upstream job again
+///
+/// # Arguments
+///
+/// * `triggers` - The existing triggers.
+/// * `job_id` - The upstream job of the new trigger.
+/// * `downstream_job_id` - The downstream job of the new trigger.
+#[must_use]
+pub fn creates_cycle(triggers: &[JobTrigger], job_id: &str, downstream_job_id: &str) -> bool {
+    let mut visited = vec![];
+    let mut pending = vec![downstream_job_id.to_string()];
+    while let Some(job) = pending.pop() {
+        if job == job_id {
+            return true;
+        }
+        if visited.contains(&job) {
+            continue;
+        }
+        pending.extend(
+            triggers
+                .iter()
+                .filter(|trigger| trigger.job_id == job)
+                .map(|trigger| trigger.downstream_job_id.clone()),
+        );
+        visited.push(job);
+    }
+    false
+}
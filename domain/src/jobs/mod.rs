@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use serde_valid::{validation, Validate};
 
 use crate::pipelines::Pipeline;
+use crate::schedules::Schedule;
 use crate::templates::pipeline::PipelineTemplate;
 use crate::RustyDomainItem;
 
@@ -22,6 +23,8 @@ pub struct JobModel {
     pub project_id: String,
     /// job pipelines
     pub pipelines: Vec<Pipeline>,
+    /// job schedules
+    pub schedules: Vec<Schedule>,
 }
 
 /// A struct representing a job.
@@ -50,7 +53,7 @@ pub struct RegisterJob {
     /// job description
     #[validate(max_length = 2048)]
     pub description: Option<String>,
-    /// job pipeline template
+    /// job pipeline template - base64 encoded or plain yaml, json or toml
     #[validate(custom(validate_template))]
     pub template: String,
     /// job project id
@@ -77,12 +80,53 @@ impl RegisterJob {
     }
 }
 
+/// A struct representing a partial update of a job.
+#[derive(Clone, Debug, Default, InputObject, Serialize, Deserialize, Validate)]
+pub struct UpdateJob {
+    /// job name
+    #[validate(min_length = 1)]
+    #[validate(max_length = 512)]
+    pub name: Option<String>,
+    /// job description - empty string clears the description
+    #[validate(max_length = 2048)]
+    pub description: Option<String>,
+    /// job pipeline template - base64 encoded or plain yaml, json or toml
+    #[validate(custom(validate_optional_template))]
+    pub template: Option<String>,
+}
+
+impl UpdateJob {
+    /// Apply the update to a job
+    #[must_use]
+    pub fn apply(&self, job: &Job) -> Job {
+        let mut job = job.clone();
+        if let Some(name) = &self.name {
+            job.name.clone_from(name);
+        }
+        match self.description.as_deref() {
+            Some("") => job.description = None,
+            Some(description) => job.description = Some(description.to_string()),
+            None => {}
+        }
+        if let Some(template) = &self.template {
+            job.template.clone_from(template);
+        }
+        job
+    }
+}
+
+fn validate_optional_template(template: &Option<String>) -> Result<(), validation::Error> {
+    template
+        .as_ref()
+        .map_or(Ok(()), |template| validate_template(template))
+}
+
 fn validate_template(url: &str) -> Result<(), validation::Error> {
     match PipelineTemplate::from_yaml(url) {
         Ok(_) => Ok(()),
-        Err(_) => Err(validation::Error::Custom(
-            "Invalid pipeline template".to_owned(),
-        )),
+        Err(err) => Err(validation::Error::Custom(format!(
+            "Invalid pipeline template: {err}"
+        ))),
     }
 }
 
@@ -95,6 +139,7 @@ impl From<&Job> for JobModel {
             template: value.clone().template,
             project_id: value.clone().project_id,
             pipelines: vec![],
+            schedules: vec![],
         }
     }
 }
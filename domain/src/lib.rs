@@ -26,21 +26,57 @@ pub mod agents;
 /// # Authentication Module
 pub mod auth;
 
+/// # Badges Module
+pub mod badges;
+
+/// # Checkouts Module
+pub mod checkouts;
+
 /// # Common Module
 pub mod commons;
 
+/// # Coverage Module
+pub mod coverage;
+
+/// # Deployments Module
+pub mod deployments;
+
+/// # Event Hooks Module
+pub mod event_hooks;
+
+/// # Job Triggers Module
+pub mod job_triggers;
+
 /// # Jobs Module
 pub mod jobs;
 
+/// # Notifications Module
+pub mod notifications;
+
 /// # Pipelines Module
 pub mod pipelines;
 
 /// # Projects Module
 pub mod projects;
 
+/// # Registries Module
+pub mod registries;
+
+/// # Status Reporters Module
+pub mod reporters;
+
+/// # Schedules Module
+pub mod schedules;
+
 /// # Template
 pub mod templates;
 
+/// # Test Reports Module
+pub mod test_reports;
+
+/// # Webhooks Module
+pub mod webhooks;
+
 /// The `RustyDomainItem` trait represents an item in a read-only domain.
 ///
 /// It defines the basic requirements that an item must fulfill in order to be considered
@@ -0,0 +1,225 @@
+use async_graphql::{Enum, InputObject, SimpleObject};
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+
+use commons::errors::RustyError;
+
+use crate::pipelines::PipelineStatus;
+use crate::RustyDomainItem;
+
+/// An enum representing a notification delivery channel.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Enum, Serialize, Deserialize)]
+pub enum NotificationChannel {
+    /// E-mail sent via SMTP server
+    #[serde(rename(deserialize = "SMTP", deserialize = "Smtp"))]
+    Smtp,
+    /// Slack-compatible incoming webhook
+    #[serde(rename(deserialize = "SLACK", deserialize = "Slack"))]
+    Slack,
+    /// Generic JSON webhook
+    #[serde(rename(deserialize = "WEBHOOK", deserialize = "Webhook"))]
+    Webhook,
+}
+
+/// An enum representing a rule deciding when a notification is sent.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Enum, Serialize, Deserialize)]
+pub enum NotificationRule {
+    /// Notify when a pipeline fails
+    #[serde(rename(deserialize = "ON_FAILURE", deserialize = "OnFailure"))]
+    OnFailure,
+    /// Notify when a pipeline succeeds after a failed one
+    #[serde(rename(deserialize = "ON_FIXED", deserialize = "OnFixed"))]
+    OnFixed,
+    /// Notify on every finished pipeline
+    #[serde(rename(deserialize = "EVERY_RUN", deserialize = "EveryRun"))]
+    EveryRun,
+}
+
+impl NotificationRule {
+    /// Check if a finished pipeline matches the rule
+    ///
+    /// # Arguments
+    ///
+    /// * `status` - The status of the finished pipeline.
+    /// * `previous` - The status of the previous finished pipeline for the same job and branch.
+    #[must_use]
+    pub fn matches(self, status: PipelineStatus, previous: Option<PipelineStatus>) -> bool {
+        let failed = |status| [PipelineStatus::Failure, PipelineStatus::Unstable].contains(&status);
+        match self {
+            Self::OnFailure => failed(status),
+            Self::OnFixed => status == PipelineStatus::Success && previous.is_some_and(failed),
+            Self::EveryRun => true,
+        }
+    }
+}
+
+/// A struct representing a project notification.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct Notification {
+    /// notification id
+    pub id: String,
+    /// notification channel
+    pub channel: NotificationChannel,
+    /// notification target - e-mail address or webhook url
+    pub target: String,
+    /// notification rule
+    pub rule: NotificationRule,
+    /// notification branch filter
+    pub branch: Option<String>,
+    /// notification project id
+    #[serde(rename(deserialize = "projectId", deserialize = "project_id"))]
+    pub project_id: String,
+}
+
+/// A struct representing the registration of a project notification.
+#[derive(Clone, Debug, InputObject, Serialize, Deserialize, Validate)]
+pub struct RegisterNotification {
+    /// notification channel
+    pub channel: NotificationChannel,
+    /// notification target - e-mail address or webhook url
+    #[validate(min_length = 1)]
+    #[validate(max_length = 2048)]
+    pub target: String,
+    /// notification rule
+    pub rule: NotificationRule,
+    /// notification branch filter
+    #[validate(max_length = 256)]
+    pub branch: Option<String>,
+    /// notification project id
+    #[serde(rename(deserialize = "projectId", deserialize = "project_id"))]
+    #[validate(min_length = 36)]
+    #[validate(max_length = 36)]
+    pub project_id: String,
+}
+
+impl RegisterNotification {
+    /// constructor
+    #[must_use]
+    pub fn new(
+        channel: NotificationChannel,
+        target: &str,
+        rule: NotificationRule,
+        project_id: &str,
+    ) -> Self {
+        Self {
+            channel,
+            target: target.to_string(),
+            rule,
+            branch: None,
+            project_id: project_id.to_string(),
+        }
+    }
+
+    /// Check if the target matches the notification channel
+    ///
+    /// # Errors
+    ///
+    /// This function can generate the following errors:
+    ///
+    /// * `RustyError` - If the target is not a valid e-mail address (SMTP) or url (webhooks).
+    pub fn validate_target(&self) -> Result<(), RustyError> {
+        let valid = match self.channel {
+            NotificationChannel::Smtp => {
+                regex::Regex::new(r"^[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}$")
+                    .map_or(false, |reg| reg.is_match(&self.target))
+            }
+            NotificationChannel::Slack | NotificationChannel::Webhook => {
+                url::Url::parse(&self.target)
+                    .map_or(false, |url| ["http", "https"].contains(&url.scheme()))
+            }
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(RustyError::ValidationError(format!(
+                "invalid target for `{:?}` notification",
+                self.channel
+            )))
+        }
+    }
+}
+
+impl From<&RegisterNotification> for Notification {
+    fn from(value: &RegisterNotification) -> Self {
+        Self {
+            id: Self::generate_id(),
+            channel: value.channel,
+            target: value.clone().target,
+            rule: value.rule,
+            branch: value.clone().branch,
+            project_id: value.clone().project_id,
+        }
+    }
+}
+
+impl RustyDomainItem for Notification {}
+
+/// A struct representing a paged result Notifications.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct PagedNotifications {
+    /// total amount of entries found
+    pub total: usize,
+    /// current page
+    pub page: usize,
+    /// size of a page
+    pub page_size: usize,
+    /// data returned by query
+    pub entries: Vec<Notification>,
+}
+
+/// A struct representing a notification delivery attempt.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct NotificationDelivery {
+    /// delivery id
+    pub id: String,
+    /// delivery date
+    pub date: String,
+    /// delivery success flag
+    pub delivered: bool,
+    /// delivery attempts
+    pub attempts: u32,
+    /// delivery error
+    pub error: Option<String>,
+    /// delivery notification id
+    #[serde(rename(deserialize = "notificationId", deserialize = "notification_id"))]
+    pub notification_id: String,
+    /// delivery pipeline id
+    #[serde(rename(deserialize = "pipelineId", deserialize = "pipeline_id"))]
+    pub pipeline_id: String,
+}
+
+impl NotificationDelivery {
+    /// constructor
+    #[must_use]
+    pub fn new(
+        notification_id: &str,
+        pipeline_id: &str,
+        attempts: u32,
+        error: Option<String>,
+    ) -> Self {
+        Self {
+            id: Self::generate_id(),
+            date: chrono::Utc::now().to_rfc3339(),
+            delivered: error.is_none(),
+            attempts,
+            error,
+            notification_id: notification_id.to_string(),
+            pipeline_id: pipeline_id.to_string(),
+        }
+    }
+}
+
+impl RustyDomainItem for NotificationDelivery {}
+
+/// A struct representing a paged result Notification Deliveries.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct PagedNotificationDeliveries {
+    /// total amount of entries found
+    pub total: usize,
+    /// current page
+    pub page: usize,
+    /// size of a page
+    pub page_size: usize,
+    /// data returned by query
+    pub entries: Vec<NotificationDelivery>,
+}
@@ -58,6 +58,18 @@ pub struct Pipeline {
     /// pipeline agent id
     #[serde(rename(deserialize = "agentId", deserialize = "agent_id"))]
     pub agent_id: Option<String>,
+    /// pipeline commit sha
+    pub commit: Option<String>,
+    /// pipeline job template revision id
+    pub revision: Option<String>,
+    /// username of the user who registered the pipeline
+    #[serde(rename(deserialize = "triggeredBy", deserialize = "triggered_by"))]
+    pub triggered_by: Option<String>,
+    /// id of the pipeline which triggered this downstream pipeline
+    #[serde(rename(deserialize = "upstreamId", deserialize = "upstream_id"))]
+    pub upstream_id: Option<String>,
+    /// variables passed by the upstream pipeline
+    pub variables: Option<HashMap<String, String>>,
 }
 
 /// A struct representing the registration of a pipeline.
@@ -68,6 +80,8 @@ pub struct RegisterPipeline {
     pub job_id: String,
     /// pipeline branch
     pub branch: Option<String>,
+    /// pipeline commit sha
+    pub commit: Option<String>,
 }
 
 impl RegisterPipeline {
@@ -77,6 +91,7 @@ impl RegisterPipeline {
         Self {
             job_id: job_id.to_string(),
             branch: Some("master".to_string()),
+            commit: None,
         }
     }
 }
@@ -94,6 +109,11 @@ impl From<&RegisterPipeline> for Pipeline {
             stage_status: HashMap::new(),
             job_id: value.clone().job_id,
             agent_id: None,
+            commit: value.clone().commit,
+            revision: None,
+            triggered_by: None,
+            upstream_id: None,
+            variables: None,
         }
     }
 }
@@ -44,6 +44,27 @@ impl RegisterGroup {
     }
 }
 
+/// A struct representing a partial update of a project group.
+#[derive(Clone, Debug, Default, InputObject, Serialize, Deserialize, Validate)]
+pub struct UpdateGroup {
+    /// project group name
+    #[validate(min_length = 1)]
+    #[validate(max_length = 512)]
+    pub name: Option<String>,
+}
+
+impl UpdateGroup {
+    /// Apply the update to a project group
+    #[must_use]
+    pub fn apply(&self, group: &Group) -> Group {
+        let mut group = group.clone();
+        if let Some(name) = &self.name {
+            group.name.clone_from(name);
+        }
+        group
+    }
+}
+
 impl From<&Group> for GroupModel {
     fn from(value: &Group) -> Self {
         Self {
@@ -81,6 +81,63 @@ impl RegisterProject {
     }
 }
 
+/// A struct representing a partial update of a project.
+#[derive(Clone, Debug, Default, InputObject, Serialize, Deserialize, Validate)]
+pub struct UpdateProject {
+    /// project name
+    #[validate(min_length = 1)]
+    #[validate(max_length = 512)]
+    pub name: Option<String>,
+    /// project url
+    #[validate(custom(validate_optional_url))]
+    pub url: Option<String>,
+    /// project main branch name
+    #[serde(rename(deserialize = "mainBranch", deserialize = "main_branch"))]
+    #[validate(min_length = 1)]
+    #[validate(max_length = 256)]
+    pub main_branch: Option<String>,
+    /// project group id - empty string removes the project from its group
+    #[serde(rename(deserialize = "groupId", deserialize = "group_id"))]
+    #[validate(custom(validate_group_id))]
+    pub group_id: Option<String>,
+}
+
+fn validate_optional_url(url: &Option<String>) -> Result<(), validation::Error> {
+    url.as_ref().map_or(Ok(()), |url| validate_url(url))
+}
+
+fn validate_group_id(id: &Option<String>) -> Result<(), validation::Error> {
+    match id {
+        Some(id) if !id.is_empty() && id.len() != 36 => {
+            Err(validation::Error::Custom("Invalid group id".to_owned()))
+        }
+        _ => Ok(()),
+    }
+}
+
+impl UpdateProject {
+    /// Apply the update to a project
+    #[must_use]
+    pub fn apply(&self, project: &Project) -> Project {
+        let mut project = project.clone();
+        if let Some(name) = &self.name {
+            project.name.clone_from(name);
+        }
+        if let Some(url) = &self.url {
+            project.url = Some(url.clone());
+        }
+        if let Some(main_branch) = &self.main_branch {
+            project.main_branch.clone_from(main_branch);
+        }
+        match self.group_id.as_deref() {
+            Some("") => project.group_id = None,
+            Some(group_id) => project.group_id = Some(group_id.to_string()),
+            None => {}
+        }
+        project
+    }
+}
+
 impl From<&Project> for ProjectModel {
     fn from(value: &Project) -> Self {
         Self {
@@ -0,0 +1,164 @@
+use async_graphql::{InputObject, SimpleObject};
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+
+use crate::RustyDomainItem;
+
+/// Registry used for images without an explicit registry host.
+pub const DEFAULT_REGISTRY: &str = "docker.io";
+
+/// A struct representing the credentials of a private docker registry of a project.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct RegistryCredential {
+    /// credential id
+    pub id: String,
+    /// registry host, e.g. `ghcr.io`
+    pub registry: String,
+    /// registry username
+    pub username: String,
+    /// registry password or access token
+    #[graphql(skip)]
+    pub password: String,
+    /// credential project id
+    #[serde(rename(deserialize = "projectId", deserialize = "project_id"))]
+    pub project_id: String,
+}
+
+impl RegistryCredential {
+    /// Check if the credential applies to a docker image
+    #[must_use]
+    pub fn matches(&self, image: &str) -> bool {
+        normalize_registry(&self.registry) == image_registry(image)
+    }
+}
+
+/// A struct representing the registration of a private docker registry credential.
+#[derive(Clone, Debug, InputObject, Serialize, Deserialize, Validate)]
+pub struct RegisterRegistryCredential {
+    /// registry host, e.g. `ghcr.io`
+    #[validate(min_length = 1)]
+    #[validate(max_length = 256)]
+    pub registry: String,
+    /// registry username
+    #[validate(min_length = 1)]
+    #[validate(max_length = 256)]
+    pub username: String,
+    /// registry password or access token
+    #[validate(min_length = 1)]
+    #[validate(max_length = 4096)]
+    pub password: String,
+    /// credential project id
+    #[serde(rename(deserialize = "projectId", deserialize = "project_id"))]
+    #[validate(min_length = 36)]
+    #[validate(max_length = 36)]
+    pub project_id: String,
+}
+
+impl RegisterRegistryCredential {
+    /// constructor
+    #[must_use]
+    pub fn new(registry: &str, username: &str, password: &str, project_id: &str) -> Self {
+        Self {
+            registry: registry.to_string(),
+            username: username.to_string(),
+            password: password.to_string(),
+            project_id: project_id.to_string(),
+        }
+    }
+}
+
+impl From<&RegisterRegistryCredential> for RegistryCredential {
+    fn from(value: &RegisterRegistryCredential) -> Self {
+        Self {
+            id: Self::generate_id(),
+            registry: normalize_registry(&value.registry),
+            username: value.clone().username,
+            password: value.clone().password,
+            project_id: value.clone().project_id,
+        }
+    }
+}
+
+impl RustyDomainItem for RegistryCredential {}
+
+/// A struct representing a paged result Registry Credentials.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct PagedRegistryCredentials {
+    /// total amount of entries found
+    pub total: usize,
+    /// current page
+    pub page: usize,
+    /// size of a page
+    pub page_size: usize,
+    /// data returned by query
+    pub entries: Vec<RegistryCredential>,
+}
+
+/// A struct representing registry credentials handed to an agent pulling images.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct RegistryAuth {
+    /// registry host
+    pub registry: String,
+    /// registry username
+    pub username: String,
+    /// registry password or access token
+    pub password: String,
+}
+
+impl From<&RegistryCredential> for RegistryAuth {
+    fn from(value: &RegistryCredential) -> Self {
+        Self {
+            registry: value.clone().registry,
+            username: value.clone().username,
+            password: value.clone().password,
+        }
+    }
+}
+
+/// Get the registry host of a docker image reference
+///
+/// Images without a registry host, e.g. `postgres:16` or `bitnami/redis`, come from Docker Hub.
+#[must_use]
+pub fn image_registry(image: &str) -> String {
+    match image.split_once('/') {
+        Some((host, _)) if host.contains(['.', ':']) || host == "localhost" => {
+            normalize_registry(host)
+        }
+        _ => DEFAULT_REGISTRY.to_string(),
+    }
+}
+
+/// Split a docker image reference into repository and tag
+///
+/// The tag defaults to `latest`, e.g. `localhost:5000/app` is split into `localhost:5000/app` and `latest`.
+#[must_use]
+pub fn split_tag(image: &str) -> (&str, &str) {
+    let name_start = image.rfind('/').map_or(0, |index| index + 1);
+    image[name_start..]
+        .rfind(':')
+        .map_or((image, "latest"), |index| {
+            (
+                &image[..name_start + index],
+                &image[name_start + index + 1..],
+            )
+        })
+}
+
+// strips the scheme and path, and maps Docker Hub aliases to `docker.io`
+fn normalize_registry(registry: &str) -> String {
+    let registry = registry
+        .trim()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+    let host = registry
+        .split('/')
+        .next()
+        .unwrap_or(registry)
+        .to_lowercase();
+    match host.as_str() {
+        "index.docker.io" | "registry-1.docker.io" | "registry.hub.docker.com" => {
+            DEFAULT_REGISTRY.to_string()
+        }
+        _ => host,
+    }
+}
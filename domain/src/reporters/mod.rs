@@ -0,0 +1,93 @@
+use async_graphql::{InputObject, SimpleObject};
+use serde::{Deserialize, Serialize};
+use serde_valid::{validation, Validate};
+
+use crate::webhooks::Forge;
+use crate::RustyDomainItem;
+
+/// A struct representing a commit status reporter of a project.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct StatusReporter {
+    /// reporter id
+    pub id: String,
+    /// reporter git forge
+    pub forge: Forge,
+    /// reporter forge api url
+    #[serde(rename(deserialize = "apiUrl", deserialize = "api_url"))]
+    pub api_url: Option<String>,
+    /// reporter forge access token
+    #[graphql(skip)]
+    pub token: String,
+    /// reporter project id
+    #[serde(rename(deserialize = "projectId", deserialize = "project_id"))]
+    pub project_id: String,
+}
+
+/// A struct representing the registration of a commit status reporter.
+#[derive(Clone, Debug, InputObject, Serialize, Deserialize, Validate)]
+pub struct RegisterStatusReporter {
+    /// reporter git forge
+    pub forge: Forge,
+    /// reporter forge api url
+    #[serde(rename(deserialize = "apiUrl", deserialize = "api_url"))]
+    #[validate(custom(validate_api_url))]
+    pub api_url: Option<String>,
+    /// reporter forge access token
+    #[validate(min_length = 1)]
+    #[validate(max_length = 1024)]
+    pub token: String,
+    /// reporter project id
+    #[serde(rename(deserialize = "projectId", deserialize = "project_id"))]
+    #[validate(min_length = 36)]
+    #[validate(max_length = 36)]
+    pub project_id: String,
+}
+
+fn validate_api_url(url: &Option<String>) -> Result<(), validation::Error> {
+    match url {
+        Some(url) if url::Url::parse(url).is_err() => {
+            Err(validation::Error::Custom("Invalid url".to_owned()))
+        }
+        _ => Ok(()),
+    }
+}
+
+impl RegisterStatusReporter {
+    /// constructor
+    #[must_use]
+    pub fn new(forge: Forge, token: &str, project_id: &str) -> Self {
+        Self {
+            forge,
+            api_url: None,
+            token: token.to_string(),
+            project_id: project_id.to_string(),
+        }
+    }
+}
+
+impl From<&RegisterStatusReporter> for StatusReporter {
+    fn from(value: &RegisterStatusReporter) -> Self {
+        Self {
+            id: Self::generate_id(),
+            forge: value.forge,
+            api_url: value.clone().api_url,
+            token: value.clone().token,
+            project_id: value.clone().project_id,
+        }
+    }
+}
+
+impl RustyDomainItem for StatusReporter {}
+
+/// A struct representing a paged result Status Reporters.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct PagedStatusReporters {
+    /// total amount of entries found
+    pub total: usize,
+    /// current page
+    pub page: usize,
+    /// size of a page
+    pub page_size: usize,
+    /// data returned by query
+    pub entries: Vec<StatusReporter>,
+}
@@ -0,0 +1,197 @@
+use std::str::FromStr;
+
+use async_graphql::{InputObject, SimpleObject};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_valid::{validation, Validate};
+
+use commons::errors::RustyError;
+
+use crate::RustyDomainItem;
+
+/// A struct representing a job schedule.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct Schedule {
+    /// schedule id
+    pub id: String,
+    /// schedule cron expression
+    pub cron: String,
+    /// schedule branch
+    pub branch: Option<String>,
+    /// skip scheduled run if the previous pipeline is still in progress
+    #[serde(rename(deserialize = "skipIfRunning", deserialize = "skip_if_running"))]
+    pub skip_if_running: bool,
+    /// schedule register date
+    #[serde(rename(deserialize = "registerDate", deserialize = "register_date"))]
+    pub register_date: String,
+    /// schedule last run date
+    #[serde(rename(deserialize = "lastRun", deserialize = "last_run"))]
+    pub last_run: Option<String>,
+    /// schedule job id
+    #[serde(rename(deserialize = "jobId", deserialize = "job_id"))]
+    pub job_id: String,
+}
+
+impl Schedule {
+    /// Calculate the first run of a schedule after its last run (or registration)
+    ///
+    /// # Errors
+    ///
+    /// This function can generate the following errors:
+    ///
+    /// * `RustyError` - If the cron expression or the stored dates are invalid.
+    pub fn next_run(&self) -> Result<Option<DateTime<Utc>>, RustyError> {
+        let since = self
+            .last_run
+            .clone()
+            .unwrap_or_else(|| self.register_date.clone());
+        let since = DateTime::parse_from_rfc3339(&since)
+            .map_err(|err| RustyError::ConvertError(err.to_string()))?
+            .with_timezone(&Utc);
+        Ok(parse_cron(&self.cron)?.after(&since).next())
+    }
+
+    /// Check if a schedule is due to run at the given moment
+    ///
+    /// # Errors
+    ///
+    /// This function can generate the following errors:
+    ///
+    /// * `RustyError` - If the cron expression or the stored dates are invalid.
+    pub fn is_due(&self, now: &DateTime<Utc>) -> Result<bool, RustyError> {
+        Ok(self.next_run()?.is_some_and(|next| next <= *now))
+    }
+}
+
+/// A struct representing the registration of a job schedule.
+#[derive(Clone, Debug, InputObject, Serialize, Deserialize, Validate)]
+pub struct RegisterSchedule {
+    /// schedule cron expression
+    #[validate(custom(validate_cron))]
+    pub cron: String,
+    /// schedule branch
+    #[validate(max_length = 256)]
+    pub branch: Option<String>,
+    /// skip scheduled run if the previous pipeline is still in progress
+    #[serde(rename(deserialize = "skipIfRunning", deserialize = "skip_if_running"))]
+    pub skip_if_running: Option<bool>,
+    /// schedule job id
+    #[serde(rename(deserialize = "jobId", deserialize = "job_id"))]
+    #[validate(min_length = 36)]
+    #[validate(max_length = 36)]
+    pub job_id: String,
+}
+
+impl RegisterSchedule {
+    /// constructor
+    #[must_use]
+    pub fn new(cron: &str, branch: &str, job_id: &str) -> Self {
+        Self {
+            cron: cron.to_string(),
+            branch: if branch.is_empty() {
+                None
+            } else {
+                Some(branch.to_string())
+            },
+            skip_if_running: Some(true),
+            job_id: job_id.to_string(),
+        }
+    }
+}
+
+/// A struct representing a partial update of a job schedule.
+#[derive(Clone, Debug, Default, InputObject, Serialize, Deserialize, Validate)]
+pub struct UpdateSchedule {
+    /// schedule cron expression
+    #[validate(custom(validate_optional_cron))]
+    pub cron: Option<String>,
+    /// schedule branch - empty string runs the schedule on the project main branch
+    #[validate(max_length = 256)]
+    pub branch: Option<String>,
+    /// skip scheduled run if the previous pipeline is still in progress
+    #[serde(rename(deserialize = "skipIfRunning", deserialize = "skip_if_running"))]
+    pub skip_if_running: Option<bool>,
+}
+
+impl UpdateSchedule {
+    /// Apply the update to a schedule
+    #[must_use]
+    pub fn apply(&self, schedule: &Schedule) -> Schedule {
+        let mut schedule = schedule.clone();
+        if let Some(cron) = &self.cron {
+            schedule.cron.clone_from(cron);
+        }
+        match self.branch.as_deref() {
+            Some("") => schedule.branch = None,
+            Some(branch) => schedule.branch = Some(branch.to_string()),
+            None => {}
+        }
+        if let Some(skip_if_running) = self.skip_if_running {
+            schedule.skip_if_running = skip_if_running;
+        }
+        schedule
+    }
+}
+
+/// Parse a cron expression.
+///
+/// Standard 5-field expressions (`minute hour day month weekday`) are supported
+/// alongside the extended format with seconds and an optional year.
+///
+/// # Errors
+///
+/// This function can generate the following errors:
+///
+/// * `RustyError` - If the expression is not a valid cron expression.
+pub fn parse_cron(expression: &str) -> Result<cron::Schedule, RustyError> {
+    let expression = expression.trim();
+    let expression = if expression.split_whitespace().count() == 5 {
+        format!("0 {expression}")
+    } else {
+        expression.to_string()
+    };
+    cron::Schedule::from_str(&expression)
+        .map_err(|err| RustyError::ValidationError(format!("Invalid cron expression: {err}")))
+}
+
+fn validate_cron(cron: &str) -> Result<(), validation::Error> {
+    match parse_cron(cron) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(validation::Error::Custom(
+            "Invalid cron expression".to_owned(),
+        )),
+    }
+}
+
+fn validate_optional_cron(cron: &Option<String>) -> Result<(), validation::Error> {
+    cron.as_ref().map_or(Ok(()), |cron| validate_cron(cron))
+}
+
+impl From<&RegisterSchedule> for Schedule {
+    fn from(value: &RegisterSchedule) -> Self {
+        Self {
+            id: Self::generate_id(),
+            cron: value.clone().cron,
+            branch: value.clone().branch,
+            skip_if_running: value.skip_if_running.unwrap_or(true),
+            register_date: Utc::now().to_rfc3339(),
+            last_run: None,
+            job_id: value.clone().job_id,
+        }
+    }
+}
+
+impl RustyDomainItem for Schedule {}
+
+/// A struct representing a paged result Schedules.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct PagedSchedules {
+    /// total amount of entries found
+    pub total: usize,
+    /// current page
+    pub page: usize,
+    /// size of a page
+    pub page_size: usize,
+    /// data returned by query
+    pub entries: Vec<Schedule>,
+}
@@ -1,2 +1,11 @@
 /// Pipeline template
 pub mod pipeline;
+
+/// Template revisions
+pub mod revision;
+
+/// Template JSON Schema
+pub mod schema;
+
+/// Template validation
+pub mod validation;
@@ -1,12 +1,15 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
 
 use async_graphql::indexmap::IndexMap;
-use serde::{Deserialize, Serialize};
+use async_graphql::Enum;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize, Serializer};
 
 use commons::errors::RustyError;
 
 /// Pipeline script
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Script {
     /// pipeline stage commands
     pub script: Vec<String>,
@@ -20,95 +23,796 @@ impl Script {
             script: script.to_vec(),
         }
     }
+
+    /// Render script lines as a single shell program
+    ///
+    /// Lines share shell state (working directory, variables), and the program exits
+    /// with the status of the first failing line - as if each line was run on its own.
+    #[must_use]
+    pub fn to_shell(&self) -> String {
+        self.script.iter().fold(String::new(), |mut shell, line| {
+            let _ = writeln!(
+                shell,
+                "{line}\n__rusty_rc=$?; [ \"$__rusty_rc\" -eq 0 ] || exit \"$__rusty_rc\""
+            );
+            shell
+        })
+    }
+}
+
+/// Pipeline service container - e.g. a database, started next to the stage containers
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Service {
+    /// service docker image
+    pub image: String,
+    /// service hostname on the pipeline network - defaults to the image name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    /// service environment variables
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_env"
+    )]
+    #[schemars(with = "Option<HashMap<String, String>>")]
+    pub env: Option<HashMap<String, String>>,
+    /// service ports awaited before running the stage
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ports: Option<Vec<u16>>,
+}
+
+impl Service {
+    /// Hostname of the service on the pipeline network
+    ///
+    /// Defaults to the image name without registry, namespace and tag, e.g. `postgres` for `docker.io/library/postgres:16`.
+    #[must_use]
+    pub fn hostname(&self) -> String {
+        self.alias.clone().unwrap_or_else(|| {
+            let image = self.image.rsplit('/').next().unwrap_or(&self.image);
+            image.split([':', '@']).next().unwrap_or(image).to_string()
+        })
+    }
+}
+
+/// Pipeline stage container resource limits
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Resources {
+    /// number of cpus, e.g. `1.5`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpus: Option<f64>,
+    /// memory limit, e.g. `512m` or `2g`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<String>,
+    /// maximum number of processes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pids: Option<i64>,
+}
+
+impl Resources {
+    /// Fill limits missing in `self` with the `defaults`
+    #[must_use]
+    pub fn or(&self, defaults: &Self) -> Self {
+        Self {
+            cpus: self.cpus.or(defaults.cpus),
+            memory: self.memory.clone().or_else(|| defaults.memory.clone()),
+            pids: self.pids.or(defaults.pids),
+        }
+    }
+
+    /// Lower limits exceeding the `max` ones - limits missing in `self` are set to `max`
+    ///
+    /// Returns the capped limits, with the names of the limits that were lowered.
+    #[must_use]
+    pub fn capped(&self, max: &Self) -> (Self, Vec<&'static str>) {
+        let mut capped = self.or(max);
+        let mut lowered = vec![];
+        if let (Some(cpus), Some(max)) = (self.cpus, max.cpus) {
+            if cpus > max {
+                capped.cpus = Some(max);
+                lowered.push("cpus");
+            }
+        }
+        if let (Some(memory), Some(max_memory)) = (self.memory_bytes(), max.memory_bytes()) {
+            if memory > max_memory {
+                capped.memory.clone_from(&max.memory);
+                lowered.push("memory");
+            }
+        }
+        if let (Some(pids), Some(max)) = (self.pids, max.pids) {
+            if pids > max {
+                capped.pids = Some(max);
+                lowered.push("pids");
+            }
+        }
+        (capped, lowered)
+    }
+
+    /// Memory limit in bytes
+    #[must_use]
+    pub fn memory_bytes(&self) -> Option<i64> {
+        self.memory.as_deref().and_then(parse_memory)
+    }
+}
+
+/// Parse a memory size, e.g. `512m`, `2g` or `1048576` (bytes)
+///
+/// Units are binary (`1k` is 1024 bytes) and case-insensitive, with an optional `b` suffix.
+#[must_use]
+pub fn parse_memory(value: &str) -> Option<i64> {
+    let value = value.trim().to_lowercase();
+    let value = value.strip_suffix('b').unwrap_or(&value);
+    let (number, unit) = match value.char_indices().last() {
+        Some((index, unit)) if unit.is_ascii_alphabetic() => (&value[..index], Some(unit)),
+        _ => (value, None),
+    };
+    let multiplier: i64 = match unit {
+        None => 1,
+        Some('k') => 1 << 10,
+        Some('m') => 1 << 20,
+        Some('g') => 1 << 30,
+        Some('t') => 1 << 40,
+        Some(_) => return None,
+    };
+    number
+        .parse::<i64>()
+        .ok()
+        .filter(|number| *number > 0)
+        .and_then(|number| number.checked_mul(multiplier))
+}
+
+/// Pipeline stage container security options
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Security {
+    /// user running the stage commands, e.g. `1000:1000` or `nobody`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// linux capabilities dropped from the stage container, e.g. `ALL`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cap_drop: Option<Vec<String>>,
+    /// whether the stage container has network access - defaults to `true`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<bool>,
+}
+
+impl Security {
+    /// Fill options missing in `self` with the `defaults`
+    #[must_use]
+    pub fn or(&self, defaults: &Self) -> Self {
+        Self {
+            user: self.user.clone().or_else(|| defaults.user.clone()),
+            cap_drop: self.cap_drop.clone().or_else(|| defaults.cap_drop.clone()),
+            network: self.network.or(defaults.network),
+        }
+    }
+}
+
+/// Pipeline stage docker image build
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Build {
+    /// build context directory, relative to the repository root - defaults to `.`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+    /// dockerfile path, relative to the build context - defaults to `Dockerfile`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dockerfile: Option<String>,
+    /// image tags, e.g. `ghcr.io/org/app:1.0`
+    pub tags: Vec<String>,
+    /// whether the tags are pushed to their registries - defaults to `false`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub push: Option<bool>,
+}
+
+impl Build {
+    /// Build context directory
+    #[must_use]
+    pub fn context(&self) -> String {
+        self.context.clone().unwrap_or_else(|| ".".to_string())
+    }
+
+    /// Dockerfile path in the build context
+    #[must_use]
+    pub fn dockerfile(&self) -> String {
+        self.dockerfile
+            .clone()
+            .unwrap_or_else(|| "Dockerfile".to_string())
+    }
+}
+
+/// Pipeline stage deployment environment - a successful stage records a deployment
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Environment {
+    /// environment name, e.g. `staging`
+    pub name: String,
+    /// environment url
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Pipeline stage reports - files uploaded by the agent after the stage
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Reports {
+    /// `JUnit` XML test reports - a glob pattern, relative to the repository root
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub junit: Option<String>,
+    /// `LCOV` or `Cobertura` XML coverage reports - a glob pattern, relative to the repository root
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<String>,
+}
+
+// relative path, not leaving the directory it is resolved against
+fn is_nested_path(path: &str) -> bool {
+    !path.starts_with('/') && !path.split(['/', '\\']).any(|segment| segment == "..")
 }
 
 /// Pipeline stage
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Stage {
     /// pipeline docker image
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub image: Option<String>,
     /// pipeline stage environment variables
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_env"
+    )]
+    #[schemars(with = "Option<HashMap<String, String>>")]
     pub env: Option<HashMap<String, String>>,
-    /// pipeline stage commands
+    /// pipeline stage commands - empty for a build stage
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub script: Vec<String>,
+    /// pipeline stage shell running the commands - overrides the pipeline shell
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shell: Option<String>,
     /// pipeline dependencies
-    #[serde(rename(deserialize = "dependsOn", deserialize = "depends_on"))]
+    #[serde(
+        rename(deserialize = "dependsOn", deserialize = "depends_on"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schemars(rename = "dependsOn")]
     pub depends_on: Option<Vec<String>>,
+    /// pipeline stage service containers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub services: Option<Vec<Service>>,
+    /// pipeline stage container resource limits
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resources: Option<Resources>,
+    /// pipeline stage container security options
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<Security>,
+    /// pipeline stage docker image build - run instead of the script
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub build: Option<Build>,
+    /// pipeline stage deployment environment
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub environment: Option<Environment>,
+    /// pipeline stage reports
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reports: Option<Reports>,
+}
+
+/// Pipeline docker image pull policy
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum PullPolicy {
+    /// pull images before every use
+    Always,
+    /// pull images missing on the agent
+    #[default]
+    IfNotPresent,
+    /// never pull images - they must be present on the agent
+    Never,
+}
+
+/// Pipeline runner - backend running the stages on an agent
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RunnerKind {
+    /// stages run in docker containers on the agent host
+    Docker,
+    /// stages run as containers of a kubernetes pod
+    Kubernetes,
+    /// stages run in a shell on the agent machine
+    Shell,
+}
+
+/// Pipeline trigger event
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerEvent {
+    /// push to a branch
+    Push,
+    /// pull request opened or updated
+    PullRequest,
+}
+
+/// Pipeline trigger
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Trigger {
+    /// pipeline trigger events
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events: Option<Vec<TriggerEvent>>,
+    /// pipeline trigger branch filters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branches: Option<Vec<String>>,
+}
+
+impl Trigger {
+    /// Check if an event on a branch matches the trigger
+    ///
+    /// Missing `events` match any event, missing `branches` match the project main branch only.
+    /// Branch filters support `*` wildcards, e.g. `release/*`.
+    #[must_use]
+    pub fn matches(&self, event: TriggerEvent, branch: &str, main_branch: &str) -> bool {
+        let event_matches = self
+            .events
+            .as_ref()
+            .map_or(true, |events| events.contains(&event));
+        let branch_matches = self.branches.as_ref().map_or_else(
+            || branch == main_branch,
+            |branches| branches.iter().any(|filter| matches_branch(filter, branch)),
+        );
+        event_matches && branch_matches
+    }
+}
+
+pub(crate) fn matches_branch(filter: &str, branch: &str) -> bool {
+    let pattern = filter
+        .split('*')
+        .map(regex::escape)
+        .collect::<Vec<String>>()
+        .join(".*");
+    regex::Regex::new(&format!("^{pattern}$")).map_or(false, |re| re.is_match(branch))
 }
 
 /// Pipeline template
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct PipelineTemplate {
     /// pipeline docker image
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub image: Option<String>,
     /// pipeline environment variables
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_env"
+    )]
+    #[schemars(with = "Option<HashMap<String, String>>")]
     pub env: Option<HashMap<String, String>>,
+    /// pipeline shell running the commands, e.g. `bash` - defaults to the agent shell
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shell: Option<String>,
+    /// pipeline runner - defaults to a container runner for templates with an image, `shell` otherwise
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runner: Option<RunnerKind>,
+    /// pipeline docker image pull policy - defaults to `if-not-present`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pull_policy: Option<PullPolicy>,
     /// pipeline before stage
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub before: Option<Script>,
     /// pipeline after stage
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub after: Option<Script>,
     /// pipeline stages
     pub stages: IndexMap<String, Stage>,
+    /// pipeline webhook trigger
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger: Option<Trigger>,
+    /// pipeline service containers - running for the whole pipeline
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub services: Option<Vec<Service>>,
+}
+
+// environment variables are serialized sorted, so the normalized template is stable
+#[allow(clippy::ref_option)]
+fn serialize_env<S: Serializer>(
+    env: &Option<HashMap<String, String>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    env.as_ref()
+        .map(|env| env.iter().collect::<BTreeMap<&String, &String>>())
+        .serialize(serializer)
+}
+
+/// Pipeline template representation
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Enum, Serialize, Deserialize)]
+pub enum TemplateFormat {
+    /// base64-url encoded template in any of the plain formats
+    #[serde(rename(deserialize = "BASE64", deserialize = "Base64"))]
+    Base64,
+    /// plain yaml
+    #[serde(rename(deserialize = "YAML", deserialize = "Yaml"))]
+    Yaml,
+    /// plain json
+    #[serde(rename(deserialize = "JSON", deserialize = "Json"))]
+    Json,
+    /// plain toml
+    #[serde(rename(deserialize = "TOML", deserialize = "Toml"))]
+    Toml,
+}
+
+impl TemplateFormat {
+    /// Detect the representation of a template from its content
+    ///
+    /// Base64 is tried first, then JSON (a document starting with `{`), then TOML.
+    /// Anything else is treated as YAML.
+    #[must_use]
+    pub fn detect(text: &str) -> Self {
+        if decode_base64(text).is_some() {
+            Self::Base64
+        } else {
+            Self::detect_plain(text)
+        }
+    }
+
+    fn detect_plain(text: &str) -> Self {
+        let text = text.trim();
+        if text.starts_with('{') {
+            Self::Json
+        } else if !text.is_empty() && toml::from_str::<toml::Table>(text).is_ok() {
+            Self::Toml
+        } else {
+            Self::Yaml
+        }
+    }
+
+    /// Resolve a template to its plain text and plain format
+    ///
+    /// Returns `None` for a template declared as base64 which cannot be decoded.
+    #[must_use]
+    pub fn resolve(self, text: &str) -> Option<(String, Self)> {
+        if self == Self::Base64 {
+            decode_base64(text).map(|text| {
+                let format = Self::detect_plain(&text);
+                (text, format)
+            })
+        } else {
+            Some((text.to_string(), self))
+        }
+    }
+}
+
+fn is_valid_hostname(hostname: &str) -> bool {
+    !hostname.is_empty()
+        && hostname.len() <= 63
+        && !hostname.starts_with('-')
+        && hostname
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+}
+
+fn decode_base64(text: &str) -> Option<String> {
+    base64_url::decode(text.trim())
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+}
+
+fn check_resources(name: &str, stage: &Stage, errors: &mut Vec<(String, &'static str)>) {
+    let Some(resources) = &stage.resources else {
+        return;
+    };
+    if resources.cpus.is_some_and(|cpus| cpus <= 0.0) {
+        errors.push((
+            format!("stages.{name}.resources.cpus"),
+            "resources.cpus must be positive",
+        ));
+    }
+    if resources.memory.is_some() && resources.memory_bytes().is_none() {
+        errors.push((
+            format!("stages.{name}.resources.memory"),
+            "resources.memory must be a size, e.g. `512m`",
+        ));
+    }
+    if resources.pids.is_some_and(|pids| pids <= 0) {
+        errors.push((
+            format!("stages.{name}.resources.pids"),
+            "resources.pids must be positive",
+        ));
+    }
+}
+
+fn check_security(name: &str, stage: &Stage, errors: &mut Vec<(String, &'static str)>) {
+    let Some(security) = &stage.security else {
+        return;
+    };
+    if security.user.as_ref().is_some_and(|u| u.trim().is_empty()) {
+        errors.push((
+            format!("stages.{name}.security.user"),
+            "security.user cannot be empty",
+        ));
+    }
+    if security.network == Some(false) && stage.services.as_ref().is_some_and(|s| !s.is_empty()) {
+        errors.push((
+            format!("stages.{name}.security.network"),
+            "stage services require network access",
+        ));
+    }
+}
+
+fn check_environment(name: &str, stage: &Stage, errors: &mut Vec<(String, &'static str)>) {
+    if stage
+        .environment
+        .as_ref()
+        .is_some_and(|environment| environment.name.trim().is_empty())
+    {
+        errors.push((
+            format!("stages.{name}.environment.name"),
+            "environment.name cannot be empty",
+        ));
+    }
+}
+
+fn check_reports(name: &str, stage: &Stage, errors: &mut Vec<(String, &'static str)>) {
+    let Some(reports) = &stage.reports else {
+        return;
+    };
+    for (kind, path) in [("junit", &reports.junit), ("coverage", &reports.coverage)] {
+        if path
+            .as_ref()
+            .is_some_and(|path| path.trim().is_empty() || !is_nested_path(path))
+        {
+            errors.push((
+                format!("stages.{name}.reports.{kind}"),
+                "report paths must be relative and stay in the repository",
+            ));
+        }
+    }
+}
+
+// returns whether the stage dependencies can be resolved
+fn check_dependencies(
+    name: &str,
+    stage: &Stage,
+    stage_names: &[String],
+    errors: &mut Vec<(String, &'static str)>,
+) -> bool {
+    let Some(depends_on) = &stage.depends_on else {
+        return true;
+    };
+    let mut valid = true;
+    if depends_on.iter().any(|s| !stage_names.contains(s)) {
+        valid = false;
+        errors.push((
+            format!("stages.{name}.dependsOn"),
+            "stage depends on an unknown stage",
+        ));
+    }
+    if depends_on.iter().any(|s| s == name) {
+        valid = false;
+        errors.push((
+            format!("stages.{name}.dependsOn"),
+            "stage cannot depend on itself",
+        ));
+    }
+    valid
 }
 
 impl PipelineTemplate {
     /// Validate pipeline from yaml
     ///
+    /// Accepts any supported representation, detected from the content - see `TemplateFormat::detect`.
+    ///
     /// # Errors
     ///
     /// This function can generate the following errors:
     ///
     /// * `RustyError` - If there was an error during the creation of the item.
     pub fn from_yaml(text: &str) -> Result<Self, RustyError> {
-        let text = String::from_utf8(base64_url::decode(text)?)?;
-        let result = serde_yaml::from_str::<Self>(&text)?;
+        Self::parse(text, None)
+    }
 
-        let mut errors: Vec<&str> = vec![];
-        if result.stages.is_empty() {
-            errors.push("stages cannot be empty");
+    /// Validate pipeline from a template in the given format
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The template.
+    /// * `format` - The template representation - detected from the content if missing.
+    ///
+    /// # Errors
+    ///
+    /// This function can generate the following errors:
+    ///
+    /// * `RustyError` - If there was an error during the creation of the item.
+    pub fn parse(text: &str, format: Option<TemplateFormat>) -> Result<Self, RustyError> {
+        let format = format.unwrap_or_else(|| TemplateFormat::detect(text));
+        let (text, format) = format.resolve(text).ok_or_else(|| {
+            RustyError::SerializationError("Pipeline template: invalid base64".to_string())
+        })?;
+        let result = match format {
+            TemplateFormat::Json => serde_json::from_str::<Self>(&text)?,
+            TemplateFormat::Toml => toml::from_str::<Self>(&text)
+                .map_err(|err| RustyError::SerializationError(err.message().to_string()))?,
+            TemplateFormat::Base64 | TemplateFormat::Yaml => serde_yaml::from_str::<Self>(&text)?,
+        };
+
+        let errors = result
+            .check()
+            .into_iter()
+            .map(|(_, message)| message)
+            .collect::<Vec<&str>>();
+        if errors.is_empty() {
+            Ok(result)
         } else {
-            let stage_names = result
+            Err(RustyError::SerializationError(
+                format!("Pipeline template: {errors:?}").replace('\"', ""),
+            ))
+        }
+    }
+
+    /// Normalize a template to plain yaml
+    ///
+    /// The normalized form is used for storage - it is readable without decoding
+    /// and stable for equal templates regardless of the input representation.
+    ///
+    /// # Errors
+    ///
+    /// This function can generate the following errors:
+    ///
+    /// * `RustyError` - If the template is invalid.
+    pub fn normalize(text: &str, format: Option<TemplateFormat>) -> Result<String, RustyError> {
+        Ok(serde_yaml::to_string(&Self::parse(text, format)?)?)
+    }
+
+    /// Check if the template uses features supported only by the docker runner - services or build stages
+    #[must_use]
+    pub fn requires_docker(&self) -> bool {
+        self.services.as_ref().is_some_and(|s| !s.is_empty())
+            || self.stages.values().any(|stage| {
+                stage.build.is_some() || stage.services.as_ref().is_some_and(|s| !s.is_empty())
+            })
+    }
+
+    /// Check pipeline template semantics
+    ///
+    /// Returns a list of issues found, each as a dotted path to the offending entry and a message.
+    #[must_use]
+    pub fn check(&self) -> Vec<(String, &'static str)> {
+        let mut errors = vec![];
+        if self.stages.is_empty() {
+            errors.push(("stages".to_string(), "stages cannot be empty"));
+        } else {
+            let stage_names = self
                 .stages
                 .iter()
-                .map(|(s, _)| s.to_string())
+                .map(|(s, _)| s.clone())
                 .collect::<Vec<String>>();
-            result.stages.iter().for_each(|(name, stage)| {
-                if stage.script.is_empty() {
-                    errors.push("stages.script cannot be empty");
-                }
-                if let Some(depends_on) = stage.clone().depends_on {
-                    if depends_on.iter().any(|s| !stage_names.contains(s)) {
-                        errors.push("stage depends on an unknown stage");
-                    }
-                    if depends_on.iter().any(|s| s == name) {
-                        errors.push("stage cannot depend on itself");
-                    }
-                }
+            let mut valid_deps = true;
+            self.stages.iter().for_each(|(name, stage)| {
+                self.check_build(name, stage, &mut errors);
+                check_resources(name, stage, &mut errors);
+                check_security(name, stage, &mut errors);
+                check_environment(name, stage, &mut errors);
+                check_reports(name, stage, &mut errors);
+                valid_deps &= check_dependencies(name, stage, &stage_names, &mut errors);
             });
+            if valid_deps && self.dependency_tree().iter().flatten().count() != self.stages.len() {
+                errors.push(("stages".to_string(), "stages have circular dependencies"));
+            }
         }
 
-        if let Some(before) = result.clone().before {
+        if let Some(before) = &self.before {
             if before.script.is_empty() {
-                errors.push("before.script cannot be empty");
+                errors.push(("before.script".to_string(), "before.script cannot be empty"));
             }
         }
 
-        if let Some(after) = result.clone().after {
+        if let Some(after) = &self.after {
             if after.script.is_empty() {
-                errors.push("after.script cannot be empty");
+                errors.push(("after.script".to_string(), "after.script cannot be empty"));
             }
         }
 
-        if errors.is_empty() {
-            Ok(result)
-        } else {
-            Err(RustyError::SerializationError(
-                format!("Pipeline template: {errors:?}").replace('\"', ""),
-            ))
+        self.check_services(&mut errors);
+        errors
+    }
+
+    // a stage either builds an image or runs a script
+    fn check_build(&self, name: &str, stage: &Stage, errors: &mut Vec<(String, &'static str)>) {
+        if let Some(build) = &stage.build {
+            if !stage.script.is_empty() {
+                errors.push((
+                    format!("stages.{name}.script"),
+                    "build stages cannot have a script",
+                ));
+            }
+            if self.image.is_none() {
+                errors.push((
+                    format!("stages.{name}.build"),
+                    "build stages require a pipeline docker image",
+                ));
+            }
+            if build.tags.is_empty() || build.tags.iter().any(|t| t.trim().is_empty()) {
+                errors.push((
+                    format!("stages.{name}.build.tags"),
+                    "build.tags cannot be empty",
+                ));
+            }
+            if !is_nested_path(&build.context()) || !is_nested_path(&build.dockerfile()) {
+                errors.push((
+                    format!("stages.{name}.build"),
+                    "build paths must be relative and stay in the repository",
+                ));
+            }
+        } else if stage.script.is_empty() {
+            errors.push((
+                format!("stages.{name}.script"),
+                "stages.script cannot be empty",
+            ));
+        }
+    }
+
+    // pipeline and stage services, and the runners able to start them
+    fn check_services(&self, errors: &mut Vec<(String, &'static str)>) {
+        let services = self
+            .stages
+            .iter()
+            .filter_map(|(name, stage)| {
+                stage
+                    .services
+                    .as_ref()
+                    .map(|services| (format!("stages.{name}.services"), services))
+            })
+            .chain(
+                self.services
+                    .as_ref()
+                    .map(|services| ("services".to_string(), services)),
+            )
+            .collect::<Vec<(String, &Vec<Service>)>>();
+        if !services.is_empty() && self.image.is_none() {
+            errors.push((
+                "services".to_string(),
+                "services require a pipeline docker image",
+            ));
+        }
+        match self.runner {
+            Some(RunnerKind::Docker | RunnerKind::Kubernetes) if self.image.is_none() => {
+                errors.push((
+                    "runner".to_string(),
+                    "container runners require a pipeline docker image",
+                ));
+            }
+            Some(RunnerKind::Kubernetes | RunnerKind::Shell)
+                if !services.is_empty() || self.stages.values().any(|s| s.build.is_some()) =>
+            {
+                errors.push((
+                    "runner".to_string(),
+                    "services and build stages require the docker runner",
+                ));
+            }
+            _ => {}
+        }
+        let global = self.services.clone().unwrap_or_default();
+        for (path, services) in services {
+            if services.iter().any(|s| s.image.trim().is_empty()) {
+                errors.push((path.clone(), "services.image cannot be empty"));
+            }
+            let mut hostnames = services
+                .iter()
+                .map(Service::hostname)
+                .collect::<Vec<String>>();
+            if path != "services" {
+                hostnames.extend(global.iter().map(Service::hostname));
+            }
+            if hostnames.iter().any(|h| !is_valid_hostname(h)) {
+                errors.push((path.clone(), "services.alias must be a valid hostname"));
+            }
+            hostnames.sort();
+            if hostnames.windows(2).any(|w| w[0] == w[1]) {
+                errors.push((path, "services.alias must be unique"));
+            }
         }
     }
 
+    /// Check if a webhook event on a branch should trigger the pipeline
+    #[must_use]
+    pub fn is_triggered(&self, event: TriggerEvent, branch: &str, main_branch: &str) -> bool {
+        self.trigger.as_ref().map_or_else(
+            || event == TriggerEvent::Push && branch == main_branch,
+            |trigger| trigger.matches(event, branch, main_branch),
+        )
+    }
+
     /// Build dependency tree of stages to run
+    ///
+    /// Stages which dependencies can never be satisfied are left out of the tree.
     #[must_use]
     pub fn dependency_tree(&self) -> Vec<Vec<String>> {
         let mut stages = self.clone().stages;
@@ -126,6 +830,9 @@ impl PipelineTemplate {
                 })
                 .map(|(name, _)| name)
                 .collect::<Vec<String>>();
+            if deps_stage.is_empty() {
+                break;
+            }
             results.push(deps_stage.clone());
             for dep in deps_stage.clone() {
                 stages.shift_remove(&dep);
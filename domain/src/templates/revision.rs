@@ -0,0 +1,140 @@
+use async_graphql::{Enum, SimpleObject};
+use serde::{Deserialize, Serialize};
+
+use commons::hashing::sha::sha256;
+
+use crate::RustyDomainItem;
+
+/// A struct representing an immutable revision of a job pipeline template.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct TemplateRevision {
+    /// revision id
+    pub id: String,
+    /// revision order number
+    pub number: u64,
+    /// revision register date
+    pub date: String,
+    /// revision author username
+    pub author: String,
+    /// revision content hash (SHA-256)
+    pub hash: String,
+    /// revision pipeline template
+    pub template: String,
+    /// revision job id
+    #[serde(rename(deserialize = "jobId", deserialize = "job_id"))]
+    pub job_id: String,
+}
+
+impl TemplateRevision {
+    /// constructor
+    #[must_use]
+    pub fn new(job_id: &str, number: u64, author: &str, template: &str) -> Self {
+        Self {
+            id: Self::generate_id(),
+            number,
+            date: chrono::Utc::now().to_rfc3339(),
+            author: author.to_string(),
+            hash: sha256(template),
+            template: template.to_string(),
+            job_id: job_id.to_string(),
+        }
+    }
+
+    /// Check if the revision holds the given template
+    #[must_use]
+    pub fn matches(&self, template: &str) -> bool {
+        self.hash == sha256(template)
+    }
+}
+
+impl RustyDomainItem for TemplateRevision {}
+
+/// A struct representing a paged result Template Revisions.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct PagedTemplateRevisions {
+    /// total amount of entries found
+    pub total: usize,
+    /// current page
+    pub page: usize,
+    /// size of a page
+    pub page_size: usize,
+    /// data returned by query
+    pub entries: Vec<TemplateRevision>,
+}
+
+/// An enum representing a kind of a diff line.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Enum, Serialize, Deserialize)]
+pub enum DiffKind {
+    /// Line present in both revisions.
+    Unchanged,
+    /// Line present only in the newer revision.
+    Added,
+    /// Line present only in the older revision.
+    Removed,
+}
+
+/// A struct representing a single line of a template diff.
+#[derive(Clone, Debug, Eq, PartialEq, SimpleObject, Serialize, Deserialize)]
+pub struct DiffLine {
+    /// diff line kind
+    pub kind: DiffKind,
+    /// diff line content
+    pub line: String,
+}
+
+/// Compute a line diff between two templates
+///
+/// Base64 encoded templates are decoded before comparison.
+///
+/// # Arguments
+///
+/// * `old` - The older template.
+/// * `new` - The newer template.
+#[must_use]
+pub fn diff(old: &str, new: &str) -> Vec<DiffLine> {
+    let (old, new) = (decode(old), decode(new));
+    let old = old.lines().collect::<Vec<&str>>();
+    let new = new.lines().collect::<Vec<&str>>();
+
+    // longest common subsequence lengths of the remaining suffixes
+    let mut lcs = vec![vec![0_usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let line = |kind, line: &str| DiffLine {
+        kind,
+        line: line.to_string(),
+    };
+    let (mut i, mut j) = (0, 0);
+    let mut result = vec![];
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            result.push(line(DiffKind::Unchanged, old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            result.push(line(DiffKind::Removed, old[i]));
+            i += 1;
+        } else {
+            result.push(line(DiffKind::Added, new[j]));
+            j += 1;
+        }
+    }
+    result.extend(old[i..].iter().map(|l| line(DiffKind::Removed, l)));
+    result.extend(new[j..].iter().map(|l| line(DiffKind::Added, l)));
+    result
+}
+
+fn decode(template: &str) -> String {
+    base64_url::decode(template)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .unwrap_or_else(|| template.to_string())
+}
@@ -0,0 +1,62 @@
+use async_graphql::SimpleObject;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::templates::pipeline::PipelineTemplate;
+
+/// Version of the pipeline template format - bumped on every change of the template types.
+///
+/// `v2` added service containers, shells, runners, pull policies, resources, security options,
+/// build stages, deployment environments and reports.
+pub const TEMPLATE_SCHEMA_VERSION: &str = "v2";
+
+/// A struct representing a versioned JSON Schema of the pipeline template format.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct TemplateSchema {
+    /// template format version
+    pub version: String,
+    /// JSON Schema document
+    pub schema: Value,
+}
+
+impl TemplateSchema {
+    /// Build the schema of the current template format
+    #[must_use]
+    pub fn current() -> Self {
+        Self {
+            version: TEMPLATE_SCHEMA_VERSION.to_string(),
+            schema: json_schema(),
+        }
+    }
+}
+
+/// Generate a JSON Schema (draft-07) of the pipeline template
+///
+/// Generated from the `PipelineTemplate` types.
+/// Stage dependencies are accepted both as `dependsOn` and `depends_on`.
+#[must_use]
+pub fn json_schema() -> Value {
+    let mut schema =
+        serde_json::to_value(schemars::schema_for!(PipelineTemplate)).unwrap_or_default();
+    if let Some(properties) = schema
+        .pointer_mut("/definitions/Stage/properties")
+        .and_then(Value::as_object_mut)
+    {
+        if let Some(depends_on) = properties.get("dependsOn").cloned() {
+            properties.insert("depends_on".to_string(), depends_on);
+        }
+    }
+    if let Some(root) = schema.as_object_mut() {
+        root.insert(
+            "$id".to_string(),
+            Value::String(format!(
+                "/schemas/pipeline-template/{TEMPLATE_SCHEMA_VERSION}.json"
+            )),
+        );
+        root.insert(
+            "version".to_string(),
+            Value::String(TEMPLATE_SCHEMA_VERSION.to_string()),
+        );
+    }
+    schema
+}
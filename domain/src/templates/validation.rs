@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use async_graphql::SimpleObject;
+use serde::{Deserialize, Serialize};
+
+use crate::templates::pipeline::{PipelineTemplate, Service, TemplateFormat};
+
+/// A struct representing a single issue found in a pipeline template.
+#[derive(Clone, Debug, Eq, PartialEq, SimpleObject, Serialize, Deserialize)]
+pub struct TemplateDiagnostic {
+    /// dotted path to the offending entry - empty for the whole document
+    pub path: String,
+    /// diagnostic message
+    pub message: String,
+    /// line in the decoded yaml (1-based)
+    pub line: Option<usize>,
+    /// column in the decoded yaml (1-based)
+    pub column: Option<usize>,
+}
+
+/// A struct representing an environment variable.
+#[derive(Clone, Debug, Eq, PartialEq, SimpleObject, Serialize, Deserialize)]
+pub struct EnvVariable {
+    /// variable name
+    pub name: String,
+    /// variable value
+    pub value: String,
+}
+
+/// A struct representing a stage as it would be run by an agent.
+#[derive(Clone, Debug, Eq, PartialEq, SimpleObject, Serialize, Deserialize)]
+pub struct StagePreview {
+    /// stage name
+    pub name: String,
+    /// effective stage docker image
+    pub image: Option<String>,
+    /// effective stage environment variables - pipeline env overridden by stage env
+    pub env: Vec<EnvVariable>,
+    /// stage dependencies
+    pub depends_on: Vec<String>,
+    /// stage service hostnames - pipeline services followed by stage services
+    pub services: Vec<String>,
+}
+
+/// A struct representing the result of a pipeline template validation.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct TemplateValidation {
+    /// whether the template can be registered
+    pub valid: bool,
+    /// issues found in the template
+    pub diagnostics: Vec<TemplateDiagnostic>,
+    /// resolved stages - each entry is a group of stages run in parallel
+    pub stages: Vec<Vec<StagePreview>>,
+}
+
+/// Validate a pipeline template
+///
+/// Collects all issues found instead of failing on the first one.
+/// Stages are resolved only for a valid template.
+/// Line and column are reported against the decoded template; for semantic issues only in yaml.
+///
+/// # Arguments
+///
+/// * `template` - The template.
+/// * `format` - The template representation - detected from the content if missing.
+#[must_use]
+pub fn validate(template: &str, format: Option<TemplateFormat>) -> TemplateValidation {
+    let format = format.unwrap_or_else(|| TemplateFormat::detect(template));
+    let Some((text, format)) = format.resolve(template) else {
+        return invalid(diagnostic("", "template is not a valid base64 string"));
+    };
+
+    let parsed = match format {
+        TemplateFormat::Json => {
+            serde_json::from_str::<PipelineTemplate>(&text).map_err(|err| json_diagnostic(&err))
+        }
+        TemplateFormat::Toml => {
+            toml::from_str::<PipelineTemplate>(&text).map_err(|err| toml_diagnostic(&text, &err))
+        }
+        TemplateFormat::Base64 | TemplateFormat::Yaml => {
+            serde_yaml::from_str::<PipelineTemplate>(&text).map_err(|err| parse_diagnostic(&err))
+        }
+    };
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(diagnostic) => return invalid(diagnostic),
+    };
+
+    let diagnostics = parsed
+        .check()
+        .into_iter()
+        .map(|(path, message)| {
+            let (line, column) = if format == TemplateFormat::Yaml {
+                locate(&text, &path).map_or((None, None), |(l, c)| (Some(l), Some(c)))
+            } else {
+                (None, None)
+            };
+            TemplateDiagnostic {
+                path,
+                message: message.to_string(),
+                line,
+                column,
+            }
+        })
+        .collect::<Vec<TemplateDiagnostic>>();
+    if !diagnostics.is_empty() {
+        return TemplateValidation {
+            valid: false,
+            diagnostics,
+            stages: vec![],
+        };
+    }
+
+    let stages = parsed
+        .dependency_tree()
+        .iter()
+        .map(|group| group.iter().map(|name| preview(&parsed, name)).collect())
+        .collect();
+    TemplateValidation {
+        valid: true,
+        diagnostics,
+        stages,
+    }
+}
+
+fn invalid(diagnostic: TemplateDiagnostic) -> TemplateValidation {
+    TemplateValidation {
+        valid: false,
+        diagnostics: vec![diagnostic],
+        stages: vec![],
+    }
+}
+
+fn diagnostic(path: &str, message: &str) -> TemplateDiagnostic {
+    TemplateDiagnostic {
+        path: path.to_string(),
+        message: message.to_string(),
+        line: None,
+        column: None,
+    }
+}
+
+// serde_yaml reports the offending path as a message prefix, e.g. `stages.test: missing field`
+fn parse_diagnostic(err: &serde_yaml::Error) -> TemplateDiagnostic {
+    let message = err.to_string();
+    let message = message
+        .rsplit_once(" at line ")
+        .map_or(message.as_str(), |(message, _)| message);
+    let mut result = match message.split_once(": ") {
+        Some((path, message)) if !path.contains(char::is_whitespace) => diagnostic(path, message),
+        _ => diagnostic("", message),
+    };
+    if let Some(location) = err.location() {
+        result.line = Some(location.line());
+        result.column = Some(location.column());
+    }
+    result
+}
+
+fn json_diagnostic(err: &serde_json::Error) -> TemplateDiagnostic {
+    let message = err.to_string();
+    let message = message
+        .rsplit_once(" at line ")
+        .map_or(message.as_str(), |(message, _)| message);
+    let mut result = diagnostic("", message);
+    result.line = Some(err.line());
+    result.column = Some(err.column());
+    result
+}
+
+fn toml_diagnostic(text: &str, err: &toml::de::Error) -> TemplateDiagnostic {
+    let mut result = diagnostic("", err.message());
+    if let Some(span) = err.span() {
+        let before = &text[..span.start.min(text.len())];
+        result.line = Some(before.matches('\n').count() + 1);
+        result.column = Some(before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1);
+    }
+    result
+}
+
+// best effort lookup of a dotted path in block style yaml, falling back to the closest parent found
+fn locate(text: &str, path: &str) -> Option<(usize, usize)> {
+    let lines = text.lines().collect::<Vec<&str>>();
+    let mut found: Option<(usize, usize)> = None;
+    for segment in path.split('.') {
+        let start = found.map_or(0, |(line, _)| line + 1);
+        let mut current = None;
+        for (index, line) in lines.iter().enumerate().skip(start) {
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let indent = line.len() - trimmed.len();
+            if found.is_some_and(|(_, parent)| indent <= parent) {
+                break;
+            }
+            let key = trimmed
+                .split_once(':')
+                .map(|(key, _)| key.trim().trim_matches(|c| c == '"' || c == '\''));
+            if key == Some(segment) {
+                current = Some((index, indent));
+                break;
+            }
+        }
+        match current {
+            Some(location) => found = Some(location),
+            None => break,
+        }
+    }
+    found.map(|(line, indent)| (line + 1, indent + 1))
+}
+
+fn preview(template: &PipelineTemplate, name: &str) -> StagePreview {
+    let stage = &template.stages[name];
+    let mut env = HashMap::new();
+    for source in [&template.env, &stage.env].into_iter().flatten() {
+        env.extend(source.clone());
+    }
+    let mut env = env
+        .into_iter()
+        .map(|(name, value)| EnvVariable { name, value })
+        .collect::<Vec<EnvVariable>>();
+    env.sort_by(|a, b| a.name.cmp(&b.name));
+    StagePreview {
+        name: name.to_string(),
+        image: stage.image.clone().or_else(|| template.image.clone()),
+        env,
+        depends_on: stage.depends_on.clone().unwrap_or_default(),
+        services: [&template.services, &stage.services]
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(Service::hostname)
+            .collect(),
+    }
+}
@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+
+use async_graphql::{Enum, InputObject, SimpleObject};
+use serde::{Deserialize, Serialize};
+
+use crate::pipelines::Pipeline;
+use crate::RustyDomainItem;
+
+/// An enum representing a test case result.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Enum, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TestStatus {
+    /// test passed
+    Passed,
+    /// test assertion failed
+    Failed,
+    /// test could not run to completion
+    Error,
+    /// test was not run
+    Skipped,
+}
+
+impl TestStatus {
+    /// Check if the status is a failed test
+    #[must_use]
+    pub fn is_failure(self) -> bool {
+        matches!(self, Self::Failed | Self::Error)
+    }
+}
+
+/// A struct representing a test case of a test report.
+#[derive(Clone, Debug, PartialEq, SimpleObject, InputObject, Serialize, Deserialize)]
+#[graphql(input_name = "TestCaseInput")]
+pub struct TestCase {
+    /// test name
+    pub name: String,
+    /// test class or module
+    pub classname: Option<String>,
+    /// duration in seconds
+    pub duration: f64,
+    /// test result
+    pub status: TestStatus,
+    /// failure or error message
+    pub message: Option<String>,
+}
+
+/// A struct representing a test suite of a test report.
+#[derive(Clone, Debug, PartialEq, SimpleObject, InputObject, Serialize, Deserialize)]
+#[graphql(input_name = "TestSuiteInput")]
+pub struct TestSuite {
+    /// suite name
+    pub name: String,
+    /// duration in seconds
+    pub duration: f64,
+    /// suite test cases
+    pub cases: Vec<TestCase>,
+}
+
+/// A struct representing the test results of a pipeline stage.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct TestReport {
+    /// report id
+    pub id: String,
+    /// report upload date
+    pub date: String,
+    /// stage producing the report
+    pub stage: String,
+    /// pipeline branch
+    pub branch: String,
+    /// total amount of test cases
+    pub tests: u64,
+    /// amount of failed test cases - including errors
+    pub failures: u64,
+    /// amount of skipped test cases
+    pub skipped: u64,
+    /// report test suites
+    pub suites: Vec<TestSuite>,
+    /// pipeline id
+    #[serde(rename(deserialize = "pipelineId", deserialize = "pipeline_id"))]
+    pub pipeline_id: String,
+    /// pipeline order number
+    #[serde(rename(deserialize = "pipelineNumber", deserialize = "pipeline_number"))]
+    pub pipeline_number: u64,
+    /// job id
+    #[serde(rename(deserialize = "jobId", deserialize = "job_id"))]
+    pub job_id: String,
+    /// project id
+    #[serde(rename(deserialize = "projectId", deserialize = "project_id"))]
+    pub project_id: String,
+}
+
+impl TestReport {
+    /// Flatten the report test cases
+    #[must_use]
+    pub fn results(&self) -> Vec<TestResult> {
+        self.suites
+            .iter()
+            .flat_map(|suite| {
+                suite.cases.iter().map(|case| TestResult {
+                    suite: suite.name.clone(),
+                    name: case.name.clone(),
+                    classname: case.classname.clone(),
+                    duration: case.duration,
+                    status: case.status,
+                    message: case.message.clone(),
+                    stage: self.stage.clone(),
+                    pipeline_id: self.pipeline_id.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+impl RustyDomainItem for TestReport {}
+
+/// A struct representing a paged result Test Reports.
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
+pub struct PagedTestReports {
+    /// total amount of entries found
+    pub total: usize,
+    /// current page
+    pub page: usize,
+    /// size of a page
+    pub page_size: usize,
+    /// data returned by query
+    pub entries: Vec<TestReport>,
+}
+
+/// A struct representing a test case result of a pipeline.
+#[derive(Clone, Debug, PartialEq, SimpleObject, Serialize, Deserialize)]
+pub struct TestResult {
+    /// suite name
+    pub suite: String,
+    /// test name
+    pub name: String,
+    /// test class or module
+    pub classname: Option<String>,
+    /// duration in seconds
+    pub duration: f64,
+    /// test result
+    pub status: TestStatus,
+    /// failure or error message
+    pub message: Option<String>,
+    /// stage producing the report
+    pub stage: String,
+    /// pipeline id
+    pub pipeline_id: String,
+}
+
+/// A struct representing a test both passing and failing across pipelines.
+#[derive(Clone, Debug, PartialEq, Eq, SimpleObject, Serialize, Deserialize)]
+pub struct FlakyTest {
+    /// suite name
+    pub suite: String,
+    /// test name
+    pub name: String,
+    /// test class or module
+    pub classname: Option<String>,
+    /// amount of passed runs
+    pub passes: u64,
+    /// amount of failed runs
+    pub failures: u64,
+    /// pipeline id of the latest failure
+    pub last_failure: String,
+}
+
+/// Build a test report from the uploaded suites, with its counters
+#[must_use]
+pub fn new_report(
+    id: &str,
+    stage: &str,
+    suites: Vec<TestSuite>,
+    pipeline: &Pipeline,
+    project_id: &str,
+) -> TestReport {
+    let cases = suites.iter().flat_map(|suite| &suite.cases);
+    let count = |f: fn(&&TestCase) -> bool| cases.clone().filter(f).count() as u64;
+    TestReport {
+        id: id.to_string(),
+        date: chrono::Utc::now().to_rfc3339(),
+        stage: stage.to_string(),
+        branch: pipeline.branch.clone(),
+        tests: count(|_| true),
+        failures: count(|case| case.status.is_failure()),
+        skipped: count(|case| case.status == TestStatus::Skipped),
+        suites,
+        pipeline_id: pipeline.id.clone(),
+        pipeline_number: pipeline.number,
+        job_id: pipeline.job_id.clone(),
+        project_id: project_id.to_string(),
+    }
+}
+
+/// Failed test cases of the reports
+#[must_use]
+pub fn failures(reports: &[TestReport]) -> Vec<TestResult> {
+    reports
+        .iter()
+        .flat_map(TestReport::results)
+        .filter(|result| result.status.is_failure())
+        .collect()
+}
+
+/// Slowest test cases of the reports, the slowest first
+#[must_use]
+pub fn slowest(reports: &[TestReport], limit: usize) -> Vec<TestResult> {
+    let mut results = reports
+        .iter()
+        .flat_map(TestReport::results)
+        .collect::<Vec<TestResult>>();
+    results.sort_by(|a, b| b.duration.total_cmp(&a.duration));
+    results.truncate(limit);
+    results
+}
+
+/// Tests both passing and failing in the reports, the most failing first
+///
+/// Tests are identified by their suite, class and name.
+#[must_use]
+pub fn flaky(reports: &[TestReport]) -> Vec<FlakyTest> {
+    let mut reports = reports.iter().collect::<Vec<&TestReport>>();
+    reports.sort_by_key(|report| report.pipeline_number);
+    let mut tests: HashMap<(String, Option<String>, String), FlakyTest> = HashMap::new();
+    for result in reports.iter().flat_map(|report| report.results()) {
+        let key = (
+            result.suite.clone(),
+            result.classname.clone(),
+            result.name.clone(),
+        );
+        let test = tests.entry(key).or_insert_with(|| FlakyTest {
+            suite: result.suite.clone(),
+            name: result.name.clone(),
+            classname: result.classname.clone(),
+            passes: 0,
+            failures: 0,
+            last_failure: String::new(),
+        });
+        if result.status.is_failure() {
+            test.failures += 1;
+            test.last_failure = result.pipeline_id;
+        } else if result.status == TestStatus::Passed {
+            test.passes += 1;
+        }
+    }
+    let mut flaky = tests
+        .into_values()
+        .filter(|test| test.passes > 0 && test.failures > 0)
+        .collect::<Vec<FlakyTest>>();
+    flaky.sort_by(|a, b| {
+        b.failures
+            .cmp(&a.failures)
+            .then_with(|| (&a.suite, &a.name).cmp(&(&b.suite, &b.name)))
+    });
+    flaky
+}
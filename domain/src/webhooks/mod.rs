@@ -0,0 +1,152 @@
+use async_graphql::Enum;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::templates::pipeline::TriggerEvent;
+
+/// Git forge sending a webhook
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Enum, Serialize, Deserialize)]
+#[graphql(rename_items = "UPPERCASE")]
+#[serde(rename_all = "lowercase")]
+pub enum Forge {
+    /// `GitHub` webhooks
+    GitHub,
+    /// `GitLab` webhooks
+    GitLab,
+    /// `Gitea` webhooks
+    Gitea,
+}
+
+/// A struct representing a git webhook event.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WebhookEvent {
+    /// webhook event type
+    pub event: TriggerEvent,
+    /// webhook event branch
+    pub branch: String,
+    /// webhook event head commit sha
+    pub commit: Option<String>,
+    /// webhook repository urls
+    pub urls: Vec<String>,
+}
+
+impl WebhookEvent {
+    /// Parse a webhook payload sent by a git forge
+    ///
+    /// # Arguments
+    ///
+    /// * `forge` - The forge which sent the webhook.
+    /// * `event` - The event name, as sent in the forge event header.
+    /// * `payload` - The webhook payload.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the event is not supported or should not trigger pipelines
+    /// (tag pushes, branch deletions, closed pull requests).
+    #[must_use]
+    pub fn parse(forge: Forge, event: &str, payload: &Value) -> Option<Self> {
+        match (forge, event) {
+            (Forge::GitHub | Forge::Gitea, "push") | (Forge::GitLab, "Push Hook") => {
+                Self::parse_push(forge, payload)
+            }
+            (Forge::GitHub | Forge::Gitea, "pull_request") => {
+                let action = get_str(payload, &["action"])?;
+                if !["opened", "reopened", "synchronize", "synchronized"].contains(&action) {
+                    return None;
+                }
+                Some(Self {
+                    event: TriggerEvent::PullRequest,
+                    branch: get_str(payload, &["pull_request", "head", "ref"])?.to_string(),
+                    commit: get_string(payload, &["pull_request", "head", "sha"]),
+                    urls: get_urls(forge, payload),
+                })
+            }
+            (Forge::GitLab, "Merge Request Hook") => {
+                let action = get_str(payload, &["object_attributes", "action"])?;
+                if !["open", "reopen", "update"].contains(&action) {
+                    return None;
+                }
+                Some(Self {
+                    event: TriggerEvent::PullRequest,
+                    branch: get_str(payload, &["object_attributes", "source_branch"])?.to_string(),
+                    commit: get_string(payload, &["object_attributes", "last_commit", "id"]),
+                    urls: get_urls(forge, payload),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_push(forge: Forge, payload: &Value) -> Option<Self> {
+        let branch = get_str(payload, &["ref"])?.strip_prefix("refs/heads/")?;
+        let deleted = payload
+            .get("deleted")
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+            || get_str(payload, &["after"]).is_some_and(|after| after.chars().all(|c| c == '0'));
+        if deleted {
+            return None;
+        }
+        Some(Self {
+            event: TriggerEvent::Push,
+            branch: branch.to_string(),
+            commit: get_string(payload, &["after"]),
+            urls: get_urls(forge, payload),
+        })
+    }
+
+    /// Check if the event refers to a repository under the given url
+    #[must_use]
+    pub fn matches_url(&self, url: &str) -> bool {
+        let url = normalize_url(url);
+        self.urls.iter().any(|u| normalize_url(u) == url)
+    }
+}
+
+/// Normalize a repository url, so that https, ssh and scp-like forms compare equal
+///
+/// e.g. `https://github.com/owner/repo.git` and `git@github.com:owner/repo` both
+/// become `github.com/owner/repo`.
+#[must_use]
+pub fn normalize_url(url: &str) -> String {
+    let url = url.trim().to_lowercase();
+    let url = url.split_once("://").map_or(url.as_str(), |(_, rest)| rest);
+    let url = match url.split_once('@') {
+        Some((user, rest)) if !user.contains('/') => rest,
+        _ => url,
+    };
+    url.replacen(':', "/", 1)
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .to_string()
+}
+
+fn get_str<'a>(payload: &'a Value, path: &[&str]) -> Option<&'a str> {
+    path.iter()
+        .try_fold(payload, |value, key| value.get(key))
+        .and_then(Value::as_str)
+}
+
+fn get_string(payload: &Value, path: &[&str]) -> Option<String> {
+    get_str(payload, path).map(ToString::to_string)
+}
+
+fn get_urls(forge: Forge, payload: &Value) -> Vec<String> {
+    let paths: &[&[&str]] = match forge {
+        Forge::GitHub | Forge::Gitea => &[
+            &["repository", "clone_url"],
+            &["repository", "html_url"],
+            &["repository", "ssh_url"],
+        ],
+        Forge::GitLab => &[
+            &["project", "git_http_url"],
+            &["project", "git_ssh_url"],
+            &["project", "web_url"],
+        ],
+    };
+    paths
+        .iter()
+        .filter_map(|path| get_str(payload, path))
+        .map(ToString::to_string)
+        .collect()
+}
@@ -124,6 +124,22 @@ impl DbClient {
         }
     }
 
+    /// Wrapper for `lock` function
+    ///
+    /// # Errors
+    ///
+    /// This function can generate the following errors:
+    ///
+    /// * `RustyError` - If there was an error during the creation of the item.
+    pub async fn lock(&self, index: &str, id: &str, ttl: u64) -> Result<bool, RustyError> {
+        match self {
+            Self::InMemory(client) => client.lock(index, id, ttl).await,
+            Self::MongoDb(client) => client.lock(index, id, ttl).await,
+            Self::PostgreSql(client) => client.lock(index, id, ttl).await,
+            Self::Redis(client) => client.lock(index, id, ttl).await,
+        }
+    }
+
     /// Wrapper for `delete_one` function
     ///
     /// # Errors
@@ -10,7 +10,7 @@ use serde_json::{json, Value};
 use commons::errors::RustyError;
 use domain::commons::search::SearchOptions;
 
-use crate::shared::{delete_one_filter, filter_results, get_value_id, sort_results};
+use crate::shared::{delete_one_filter, filter_results, get_value_id, sort_results, timestamp};
 use crate::{Persistence, PersistenceBuilder};
 
 type Store = Arc<Mutex<HashMap<String, HashMap<String, Value>>>>;
@@ -139,6 +139,23 @@ impl Persistence for InMemoryClient {
         Ok(1)
     }
 
+    #[allow(clippy::significant_drop_tightening)]
+    async fn lock(&self, index: &str, id: &str, ttl: u64) -> Result<bool, RustyError> {
+        let now = timestamp();
+        let mut guarded_store = self.store.lock().unwrap();
+        let locks = guarded_store.entry(index.to_string()).or_default();
+        let expired = locks
+            .get(id)
+            .and_then(|lock| lock.get("expiry"))
+            .and_then(Value::as_i64)
+            .map_or(true, |expiry| expiry < now);
+        if expired {
+            let expiry = now.saturating_add(i64::try_from(ttl)?);
+            locks.insert(id.to_string(), json!({ "id": id, "expiry": expiry }));
+        }
+        Ok(expired)
+    }
+
     async fn delete_one(&self, index: &str, filter: Value) -> Result<u64, RustyError> {
         let filter = delete_one_filter(&filter);
         self.get_one(index, filter).await?.map_or(Ok(0), |found| {
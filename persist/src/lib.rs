@@ -232,6 +232,33 @@ pub trait Persistence: Send + Sync {
         entry: &str,
     ) -> impl Future<Output = Result<u64, RustyError>> + Send;
 
+    /// Acquires a named lock in the specified index.
+    ///
+    /// The lock is granted if it is not held yet or if the previous holder's lock has expired.
+    /// It allows coordinating work between multiple instances sharing the same database.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The name of the index where the lock is stored.
+    /// * `id` - The name of the lock.
+    /// * `ttl` - Time (in seconds) after which the lock expires.
+    ///
+    /// # Returns
+    ///
+    /// A future that resolves to `true` if the lock was acquired, `false` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// This function can generate the following errors:
+    ///
+    /// * `RustyError` - If there was an error during the creation of the item.
+    fn lock(
+        &self,
+        index: &str,
+        id: &str,
+        ttl: u64,
+    ) -> impl Future<Output = Result<bool, RustyError>> + Send;
+
     /// Deletes an item from the database.
     ///
     /// # Arguments
@@ -10,7 +10,7 @@ use commons::env::{var, var_or_default};
 use commons::errors::RustyError;
 use domain::commons::search::{SearchOptions, SortOptions};
 
-use crate::shared::{filter_results, get_value_id};
+use crate::shared::{filter_results, get_value_id, timestamp};
 use crate::{Persistence, PersistenceBuilder};
 
 /// Represents a `MongoDB` client.
@@ -177,6 +177,31 @@ impl Persistence for MongoDBClient {
         Ok(1)
     }
 
+    async fn lock(&self, index: &str, id: &str, ttl: u64) -> Result<bool, RustyError> {
+        let collection = self
+            .client
+            .database(&self.database)
+            .collection::<Document>(index);
+
+        let now = timestamp();
+        let expiry = now.saturating_add(i64::try_from(ttl)?);
+        if collection
+            .insert_one(doc! { "_id": id, "id": id, "expiry": expiry })
+            .await
+            .is_ok()
+        {
+            return Ok(true);
+        }
+
+        let result = collection
+            .update_one(
+                doc! { "_id": id, "expiry": { "$lt": now } },
+                doc! { "$set": { "expiry": expiry } },
+            )
+            .await?;
+        Ok(result.modified_count == 1)
+    }
+
     async fn delete_one(&self, index: &str, filter: Value) -> Result<u64, RustyError> {
         self.client
             .database(&self.database)
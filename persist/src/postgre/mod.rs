@@ -1,15 +1,16 @@
+use std::collections::HashMap;
 use std::time::Duration;
 
 use bb8_postgres::bb8::Pool;
-use bb8_postgres::tokio_postgres::{types::Type, NoTls, Row};
+use bb8_postgres::tokio_postgres::{types::Type, Client, NoTls, Row};
 use bb8_postgres::PostgresConnectionManager;
-use serde_json::{json, Map, Value};
+use serde_json::{json, Map, Number, Value};
 
 use commons::env::{var, var_or_default};
 use commons::errors::RustyError;
 use domain::commons::search::{SearchOptions, SortOptions};
 
-use crate::shared::{filter_results, get_value_id};
+use crate::shared::{filter_results, get_value_id, timestamp};
 use crate::{Persistence, PersistenceBuilder};
 
 /// Represents a `PostgreSQL` client.
@@ -88,6 +89,24 @@ impl PostgreSQLClient {
 
         Ok(())
     }
+
+    // column types of a table (`udt_name`, e.g. `varchar`, `_text` for text arrays, `jsonb`),
+    // so values are cast to the type of their column
+    async fn column_types(
+        &self,
+        conn: &Client,
+        index: &str,
+    ) -> Result<HashMap<String, String>, RustyError> {
+        let statement = "select column_name, udt_name from information_schema.columns \
+            where table_schema = $1 and table_name = $2";
+        let rows = conn
+            .query(statement, &[&self.schema, &index.to_lowercase()])
+            .await?;
+        Ok(rows
+            .iter()
+            .map(|row| (row.get::<usize, String>(0), row.get::<usize, String>(1)))
+            .collect())
+    }
 }
 
 #[allow(clippy::manual_async_fn)]
@@ -159,7 +178,8 @@ impl Persistence for PostgreSQLClient {
 
     async fn create(&self, index: &str, item: &Value) -> Result<String, RustyError> {
         let conn = self.client.get().await?;
-        let values = parse_filter(&Some(item.clone()), false).join(", ");
+        let columns = self.column_types(&conn, index).await?;
+        let values = parse_filter(&Some(item.clone()), false, &columns).join(", ");
         let statement = format!("insert into {}.{index} values ({values})", self.schema);
         println!("{statement}");
         let _ = conn.execute(&statement, &[]).await?;
@@ -173,10 +193,12 @@ impl Persistence for PostgreSQLClient {
 
     async fn update(&self, index: &str, id: &str, item: &Value) -> Result<String, RustyError> {
         let conn = self.client.get().await?;
-        let values = parse_filter(&Some(item.clone()), true).join(", ");
+        let columns = self.column_types(&conn, index).await?;
+        let values = parse_filter(&Some(item.clone()), true, &columns).join(", ");
         let statement = format!(
-            "update {}.{index} set {values} where id = '{id}'",
-            self.schema
+            "update {}.{index} set {values} where id = {}",
+            self.schema,
+            quote(id)
         );
         let _ = conn.execute(&statement, &[]).await?;
         let _ = messaging::internal::send(
@@ -190,8 +212,9 @@ impl Persistence for PostgreSQLClient {
     async fn append(&self, index: &str, id: &str, entry: &str) -> Result<u64, RustyError> {
         let conn = self.client.get().await?;
         let statement = format!(
-            "select exists (select 1 from {}.{index} where id = '{id}')",
+            "select exists (select 1 from {}.{index} where id = {})",
             self.schema,
+            quote(id),
         );
         let entry: Value = serde_json::from_str(&format!("[{entry}]"))?;
         if conn.query_one(&statement, &[]).await?.get(0) {
@@ -210,6 +233,20 @@ impl Persistence for PostgreSQLClient {
         Ok(1)
     }
 
+    async fn lock(&self, index: &str, id: &str, ttl: u64) -> Result<bool, RustyError> {
+        let conn = self.client.get().await?;
+        let now = timestamp();
+        let expiry = now.saturating_add(i64::try_from(ttl)?);
+        let statement = format!(
+            "insert into {schema}.{index} (id, expiry) values ($1, $2) \
+            on conflict (id) do update set expiry = excluded.expiry \
+            where {schema}.{index}.expiry < $3",
+            schema = self.schema,
+        );
+        let acquired = conn.execute(&statement, &[&id, &expiry, &now]).await?;
+        Ok(acquired == 1)
+    }
+
     async fn delete_one(&self, index: &str, filter: Value) -> Result<u64, RustyError> {
         let conn = self.client.get().await?;
         if filter.as_object().unwrap_or(&Map::new()).is_empty() {
@@ -217,7 +254,8 @@ impl Persistence for PostgreSQLClient {
             return Ok(0);
         }
 
-        let values = parse_filter(&Some(filter), true);
+        let columns = self.column_types(&conn, index).await?;
+        let values = parse_filter(&Some(filter), true, &columns);
         let where_clause = if values.is_empty() {
             String::new()
         } else {
@@ -245,18 +283,25 @@ impl Persistence for PostgreSQLClient {
     }
 }
 
-fn parse_filter(filter: &Option<Value>, is_where: bool) -> Vec<String> {
+fn parse_filter(
+    filter: &Option<Value>,
+    is_where: bool,
+    columns: &HashMap<String, String>,
+) -> Vec<String> {
     filter.clone().map_or_else(Vec::new, |filter| {
         filter
             .as_object()
             .unwrap_or(&Map::new())
             .iter()
-            .map(|(key, value)| parse_value(key, value, is_where))
+            .map(|(key, value)| {
+                let column = columns.get(key).map(String::as_str);
+                parse_value(key, value, is_where, column)
+            })
             .collect::<Vec<String>>()
     })
 }
 
-fn parse_value(key: &str, value: &Value, is_where: bool) -> String {
+fn parse_value(key: &str, value: &Value, is_where: bool, column: Option<&str>) -> String {
     let key = if is_where {
         format!("{key} = ")
     } else {
@@ -265,27 +310,56 @@ fn parse_value(key: &str, value: &Value, is_where: bool) -> String {
     match value {
         Value::Bool(v) => format!("{key}{v}"),
         Value::Number(v) => format!("{key}{v}"),
-        Value::String(v) => format!("{key}'{v}'"),
+        Value::String(v) => format!("{key}{}", quote(v)),
+        // arrays of structs are stored as a single jsonb document
+        Value::Array(v) if column == Some("jsonb") || v.iter().any(Value::is_object) => {
+            format!("{key}{}::jsonb", quote(&serde_json::to_string(&v).unwrap()))
+        }
+        // arrays are cast to the element type of their column - without a length, so values are not truncated
         Value::Array(v) => format!(
-            "{key}ARRAY[{}]::varchar(36)[]",
+            "{key}ARRAY[{}]::{}[]",
             v.iter()
-                .map(|i| parse_value("", i, false))
+                .map(|i| parse_value("", i, false, None))
                 .collect::<Vec<String>>()
-                .join(", ")
+                .join(", "),
+            column
+                .and_then(|column| column.strip_prefix('_'))
+                .unwrap_or("text")
         ),
         Value::Null => format!("{key}null"),
         Value::Object(map) if map.is_empty() => format!("{key}'{{}}'::jsonb"),
-        Value::Object(v) => format!("{key}'{}'::jsonb", serde_json::to_string(&v).unwrap()),
+        Value::Object(v) => format!("{key}{}::jsonb", quote(&serde_json::to_string(&v).unwrap())),
     }
 }
 
+// statements are built as text - quotes within values are escaped by doubling them
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
 fn parse_row(row: &Row) -> Value {
     let mut value = Map::new();
     for column in row.columns() {
         let column_name = column.name().to_string();
         let entry = match column.type_() {
             // add other types
-            &Type::INT4 => Value::Number(row.get::<&str, i32>(&column_name).into()),
+            &Type::BOOL => row
+                .get::<&str, Option<bool>>(&column_name)
+                .map_or_else(|| Value::Null, Value::Bool),
+            &Type::INT4 => row
+                .get::<&str, Option<i32>>(&column_name)
+                .map_or_else(|| Value::Null, |v| Value::Number(v.into())),
+            &Type::INT8 => row
+                .get::<&str, Option<i64>>(&column_name)
+                .map_or_else(|| Value::Null, |v| Value::Number(v.into())),
+            &Type::FLOAT4 => row
+                .get::<&str, Option<f32>>(&column_name)
+                .and_then(|v| Number::from_f64(f64::from(v)))
+                .map_or_else(|| Value::Null, Value::Number),
+            &Type::FLOAT8 => row
+                .get::<&str, Option<f64>>(&column_name)
+                .and_then(Number::from_f64)
+                .map_or_else(|| Value::Null, Value::Number),
             &Type::VARCHAR | &Type::TEXT => row
                 .get::<&str, Option<String>>(&column_name)
                 .map_or_else(|| Value::Null, Value::String),
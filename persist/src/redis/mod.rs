@@ -8,7 +8,7 @@ use commons::env::{var, var_or_default};
 use commons::errors::RustyError;
 use domain::commons::search::SearchOptions;
 
-use crate::shared::{delete_one_filter, filter_results, get_value_id, sort_results};
+use crate::shared::{delete_one_filter, filter_results, get_value_id, sort_results, timestamp};
 use crate::{Persistence, PersistenceBuilder};
 
 /// Represents a `Redis` client.
@@ -126,6 +126,21 @@ impl Persistence for RedisClient {
         Ok(1)
     }
 
+    async fn lock(&self, index: &str, id: &str, ttl: u64) -> Result<bool, RustyError> {
+        let mut conn = self.client.get().await?;
+        let expiry = timestamp().saturating_add(i64::try_from(ttl)?);
+        let item = json!({ "id": id, "expiry": expiry }).to_string();
+        let result: Option<String> = bb8_redis::redis::cmd("SET")
+            .arg(format!("{index}_{id}"))
+            .arg(item)
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl)
+            .query_async(&mut *conn)
+            .await?;
+        Ok(result.is_some())
+    }
+
     async fn delete_one(&self, index: &str, filter: Value) -> Result<u64, RustyError> {
         let mut conn = self.client.get().await?;
         let filter = delete_one_filter(&filter);
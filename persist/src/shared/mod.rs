@@ -29,6 +29,14 @@ pub(crate) fn get_value_id(value: &Value) -> String {
         .to_string()
 }
 
+pub(crate) fn timestamp() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |duration| {
+            i64::try_from(duration.as_secs()).unwrap_or(i64::MAX)
+        })
+}
+
 fn compare_strings(item: &Value, f: &Value, comparison: fn(&String, &String) -> bool) -> bool {
     if item.is_string() && f.is_string() {
         comparison(
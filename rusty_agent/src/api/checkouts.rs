@@ -0,0 +1,39 @@
+use commons::errors::RustyError;
+use domain::checkouts::Checkout;
+
+use crate::api::client::reqwest_post_bearer;
+
+/// Function to retrieve the checkout settings of a project from a GraphQL endpoint.
+///
+/// # Errors
+///
+/// This function can generate the following errors:
+///
+/// * `RustyError` - If there was an error during the creation of the item.
+#[allow(clippy::future_not_send)]
+pub async fn get_checkout(project_id: &str) -> Result<Option<Checkout>, RustyError> {
+    let payload = serde_json::json!({
+        "query": format!(r#"query {{
+            checkouts {{
+                getCheckout(projectId: "{}") {{
+                    depth
+                    submodules
+                    lfs
+                    sparsePaths
+                    sshKey
+                    username
+                    token
+                }}
+            }}
+        }}"#, project_id),
+        "variables": {}
+    });
+
+    let data = reqwest_post_bearer(&payload).await?;
+    let json_data: serde_json::Value = serde_json::from_str(&data)?;
+    match &json_data["data"]["checkouts"]["getCheckout"] {
+        serde_json::Value::Null if json_data["data"]["checkouts"].is_object() => Ok(None),
+        checkout if checkout.is_object() => Ok(Some(serde_json::from_value(checkout.clone())?)),
+        _ => Err(RustyError::RequestError("No results".to_string())),
+    }
+}
@@ -0,0 +1,45 @@
+use commons::errors::RustyError;
+use domain::coverage::FileCoverage;
+
+use crate::api::client::reqwest_post_bearer;
+use crate::api::utils::parse_entries;
+
+/// Function to upload the coverage report of a pipeline stage to a GraphQL endpoint.
+///
+/// # Errors
+///
+/// This function can generate the following errors:
+///
+/// * `RustyError` - If there was an error during the creation of the item.
+#[allow(clippy::future_not_send)]
+pub async fn register_coverage(
+    pipeline_id: &str,
+    agent_id: &str,
+    stage: &str,
+    files: &[FileCoverage],
+) -> Result<String, RustyError> {
+    // reports list arbitrary file paths - sent as variables instead of inlined
+    let payload = serde_json::json!({
+        "query": r#"mutation($pipelineId: String!, $agentId: String!, $stage: String!, $files: [FileCoverageInput!]!) {
+            coverage {
+                register(
+                    pipelineId: $pipelineId,
+                    agentId: $agentId,
+                    stage: $stage,
+                    files: $files
+                )
+            }
+        }"#,
+        "variables": {
+            "pipelineId": pipeline_id,
+            "agentId": agent_id,
+            "stage": stage,
+            "files": files,
+        }
+    });
+
+    let data = reqwest_post_bearer(&payload).await?;
+    let json_data: serde_json::Value = serde_json::from_str(&data)?;
+    let json_data = json_data["data"]["coverage"]["register"].clone();
+    parse_entries(json_data)
+}
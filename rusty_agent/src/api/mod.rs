@@ -12,9 +12,15 @@ pub mod agents;
 /// Server API for authentication.
 pub mod auth;
 
+/// Server API for checkout settings.
+pub mod checkouts;
+
 /// Server API client wrapper.
 pub mod client;
 
+/// Server API for coverage reports.
+pub mod coverage;
+
 /// Server API for jobs.
 pub mod jobs;
 
@@ -24,6 +30,15 @@ pub mod pipelines;
 /// Server API for projects.
 pub mod projects;
 
+/// Server API for registry credentials.
+pub mod registries;
+
+/// Server API for job template revisions.
+pub mod revisions;
+
+/// Server API for test reports.
+pub mod test_reports;
+
 /// Utilities for Server API operations.
 pub mod utils;
 
@@ -52,6 +52,10 @@ async fn get_pipeline(filter: &str) -> Result<Pipeline, RustyError> {
 						stageStatus
                         jobId
                         agentId
+                        commit
+                        revision
+                        upstreamId
+                        variables
                     }}
                 }}
             }}
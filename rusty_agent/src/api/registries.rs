@@ -0,0 +1,38 @@
+use commons::errors::RustyError;
+use domain::registries::RegistryAuth;
+
+use crate::api::client::reqwest_post_bearer;
+
+/// Function to retrieve the registry credentials of a project from a GraphQL endpoint.
+///
+/// # Errors
+///
+/// This function can generate the following errors:
+///
+/// * `RustyError` - If there was an error during the creation of the item.
+#[allow(clippy::future_not_send)]
+pub async fn get_registry_auths(project_id: &str) -> Result<Vec<RegistryAuth>, RustyError> {
+    let payload = serde_json::json!({
+        "query": format!(r#"query {{
+            registries {{
+                getAuths(projectId: "{}") {{
+                    registry
+                    username
+                    password
+                }}
+            }}
+        }}"#, project_id),
+        "variables": {}
+    });
+
+    let data = reqwest_post_bearer(&payload).await?;
+    let json_data: serde_json::Value = serde_json::from_str(&data)?;
+    if let Some(auths) = json_data["data"]["registries"]["getAuths"].as_array() {
+        Ok(auths
+            .iter()
+            .filter_map(|auth| serde_json::from_value(auth.clone()).ok())
+            .collect())
+    } else {
+        Err(RustyError::RequestError("No results".to_string()))
+    }
+}
@@ -0,0 +1,34 @@
+use commons::errors::RustyError;
+use domain::templates::pipeline::PipelineTemplate;
+
+use crate::api::client::reqwest_post_bearer;
+
+/// Function to retrieve a job template revision from a GraphQL endpoint by id.
+///
+/// # Errors
+///
+/// This function can generate the following errors:
+///
+/// * `RustyError` - If there was an error during the creation of the item.
+#[allow(clippy::future_not_send)]
+pub async fn get_revision_template(id: &str) -> Result<PipelineTemplate, RustyError> {
+    let payload = serde_json::json!({
+        "query": format!(r#"query {{
+            revisions {{
+                getById(id: "{}") {{
+                    template
+                }}
+            }}
+        }}"#, id),
+        "variables": {}
+    });
+
+    let data = reqwest_post_bearer(&payload).await?;
+    let json_data: serde_json::Value = serde_json::from_str(&data)?;
+    if let Some(revision) = json_data["data"]["revisions"]["getById"].as_object() {
+        let template = revision["template"].as_str().unwrap_or_default();
+        PipelineTemplate::from_yaml(template)
+    } else {
+        Err(RustyError::RequestError("No results".to_string()))
+    }
+}
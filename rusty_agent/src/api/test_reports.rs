@@ -0,0 +1,45 @@
+use commons::errors::RustyError;
+use domain::test_reports::TestSuite;
+
+use crate::api::client::reqwest_post_bearer;
+use crate::api::utils::parse_entries;
+
+/// Function to upload the test results of a pipeline stage to a GraphQL endpoint.
+///
+/// # Errors
+///
+/// This function can generate the following errors:
+///
+/// * `RustyError` - If there was an error during the creation of the item.
+#[allow(clippy::future_not_send)]
+pub async fn register_tests(
+    pipeline_id: &str,
+    agent_id: &str,
+    stage: &str,
+    suites: &[TestSuite],
+) -> Result<String, RustyError> {
+    // suites carry arbitrary test names and messages - sent as variables instead of inlined
+    let payload = serde_json::json!({
+        "query": r#"mutation($pipelineId: String!, $agentId: String!, $stage: String!, $suites: [TestSuiteInput!]!) {
+            testReports {
+                register(
+                    pipelineId: $pipelineId,
+                    agentId: $agentId,
+                    stage: $stage,
+                    suites: $suites
+                )
+            }
+        }"#,
+        "variables": {
+            "pipelineId": pipeline_id,
+            "agentId": agent_id,
+            "stage": stage,
+            "suites": suites,
+        }
+    });
+
+    let data = reqwest_post_bearer(&payload).await?;
+    let json_data: serde_json::Value = serde_json::from_str(&data)?;
+    let json_data = json_data["data"]["testReports"]["register"].clone();
+    parse_entries(json_data)
+}
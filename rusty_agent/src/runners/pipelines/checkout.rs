@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use base64::Engine;
+
+use domain::checkouts::{Checkout, Submodules};
+
+const SSH_KEY_VAR: &str = "RUSTY_SSH_KEY";
+
+/// Shell script cloning a repository with the project checkout settings
+///
+/// Credentials are read from the environment built by [`env`],
+/// so they never appear in the script, the process arguments or the repository config.
+/// With a `commit`, the commit is checked out instead of the branch head.
+/// With a `reference` mirror, objects are copied from the mirror instead of the network.
+#[must_use]
+pub fn script(
+    checkout: &Checkout,
+    repo_url: &str,
+    branch: &str,
+    commit: Option<&str>,
+    dir: &str,
+    reference: Option<&str>,
+) -> String {
+    let mut lines = credentials_setup(checkout);
+
+    let mut clone = vec!["git clone".to_string()];
+    if let Some(depth) = checkout.depth {
+        clone.push(format!("--depth {depth}"));
+    }
+    if let Some(reference) = reference {
+        clone.push(format!(
+            "--reference-if-able {} --dissociate",
+            quote(reference)
+        ));
+    }
+    if checkout.sparse_paths.is_some() {
+        clone.push("--no-checkout".to_string());
+    }
+    clone.push(format!(
+        "-b {} {} {}",
+        quote(branch),
+        quote(repo_url),
+        quote(dir)
+    ));
+    lines.push(clone.join(" "));
+    lines.push(format!("cd {}", quote(dir)));
+
+    // a shallow clone may not reach the commit
+    if let (Some(commit), Some(depth)) = (commit, checkout.depth) {
+        lines.push(format!(
+            "git fetch --depth {depth} origin {}",
+            quote(commit)
+        ));
+    }
+    if let Some(paths) = &checkout.sparse_paths {
+        let paths = paths
+            .iter()
+            .map(|path| quote(path))
+            .collect::<Vec<String>>();
+        lines.push(format!("git sparse-checkout set {}", paths.join(" ")));
+        lines.push(format!("git checkout {}", quote(commit.unwrap_or(branch))));
+    } else if let Some(commit) = commit {
+        lines.push(format!("git checkout --detach {}", quote(commit)));
+    }
+    if checkout.submodules == Some(Submodules::Recursive) {
+        let depth = checkout
+            .depth
+            .map_or_else(String::new, |depth| format!(" --depth {depth}"));
+        lines.push(format!("git submodule update --init --recursive{depth}"));
+    }
+    if checkout.lfs == Some(true) {
+        lines.push("git lfs install --local".to_string());
+        lines.push("git lfs pull".to_string());
+    }
+    lines.join("\n")
+}
+
+// shell prelude of the git commands - the ssh key file is removed when the script exits
+pub(crate) fn credentials_setup(checkout: &Checkout) -> Vec<String> {
+    let mut lines = vec!["set -e".to_string()];
+    if checkout.ssh_key.is_some() {
+        lines.push(r#"key="$(mktemp)""#.to_string());
+        lines.push(r#"trap 'rm -f "$key"' EXIT"#.to_string());
+        lines.push(format!(r#"printf '%s\n' "${SSH_KEY_VAR}" > "$key""#));
+        lines.push(
+            r#"export GIT_SSH_COMMAND="ssh -i $key -o IdentitiesOnly=yes -o StrictHostKeyChecking=accept-new""#
+                .to_string(),
+        );
+    }
+    lines
+}
+
+/// Alpine packages required by the checkout script
+#[must_use]
+pub fn packages(checkout: &Checkout) -> Vec<&'static str> {
+    let mut packages = vec!["git"];
+    if checkout.ssh_key.is_some() {
+        packages.push("openssh-client");
+    }
+    if checkout.lfs == Some(true) {
+        packages.push("git-lfs");
+    }
+    packages
+}
+
+/// Environment of the checkout script, holding the credentials
+///
+/// The https token is sent as an authorization header to the repository host only.
+#[must_use]
+pub fn env(checkout: &Checkout, repo_url: &str) -> HashMap<String, String> {
+    let mut env = HashMap::new();
+    if let Some(key) = &checkout.ssh_key {
+        env.insert(SSH_KEY_VAR.to_string(), key.clone());
+    }
+    if let (Some(header), Some(host)) = (authorization(checkout), http_host(repo_url)) {
+        env.insert("GIT_CONFIG_COUNT".to_string(), "1".to_string());
+        env.insert(
+            "GIT_CONFIG_KEY_0".to_string(),
+            format!("http.{host}.extraHeader"),
+        );
+        env.insert("GIT_CONFIG_VALUE_0".to_string(), header);
+    }
+    env
+}
+
+/// Values masked in the pipeline logs
+#[must_use]
+pub fn secrets(checkout: &Checkout) -> Vec<String> {
+    let mut secrets = vec![];
+    if let Some(token) = &checkout.token {
+        secrets.push(token.clone());
+    }
+    if let Some(header) = authorization(checkout) {
+        secrets.push(
+            header
+                .trim_start_matches("Authorization: Basic ")
+                .to_string(),
+        );
+    }
+    if let Some(key) = &checkout.ssh_key {
+        secrets.extend(
+            key.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with("-----"))
+                .map(ToString::to_string),
+        );
+    }
+    secrets
+}
+
+/// Replace secrets in a log line
+#[must_use]
+pub fn mask(line: &str, secrets: &[String]) -> String {
+    secrets
+        .iter()
+        .filter(|secret| !secret.is_empty())
+        .fold(line.to_string(), |line, secret| line.replace(secret, "***"))
+}
+
+fn authorization(checkout: &Checkout) -> Option<String> {
+    let token = checkout.token.as_ref()?;
+    let username = checkout.username.as_deref().unwrap_or("oauth2");
+    let credentials = base64::prelude::BASE64_STANDARD.encode(format!("{username}:{token}"));
+    Some(format!("Authorization: Basic {credentials}"))
+}
+
+// e.g. `https://github.com/org/repo.git` -> `https://github.com/`
+fn http_host(repo_url: &str) -> Option<String> {
+    let (scheme, rest) = repo_url.split_once("://")?;
+    if scheme != "https" && scheme != "http" {
+        return None;
+    }
+    let host = rest.split('/').next().filter(|host| !host.is_empty())?;
+    Some(format!("{scheme}://{host}/"))
+}
+
+pub(crate) fn quote(value: &str) -> String {
+    shlex::try_quote(value).map_or_else(|_| format!("'{value}'"), |quoted| quoted.to_string())
+}
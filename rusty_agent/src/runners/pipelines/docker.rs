@@ -1,240 +1,514 @@
-use crate::api::pipelines::update_stage;
-use crate::runners::pipelines::shared;
-use bollard::container::Config;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::runners::pipelines::mirror::{update_script, MirrorCache};
+use crate::runners::pipelines::{checkout, shared, PipelineContext, Runner, StageRun};
+use bollard::auth::DockerCredentials;
+use bollard::container::{Config, ListContainersOptions, NetworkingConfig, RemoveContainerOptions};
 use bollard::exec::{CreateExecOptions, StartExecResults};
-use bollard::image::CreateImageOptions;
-use bollard::models::HostConfig;
+use bollard::image::{BuildImageOptions, CreateImageOptions, PushImageOptions, TagImageOptions};
+use bollard::models::{ContainerInspectResponse, EndpointSettings, HealthStatusEnum, HostConfig};
+use bollard::network::CreateNetworkOptions;
 use bollard::Docker;
+use commons::env::var_or_default;
 use commons::errors::RustyError;
 use commons::errors::RustyError::DockerError;
-use domain::pipelines::{Pipeline, PipelineStatus};
-use domain::templates::pipeline::{PipelineTemplate, Script, Stage};
-use futures_util::future::try_join_all;
-use futures_util::{StreamExt, TryStreamExt};
+use domain::registries::{image_registry, split_tag, RegistryAuth, DEFAULT_REGISTRY};
+use domain::templates::pipeline::{
+    Build, PipelineTemplate, PullPolicy, Resources, Security, Service, Stage,
+};
+use futures_util::StreamExt;
 use messaging::mq_client::MqClient;
-use tokio::spawn;
+use tokio::net::TcpStream;
 use tokio::time::Instant;
 
-pub async fn execute_docker(
-    messaging: &MqClient,
-    pipeline: &Pipeline,
-    template: &PipelineTemplate,
-    repo_url: &str,
-    branch: &str,
-    docker_image: &str,
-    agent_uuid: &str,
-) -> Result<(), RustyError> {
-    let docker = Docker::connect_with_local_defaults()?;
-    if clone_repository(&docker, messaging, repo_url, branch, &pipeline.id)
-        .await
-        .is_err()
-    {
-        shared::cleanup(
+const PIPELINE_LABEL: &str = "rusty.pipeline";
+
+#[derive(Clone, Debug)]
+struct ImagePull {
+    policy: PullPolicy,
+    auths: Vec<RegistryAuth>,
+}
+
+/// Runner executing stages in docker containers on the agent host
+#[derive(Debug)]
+pub struct DockerRunner {
+    docker: Docker,
+    pull: ImagePull,
+    mirrors: Option<MirrorCache>,
+}
+
+impl DockerRunner {
+    /// Connect to the local docker daemon
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The pipeline template.
+    /// * `auths` - The project registry credentials.
+    ///
+    /// # Errors
+    ///
+    /// This function can generate the following errors:
+    ///
+    /// * `RustyError` - If the docker daemon is not available.
+    pub fn new(template: &PipelineTemplate, auths: &[RegistryAuth]) -> Result<Self, RustyError> {
+        Ok(Self {
+            docker: Docker::connect_with_local_defaults()?,
+            pull: ImagePull {
+                policy: template.pull_policy.unwrap_or_default(),
+                auths: auths.to_vec(),
+            },
+            mirrors: MirrorCache::from_env(),
+        })
+    }
+
+    async fn run_script(
+        &self,
+        context: &PipelineContext<'_>,
+        stage: &StageRun,
+    ) -> Result<(), RustyError> {
+        let Some(script) = &stage.script else {
+            return Ok(());
+        };
+        let (docker, messaging) = (&self.docker, context.messaging);
+        let (pipeline_id, stage_name) = (&context.pipeline.id, &stage.name);
+        let docker_image = stage
+            .stage
+            .as_ref()
+            .and_then(|stage| stage.image.clone())
+            .or_else(|| context.template.image.clone())
+            .unwrap_or_default();
+        create_image(
+            docker,
             messaging,
-            agent_uuid,
-            &pipeline.id,
-            "rusty-before",
-            PipelineStatus::Failure,
+            pipeline_id,
+            stage_name,
+            &docker_image,
+            &self.pull,
         )
-        .await;
-    };
+        .await?;
 
-    execute_stage(
-        &docker,
-        messaging,
-        docker_image,
-        &pipeline.id,
-        agent_uuid,
-        &template.before,
-        &prepare_env(template, &None),
-        "rusty-before",
-    )
-    .await?;
+        let (resources, lowered) = shared::resources(&stage.stage);
+        for limit in lowered {
+            let line = format!("`{stage_name}` stage {limit} limit lowered to the agent maximum");
+            shared::print_line(messaging, pipeline_id, stage_name, &line).await;
+        }
+        let security = shared::security(&stage.stage);
+
+        // every stage mounts the same checkout, so files written by a stage are seen by the next ones
+        let working_dir = shared::pipeline_dir(pipeline_id);
+        let container_id = create_container(
+            docker,
+            &docker_image,
+            vec![format!("{working_dir}:{working_dir}")],
+            pipeline_id,
+            &resources,
+            &security,
+        )
+        .await?;
+        start_container(docker, &container_id).await?;
+        if let Some(user) = &security.user {
+            chown_checkout(docker, &container_id, &working_dir, user).await?;
+        }
 
-    let stages_tree = template.dependency_tree();
-    for branch in stages_tree {
-        let mut tasks = Vec::new();
-
-        for leaf in branch {
-            let docker = docker.clone();
-            let docker_image = docker_image.to_string();
-            let uuid = agent_uuid.to_string();
-            let template = template.clone();
-            let pipeline = pipeline.clone();
-            let messaging = messaging.clone();
-
-            let task = spawn(async move {
-                let start = Instant::now();
-                let (name, stage) = template.stages.iter().find(|(n, _)| leaf == **n).unwrap();
-                let docker_image = if let Some(image) = stage.clone().image {
-                    image
-                } else {
-                    docker_image
-                };
-                log::debug!("running stage: {name}");
-
-                if let Err(err) = execute_stage(
-                    &docker,
-                    &messaging,
-                    &docker_image,
-                    &pipeline.id,
-                    &uuid,
-                    &Some(Script::new(&stage.script)),
-                    &prepare_env(&template, &Some(stage.clone())),
-                    "rusty-before",
-                )
-                .await
-                {
-                    log::error!("Error in pipeline {}: {}", &pipeline.id, err);
-                    return Err(());
-                }
-
-                let duration = start.elapsed().as_millis();
-                log::debug!("done: running stage: {name} in {duration} ms");
-                Ok(())
-            });
-            tasks.push(task);
+        // the whole script runs in one shell, so `cd` and `export` carry over to the next lines
+        let shell = shared::shell(context.template, &stage.stage);
+        let command = vec![shell, "-c".to_string(), script.to_shell()];
+        if let Err(err) = execute_command(
+            docker,
+            messaging,
+            &working_dir,
+            &container_id,
+            &command,
+            &prepare_env(context.template, &stage.stage),
+            pipeline_id,
+            stage_name,
+        )
+        .await
+        {
+            return match limit_error(docker, &container_id, &resources, stage_name).await {
+                Some(message) => Err(DockerError(message)),
+                None => Err(err),
+            };
         }
 
-        try_join_all(tasks).await?;
+        stop_container(docker, &container_id).await?;
+        remove_container(docker, &container_id).await?;
+        Ok(())
     }
 
-    execute_stage(
-        &docker,
-        messaging,
-        docker_image,
-        &pipeline.id,
-        agent_uuid,
-        &template.after,
-        &prepare_env(template, &None),
-        "rusty-after",
-    )
-    .await?;
+    async fn run_build(
+        &self,
+        context: &PipelineContext<'_>,
+        stage: &StageRun,
+        build: &Build,
+    ) -> Result<(), RustyError> {
+        let (docker, messaging) = (&self.docker, context.messaging);
+        let (pipeline_id, stage_name) = (&context.pipeline.id, &stage.name);
+        build_image(
+            docker,
+            messaging,
+            &self.pull,
+            pipeline_id,
+            build,
+            shared::prepare_env(context.template, &stage.stage),
+            stage_name,
+        )
+        .await?;
+        if build.push == Some(true) {
+            push_images(
+                docker,
+                messaging,
+                &self.pull,
+                pipeline_id,
+                &build.tags,
+                stage_name,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+}
 
-    shared::cleanup(
-        messaging,
-        agent_uuid,
-        &pipeline.id,
-        "rusty-after",
-        PipelineStatus::Success,
-    )
-    .await;
+impl Runner for DockerRunner {
+    async fn prepare_workspace(&self, context: &PipelineContext<'_>) -> Result<(), RustyError> {
+        let (docker, messaging) = (&self.docker, context.messaging);
+        let pipeline_id = &context.pipeline.id;
+        create_network(docker, pipeline_id).await?;
+        clone_repository(
+            docker,
+            messaging,
+            context,
+            &self.pull,
+            self.mirrors.as_ref(),
+        )
+        .await?;
+        // pipeline services are removed with the other pipeline containers on cleanup
+        let services = context.template.services.clone().unwrap_or_default();
+        start_services(
+            docker,
+            messaging,
+            pipeline_id,
+            &services,
+            &self.pull,
+            "rusty-before",
+        )
+        .await?;
+        Ok(())
+    }
 
-    Ok(())
+    async fn run_stage(
+        &self,
+        context: &PipelineContext<'_>,
+        stage: &StageRun,
+    ) -> Result<(), RustyError> {
+        let (docker, messaging) = (&self.docker, context.messaging);
+        let services = stage
+            .stage
+            .as_ref()
+            .and_then(|stage| stage.services.clone())
+            .unwrap_or_default();
+        let services = start_services(
+            docker,
+            messaging,
+            &context.pipeline.id,
+            &services,
+            &self.pull,
+            &stage.name,
+        )
+        .await?;
+        let result = match stage.stage.as_ref().and_then(|stage| stage.build.clone()) {
+            Some(build) => self.run_build(context, stage, &build).await,
+            None => self.run_script(context, stage).await,
+        };
+        remove_containers(docker, &services).await;
+        result
+    }
+
+    async fn cleanup(&self, context: &PipelineContext<'_>) {
+        teardown(&self.docker, &context.pipeline.id).await;
+    }
+
+    // containers of cancelled stages keep running when their stage future is dropped
+    async fn cancel(&self, context: &PipelineContext<'_>) {
+        remove_pipeline_containers(&self.docker, &context.pipeline.id).await;
+    }
 }
 
 async fn clone_repository(
     docker: &Docker,
     messaging: &MqClient,
-    repo_url: &str,
-    branch: &str,
-    pipeline_id: &str,
+    context: &PipelineContext<'_>,
+    pull: &ImagePull,
+    mirrors: Option<&MirrorCache>,
 ) -> Result<(), RustyError> {
-    create_image(docker, "alpine:3.20").await?;
-    let container_id =
-        create_container(docker, "alpine:3.20", "/tmp/rusty:/tmp/rusty".to_string()).await?;
+    let pipeline_id = &context.pipeline.id;
+    create_image(
+        docker,
+        messaging,
+        pipeline_id,
+        "rusty-before",
+        "alpine:3.20",
+        pull,
+    )
+    .await?;
+    let working_dir = shared::working_dir();
+    let mut binds = vec![format!("{working_dir}:{working_dir}")];
+    if let Some(mirrors) = mirrors {
+        std::fs::create_dir_all(&mirrors.dir)?;
+        binds.push(format!("{0}:{0}", mirrors.dir));
+    }
+    let container_id = create_container(
+        docker,
+        "alpine:3.20",
+        binds,
+        pipeline_id,
+        &Resources::default(),
+        &Security::default(),
+    )
+    .await?;
     start_container(docker, &container_id).await?;
+    let packages = checkout::packages(context.checkout).join(" ");
     execute_command(
         docker,
         messaging,
-        shared::WORKING_DIR,
+        &working_dir,
         &container_id,
-        &split_command("apk add git"),
+        &split_command(&format!("apk add {packages}")),
         &[],
         pipeline_id,
         "rusty-before",
     )
     .await?;
-    let clone_command = format!(
-        "git clone {repo_url} -b {branch} {}/{pipeline_id}",
-        shared::WORKING_DIR
+    let env = checkout::env(context.checkout, context.repo_url)
+        .into_iter()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect::<Vec<String>>();
+    // a failed mirror update falls back to a network clone
+    let mirror = match mirrors {
+        Some(mirrors) => {
+            let mirror = mirrors.path(context.repo_url);
+            let guard = mirrors.lock(&mirror).await;
+            let script = update_script(context.checkout, context.repo_url, &mirror);
+            if let Err(err) = execute_command(
+                docker,
+                messaging,
+                &mirrors.dir,
+                &container_id,
+                &["sh".to_string(), "-c".to_string(), script],
+                &env,
+                pipeline_id,
+                "rusty-before",
+            )
+            .await
+            {
+                let line = format!("failed to update the repository mirror: {err}");
+                shared::print_line(messaging, pipeline_id, "rusty-before", &line).await;
+            }
+            Some((mirror, guard.downgrade()))
+        }
+        None => None,
+    };
+    let script = checkout::script(
+        context.checkout,
+        context.repo_url,
+        context.branch,
+        context.pipeline.commit.as_deref(),
+        &shared::pipeline_dir(pipeline_id),
+        mirror.as_ref().map(|(mirror, _)| mirror.as_str()),
     );
-    execute_command(
+    let result = execute_command(
         docker,
         messaging,
-        shared::WORKING_DIR,
+        &working_dir,
         &container_id,
-        &split_command(&clone_command),
-        &[],
+        &["sh".to_string(), "-c".to_string(), script],
+        &env,
         pipeline_id,
         "rusty-before",
     )
-    .await?;
+    .await;
+    if let (Some(mirrors), Some((mirror, guard))) = (mirrors, mirror) {
+        drop(guard);
+        mirrors.touch(&mirror);
+        mirrors.collect_garbage();
+    }
+    result?;
     stop_container(docker, &container_id).await?;
     remove_container(docker, &container_id).await?;
     Ok(())
 }
 
-#[allow(clippy::too_many_arguments)]
-async fn execute_stage(
+async fn create_image(
     docker: &Docker,
     messaging: &MqClient,
-    docker_image: &str,
     pipeline_id: &str,
-    uuid: &str,
-    script: &Option<Script>,
-    env: &[String],
     stage_name: &str,
+    docker_image: &str,
+    pull: &ImagePull,
 ) -> Result<(), RustyError> {
-    let _ = update_stage(pipeline_id, uuid, stage_name, PipelineStatus::InProgress).await;
+    if pull.policy != PullPolicy::Always {
+        if docker.inspect_image(docker_image).await.is_ok() {
+            log::debug!("Image present: {docker_image}");
+            return Ok(());
+        }
+        if pull.policy == PullPolicy::Never {
+            return Err(DockerError(format!(
+                "image `{docker_image}` is not present on the agent and pull policy is `never`"
+            )));
+        }
+    }
 
-    if let Some(script) = &script {
-        create_image(docker, docker_image).await?;
-        let working_dir = format!("{}/{pipeline_id}", shared::WORKING_DIR);
-        let container_id =
-            create_container(docker, docker_image, format!("{working_dir}:{working_dir}")).await?;
-        start_container(docker, &container_id).await?;
+    let credentials = image_credentials(&pull.auths, docker_image);
+    let line = format!("pulling image `{docker_image}`");
+    shared::print_line(messaging, pipeline_id, stage_name, &line).await;
+    let mut stream = docker.create_image(
+        Some(CreateImageOptions {
+            from_image: docker_image,
+            ..Default::default()
+        }),
+        None,
+        credentials,
+    );
+    while let Some(info) = stream.next().await {
+        let info = info?;
+        // skip the download progress bar updates, the layer status changes are enough
+        if info.progress.is_some() {
+            continue;
+        }
+        if let Some(status) = info.status {
+            let line = info
+                .id
+                .map_or(status.clone(), |id| format!("{id}: {status}"));
+            shared::print_line(messaging, pipeline_id, stage_name, &line).await;
+        }
+    }
+    log::debug!("Image created: {docker_image}");
 
-        for command in &script.script {
-            if let Err(err) = execute_command(
-                docker,
-                messaging,
-                // &working_dir,
-                "/",
-                &container_id,
-                &split_command(command),
-                env,
-                pipeline_id,
-                stage_name,
-            )
-            .await
-            {
-                log::error!("Error in pipeline {}: {}", pipeline_id, err);
-                shared::cleanup(
-                    messaging,
-                    uuid,
-                    pipeline_id,
-                    stage_name,
-                    PipelineStatus::Failure,
-                )
-                .await;
-                return Err(RustyError::IoError(format!(
-                    "`{stage_name}` stage failed for pipeline `{pipeline_id}`"
-                )));
+    Ok(())
+}
+
+fn docker_credentials(auth: &RegistryAuth) -> DockerCredentials {
+    DockerCredentials {
+        username: Some(auth.username.clone()),
+        password: Some(auth.password.clone()),
+        serveraddress: Some(registry_address(&auth.registry)),
+        ..Default::default()
+    }
+}
+
+fn image_credentials(auths: &[RegistryAuth], image: &str) -> Option<DockerCredentials> {
+    let registry = image_registry(image);
+    auths
+        .iter()
+        .find(|auth| auth.registry == registry)
+        .map(docker_credentials)
+}
+
+// docker keeps Docker Hub credentials under its legacy index address
+fn registry_address(registry: &str) -> String {
+    if registry == DEFAULT_REGISTRY {
+        "https://index.docker.io/v1/".to_string()
+    } else {
+        registry.to_string()
+    }
+}
+
+async fn build_image(
+    docker: &Docker,
+    messaging: &MqClient,
+    pull: &ImagePull,
+    pipeline_id: &str,
+    build: &Build,
+    build_args: HashMap<String, String>,
+    stage_name: &str,
+) -> Result<(), RustyError> {
+    let context = format!("{}/{}", shared::pipeline_dir(pipeline_id), build.context());
+    let archive = tokio::task::spawn_blocking(move || archive_context(&context))
+        .await
+        .map_err(|err| DockerError(err.to_string()))??;
+
+    let image = build.tags[0].clone();
+    let line = format!("building image `{image}`");
+    shared::print_line(messaging, pipeline_id, stage_name, &line).await;
+    // credentials of every project registry, for pulling the base images
+    let credentials = pull
+        .auths
+        .iter()
+        .map(|auth| (registry_address(&auth.registry), docker_credentials(auth)))
+        .collect::<HashMap<String, DockerCredentials>>();
+    let mut stream = docker.build_image(
+        BuildImageOptions {
+            dockerfile: build.dockerfile(),
+            t: image.clone(),
+            buildargs: build_args,
+            pull: pull.policy == PullPolicy::Always,
+            rm: true,
+            ..Default::default()
+        },
+        Some(credentials),
+        Some(archive.into()),
+    );
+    while let Some(info) = stream.next().await {
+        let info = info?;
+        if let Some(error) = info.error {
+            return Err(DockerError(format!("Build error: {error}")));
+        }
+        for line in info.stream.unwrap_or_default().lines() {
+            if !line.trim().is_empty() {
+                shared::print_line(messaging, pipeline_id, stage_name, line.trim_end()).await;
             }
         }
+    }
 
-        stop_container(docker, &container_id).await?;
-        remove_container(docker, &container_id).await?;
+    for tag in &build.tags[1..] {
+        let (repo, tag) = split_tag(tag);
+        docker
+            .tag_image(&image, Some(TagImageOptions { repo, tag }))
+            .await?;
     }
+    log::debug!("Image built: {image}");
 
-    let _ = update_stage(pipeline_id, uuid, stage_name, PipelineStatus::Success).await;
     Ok(())
 }
 
-async fn create_image(docker: &Docker, docker_image: &str) -> Result<(), RustyError> {
-    let _ = docker
-        .create_image(
-            Some(CreateImageOptions {
-                from_image: docker_image,
-                ..Default::default()
-            }),
-            None,
-            None,
-        )
-        .try_collect::<Vec<_>>()
-        .await?;
-    log::debug!("Image created: {docker_image}");
+fn archive_context(path: &str) -> Result<Vec<u8>, RustyError> {
+    let mut archive = tar::Builder::new(Vec::new());
+    archive.append_dir_all(".", path)?;
+    Ok(archive.into_inner()?)
+}
+
+async fn push_images(
+    docker: &Docker,
+    messaging: &MqClient,
+    pull: &ImagePull,
+    pipeline_id: &str,
+    images: &[String],
+    stage_name: &str,
+) -> Result<(), RustyError> {
+    for image in images {
+        let line = format!("pushing image `{image}`");
+        shared::print_line(messaging, pipeline_id, stage_name, &line).await;
+        let (repository, tag) = split_tag(image);
+        let mut stream = docker.push_image(
+            repository,
+            Some(PushImageOptions { tag }),
+            image_credentials(&pull.auths, image),
+        );
+        while let Some(info) = stream.next().await {
+            let info = info?;
+            if let Some(error) = info.error {
+                return Err(DockerError(format!("Push error: {error}")));
+            }
+            if info.progress.is_some() {
+                continue;
+            }
+            if let Some(status) = info.status {
+                shared::print_line(messaging, pipeline_id, stage_name, &status).await;
+            }
+        }
+        log::debug!("Image pushed: {image}");
+    }
 
     Ok(())
 }
@@ -242,13 +516,34 @@ async fn create_image(docker: &Docker, docker_image: &str) -> Result<(), RustyEr
 async fn create_container(
     docker: &Docker,
     docker_image: &str,
-    volume_path: String,
+    binds: Vec<String>,
+    pipeline_id: &str,
+    resources: &Resources,
+    security: &Security,
 ) -> Result<String, RustyError> {
+    let network_mode = if security.network == Some(false) {
+        "none".to_string()
+    } else {
+        network_name(pipeline_id)
+    };
     let config = Config {
         image: Some(docker_image),
         tty: Some(true),
+        user: security.user.as_deref(),
+        labels: Some(HashMap::from([(PIPELINE_LABEL, pipeline_id)])),
         host_config: Some(HostConfig {
-            binds: Some(vec![volume_path]),
+            binds: Some(binds),
+            network_mode: Some(network_mode),
+            nano_cpus: resources.cpus.map(|cpus| (cpus * 1e9) as i64),
+            memory: resources.memory_bytes(),
+            // no swap on top of the memory limit
+            memory_swap: resources.memory_bytes(),
+            pids_limit: resources.pids,
+            cap_drop: security.cap_drop.clone(),
+            security_opt: security
+                .user
+                .as_ref()
+                .map(|_| vec!["no-new-privileges".to_string()]),
             ..Default::default()
         }),
         ..Default::default()
@@ -259,6 +554,319 @@ async fn create_container(
     Ok(container.id)
 }
 
+// the checkout is cloned as root - hand it over to the user running the stage
+async fn chown_checkout(
+    docker: &Docker,
+    container_id: &str,
+    working_dir: &str,
+    user: &str,
+) -> Result<(), RustyError> {
+    let exec_id = docker
+        .create_exec(
+            container_id,
+            CreateExecOptions {
+                attach_stdout: Some(true),
+                attach_stderr: Some(true),
+                user: Some("root"),
+                cmd: Some(vec!["chown", "-R", user, working_dir]),
+                ..Default::default()
+            },
+        )
+        .await?
+        .id;
+    if let StartExecResults::Attached { mut output, .. } = docker.start_exec(&exec_id, None).await?
+    {
+        while output.next().await.is_some() {}
+    }
+    if docker.inspect_exec(&exec_id).await?.exit_code != Some(0) {
+        log::warn!("Failed to change the checkout owner to `{user}`");
+    }
+
+    Ok(())
+}
+
+// explains a stage failure caused by the container limits
+async fn limit_error(
+    docker: &Docker,
+    container_id: &str,
+    resources: &Resources,
+    stage_name: &str,
+) -> Option<String> {
+    let state = docker
+        .inspect_container(container_id, None)
+        .await
+        .ok()?
+        .state?;
+    if state.oom_killed == Some(true) {
+        let limit = resources.memory.clone().unwrap_or_default();
+        Some(format!(
+            "`{stage_name}` stage was killed: out of memory (memory limit: {limit})"
+        ))
+    } else if resources.pids.is_some() && pids_limit_reached(docker, container_id).await {
+        let limit = resources.pids.unwrap_or_default();
+        Some(format!(
+            "`{stage_name}` stage failed: process limit reached (pids limit: {limit})"
+        ))
+    } else {
+        None
+    }
+}
+
+// docker does not report refused forks - the pids cgroup of the still running container counts them
+// (`pids.events` of cgroup v2, or of the pids controller of cgroup v1); images without `cat` are not checked
+async fn pids_limit_reached(docker: &Docker, container_id: &str) -> bool {
+    let Ok(exec) = docker
+        .create_exec(
+            container_id,
+            CreateExecOptions {
+                attach_stdout: Some(true),
+                user: Some("root"),
+                cmd: Some(vec![
+                    "cat",
+                    "/sys/fs/cgroup/pids.events",
+                    "/sys/fs/cgroup/pids/pids.events",
+                ]),
+                ..Default::default()
+            },
+        )
+        .await
+    else {
+        return false;
+    };
+    let mut events = String::new();
+    if let Ok(StartExecResults::Attached { mut output, .. }) =
+        docker.start_exec(&exec.id, None).await
+    {
+        while let Some(Ok(chunk)) = output.next().await {
+            events.push_str(&chunk.to_string());
+        }
+    }
+    // e.g. `max 3` - the amount of forks refused by the limit
+    events.lines().any(|line| {
+        line.strip_prefix("max ")
+            .and_then(|count| count.trim().parse::<u64>().ok())
+            .is_some_and(|count| count > 0)
+    })
+}
+
+fn network_name(pipeline_id: &str) -> String {
+    format!("rusty-{pipeline_id}")
+}
+
+async fn create_network(docker: &Docker, pipeline_id: &str) -> Result<(), RustyError> {
+    docker
+        .create_network(CreateNetworkOptions {
+            name: network_name(pipeline_id),
+            labels: HashMap::from([(PIPELINE_LABEL.to_string(), pipeline_id.to_string())]),
+            ..Default::default()
+        })
+        .await?;
+    log::debug!("Network created: {}", network_name(pipeline_id));
+
+    Ok(())
+}
+
+async fn start_services(
+    docker: &Docker,
+    messaging: &MqClient,
+    pipeline_id: &str,
+    services: &[Service],
+    pull: &ImagePull,
+    stage_name: &str,
+) -> Result<Vec<String>, RustyError> {
+    let mut started = vec![];
+    for service in services {
+        let result = start_service(docker, messaging, pipeline_id, service, pull, stage_name).await;
+        match result {
+            Ok(container_id) => started.push(container_id),
+            Err(err) => {
+                remove_containers(docker, &started).await;
+                return Err(err);
+            }
+        }
+    }
+    Ok(started)
+}
+
+async fn start_service(
+    docker: &Docker,
+    messaging: &MqClient,
+    pipeline_id: &str,
+    service: &Service,
+    pull: &ImagePull,
+    stage_name: &str,
+) -> Result<String, RustyError> {
+    let hostname = service.hostname();
+    let line = format!("starting service `{hostname}` ({})", service.image);
+    shared::print_line(messaging, pipeline_id, stage_name, &line).await;
+
+    create_image(
+        docker,
+        messaging,
+        pipeline_id,
+        stage_name,
+        &service.image,
+        pull,
+    )
+    .await?;
+    let env = service
+        .env
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect::<Vec<String>>();
+    let network = network_name(pipeline_id);
+    let config = Config {
+        image: Some(service.image.clone()),
+        env: Some(env),
+        labels: Some(HashMap::from([(
+            PIPELINE_LABEL.to_string(),
+            pipeline_id.to_string(),
+        )])),
+        host_config: Some(HostConfig {
+            network_mode: Some(network.clone()),
+            ..Default::default()
+        }),
+        networking_config: Some(NetworkingConfig {
+            endpoints_config: HashMap::from([(
+                network,
+                EndpointSettings {
+                    aliases: Some(vec![hostname.clone()]),
+                    ..Default::default()
+                },
+            )]),
+        }),
+        ..Default::default()
+    };
+    let container_id = docker
+        .create_container::<String, String>(None, config)
+        .await?
+        .id;
+    log::debug!("Service container created: {container_id}");
+    start_container(docker, &container_id).await?;
+
+    if let Err(err) = wait_for_service(docker, &container_id, pipeline_id, service).await {
+        remove_containers(docker, &[container_id]).await;
+        return Err(err);
+    }
+    let line = format!("service `{hostname}` is ready");
+    shared::print_line(messaging, pipeline_id, stage_name, &line).await;
+    Ok(container_id)
+}
+
+async fn wait_for_service(
+    docker: &Docker,
+    container_id: &str,
+    pipeline_id: &str,
+    service: &Service,
+) -> Result<(), RustyError> {
+    let timeout = var_or_default("SERVICE_HEALTH_TIMEOUT", 60);
+    let deadline = Instant::now() + Duration::from_secs(timeout);
+    loop {
+        let info = docker.inspect_container(container_id, None).await?;
+        let state = info.state.clone().unwrap_or_default();
+        if state.running != Some(true) {
+            return Err(DockerError(format!(
+                "service `{}` is not running",
+                service.hostname()
+            )));
+        }
+        let ready = match state.health.and_then(|health| health.status) {
+            Some(HealthStatusEnum::HEALTHY) => true,
+            Some(HealthStatusEnum::UNHEALTHY) => {
+                return Err(DockerError(format!(
+                    "service `{}` is unhealthy",
+                    service.hostname()
+                )));
+            }
+            Some(HealthStatusEnum::STARTING) => false,
+            // no health check defined in the image - wait for the declared ports instead
+            _ => {
+                ports_open(
+                    &info,
+                    pipeline_id,
+                    &service.ports.clone().unwrap_or_default(),
+                )
+                .await
+            }
+        };
+        if ready {
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            return Err(DockerError(format!(
+                "service `{}` was not ready within {timeout} seconds",
+                service.hostname()
+            )));
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
+
+async fn ports_open(info: &ContainerInspectResponse, pipeline_id: &str, ports: &[u16]) -> bool {
+    let ip = info
+        .network_settings
+        .as_ref()
+        .and_then(|settings| settings.networks.as_ref())
+        .and_then(|networks| networks.get(&network_name(pipeline_id)))
+        .and_then(|endpoint| endpoint.ip_address.clone())
+        .unwrap_or_default();
+    if ports.is_empty() {
+        return true;
+    }
+    if ip.is_empty() {
+        return false;
+    }
+    for port in ports {
+        let connect = TcpStream::connect((ip.as_str(), *port));
+        if !matches!(
+            tokio::time::timeout(Duration::from_secs(1), connect).await,
+            Ok(Ok(_))
+        ) {
+            return false;
+        }
+    }
+    true
+}
+
+async fn remove_containers(docker: &Docker, container_ids: &[String]) {
+    for container_id in container_ids {
+        let _ = docker
+            .remove_container(
+                container_id,
+                Some(RemoveContainerOptions {
+                    force: true,
+                    ..Default::default()
+                }),
+            )
+            .await;
+        log::debug!("Container removed: {:?}", container_id);
+    }
+}
+
+async fn remove_pipeline_containers(docker: &Docker, pipeline_id: &str) {
+    let label = format!("{PIPELINE_LABEL}={pipeline_id}");
+    let containers = docker
+        .list_containers(Some(ListContainersOptions {
+            all: true,
+            filters: HashMap::from([("label", vec![label.as_str()])]),
+            ..Default::default()
+        }))
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|container| container.id)
+        .collect::<Vec<String>>();
+    remove_containers(docker, &containers).await;
+}
+
+async fn teardown(docker: &Docker, pipeline_id: &str) {
+    remove_pipeline_containers(docker, pipeline_id).await;
+    let _ = docker.remove_network(&network_name(pipeline_id)).await;
+    log::debug!("Network removed: {}", network_name(pipeline_id));
+}
+
 async fn start_container(docker: &Docker, container_id: &str) -> Result<(), RustyError> {
     docker.start_container::<String>(container_id, None).await?;
     log::debug!("Container started: {:?}", container_id);
@@ -319,7 +927,7 @@ async fn execute_command(
     if let Some(exit_code) = docker.inspect_exec(&exec_id).await?.exit_code {
         if exit_code != 0 {
             return Err(DockerError(format!(
-                "command terminated with {exit_code} exit code"
+                "Command error: exit status: {exit_code}"
             )));
         }
     }
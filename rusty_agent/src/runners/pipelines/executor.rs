@@ -0,0 +1,113 @@
+use futures_util::future::try_join_all;
+use tokio::time::Instant;
+
+use commons::errors::RustyError;
+use domain::pipelines::PipelineStatus;
+
+use crate::api::pipelines::update_stage;
+use crate::runners::pipelines::{reports, shared, PipelineContext, Runner, StageRun};
+
+/// Run a pipeline with a runner
+///
+/// Stages of a dependency group run concurrently; when one of them fails, the others are cancelled
+/// and the pipeline is finalized as failed on the failing stage.
+pub async fn execute<R: Runner>(
+    runner: &R,
+    context: &PipelineContext<'_>,
+) -> Result<(), RustyError> {
+    let pipeline_id = &context.pipeline.id;
+    let result = run_pipeline(runner, context).await;
+    runner.cleanup(context).await;
+
+    match result {
+        Ok(()) => {
+            shared::cleanup(
+                context.messaging,
+                context.agent_uuid,
+                pipeline_id,
+                "rusty-after",
+                PipelineStatus::Success,
+            )
+            .await;
+            log::debug!("done: running pipeline {pipeline_id}");
+            Ok(())
+        }
+        Err((stage, err)) => {
+            log::error!("Error in pipeline {}: {}", pipeline_id, err);
+            shared::print_line(context.messaging, pipeline_id, &stage, &err.to_string()).await;
+            shared::cleanup(
+                context.messaging,
+                context.agent_uuid,
+                pipeline_id,
+                &stage,
+                PipelineStatus::Failure,
+            )
+            .await;
+            Err(RustyError::IoError(format!(
+                "`{stage}` stage failed for pipeline `{pipeline_id}`"
+            )))
+        }
+    }
+}
+
+async fn run_pipeline<R: Runner>(
+    runner: &R,
+    context: &PipelineContext<'_>,
+) -> Result<(), (String, RustyError)> {
+    let template = context.template;
+    let before = StageRun::before(template);
+    runner
+        .prepare_workspace(context)
+        .await
+        .map_err(|err| (before.name.clone(), err))?;
+    run_stage(runner, context, &before).await?;
+
+    for group in template.dependency_tree() {
+        let stages = group
+            .iter()
+            .map(|name| StageRun::stage(name, &template.stages[name]))
+            .collect::<Vec<StageRun>>();
+        // dropping the remaining stages is not enough for runners with detached processes
+        if let Err(err) =
+            try_join_all(stages.iter().map(|stage| run_stage(runner, context, stage))).await
+        {
+            runner.cancel(context).await;
+            return Err(err);
+        }
+    }
+
+    run_stage(runner, context, &StageRun::after(template)).await
+}
+
+async fn run_stage<R: Runner>(
+    runner: &R,
+    context: &PipelineContext<'_>,
+    stage: &StageRun,
+) -> Result<(), (String, RustyError)> {
+    let pipeline_id = &context.pipeline.id;
+    let start = Instant::now();
+    log::debug!("running stage: {}", stage.name);
+    let _ = update_stage(
+        pipeline_id,
+        context.agent_uuid,
+        &stage.name,
+        PipelineStatus::InProgress,
+    )
+    .await;
+
+    let result = runner.run_stage(context, stage).await;
+    // failing tests usually fail the stage - their reports are uploaded anyway
+    reports::upload(context, stage).await;
+    result.map_err(|err| (stage.name.clone(), err))?;
+
+    let _ = update_stage(
+        pipeline_id,
+        context.agent_uuid,
+        &stage.name,
+        PipelineStatus::Success,
+    )
+    .await;
+    let duration = start.elapsed().as_millis();
+    log::debug!("done: running stage: {} in {duration} ms", stage.name);
+    Ok(())
+}
@@ -0,0 +1,560 @@
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tokio::time::Instant;
+
+use commons::env::{var, var_or_default};
+use commons::errors::RustyError;
+use domain::checkouts::Checkout;
+use domain::pipelines::PipelineStatus;
+use domain::templates::pipeline::{PipelineTemplate, PullPolicy, Script, Stage};
+use messaging::mq_client::MqClient;
+
+use crate::runners::pipelines::{checkout, shared, PipelineContext, Runner, StageRun};
+
+const SERVICE_ACCOUNT_DIR: &str = "/var/run/secrets/kubernetes.io/serviceaccount";
+const WORKSPACE_DIR: &str = "/workspace";
+const CLONE_CONTAINER: &str = "rusty-clone";
+
+/// Kubernetes API client, limited to the pod operations used by the runner.
+#[derive(Clone, Debug)]
+pub struct KubernetesClient {
+    api_url: String,
+    token: Option<String>,
+    namespace: String,
+    client: reqwest::Client,
+}
+
+impl KubernetesClient {
+    /// constructor
+    #[must_use]
+    pub fn new(api_url: &str, token: Option<String>, namespace: &str) -> Self {
+        Self {
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token,
+            namespace: namespace.to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Build a client from the agent configuration, falling back to the in-cluster service account
+    ///
+    /// # Errors
+    ///
+    /// This function can generate the following errors:
+    ///
+    /// * `RustyError` - If the cluster CA certificate cannot be loaded.
+    pub fn from_env() -> Result<Self, RustyError> {
+        let api_url = var_or_default(
+            "KUBERNETES_API_URL",
+            "https://kubernetes.default.svc".to_string(),
+        );
+        let token = var::<String>("KUBERNETES_TOKEN")
+            .ok()
+            .or_else(|| std::fs::read_to_string(format!("{SERVICE_ACCOUNT_DIR}/token")).ok())
+            .map(|token| token.trim().to_string());
+        let namespace = var::<String>("KUBERNETES_NAMESPACE")
+            .ok()
+            .or_else(|| std::fs::read_to_string(format!("{SERVICE_ACCOUNT_DIR}/namespace")).ok())
+            .map_or_else(|| "default".to_string(), |ns| ns.trim().to_string());
+
+        let mut builder = reqwest::Client::builder();
+        let ca_cert = var_or_default(
+            "KUBERNETES_CA_CERT",
+            format!("{SERVICE_ACCOUNT_DIR}/ca.crt"),
+        );
+        if let Ok(pem) = std::fs::read(&ca_cert) {
+            let cert = reqwest::Certificate::from_pem(&pem)
+                .map_err(|err| RustyError::RequestError(err.to_string()))?;
+            builder = builder.add_root_certificate(cert);
+        }
+        let client = builder
+            .build()
+            .map_err(|err| RustyError::RequestError(err.to_string()))?;
+        Ok(Self {
+            client,
+            ..Self::new(&api_url, token, &namespace)
+        })
+    }
+
+    fn pods_url(&self) -> String {
+        format!("{}/api/v1/namespaces/{}/pods", self.api_url, self.namespace)
+    }
+
+    fn request(&self, method: reqwest::Method, url: &str) -> reqwest::RequestBuilder {
+        let request = self.client.request(method, url);
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    /// Create a pod from a manifest
+    ///
+    /// # Errors
+    ///
+    /// This function can generate the following errors:
+    ///
+    /// * `RustyError` - If the API server rejected the pod.
+    pub async fn create_pod(&self, manifest: &Value) -> Result<(), RustyError> {
+        let response = self
+            .request(reqwest::Method::POST, &self.pods_url())
+            .json(manifest)
+            .send()
+            .await?;
+        check_status(response).await.map(|_| ())
+    }
+
+    /// Get a pod by name
+    ///
+    /// # Errors
+    ///
+    /// This function can generate the following errors:
+    ///
+    /// * `RustyError` - If the pod cannot be fetched.
+    pub async fn get_pod(&self, name: &str) -> Result<Value, RustyError> {
+        let url = format!("{}/{name}", self.pods_url());
+        let response = self.request(reqwest::Method::GET, &url).send().await?;
+        let body = check_status(response).await?;
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    /// Open the log stream of a pod container, following it until the container terminates
+    ///
+    /// # Errors
+    ///
+    /// This function can generate the following errors:
+    ///
+    /// * `RustyError` - If the log stream cannot be opened.
+    pub async fn logs(&self, name: &str, container: &str) -> Result<reqwest::Response, RustyError> {
+        let url = format!("{}/{name}/log", self.pods_url());
+        let response = self
+            .request(reqwest::Method::GET, &url)
+            .query(&[("container", container), ("follow", "true")])
+            .send()
+            .await?;
+        if response.status().is_success() {
+            Ok(response)
+        } else {
+            Err(RustyError::RequestError(format!(
+                "Kubernetes API error: {}",
+                response.status()
+            )))
+        }
+    }
+
+    /// Delete a pod by name
+    ///
+    /// # Errors
+    ///
+    /// This function can generate the following errors:
+    ///
+    /// * `RustyError` - If the pod cannot be deleted.
+    pub async fn delete_pod(&self, name: &str) -> Result<(), RustyError> {
+        let url = format!("{}/{name}", self.pods_url());
+        let response = self
+            .request(reqwest::Method::DELETE, &url)
+            .query(&[("gracePeriodSeconds", "0")])
+            .send()
+            .await?;
+        check_status(response).await.map(|_| ())
+    }
+}
+
+async fn check_status(response: reqwest::Response) -> Result<String, RustyError> {
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|err| RustyError::RequestError(err.to_string()))?;
+    if status.is_success() {
+        Ok(body)
+    } else {
+        let message = serde_json::from_str::<Value>(&body)
+            .ok()
+            .and_then(|body| body["message"].as_str().map(ToString::to_string))
+            .unwrap_or(body);
+        Err(RustyError::RequestError(format!(
+            "Kubernetes API error: {status}: {message}"
+        )))
+    }
+}
+
+/// Runner executing a whole pipeline as a kubernetes pod
+///
+/// The pod is created with the workspace, every stage then waits for its container and streams its logs.
+#[derive(Debug)]
+pub struct KubernetesRunner {
+    client: KubernetesClient,
+    steps: Vec<PodStep>,
+}
+
+impl KubernetesRunner {
+    /// Build a runner from the agent configuration
+    ///
+    /// # Errors
+    ///
+    /// This function can generate the following errors:
+    ///
+    /// * `RustyError` - If the template uses services or build stages, or the client cannot be configured.
+    pub fn new(template: &PipelineTemplate) -> Result<Self, RustyError> {
+        let services = template.services.is_some()
+            || template
+                .stages
+                .values()
+                .any(|stage| stage.services.is_some());
+        if services || template.stages.values().any(|stage| stage.build.is_some()) {
+            return Err(RustyError::IoError(
+                "services and build stages require the docker runner".to_string(),
+            ));
+        }
+        Ok(Self {
+            client: KubernetesClient::from_env()?,
+            steps: pod_steps(template),
+        })
+    }
+}
+
+impl Runner for KubernetesRunner {
+    async fn prepare_workspace(&self, context: &PipelineContext<'_>) -> Result<(), RustyError> {
+        let pipeline_id = &context.pipeline.id;
+        let manifest = pod_manifest(
+            pipeline_id,
+            context.template,
+            context.repo_url,
+            context.branch,
+            context.pipeline.commit.as_deref(),
+            context.checkout,
+        );
+        self.client.create_pod(&manifest).await?;
+        let clone = PodStep {
+            stage: "rusty-before".to_string(),
+            container: Some(CLONE_CONTAINER.to_string()),
+        };
+        run_container(&self.client, context.messaging, pipeline_id, &clone).await
+    }
+
+    async fn run_stage(
+        &self,
+        context: &PipelineContext<'_>,
+        stage: &StageRun,
+    ) -> Result<(), RustyError> {
+        match self.steps.iter().find(|step| step.stage == stage.name) {
+            Some(step) => {
+                run_container(&self.client, context.messaging, &context.pipeline.id, step).await
+            }
+            None => Ok(()),
+        }
+    }
+
+    async fn cleanup(&self, context: &PipelineContext<'_>) {
+        let name = pod_name(&context.pipeline.id);
+        let _ = self.client.delete_pod(&name).await;
+        log::debug!("Pod deleted: {name}");
+    }
+
+    // init containers run one at a time, so deleting the pod stops the remaining stages
+    async fn cancel(&self, context: &PipelineContext<'_>) {
+        self.cleanup(context).await;
+    }
+}
+
+/// A pipeline step run as a container of the pipeline pod.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PodStep {
+    /// pipeline stage name reported to the server
+    pub stage: String,
+    /// pod container name - `None` for stages without a script
+    pub container: Option<String>,
+}
+
+/// Name of the pod running a pipeline
+#[must_use]
+pub fn pod_name(pipeline_id: &str) -> String {
+    format!("rusty-{pipeline_id}")
+}
+
+/// Steps of a pipeline, in the order they run in the pod
+///
+/// Stages run one after another as init containers sharing an `emptyDir` workspace,
+/// so stages of the same dependency group do not run in parallel.
+#[must_use]
+pub fn pod_steps(template: &PipelineTemplate) -> Vec<PodStep> {
+    let script_step = |stage: &str, script: &Option<Script>| PodStep {
+        stage: stage.to_string(),
+        container: script.as_ref().map(|_| stage.to_string()),
+    };
+    let mut steps = vec![script_step("rusty-before", &template.before)];
+    steps.extend(
+        template
+            .dependency_tree()
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .map(|(index, stage)| PodStep {
+                stage,
+                container: Some(format!("stage-{index}")),
+            }),
+    );
+    steps.push(script_step("rusty-after", &template.after));
+    steps
+}
+
+/// Pod manifest running a whole pipeline
+///
+/// The repository is cloned by the first init container into the `emptyDir` workspace,
+/// followed by the before script and the stages. The after script is the main container.
+#[must_use]
+pub fn pod_manifest(
+    pipeline_id: &str,
+    template: &PipelineTemplate,
+    repo_url: &str,
+    branch: &str,
+    commit: Option<&str>,
+    checkout: &Checkout,
+) -> Value {
+    let image = template.image.clone().unwrap_or_default();
+    let pull_policy = match template.pull_policy.unwrap_or_default() {
+        PullPolicy::Always => "Always",
+        PullPolicy::IfNotPresent => "IfNotPresent",
+        PullPolicy::Never => "Never",
+    };
+    let container = |name: &str, image: &str, script: &Script, stage: &Option<Stage>| {
+        let env = shared::prepare_env(template, stage)
+            .into_iter()
+            .map(|(name, value)| json!({ "name": name, "value": value }))
+            .collect::<Vec<Value>>();
+        let mut container = json!({
+            "name": name,
+            "image": image,
+            "imagePullPolicy": pull_policy,
+            "command": [shared::shell(template, stage), "-c", script.to_shell()],
+            "workingDir": WORKSPACE_DIR,
+            "env": env,
+            "volumeMounts": [{ "name": "workspace", "mountPath": WORKSPACE_DIR }],
+        });
+        if stage.is_some() {
+            container["resources"] = container_resources(stage);
+            container["securityContext"] = security_context(stage);
+        }
+        container
+    };
+
+    // checkout credentials end up in the pod spec, readable to those allowed to read pods
+    let clone_env = checkout::env(checkout, repo_url)
+        .into_iter()
+        .map(|(name, value)| json!({ "name": name, "value": value }))
+        .collect::<Vec<Value>>();
+    let clone_script = format!(
+        "set -e\napk add {}\n{}",
+        checkout::packages(checkout).join(" "),
+        checkout::script(checkout, repo_url, branch, commit, WORKSPACE_DIR, None)
+    );
+    let mut init_containers = vec![json!({
+        "name": CLONE_CONTAINER,
+        "image": "alpine:3.20",
+        "imagePullPolicy": pull_policy,
+        "command": ["sh", "-c", clone_script],
+        "env": clone_env,
+        "volumeMounts": [{ "name": "workspace", "mountPath": WORKSPACE_DIR }],
+    })];
+    if let Some(before) = &template.before {
+        init_containers.push(container("rusty-before", &image, before, &None));
+    }
+    for (step, name) in pod_steps(template)
+        .iter()
+        .filter_map(|step| step.container.clone().map(|c| (step, c)))
+        .filter(|(step, _)| template.stages.contains_key(&step.stage))
+    {
+        let stage = template.stages[&step.stage].clone();
+        let stage_image = stage.image.clone().unwrap_or_else(|| image.clone());
+        let script = Script::new(&stage.script);
+        init_containers.push(container(&name, &stage_image, &script, &Some(stage)));
+    }
+    let main_container = template.after.as_ref().map_or_else(
+        || {
+            json!({
+                "name": "rusty-after",
+                "image": "alpine:3.20",
+                "imagePullPolicy": pull_policy,
+                "command": ["true"],
+            })
+        },
+        |after| container("rusty-after", &image, after, &None),
+    );
+
+    let mut spec = json!({
+        "restartPolicy": "Never",
+        "initContainers": init_containers,
+        "containers": [main_container],
+        "volumes": [{ "name": "workspace", "emptyDir": {} }],
+    });
+    let pull_secrets = var::<String>("KUBERNETES_IMAGE_PULL_SECRETS")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|secret| !secret.is_empty())
+        .map(|secret| json!({ "name": secret }))
+        .collect::<Vec<Value>>();
+    if !pull_secrets.is_empty() {
+        spec["imagePullSecrets"] = json!(pull_secrets);
+    }
+    json!({
+        "apiVersion": "v1",
+        "kind": "Pod",
+        "metadata": {
+            "name": pod_name(pipeline_id),
+            "labels": { "rusty.pipeline": pipeline_id },
+        },
+        "spec": spec,
+    })
+}
+
+// the pids limit has no container level equivalent in kubernetes
+fn container_resources(stage: &Option<Stage>) -> Value {
+    let (resources, _) = shared::resources(stage);
+    let mut limits = json!({});
+    if let Some(cpus) = resources.cpus {
+        limits["cpu"] = json!(cpus.to_string());
+    }
+    if let Some(memory) = resources.memory_bytes() {
+        limits["memory"] = json!(memory.to_string());
+    }
+    json!({ "limits": limits })
+}
+
+// only numeric users can be enforced by kubernetes, network access is shared by the whole pod
+fn security_context(stage: &Option<Stage>) -> Value {
+    let security = shared::security(stage);
+    let mut context = json!({});
+    if let Some(user) = &security.user {
+        let (uid, gid) = user.split_once(':').unwrap_or((user, ""));
+        if let Ok(uid) = uid.parse::<i64>() {
+            context["runAsUser"] = json!(uid);
+            context["allowPrivilegeEscalation"] = json!(false);
+        }
+        if let Ok(gid) = gid.parse::<i64>() {
+            context["runAsGroup"] = json!(gid);
+        }
+    }
+    if let Some(cap_drop) = &security.cap_drop {
+        context["capabilities"] = json!({ "drop": cap_drop });
+    }
+    context
+}
+
+/// State of a pod container, looked up in the init and regular container statuses
+#[must_use]
+pub fn container_state(pod: &Value, container: &str) -> Option<Value> {
+    ["initContainerStatuses", "containerStatuses"]
+        .iter()
+        .filter_map(|statuses| pod["status"][statuses].as_array())
+        .flatten()
+        .find(|status| status["name"] == container)
+        .map(|status| status["state"].clone())
+}
+
+/// Stage status of a terminated container, with a failure message
+#[must_use]
+pub fn termination_status(state: &Value) -> Option<(PipelineStatus, Option<String>)> {
+    let terminated = state.get("terminated")?;
+    let exit_code = terminated["exitCode"].as_i64().unwrap_or(-1);
+    if exit_code == 0 {
+        return Some((PipelineStatus::Success, None));
+    }
+    let message = match terminated["reason"].as_str() {
+        Some("OOMKilled") => "killed: out of memory".to_string(),
+        Some(reason) => format!("failed: {reason} (exit status: {exit_code})"),
+        None => format!("failed: exit status: {exit_code}"),
+    };
+    Some((PipelineStatus::Failure, Some(message)))
+}
+
+// waits for the container to start, streams its logs and maps its exit status
+async fn run_container(
+    client: &KubernetesClient,
+    messaging: &MqClient,
+    pipeline_id: &str,
+    step: &PodStep,
+) -> Result<(), RustyError> {
+    let Some(container) = &step.container else {
+        return Ok(());
+    };
+    let pod = &pod_name(pipeline_id);
+    wait_for_container(client, pod, container).await?;
+
+    let mut logs = client.logs(pod, container).await?;
+    let mut buffer = String::new();
+    while let Some(chunk) = logs
+        .chunk()
+        .await
+        .map_err(|err| RustyError::RequestError(err.to_string()))?
+    {
+        buffer.push_str(&String::from_utf8_lossy(&chunk));
+        while let Some((line, rest)) = buffer.split_once('\n') {
+            shared::print_line(messaging, pipeline_id, &step.stage, line).await;
+            buffer = rest.to_string();
+        }
+    }
+    if !buffer.is_empty() {
+        shared::print_line(messaging, pipeline_id, &step.stage, &buffer).await;
+    }
+
+    let timeout = var_or_default("KUBERNETES_POD_TIMEOUT", 600);
+    let deadline = Instant::now() + Duration::from_secs(timeout);
+    loop {
+        let state = container_state(&client.get_pod(pod).await?, container);
+        if let Some((status, message)) = state.as_ref().and_then(termination_status) {
+            return match status {
+                PipelineStatus::Success => Ok(()),
+                _ => Err(RustyError::IoError(format!(
+                    "`{}` stage {}",
+                    step.stage,
+                    message.unwrap_or_default()
+                ))),
+            };
+        }
+        if Instant::now() >= deadline {
+            return Err(RustyError::IoError(format!(
+                "`{}` stage did not finish within {timeout} seconds",
+                step.stage
+            )));
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
+
+async fn wait_for_container(
+    client: &KubernetesClient,
+    pod: &str,
+    container: &str,
+) -> Result<(), RustyError> {
+    let timeout = var_or_default("KUBERNETES_POD_TIMEOUT", 600);
+    let deadline = Instant::now() + Duration::from_secs(timeout);
+    loop {
+        let state = container_state(&client.get_pod(pod).await?, container).unwrap_or_default();
+        if state.get("running").is_some() || state.get("terminated").is_some() {
+            return Ok(());
+        }
+        if let Some(reason) = state["waiting"]["reason"].as_str() {
+            if matches!(
+                reason,
+                "ErrImagePull"
+                    | "ImagePullBackOff"
+                    | "InvalidImageName"
+                    | "CreateContainerConfigError"
+            ) {
+                let message = state["waiting"]["message"].as_str().unwrap_or_default();
+                return Err(RustyError::IoError(format!(
+                    "container `{container}` cannot start: {reason} {message}"
+                )));
+            }
+        }
+        if Instant::now() >= deadline {
+            return Err(RustyError::IoError(format!(
+                "container `{container}` did not start within {timeout} seconds"
+            )));
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
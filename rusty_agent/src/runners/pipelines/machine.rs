@@ -1,192 +1,188 @@
 use std::collections::HashMap;
 
-use futures_util::future::try_join_all;
 use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
 use tokio::process::Command;
 use tokio::spawn;
-use tokio::time::Instant;
+use tokio::sync::OwnedRwLockReadGuard;
 
 use commons::errors::RustyError;
-use domain::pipelines::{Pipeline, PipelineStatus};
-use domain::templates::pipeline::{PipelineTemplate, Script};
+use domain::templates::pipeline::Stage;
 use messaging::mq_client::MqClient;
 
-use crate::api::pipelines::update_stage;
-use crate::runners::pipelines::shared;
+use crate::runners::pipelines::mirror::{update_script, MirrorCache};
+use crate::runners::pipelines::sandbox::{Sandbox, SandboxProcess};
+use crate::runners::pipelines::{checkout, shared, PipelineContext, Runner, StageRun};
 
-pub async fn execute_machine(
-    messaging: &MqClient,
-    pipeline: &Pipeline,
-    template: &PipelineTemplate,
-    repo_url: &str,
-    branch: &str,
-    agent_uuid: &str,
-) -> Result<(), RustyError> {
-    std::fs::create_dir_all(shared::WORKING_DIR)?;
-    clone_repository(agent_uuid, &pipeline.id, repo_url, branch, messaging).await?;
-
-    execute_stage(
-        messaging,
-        &pipeline.id,
-        agent_uuid,
-        &template.before,
-        &shared::prepare_env(template, &None),
-        "rusty-before",
-    )
-    .await?;
-
-    let stages_tree = template.dependency_tree();
-    for branch in stages_tree {
-        let mut tasks = Vec::new();
-
-        for leaf in branch {
-            let uuid = agent_uuid.to_string();
-            let template = template.clone();
-            let pipeline = pipeline.clone();
-            let messaging = messaging.clone();
-
-            let task = spawn(async move {
-                let start = Instant::now();
-                let (name, stage) = template.stages.iter().find(|(n, _)| leaf == **n).unwrap();
-                log::debug!("running stage: {name}");
-                if let Err(err) = execute_stage(
-                    &messaging,
-                    &pipeline.id,
-                    &uuid,
-                    &Some(Script::new(&stage.script)),
-                    &shared::prepare_env(&template, &Some(stage.clone())),
-                    name,
+/// Runner executing stages in a shell on the agent machine
+#[derive(Clone, Debug, Default)]
+pub struct ShellRunner {
+    sandbox: Option<Sandbox>,
+    mirrors: Option<MirrorCache>,
+}
+
+impl ShellRunner {
+    /// Build a runner from the agent configuration
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self {
+            sandbox: Sandbox::from_env(),
+            mirrors: MirrorCache::from_env(),
+        }
+    }
+
+    // the mirror is refreshed as the agent user - a failed update falls back to a network clone
+    async fn update_mirror(
+        &self,
+        context: &PipelineContext<'_>,
+    ) -> Option<(String, OwnedRwLockReadGuard<()>)> {
+        let mirrors = self.mirrors.as_ref()?;
+        let mirror = mirrors.path(context.repo_url);
+        let guard = mirrors.lock(&mirror).await;
+        let result = match std::fs::create_dir_all(&mirrors.dir) {
+            Ok(()) => {
+                run_bash_command(
+                    context.messaging,
+                    &mirrors.dir,
+                    &update_script(context.checkout, context.repo_url, &mirror),
+                    &checkout::env(context.checkout, context.repo_url),
+                    "sh",
+                    None,
+                    &context.pipeline.id,
+                    "rusty-before",
                 )
                 .await
-                {
-                    log::error!("Error in pipeline {}: {}", &pipeline.id, err);
-                    return Err(());
-                }
-
-                let duration = start.elapsed().as_millis();
-                log::debug!("done: running stage: {name} in {duration} ms");
-                Ok(())
-            });
-            tasks.push(task);
+            }
+            Err(err) => Err(err.into()),
+        };
+        if let Err(err) = result {
+            let line = format!("failed to update the repository mirror: {err}");
+            shared::print_line(
+                context.messaging,
+                &context.pipeline.id,
+                "rusty-before",
+                &line,
+            )
+            .await;
         }
-
-        try_join_all(tasks).await?;
+        Some((mirror, guard.downgrade()))
     }
 
-    execute_stage(
-        messaging,
-        &pipeline.id,
-        agent_uuid,
-        &template.after,
-        &shared::prepare_env(template, &None),
-        "rusty-after",
-    )
-    .await?;
-
-    shared::cleanup(
-        messaging,
-        agent_uuid,
-        &pipeline.id,
-        "rusty-after",
-        PipelineStatus::Success,
-    )
-    .await;
-    log::debug!("done: running pipeline {}", pipeline.id);
-    Ok(())
+    async fn process(
+        &self,
+        context: &PipelineContext<'_>,
+        stage_name: &str,
+        stage: &Option<Stage>,
+    ) -> Result<Option<SandboxProcess>, RustyError> {
+        let Some(sandbox) = &self.sandbox else {
+            return Ok(None);
+        };
+        let pipeline_id = &context.pipeline.id;
+        let env = shared::prepare_env(context.template, stage);
+        let process = sandbox.process(&shared::pipeline_dir(pipeline_id), &env, stage)?;
+        for limit in &process.lowered {
+            let line = format!("`{stage_name}` stage {limit} limit lowered to the agent maximum");
+            shared::print_line(context.messaging, pipeline_id, stage_name, &line).await;
+        }
+        Ok(Some(process))
+    }
 }
 
-async fn clone_repository(
-    uuid: &str,
-    pipeline_id: &str,
-    repo_url: &str,
-    branch: &str,
-    messaging: &MqClient,
-) -> Result<(), RustyError> {
-    log::debug!("cloning repository: {repo_url} -b {branch}");
-    if let Err(err) = run_bash_command(
-        messaging,
-        shared::WORKING_DIR,
-        &format!("git clone {repo_url} -b {branch} {pipeline_id}"),
-        &HashMap::new(),
-        pipeline_id,
-        "rusty-before",
-    )
-    .await
-    {
-        log::error!("Error in pipeline {}: {}", &pipeline_id, err);
-        shared::cleanup(
-            messaging,
-            uuid,
-            pipeline_id,
+impl Runner for ShellRunner {
+    async fn prepare_workspace(&self, context: &PipelineContext<'_>) -> Result<(), RustyError> {
+        let (repo_url, branch) = (context.repo_url, context.branch);
+        log::debug!("cloning repository: {repo_url} -b {branch}");
+        let dir = shared::pipeline_dir(&context.pipeline.id);
+        match &self.sandbox {
+            Some(sandbox) => sandbox.create_workspace(&dir)?,
+            None => std::fs::create_dir_all(&dir)?,
+        }
+        let mirror = self.update_mirror(context).await;
+        // the repository is cloned as the default stage user
+        let process = self.process(context, "rusty-before", &None).await?;
+        let reference = mirror.as_ref().map(|(mirror, _)| mirror.as_str());
+        let result = run_bash_command(
+            context.messaging,
+            &dir,
+            &checkout::script(
+                context.checkout,
+                repo_url,
+                branch,
+                context.pipeline.commit.as_deref(),
+                &dir,
+                reference,
+            ),
+            &checkout::env(context.checkout, repo_url),
+            "sh",
+            process.as_ref(),
+            &context.pipeline.id,
             "rusty-before",
-            PipelineStatus::Failure,
         )
         .await;
-        Err(err)
-    } else {
-        Ok(())
+        if let (Some(mirrors), Some((mirror, guard))) = (&self.mirrors, mirror) {
+            drop(guard);
+            mirrors.touch(&mirror);
+            mirrors.collect_garbage();
+        }
+        result
     }
-}
 
-async fn execute_stage(
-    messaging: &MqClient,
-    pipeline_id: &str,
-    uuid: &str,
-    script: &Option<Script>,
-    env: &HashMap<String, String>,
-    stage_name: &str,
-) -> Result<(), RustyError> {
-    let _ = update_stage(pipeline_id, uuid, stage_name, PipelineStatus::InProgress).await;
-
-    if let Some(script) = &script {
-        for command in &script.script {
-            if let Err(err) = run_bash_command(
-                messaging,
-                &format!("{}/{pipeline_id}", shared::WORKING_DIR),
+    async fn run_stage(
+        &self,
+        context: &PipelineContext<'_>,
+        stage: &StageRun,
+    ) -> Result<(), RustyError> {
+        let pipeline_id = &context.pipeline.id;
+        let env = shared::prepare_env(context.template, &stage.stage);
+        let shell = shared::shell(context.template, &stage.stage);
+        let process = self.process(context, &stage.name, &stage.stage).await?;
+        for command in stage.script.iter().flat_map(|script| &script.script) {
+            run_bash_command(
+                context.messaging,
+                &shared::pipeline_dir(pipeline_id),
                 command,
-                env,
+                &env,
+                &shell,
+                process.as_ref(),
                 pipeline_id,
-                stage_name,
+                &stage.name,
             )
-            .await
-            {
-                log::error!("Error in pipeline {}: {}", pipeline_id, err);
-                shared::cleanup(
-                    messaging,
-                    uuid,
-                    pipeline_id,
-                    stage_name,
-                    PipelineStatus::Failure,
-                )
-                .await;
-                return Err(RustyError::IoError(format!(
-                    "`{stage_name}` stage failed for pipeline `{pipeline_id}`"
-                )));
-            }
+            .await?;
         }
+        Ok(())
     }
 
-    let _ = update_stage(pipeline_id, uuid, stage_name, PipelineStatus::Success).await;
-    Ok(())
+    // the workspace is removed when the pipeline is finalized
+    async fn cleanup(&self, _: &PipelineContext<'_>) {}
+
+    // processes of cancelled stages are killed when their futures are dropped
+    async fn cancel(&self, _: &PipelineContext<'_>) {}
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn run_bash_command(
     messaging: &MqClient,
     dir: &str,
     command: &str,
     env: &HashMap<String, String>,
+    shell: &str,
+    sandbox: Option<&SandboxProcess>,
     pipeline_id: &str,
     stage: &str,
 ) -> Result<(), RustyError> {
-    let mut process = Command::new("sh")
+    let mut command_builder = Command::new(shell);
+    command_builder
         .current_dir(dir)
         .arg("-c")
         .arg(command)
-        .envs(env)
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped())
-        .kill_on_drop(true)
-        .spawn()?;
+        .kill_on_drop(true);
+    if let Some(sandbox) = sandbox {
+        sandbox.apply(&mut command_builder);
+    }
+    // set after the sandbox, which clears the environment
+    command_builder.envs(env);
+    let mut process = command_builder.spawn()?;
 
     let stdout = process.stdout.take().unwrap();
     let mq_out = messaging.clone();
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use once_cell::sync::Lazy;
+use tokio::sync::{OwnedRwLockWriteGuard, RwLock};
+
+use commons::env::var_or_default;
+use domain::checkouts::Checkout;
+use domain::templates::pipeline::parse_memory;
+
+use crate::runners::pipelines::checkout::{credentials_setup, quote};
+use crate::runners::pipelines::shared;
+
+// marker file touched on every use of a mirror
+const LAST_USED: &str = "rusty-last-used";
+
+// mirrors are updated and removed under a write lock, and cloned from under a read lock
+// the locks only exist in the agent process, so the mirror directory cannot be shared between agents
+static LOCKS: Lazy<Mutex<HashMap<String, Arc<RwLock<()>>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Local bare mirrors of the project repositories, enabled with `MIRROR_CACHE`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MirrorCache {
+    /// directory of the mirrors
+    pub dir: String,
+    /// disk usage above which the least recently used mirrors are removed
+    pub limit: Option<u64>,
+}
+
+/// Disk usage of a mirror
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MirrorUsage {
+    /// mirror directory
+    pub path: String,
+    /// size in bytes
+    pub size: u64,
+    /// last pipeline checkout from the mirror
+    pub last_used: SystemTime,
+}
+
+impl MirrorCache {
+    /// Mirror cache configured for the agent - `None` when disabled
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        if !var_or_default("MIRROR_CACHE", true) {
+            return None;
+        }
+        let dir = var_or_default("MIRROR_DIR", format!("{}/.mirrors", shared::working_dir()));
+        let limit = parse_memory(&var_or_default("MIRROR_CACHE_LIMIT", "10g".to_string()))
+            .and_then(|limit| u64::try_from(limit).ok());
+        Some(Self { dir, limit })
+    }
+
+    /// Mirror directory of a repository
+    #[must_use]
+    pub fn path(&self, repo_url: &str) -> String {
+        format!("{}/{}", self.dir, mirror_name(repo_url))
+    }
+
+    /// Lock a mirror for an update - downgrade the guard to keep the mirror while cloning from it
+    pub async fn lock(&self, mirror: &str) -> OwnedRwLockWriteGuard<()> {
+        mirror_lock(mirror).write_owned().await
+    }
+
+    /// Record a checkout from a mirror - garbage collection removes the least recently used mirrors first
+    pub fn touch(&self, mirror: &str) {
+        let _ = std::fs::write(
+            format!("{mirror}/{LAST_USED}"),
+            chrono::Utc::now().to_rfc3339(),
+        );
+    }
+
+    /// Remove the least recently used mirrors while the cache is over its limit
+    ///
+    /// Mirrors in use by a pipeline are skipped.
+    pub fn collect_garbage(&self) {
+        let Some(limit) = self.limit else {
+            return;
+        };
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+        let usages = entries
+            .filter_map(Result::ok)
+            // mirrors being cloned are moved in place once complete
+            .filter(|entry| {
+                entry.path().is_dir() && !entry.file_name().to_string_lossy().ends_with(".tmp")
+            })
+            .map(|entry| {
+                let path = entry.path();
+                let last_used = std::fs::metadata(path.join(LAST_USED))
+                    .or_else(|_| entry.metadata())
+                    .and_then(|metadata| metadata.modified())
+                    .unwrap_or(SystemTime::UNIX_EPOCH);
+                MirrorUsage {
+                    path: path.to_string_lossy().to_string(),
+                    size: dir_size(&path),
+                    last_used,
+                }
+            })
+            .collect::<Vec<MirrorUsage>>();
+
+        for mirror in gc_candidates(&usages, limit) {
+            let lock = mirror_lock(&mirror);
+            let Ok(_guard) = lock.try_write() else {
+                log::debug!("mirror in use, not removed: {mirror}");
+                continue;
+            };
+            match std::fs::remove_dir_all(&mirror) {
+                Ok(()) => log::debug!("mirror removed: {mirror}"),
+                Err(err) => log::warn!("failed to remove mirror {mirror}: {err}"),
+            }
+        }
+    }
+}
+
+/// Shell script creating or refreshing the mirror of a repository
+///
+/// A new mirror is cloned next to its final location and moved in place once complete,
+/// so an interrupted clone never leaves a broken mirror.
+#[must_use]
+pub fn update_script(checkout: &Checkout, repo_url: &str, mirror: &str) -> String {
+    let (repo_url, tmp, mirror) = (
+        quote(repo_url),
+        quote(&format!("{mirror}.tmp")),
+        quote(mirror),
+    );
+    let mut lines = credentials_setup(checkout);
+    lines.push(format!("if [ -d {mirror} ]; then"));
+    lines.push(format!("  git -C {mirror} fetch --prune origin"));
+    lines.push("else".to_string());
+    lines.push(format!("  rm -rf {tmp}"));
+    lines.push(format!("  git clone --mirror {repo_url} {tmp}"));
+    lines.push(format!("  mv {tmp} {mirror}"));
+    lines.push("fi".to_string());
+    lines.join("\n")
+}
+
+/// Mirrors to remove to bring the cache under its limit - the least recently used first
+#[must_use]
+pub fn gc_candidates(mirrors: &[MirrorUsage], limit: u64) -> Vec<String> {
+    let mut mirrors = mirrors.to_vec();
+    mirrors.sort_by_key(|mirror| mirror.last_used);
+    let mut total = mirrors.iter().map(|mirror| mirror.size).sum::<u64>();
+    let mut removed = vec![];
+    for mirror in mirrors {
+        if total <= limit {
+            break;
+        }
+        total -= mirror.size;
+        removed.push(mirror.path);
+    }
+    removed
+}
+
+// e.g. `https://github.com/org/repo.git` -> `github.com_org_repo.git`
+fn mirror_name(repo_url: &str) -> String {
+    let url = repo_url
+        .split_once("://")
+        .map_or(repo_url, |(_, rest)| rest);
+    // credentials embedded in the url are not part of the name
+    let url = url.rsplit_once('@').map_or(url, |(_, rest)| rest);
+    let name = url
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    format!("{name}.git")
+}
+
+fn mirror_lock(mirror: &str) -> Arc<RwLock<()>> {
+    LOCKS
+        .lock()
+        .unwrap()
+        .entry(mirror.to_string())
+        .or_default()
+        .clone()
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .filter_map(Result::ok)
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => dir_size(&entry.path()),
+            Ok(file_type) if file_type.is_file() => entry.metadata().map_or(0, |m| m.len()),
+            _ => 0,
+        })
+        .sum()
+}
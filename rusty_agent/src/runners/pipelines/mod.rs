@@ -1,15 +1,140 @@
+use std::collections::HashMap;
+use std::future::Future;
+
+use commons::env::var_or_default;
 use commons::errors::RustyError;
-use domain::pipelines::Pipeline;
+use domain::checkouts::Checkout;
+use domain::pipelines::{Pipeline, PipelineStatus};
+use domain::templates::pipeline::{PipelineTemplate, RunnerKind, Script, Stage};
+use messaging::mq_client::MqClient;
 
+use crate::api::checkouts::get_checkout;
 use crate::api::jobs::get_pipeline_template;
 use crate::api::projects::get_pipeline_project;
+use crate::api::registries::get_registry_auths;
+use crate::api::revisions::get_revision_template;
 use crate::messaging::get_messaging;
-use crate::runners::pipelines::{docker::execute_docker, machine::execute_machine};
+use crate::runners::pipelines::{
+    docker::DockerRunner, kubernetes::KubernetesRunner, machine::ShellRunner,
+};
 
+pub mod checkout;
 mod docker;
+mod executor;
+pub mod kubernetes;
 mod machine;
+pub mod mirror;
+pub mod reports;
+pub mod sandbox;
 mod shared;
 
+/// Everything a runner needs to know about a pipeline run
+#[derive(Debug)]
+pub struct PipelineContext<'a> {
+    /// pipeline logs and status messaging
+    pub messaging: &'a MqClient,
+    /// pipeline to run
+    pub pipeline: &'a Pipeline,
+    /// pipeline template
+    pub template: &'a PipelineTemplate,
+    /// repository url
+    pub repo_url: &'a str,
+    /// branch to check out
+    pub branch: &'a str,
+    /// project checkout settings
+    pub checkout: &'a Checkout,
+    /// id of the agent running the pipeline
+    pub agent_uuid: &'a str,
+}
+
+/// A stage as run by a runner - the before and after scripts are stages without a template stage
+#[derive(Clone, Debug)]
+pub struct StageRun {
+    /// stage name reported to the server
+    pub name: String,
+    /// stage commands
+    pub script: Option<Script>,
+    /// template stage
+    pub stage: Option<Stage>,
+}
+
+impl StageRun {
+    /// The before script of a template
+    #[must_use]
+    pub fn before(template: &PipelineTemplate) -> Self {
+        Self {
+            name: "rusty-before".to_string(),
+            script: template.before.clone(),
+            stage: None,
+        }
+    }
+
+    /// The after script of a template
+    #[must_use]
+    pub fn after(template: &PipelineTemplate) -> Self {
+        Self {
+            name: "rusty-after".to_string(),
+            script: template.after.clone(),
+            stage: None,
+        }
+    }
+
+    /// A template stage
+    #[must_use]
+    pub fn stage(name: &str, stage: &Stage) -> Self {
+        Self {
+            name: name.to_string(),
+            script: Some(Script::new(&stage.script)),
+            stage: Some(stage.clone()),
+        }
+    }
+}
+
+/// Backend running pipeline stages
+///
+/// The shared executor drives a runner: it prepares the workspace, runs the stages
+/// in dependency order, reports their statuses and cleans up.
+pub trait Runner {
+    /// Prepare the pipeline workspace - e.g. check out the repository
+    fn prepare_workspace(
+        &self,
+        context: &PipelineContext<'_>,
+    ) -> impl Future<Output = Result<(), RustyError>>;
+
+    /// Run a single stage - its output is published to the pipeline logs
+    fn run_stage(
+        &self,
+        context: &PipelineContext<'_>,
+        stage: &StageRun,
+    ) -> impl Future<Output = Result<(), RustyError>>;
+
+    /// Release the resources of the pipeline - called once, whatever the pipeline result
+    fn cleanup(&self, context: &PipelineContext<'_>) -> impl Future<Output = ()>;
+
+    /// Stop stages still running - called when a stage of a parallel group failed
+    fn cancel(&self, context: &PipelineContext<'_>) -> impl Future<Output = ()>;
+}
+
+/// Select the runner of a template
+///
+/// Without an explicit `runner`, templates with a docker image run with the `CONTAINER_RUNNER`
+/// backend (`docker` or `kubernetes`), and templates without an image run in a shell.
+/// Templates with services or build stages always run with docker, the only runner supporting them.
+#[must_use]
+pub fn select_runner(template: &PipelineTemplate) -> RunnerKind {
+    template.runner.unwrap_or_else(|| {
+        if template.image.is_none() {
+            RunnerKind::Shell
+        } else if template.requires_docker() {
+            RunnerKind::Docker
+        } else if var_or_default("CONTAINER_RUNNER", "docker".to_string()) == "kubernetes" {
+            RunnerKind::Kubernetes
+        } else {
+            RunnerKind::Docker
+        }
+    })
+}
+
 pub async fn execute(pipeline: Pipeline, uuid: &str) -> Result<(), RustyError> {
     log::debug!("running pipeline {}", pipeline.id);
 
@@ -19,6 +144,18 @@ pub async fn execute(pipeline: Pipeline, uuid: &str) -> Result<(), RustyError> {
         .await;
 
     let (project_id, template) = get_pipeline_template(&pipeline.job_id).await?;
+    // pipelines run the template revision they were registered with
+    let mut template = match &pipeline.revision {
+        Some(revision) => get_revision_template(revision).await?,
+        None => template,
+    };
+    // variables of triggered pipelines override the template env, stage env still takes precedence
+    if let Some(variables) = &pipeline.variables {
+        template
+            .env
+            .get_or_insert_with(HashMap::new)
+            .extend(variables.clone());
+    }
     let (default_branch, repo_url) = get_pipeline_project(&project_id).await?;
     let branch = if pipeline.branch.is_empty() {
         default_branch
@@ -26,12 +163,54 @@ pub async fn execute(pipeline: Pipeline, uuid: &str) -> Result<(), RustyError> {
         pipeline.branch.clone()
     };
 
-    if let Some(ref image) = template.image {
-        execute_docker(
-            &messaging, &pipeline, &template, &repo_url, &branch, image, uuid,
-        )
+    // public repositories can still be cloned when the settings cannot be fetched
+    let checkout = get_checkout(&project_id)
         .await
-    } else {
-        execute_machine(&messaging, &pipeline, &template, &repo_url, &branch, uuid).await
+        .unwrap_or_else(|err| {
+            log::warn!("failed to fetch checkout settings: {err}");
+            None
+        })
+        .unwrap_or_default();
+    shared::register_secrets(&pipeline.id, checkout::secrets(&checkout));
+
+    let context = PipelineContext {
+        messaging: &messaging,
+        pipeline: &pipeline,
+        template: &template,
+        repo_url: &repo_url,
+        branch: &branch,
+        checkout: &checkout,
+        agent_uuid: uuid,
+    };
+    match select_runner(&template) {
+        RunnerKind::Docker => {
+            // pulling public images still works when the credentials cannot be fetched
+            let auths = get_registry_auths(&project_id).await.unwrap_or_else(|err| {
+                log::warn!("failed to fetch registry credentials: {err}");
+                vec![]
+            });
+            match DockerRunner::new(&template, &auths) {
+                Ok(runner) => executor::execute(&runner, &context).await,
+                Err(err) => unavailable(&context, err).await,
+            }
+        }
+        RunnerKind::Kubernetes => match KubernetesRunner::new(&template) {
+            Ok(runner) => executor::execute(&runner, &context).await,
+            Err(err) => unavailable(&context, err).await,
+        },
+        RunnerKind::Shell => executor::execute(&ShellRunner::from_env(), &context).await,
     }
 }
+
+async fn unavailable(context: &PipelineContext<'_>, err: RustyError) -> Result<(), RustyError> {
+    log::error!("Error in pipeline {}: {}", context.pipeline.id, err);
+    shared::cleanup(
+        context.messaging,
+        context.agent_uuid,
+        &context.pipeline.id,
+        "rusty-before",
+        PipelineStatus::Failure,
+    )
+    .await;
+    Err(err)
+}
@@ -0,0 +1,298 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+use roxmltree::{Document, Node};
+
+use commons::errors::RustyError;
+use domain::coverage::FileCoverage;
+use domain::test_reports::{TestCase, TestStatus, TestSuite};
+
+use crate::api::coverage::register_coverage;
+use crate::api::test_reports::register_tests;
+use crate::runners::pipelines::{shared, PipelineContext, StageRun};
+
+// failure messages and outputs can be huge - only their beginning is uploaded
+const MAX_MESSAGE_LENGTH: usize = 4096;
+
+/// Upload the reports declared by a stage
+///
+/// Reports are read from the pipeline checkout on the agent, whatever the stage result.
+/// Missing or invalid reports are logged to the pipeline, and do not fail the stage.
+pub async fn upload(context: &PipelineContext<'_>, stage: &StageRun) {
+    let Some(reports) = stage
+        .stage
+        .as_ref()
+        .and_then(|stage| stage.reports.as_ref())
+    else {
+        return;
+    };
+    let pipeline_id = &context.pipeline.id;
+    if let Some(pattern) = &reports.junit {
+        let suites = read(context, stage, "junit", pattern, parse_junit).await;
+        if !suites.is_empty() {
+            if let Err(err) =
+                register_tests(pipeline_id, context.agent_uuid, &stage.name, &suites).await
+            {
+                log::warn!("failed to upload test report of pipeline {pipeline_id}: {err}");
+            }
+        }
+    }
+    if let Some(pattern) = &reports.coverage {
+        let files = merge_coverage(read(context, stage, "coverage", pattern, parse_coverage).await);
+        if !files.is_empty() {
+            if let Err(err) =
+                register_coverage(pipeline_id, context.agent_uuid, &stage.name, &files).await
+            {
+                log::warn!("failed to upload coverage report of pipeline {pipeline_id}: {err}");
+            }
+        }
+    }
+}
+
+// parse every report matching the pattern, logging the invalid ones
+async fn read<T>(
+    context: &PipelineContext<'_>,
+    stage: &StageRun,
+    kind: &str,
+    pattern: &str,
+    parse: fn(&str) -> Result<Vec<T>, RustyError>,
+) -> Vec<T> {
+    let pipeline_id = &context.pipeline.id;
+    let mut entries = vec![];
+    for path in find(&shared::pipeline_dir(pipeline_id), pattern) {
+        match std::fs::read_to_string(&path)
+            .map_err(RustyError::from)
+            .and_then(|content| parse(&content))
+        {
+            Ok(parsed) => entries.extend(parsed),
+            Err(err) => {
+                let line = format!("invalid {kind} report `{}`: {err}", path.display());
+                shared::print_line(context.messaging, pipeline_id, &stage.name, &line).await;
+            }
+        }
+    }
+    if entries.is_empty() {
+        let line = format!("no {kind} report found for `{pattern}`");
+        shared::print_line(context.messaging, pipeline_id, &stage.name, &line).await;
+    }
+    entries
+}
+
+/// Files of the checkout matching a report glob pattern
+#[must_use]
+pub fn find(dir: &str, pattern: &str) -> Vec<PathBuf> {
+    let pattern = format!("{}/{pattern}", glob::Pattern::escape(dir));
+    glob::glob(&pattern).map_or_else(
+        |_| vec![],
+        |paths| {
+            paths
+                .filter_map(Result::ok)
+                .filter(|path| path.is_file())
+                .collect()
+        },
+    )
+}
+
+/// Parse a JUnit XML report - a `testsuites` or a single `testsuite` root
+///
+/// # Errors
+///
+/// This function can generate the following errors:
+///
+/// * `RustyError` - If the report is not valid XML.
+pub fn parse_junit(xml: &str) -> Result<Vec<TestSuite>, RustyError> {
+    let document =
+        Document::parse(xml).map_err(|err| RustyError::SerializationError(err.to_string()))?;
+    Ok(document
+        .descendants()
+        .filter(|node| node.has_tag_name("testsuite"))
+        .filter(|suite| suite.children().any(|node| node.has_tag_name("testcase")))
+        .map(parse_suite)
+        .collect())
+}
+
+fn parse_suite(suite: Node<'_, '_>) -> TestSuite {
+    let cases = suite
+        .children()
+        .filter(|node| node.has_tag_name("testcase"))
+        .map(parse_case)
+        .collect::<Vec<TestCase>>();
+    TestSuite {
+        name: suite.attribute("name").unwrap_or_default().to_string(),
+        duration: parse_time(suite.attribute("time"))
+            .unwrap_or_else(|| cases.iter().map(|case| case.duration).sum()),
+        cases,
+    }
+}
+
+fn parse_case(case: Node<'_, '_>) -> TestCase {
+    let child = |name: &str| case.children().find(|node| node.has_tag_name(name));
+    let (status, result) = if let Some(failure) = child("failure") {
+        (TestStatus::Failed, Some(failure))
+    } else if let Some(error) = child("error") {
+        (TestStatus::Error, Some(error))
+    } else if let Some(skipped) = child("skipped") {
+        (TestStatus::Skipped, Some(skipped))
+    } else {
+        (TestStatus::Passed, None)
+    };
+    TestCase {
+        name: case.attribute("name").unwrap_or_default().to_string(),
+        classname: case.attribute("classname").map(ToString::to_string),
+        duration: parse_time(case.attribute("time")).unwrap_or_default(),
+        status,
+        message: result.and_then(message),
+    }
+}
+
+// the message attribute, followed by the element text (e.g. a stack trace)
+fn message(node: Node<'_, '_>) -> Option<String> {
+    let mut parts = [node.attribute("message"), node.text()]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<&str>>();
+    parts.dedup();
+    let message = parts.join("\n");
+    if message.is_empty() {
+        None
+    } else {
+        Some(message.chars().take(MAX_MESSAGE_LENGTH).collect())
+    }
+}
+
+// some reporters format durations with thousands separators
+fn parse_time(time: Option<&str>) -> Option<f64> {
+    time.and_then(|time| time.replace(',', "").parse().ok())
+}
+
+/// Parse a coverage report - LCOV, or Cobertura XML when the report is an XML document
+///
+/// # Errors
+///
+/// This function can generate the following errors:
+///
+/// * `RustyError` - If the report is invalid or contains no coverage data.
+pub fn parse_coverage(report: &str) -> Result<Vec<FileCoverage>, RustyError> {
+    let files = if report.trim_start().starts_with('<') {
+        parse_cobertura(report)?
+    } else {
+        parse_lcov(report)?
+    };
+    if files.is_empty() {
+        Err(RustyError::SerializationError(
+            "no coverage data".to_string(),
+        ))
+    } else {
+        Ok(files)
+    }
+}
+
+/// Parse an LCOV tracefile into per file line coverage
+///
+/// # Errors
+///
+/// This function can generate the following errors:
+///
+/// * `RustyError` - If a line record is malformed.
+pub fn parse_lcov(report: &str) -> Result<Vec<FileCoverage>, RustyError> {
+    let mut hits = LineHits::new();
+    let mut file = None;
+    for line in report.lines().map(str::trim) {
+        if let Some(path) = line.strip_prefix("SF:") {
+            file = Some(path.to_string());
+        } else if let Some(record) = line.strip_prefix("DA:") {
+            // DA:<line>,<hits>[,<checksum>]
+            let mut fields = record.split(',');
+            let (Some(Ok(number)), Some(Ok(count))) = (
+                fields.next().map(str::parse::<u64>),
+                fields.next().map(str::parse::<f64>),
+            ) else {
+                return Err(RustyError::SerializationError(format!(
+                    "invalid line record `{line}`"
+                )));
+            };
+            if let Some(file) = &file {
+                add_hits(&mut hits, file, number, count > 0.0);
+            }
+        } else if line == "end_of_record" {
+            file = None;
+        }
+    }
+    Ok(to_files(hits))
+}
+
+/// Parse a Cobertura XML report into per file line coverage
+///
+/// # Errors
+///
+/// This function can generate the following errors:
+///
+/// * `RustyError` - If the report is not valid XML.
+pub fn parse_cobertura(xml: &str) -> Result<Vec<FileCoverage>, RustyError> {
+    let document =
+        Document::parse(xml).map_err(|err| RustyError::SerializationError(err.to_string()))?;
+    let mut hits = LineHits::new();
+    for class in document
+        .descendants()
+        .filter(|node| node.has_tag_name("class"))
+    {
+        let Some(file) = class.attribute("filename") else {
+            continue;
+        };
+        // method lines are repeated in the class lines
+        for line in class
+            .children()
+            .filter(|node| node.has_tag_name("lines"))
+            .flat_map(|lines| lines.children())
+            .filter(|node| node.has_tag_name("line"))
+        {
+            let number = line.attribute("number").and_then(|n| n.parse::<u64>().ok());
+            let count = line.attribute("hits").and_then(|h| h.parse::<f64>().ok());
+            if let (Some(number), Some(count)) = (number, count) {
+                add_hits(&mut hits, file, number, count > 0.0);
+            }
+        }
+    }
+    Ok(to_files(hits))
+}
+
+/// Merge the coverage of files found in several reports, keeping the best coverage of each file
+#[must_use]
+pub fn merge_coverage(files: Vec<FileCoverage>) -> Vec<FileCoverage> {
+    let mut merged: HashMap<String, FileCoverage> = HashMap::new();
+    for file in files {
+        match merged.get(&file.path) {
+            Some(best) if best.covered >= file.covered => {}
+            _ => {
+                merged.insert(file.path.clone(), file);
+            }
+        }
+    }
+    let mut files = merged.into_values().collect::<Vec<FileCoverage>>();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    files
+}
+
+// covered state of every instrumented line, per file
+type LineHits = BTreeMap<String, BTreeMap<u64, bool>>;
+
+// a line is covered when any record of the file hits it
+fn add_hits(hits: &mut LineHits, file: &str, line: u64, covered: bool) {
+    *hits
+        .entry(file.to_string())
+        .or_default()
+        .entry(line)
+        .or_default() |= covered;
+}
+
+fn to_files(hits: LineHits) -> Vec<FileCoverage> {
+    hits.into_iter()
+        .map(|(path, lines)| FileCoverage {
+            path,
+            lines: lines.len() as u64,
+            covered: lines.values().filter(|covered| **covered).count() as u64,
+        })
+        .collect()
+}
@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::io;
+use std::os::unix::fs::{lchown, DirBuilderExt};
+use std::path::Path;
+
+use tokio::process::Command;
+
+use commons::env::{var, var_or_default};
+use commons::errors::RustyError;
+use domain::templates::pipeline::Stage;
+
+use crate::runners::pipelines::shared;
+
+/// Isolation of the shell runner processes, enabled with `SHELL_SANDBOX`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Sandbox {
+    /// agent environment variables kept in the stage processes
+    pub allowed_env: Vec<String>,
+    /// user running the stage processes, set with `SANDBOX_USER`
+    pub user: Option<String>,
+    /// other users a stage may run as, set with `SANDBOX_USERS`
+    pub allowed_users: Vec<String>,
+}
+
+/// Settings of the sandboxed processes of a stage
+#[derive(Clone, Debug)]
+pub struct SandboxProcess {
+    env: HashMap<String, String>,
+    user: (u32, u32),
+    limits: Limits,
+    /// stage limits lowered to the agent maximums
+    pub lowered: Vec<&'static str>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Limits {
+    memory: Option<u64>,
+    processes: Option<u64>,
+}
+
+impl Sandbox {
+    /// Sandbox configured for the agent - `None` when disabled
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        if !var_or_default("SHELL_SANDBOX", false) {
+            return None;
+        }
+        Some(Self {
+            allowed_env: split_list(&var_or_default(
+                "SHELL_ALLOWED_ENV",
+                "PATH,LANG".to_string(),
+            )),
+            user: var("SANDBOX_USER").ok(),
+            allowed_users: split_list(&var_or_default("SANDBOX_USERS", String::new())),
+        })
+    }
+
+    /// Environment of a sandboxed process
+    ///
+    /// Only the allowed agent variables are kept, `HOME` points to the pipeline checkout,
+    /// and the pipeline variables are set last.
+    #[must_use]
+    pub fn env(
+        &self,
+        agent_env: impl IntoIterator<Item = (String, String)>,
+        pipeline_env: &HashMap<String, String>,
+        home: &str,
+    ) -> HashMap<String, String> {
+        let mut env = agent_env
+            .into_iter()
+            .filter(|(name, _)| self.allowed_env.contains(name))
+            .collect::<HashMap<String, String>>();
+        env.insert("HOME".to_string(), home.to_string());
+        env.extend(pipeline_env.clone());
+        env
+    }
+
+    /// Create a pipeline checkout directory, only accessible to its owner
+    ///
+    /// # Errors
+    ///
+    /// This function can generate the following errors:
+    ///
+    /// * `RustyError` - If the directory cannot be created.
+    pub fn create_workspace(&self, dir: &str) -> Result<(), RustyError> {
+        std::fs::create_dir_all(shared::working_dir())?;
+        std::fs::DirBuilder::new().mode(0o700).create(dir)?;
+        Ok(())
+    }
+
+    /// Resolve the user running the processes of a stage
+    ///
+    /// A stage picks its user only among the agent allowed users, otherwise the agent
+    /// sandbox user is used. Stages never run as root.
+    ///
+    /// # Errors
+    ///
+    /// This function can generate the following errors:
+    ///
+    /// * `RustyError` - If no sandbox user is configured, or the stage user is not allowed, unknown or root.
+    pub fn stage_user(
+        &self,
+        requested: Option<&str>,
+        passwd: &str,
+        group: &str,
+    ) -> Result<(u32, u32), RustyError> {
+        let user = match requested {
+            Some(user)
+                if self.user.as_deref() == Some(user)
+                    || self.allowed_users.iter().any(|allowed| allowed == user) =>
+            {
+                user
+            }
+            Some(user) => {
+                return Err(RustyError::IoError(format!(
+                    "stage user `{user}` is not allowed by the agent"
+                )))
+            }
+            None => self.user.as_deref().ok_or_else(|| {
+                RustyError::IoError("sandboxed stages need a `SANDBOX_USER`".to_string())
+            })?,
+        };
+        match resolve_user(user, passwd, group) {
+            Some((0, _)) => Err(RustyError::IoError(format!(
+                "stage user `{user}` cannot be root"
+            ))),
+            Some(ids) => Ok(ids),
+            None => Err(RustyError::IoError(format!("unknown stage user `{user}`"))),
+        }
+    }
+
+    /// Resolve the process settings of a stage
+    ///
+    /// The checkout is handed over to the stage user, so files written by a stage
+    /// can be changed by the next ones.
+    ///
+    /// # Errors
+    ///
+    /// This function can generate the following errors:
+    ///
+    /// * `RustyError` - If the stage user cannot be resolved or the checkout cannot be handed over.
+    pub fn process(
+        &self,
+        dir: &str,
+        pipeline_env: &HashMap<String, String>,
+        stage: &Option<Stage>,
+    ) -> Result<SandboxProcess, RustyError> {
+        let requested = stage
+            .as_ref()
+            .and_then(|stage| stage.security.as_ref())
+            .and_then(|security| security.user.as_deref());
+        let passwd = std::fs::read_to_string("/etc/passwd").unwrap_or_default();
+        let group = std::fs::read_to_string("/etc/group").unwrap_or_default();
+        let (uid, gid) = self.stage_user(requested, &passwd, &group)?;
+        chown_tree(Path::new(dir), uid, gid)?;
+        let (resources, lowered) = shared::resources(stage);
+        let limits = Limits {
+            memory: resources.memory_bytes().and_then(|m| u64::try_from(m).ok()),
+            processes: resources.pids.and_then(|pids| u64::try_from(pids).ok()),
+        };
+        Ok(SandboxProcess {
+            env: self.env(std::env::vars(), pipeline_env, dir),
+            user: (uid, gid),
+            limits,
+            lowered,
+        })
+    }
+}
+
+impl SandboxProcess {
+    /// Apply the sandbox to a command - the user and limits are set before the shell starts
+    pub fn apply(&self, command: &mut Command) {
+        command.env_clear().envs(&self.env);
+        let (uid, gid) = self.user;
+        command.uid(uid).gid(gid);
+        let limits = self.limits;
+        // SAFETY: `setrlimit` is async-signal-safe, and nothing is allocated between fork and exec
+        unsafe {
+            command.pre_exec(move || set_limits(limits));
+        }
+    }
+}
+
+/// Resolve a stage user - a name or uid, with an optional group name or gid (`user:group`)
+///
+/// Without a group, the primary group of the user is used, or the uid for unknown numeric users.
+#[must_use]
+pub fn resolve_user(user: &str, passwd: &str, group: &str) -> Option<(u32, u32)> {
+    let (user, group_name) = match user.split_once(':') {
+        Some((user, group)) => (user, Some(group)),
+        None => (user, None),
+    };
+    let entry = passwd
+        .lines()
+        .map(|line| line.split(':').collect::<Vec<&str>>())
+        .find(|fields| fields.len() > 3 && (fields[0] == user || fields[2] == user));
+    let uid = match user.parse::<u32>() {
+        Ok(uid) => uid,
+        Err(_) => entry.as_ref()?[2].parse().ok()?,
+    };
+    let gid = match group_name {
+        Some(name) => name.parse::<u32>().ok().or_else(|| {
+            group
+                .lines()
+                .map(|line| line.split(':').collect::<Vec<&str>>())
+                .find(|fields| fields.len() > 2 && fields[0] == name)
+                .and_then(|fields| fields[2].parse().ok())
+        })?,
+        None => entry
+            .and_then(|fields| fields[3].parse().ok())
+            .unwrap_or(uid),
+    };
+    Some((uid, gid))
+}
+
+fn split_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
+
+fn chown_tree(path: &Path, uid: u32, gid: u32) -> Result<(), RustyError> {
+    lchown(path, Some(uid), Some(gid))?;
+    if path.is_dir() && !path.is_symlink() {
+        for entry in std::fs::read_dir(path)? {
+            chown_tree(&entry?.path(), uid, gid)?;
+        }
+    }
+    Ok(())
+}
+
+fn set_limits(limits: Limits) -> io::Result<()> {
+    let limit = |value: u64| libc::rlimit {
+        rlim_cur: value,
+        rlim_max: value,
+    };
+    if let Some(memory) = limits.memory {
+        if unsafe { libc::setrlimit(libc::RLIMIT_AS, &limit(memory)) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    if let Some(processes) = limits.processes {
+        if unsafe { libc::setrlimit(libc::RLIMIT_NPROC, &limit(processes)) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
@@ -1,13 +1,28 @@
+use once_cell::sync::Lazy;
 use serde_json::json;
 use std::collections::HashMap;
+use std::sync::Mutex;
 
+use commons::env::{var, var_or_default};
 use domain::pipelines::PipelineStatus;
-use domain::templates::pipeline::{PipelineTemplate, Stage};
+use domain::templates::pipeline::{PipelineTemplate, Resources, Security, Stage};
 use messaging::mq_client::MqClient;
 
 use crate::api::pipelines::{finalize, update_stage};
+use crate::runners::pipelines::checkout;
 
-pub const WORKING_DIR: &str = "/tmp/rusty";
+// secrets masked in the logs of running pipelines
+static SECRETS: Lazy<Mutex<HashMap<String, Vec<String>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+// base directory of the pipeline checkouts
+pub fn working_dir() -> String {
+    var_or_default("WORKING_DIR", "/tmp/rusty".to_string())
+}
+
+pub fn pipeline_dir(pipeline_id: &str) -> String {
+    format!("{}/{pipeline_id}", working_dir())
+}
 
 pub async fn cleanup(
     messaging: &MqClient,
@@ -16,19 +31,31 @@ pub async fn cleanup(
     stage_name: &str,
     status: PipelineStatus,
 ) {
-    let _ = std::fs::remove_dir_all(&format!("{WORKING_DIR}/{pipeline_id}"));
+    let _ = std::fs::remove_dir_all(pipeline_dir(pipeline_id));
     let _ = update_stage(pipeline_id, uuid, stage_name, status).await;
     let _ = finalize(pipeline_id, uuid, status).await;
     let _ = messaging
         .publish(&format!("pipeline-logs-{pipeline_id}"), "EOF")
         .await;
+    SECRETS.lock().unwrap().remove(pipeline_id);
+}
+
+pub fn register_secrets(pipeline_id: &str, secrets: Vec<String>) {
+    SECRETS
+        .lock()
+        .unwrap()
+        .insert(pipeline_id.to_string(), secrets);
 }
 
 pub async fn print_line(messaging: &MqClient, pipeline_id: &str, stage: &str, line: &str) {
+    let line = match SECRETS.lock().unwrap().get(pipeline_id) {
+        Some(secrets) => checkout::mask(line, secrets),
+        None => line.to_string(),
+    };
     let _ = messaging
         .publish(
             &format!("pipeline-logs-{pipeline_id}"),
-            &json!({ "stage": stage, "line": line }).to_string(),
+            &json!({ "stage": stage, "line": &line }).to_string(),
         )
         .await;
     log::debug!("{line}");
@@ -50,3 +77,49 @@ pub fn prepare_env(template: &PipelineTemplate, stage: &Option<Stage>) -> HashMa
     }
     envs
 }
+
+pub fn shell(template: &PipelineTemplate, stage: &Option<Stage>) -> String {
+    stage
+        .as_ref()
+        .and_then(|stage| stage.shell.clone())
+        .or_else(|| template.shell.clone())
+        .unwrap_or_else(|| var_or_default("RUNNER_SHELL", "sh".to_string()))
+}
+
+// stage limits fall back to the agent defaults, and are capped by the agent maximums
+pub fn resources(stage: &Option<Stage>) -> (Resources, Vec<&'static str>) {
+    let defaults = Resources {
+        cpus: var("STAGE_CPUS").ok(),
+        memory: var("STAGE_MEMORY").ok(),
+        pids: var("STAGE_PIDS").ok(),
+    };
+    let max = Resources {
+        cpus: var("STAGE_CPUS_MAX").ok(),
+        memory: var("STAGE_MEMORY_MAX").ok(),
+        pids: var("STAGE_PIDS_MAX").ok(),
+    };
+    stage
+        .as_ref()
+        .and_then(|stage| stage.resources.clone())
+        .unwrap_or_default()
+        .or(&defaults)
+        .capped(&max)
+}
+
+pub fn security(stage: &Option<Stage>) -> Security {
+    let defaults = Security {
+        user: var("STAGE_USER").ok(),
+        cap_drop: var::<String>("STAGE_CAP_DROP").ok().map(|caps| {
+            caps.split(',')
+                .map(|cap| cap.trim().to_string())
+                .filter(|cap| !cap.is_empty())
+                .collect()
+        }),
+        network: var("STAGE_NETWORK").ok(),
+    };
+    stage
+        .as_ref()
+        .and_then(|stage| stage.security.clone())
+        .unwrap_or_default()
+        .or(&defaults)
+}
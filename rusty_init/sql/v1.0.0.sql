@@ -75,6 +75,11 @@ create table if not exists rusty.pipelines (
     stage_status jsonb not null,
     job_id text not null,
     agent_id text,
+    commit varchar(64),
+    revision varchar(36),
+    triggered_by varchar(256),
+    upstream_id varchar(36),
+    variables jsonb,
     constraint fk_pipeline_job
         foreign key(job_id)
             references rusty.jobs(id)
@@ -87,3 +92,196 @@ create table if not exists rusty.pipelineLogs (
         foreign key(id)
             references rusty.pipelines(id)
 );
+
+create table if not exists rusty.schedules (
+    id varchar(36) primary key,
+    cron varchar(256) not null,
+    branch varchar(256),
+    skip_if_running boolean not null,
+    register_date text not null,
+    last_run text,
+    job_id text not null,
+    constraint fk_schedule_job
+        foreign key(job_id)
+            references rusty.jobs(id)
+);
+
+create table if not exists rusty.locks (
+    id varchar(256) primary key,
+    expiry bigint not null
+);
+
+create table if not exists rusty.status_reporters (
+    id varchar(36) primary key,
+    forge varchar(16) not null,
+    api_url text,
+    token text not null,
+    project_id varchar(36) not null,
+    constraint fk_status_reporter_project
+        foreign key(project_id)
+            references rusty.projects(id)
+);
+
+create table if not exists rusty.registry_credentials (
+    id varchar(36) primary key,
+    registry varchar(256) not null,
+    username varchar(256) not null,
+    password text not null,
+    project_id varchar(36) not null,
+    constraint fk_registry_credential_project
+        foreign key(project_id)
+            references rusty.projects(id)
+);
+
+create table if not exists rusty.checkout_settings (
+    id varchar(36) primary key,
+    depth integer,
+    submodules varchar(16),
+    lfs boolean,
+    sparse_paths text[],
+    ssh_key text,
+    username varchar(256),
+    token text,
+    project_id varchar(36) not null,
+    constraint fk_checkout_settings_project
+        foreign key(project_id)
+            references rusty.projects(id)
+);
+
+create table if not exists rusty.notifications (
+    id varchar(36) primary key,
+    channel varchar(16) not null,
+    target text not null,
+    rule varchar(16) not null,
+    branch varchar(256),
+    project_id varchar(36) not null,
+    constraint fk_notification_project
+        foreign key(project_id)
+            references rusty.projects(id)
+);
+
+create table if not exists rusty.notification_deliveries (
+    id varchar(36) primary key,
+    date text not null,
+    delivered boolean not null,
+    attempts integer not null,
+    error text,
+    notification_id varchar(36) not null,
+    pipeline_id varchar(36) not null
+);
+
+create table if not exists rusty.event_hooks (
+    id varchar(36) primary key,
+    url text not null,
+    secret text not null,
+    indexes varchar(36)[] not null,
+    operations varchar(36)[] not null,
+    project_id varchar(36),
+    owner varchar(256) not null
+);
+
+create table if not exists rusty.event_hook_deliveries (
+    id varchar(36) primary key,
+    date text not null,
+    index varchar(64) not null,
+    op varchar(16) not null,
+    payload text not null,
+    status varchar(16) not null,
+    attempts integer not null,
+    error text,
+    hook_id varchar(36) not null
+);
+
+create table if not exists rusty.job_template_revisions (
+    id varchar(36) primary key,
+    number bigint not null,
+    date text not null,
+    author varchar(256) not null,
+    hash varchar(64) not null,
+    template text not null,
+    job_id varchar(36) not null,
+    constraint fk_template_revision_job
+        foreign key(job_id)
+            references rusty.jobs(id)
+);
+
+create table if not exists rusty.deployments (
+    id varchar(36) primary key,
+    environment varchar(256) not null,
+    url text,
+    date text not null,
+    branch varchar(256) not null,
+    commit varchar(64),
+    stage varchar(256) not null,
+    deployed_by varchar(256),
+    pipeline_id varchar(36) not null,
+    pipeline_number integer not null,
+    job_id varchar(36) not null,
+    project_id varchar(36) not null,
+    constraint fk_deployment_project
+        foreign key(project_id)
+            references rusty.projects(id)
+);
+
+create table if not exists rusty.test_reports (
+    id varchar(36) primary key,
+    date text not null,
+    stage varchar(256) not null,
+    branch varchar(256) not null,
+    tests integer not null,
+    failures integer not null,
+    skipped integer not null,
+    suites jsonb not null,
+    pipeline_id varchar(36) not null,
+    pipeline_number integer not null,
+    job_id varchar(36) not null,
+    project_id varchar(36) not null,
+    constraint fk_test_report_project
+        foreign key(project_id)
+            references rusty.projects(id)
+);
+
+create table if not exists rusty.coverage_reports (
+    id varchar(36) primary key,
+    date text not null,
+    stage varchar(256) not null,
+    branch varchar(256) not null,
+    commit varchar(64),
+    lines integer not null,
+    covered integer not null,
+    percent double precision not null,
+    files jsonb not null,
+    pipeline_id varchar(36) not null,
+    pipeline_number integer not null,
+    job_id varchar(36) not null,
+    project_id varchar(36) not null,
+    constraint fk_coverage_report_project
+        foreign key(project_id)
+            references rusty.projects(id)
+);
+
+create table if not exists rusty.badge_settings (
+    id varchar(36) primary key,
+    public boolean not null,
+    job_id varchar(36) not null,
+    project_id varchar(36) not null,
+    constraint fk_badge_settings_job
+        foreign key(job_id)
+            references rusty.jobs(id)
+);
+
+create table if not exists rusty.job_triggers (
+    id varchar(36) primary key,
+    condition varchar(16) not null,
+    branch varchar(256),
+    variables jsonb,
+    job_id varchar(36) not null,
+    downstream_job_id varchar(36) not null,
+    project_id varchar(36) not null,
+    constraint fk_job_trigger_job
+        foreign key(job_id)
+            references rusty.jobs(id),
+    constraint fk_job_trigger_downstream_job
+        foreign key(downstream_job_id)
+            references rusty.jobs(id)
+);
@@ -0,0 +1,136 @@
+use axum::extract::{Path, Query};
+use axum::http::{header, HeaderMap, HeaderName, StatusCode};
+use axum::Extension;
+use serde::Deserialize;
+
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::pipelines::PipelineStatus;
+use persist::db_client::DbClient;
+
+use crate::server_ext::extract_auth_header;
+use crate::services::badges as service;
+
+type BadgeResponse = Result<([(HeaderName, &'static str); 2], String), (StatusCode, String)>;
+
+/// Badge request parameters
+#[derive(Debug, Default, Deserialize)]
+pub struct BadgeQuery {
+    /// pipelines branch - the project main branch by default
+    pub branch: Option<String>,
+}
+
+pub async fn badge_handler(
+    Extension(db): Extension<DbClient>,
+    Path((job_id, badge)): Path<(String, String)>,
+    Query(query): Query<BadgeQuery>,
+    headers: HeaderMap,
+) -> BadgeResponse {
+    log::debug!("handling `badges::{badge}` request for job `{job_id}`");
+    let cred = match service::is_public(&db, &job_id).await {
+        Ok(true) => Credential::System,
+        Ok(false) => {
+            let cred = extract_auth_header(&headers);
+            if !matches!(cred, Credential::Bearer(_))
+                || auth::authenticate(&db, &cred).await.is_err()
+            {
+                return Err((
+                    StatusCode::UNAUTHORIZED,
+                    "badges of the job are not public".to_string(),
+                ));
+            }
+            cred
+        }
+        Err(err) => return Err(error_response(&err)),
+    };
+
+    let svg = match badge.trim_end_matches(".svg") {
+        "status" => service::get_status(&db, &cred, &job_id, query.branch)
+            .await
+            .map(status_badge),
+        "coverage" => service::get_coverage(&db, &cred, &job_id, query.branch)
+            .await
+            .map(coverage_badge),
+        _ => {
+            return Err((StatusCode::NOT_FOUND, format!("unknown badge: {badge}")));
+        }
+    }
+    .map_err(|err| error_response(&err))?;
+    // badges are proxied and cached by git forges - they must follow the latest pipeline
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/svg+xml"),
+            (header::CACHE_CONTROL, "no-cache, max-age=0"),
+        ],
+        svg,
+    ))
+}
+
+/// Badge of the latest pipeline status
+#[must_use]
+pub fn status_badge(status: Option<PipelineStatus>) -> String {
+    let (message, color) = match status {
+        None => ("unknown", "#9f9f9f"),
+        Some(PipelineStatus::Defined | PipelineStatus::Assigned) => ("pending", "#9f9f9f"),
+        Some(PipelineStatus::InProgress) => ("running", "#007ec6"),
+        Some(PipelineStatus::Success) => ("passing", "#4c1"),
+        Some(PipelineStatus::Failure) => ("failing", "#e05d44"),
+        Some(PipelineStatus::Unstable) => ("unstable", "#dfb317"),
+    };
+    render("pipeline", message, color)
+}
+
+/// Badge of the latest coverage percentage
+#[must_use]
+pub fn coverage_badge(percent: Option<f64>) -> String {
+    match percent {
+        None => render("coverage", "unknown", "#9f9f9f"),
+        Some(percent) => {
+            let color = match percent {
+                p if p >= 90.0 => "#4c1",
+                p if p >= 75.0 => "#97ca00",
+                p if p >= 60.0 => "#dfb317",
+                p if p >= 40.0 => "#fe7d37",
+                _ => "#e05d44",
+            };
+            render("coverage", &format!("{percent:.0}%"), color)
+        }
+    }
+}
+
+/// Render a flat badge - a grey label next to a colored message
+#[must_use]
+pub fn render(label: &str, message: &str, color: &str) -> String {
+    let (label_width, message_width) = (text_width(label), text_width(message));
+    let width = label_width + message_width;
+    let (label_x, message_x) = (label_width / 2, label_width + message_width / 2);
+    let (label, message) = (escape(label), escape(message));
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {message}"><title>{label}: {message}</title><linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient><clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath><g clip-path="url(#r)"><rect width="{label_width}" height="20" fill="#555"/><rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/><rect width="{width}" height="20" fill="url(#s)"/></g><g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11"><text x="{label_x}" y="15" fill="#010101" fill-opacity=".3">{label}</text><text x="{label_x}" y="14">{label}</text><text x="{message_x}" y="15" fill="#010101" fill-opacity=".3">{message}</text><text x="{message_x}" y="14">{message}</text></g></svg>"##
+    )
+}
+
+fn error_response(err: &RustyError) -> (StatusCode, String) {
+    match err {
+        RustyError::ValidationError(message) => (StatusCode::NOT_FOUND, message.clone()),
+        RustyError::UnauthenticatedError | RustyError::UnauthorizedError => {
+            (StatusCode::UNAUTHORIZED, err.to_string())
+        }
+        _ => {
+            log::error!("`badges`: {err}");
+            (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+        }
+    }
+}
+
+// approximate width of a text in 11px Verdana, with padding
+fn text_width(text: &str) -> usize {
+    text.chars().count() * 7 + 10
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,50 @@
+use commons::errors::RustyError;
+use commons::hashing::sha::hmac256;
+use domain::event_hooks::{EventHook, EventHookDelivery};
+
+/// Indexes never published to outgoing webhooks - credentials and delivery bookkeeping
+pub const PROTECTED_INDEXES: [&str; 11] = [
+    "users",
+    "permissions",
+    "roles",
+    "locks",
+    "status_reporters",
+    "registry_credentials",
+    "checkout_settings",
+    "event_hooks",
+    "event_hook_deliveries",
+    "notifications",
+    "notification_deliveries",
+];
+
+/// Compute the `X-Rusty-Signature-256` header value for a payload
+pub fn signature(secret: &str, payload: &str) -> Result<String, RustyError> {
+    Ok(format!("sha256={}", hmac256(secret, payload.as_bytes())?))
+}
+
+/// Post a delivery payload to the hook target
+pub async fn send(hook: &EventHook, delivery: &EventHookDelivery) -> Result<(), RustyError> {
+    let response = reqwest::Client::new()
+        .post(&hook.url)
+        .header("Content-Type", "application/json")
+        .header(
+            "X-Rusty-Event",
+            format!("{}.{}", delivery.index, delivery.op),
+        )
+        .header("X-Rusty-Delivery", &delivery.id)
+        .header(
+            "X-Rusty-Signature-256",
+            signature(&hook.secret, &delivery.payload)?,
+        )
+        .body(delivery.payload.clone())
+        .send()
+        .await?;
+    let status = response.status();
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(RustyError::RequestError(format!(
+            "hook target responded with `{status}`"
+        )))
+    }
+}
@@ -0,0 +1,86 @@
+use async_graphql::{Context, Object};
+use serde_json::Value;
+
+use auth::{authenticate, authorize};
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::badges::{BadgeSettings, PagedBadgeSettings, RegisterBadgeSettings};
+use domain::commons::search::SearchOptions;
+use persist::db_client::DbClient;
+
+use crate::gql::{get_public_gql_endpoints, shared::paginate};
+use crate::services::badges as service;
+
+pub struct BadgesQuery;
+
+#[Object]
+impl BadgesQuery {
+    #[auth_macro::authenticate(bearer)]
+    async fn get(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<Value>,
+        options: Option<SearchOptions>,
+    ) -> async_graphql::Result<PagedBadgeSettings, RustyError> {
+        log::debug!("handling `badges::get` request");
+        let entries = service::get_all(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &filter,
+            &options,
+        )
+        .await?;
+        let (total, page, page_size, entries) = paginate(&entries, options);
+        log::debug!("`badges::get`: found {} entries", total);
+        Ok(PagedBadgeSettings {
+            total,
+            page,
+            page_size,
+            entries,
+        })
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn get_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<Option<BadgeSettings>, RustyError> {
+        log::debug!("handling `badges::getById` request");
+        let entry =
+            service::get_by_id(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, &id).await?;
+        log::debug!("`badges::getById`: found entry by id: `{}`", id);
+        Ok(entry)
+    }
+}
+
+pub struct BadgesMutation;
+
+#[Object]
+impl BadgesMutation {
+    #[auth_macro::authenticate(bearer)]
+    async fn register(
+        &self,
+        ctx: &Context<'_>,
+        settings: RegisterBadgeSettings,
+    ) -> async_graphql::Result<String, RustyError> {
+        log::debug!("handling `badges::register` request");
+        let id =
+            service::create(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, settings).await?;
+        log::debug!("`badges::register`: created badge settings with id `{id}`");
+        Ok(id)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn delete_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<u64, RustyError> {
+        log::debug!("handling `badges::deleteById` request");
+        let deleted =
+            service::delete_by_id(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, &id).await?;
+        log::debug!("`badges::deleteById`: deleted badge settings with id `{id}`");
+        Ok(deleted)
+    }
+}
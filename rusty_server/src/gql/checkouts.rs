@@ -0,0 +1,113 @@
+use async_graphql::{Context, Object};
+use serde_json::Value;
+
+use auth::{authenticate, authorize};
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::checkouts::{
+    Checkout, CheckoutSettings, PagedCheckoutSettings, RegisterCheckoutSettings,
+};
+use domain::commons::search::SearchOptions;
+use persist::db_client::DbClient;
+
+use crate::gql::{get_public_gql_endpoints, shared::paginate};
+use crate::services::checkouts as service;
+
+pub struct CheckoutsQuery;
+
+#[Object]
+impl CheckoutsQuery {
+    #[auth_macro::authenticate(bearer)]
+    async fn get(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<Value>,
+        options: Option<SearchOptions>,
+    ) -> async_graphql::Result<PagedCheckoutSettings, RustyError> {
+        log::debug!("handling `checkouts::get` request");
+        let entries = service::get_all(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &filter,
+            &options,
+        )
+        .await?;
+        let (total, page, page_size, entries) = paginate(&entries, options);
+        log::debug!("`checkouts::get`: found {} entries", total);
+        Ok(PagedCheckoutSettings {
+            total,
+            page,
+            page_size,
+            entries,
+        })
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn get_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<Option<CheckoutSettings>, RustyError> {
+        log::debug!("handling `checkouts::getById` request");
+        let entry =
+            service::get_by_id(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, &id).await?;
+        log::debug!("`checkouts::getById`: found entry by id: `{}`", id);
+        Ok(entry)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn get_checkout(
+        &self,
+        ctx: &Context<'_>,
+        project_id: String,
+    ) -> async_graphql::Result<Option<Checkout>, RustyError> {
+        log::debug!("handling `checkouts::getCheckout` request");
+        let entry = service::get_checkout(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &project_id,
+        )
+        .await?;
+        log::debug!("`checkouts::getCheckout`: found checkout for project `{project_id}`");
+        Ok(entry)
+    }
+}
+
+pub struct CheckoutsMutation;
+
+#[Object]
+impl CheckoutsMutation {
+    #[auth_macro::authenticate(bearer)]
+    async fn register(
+        &self,
+        ctx: &Context<'_>,
+        settings: RegisterCheckoutSettings,
+    ) -> async_graphql::Result<String, RustyError> {
+        log::debug!("handling `checkouts::register` request");
+        let id =
+            service::create(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, settings).await?;
+        log::debug!("`checkouts::register`: created checkout settings with id `{id}`");
+        Ok(id)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn delete_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<u64, RustyError> {
+        log::debug!("handling `checkouts::deleteById` request");
+        let deleted =
+            service::delete_by_id(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, &id).await?;
+        log::debug!("`checkouts::deleteById`: deleted checkout settings with id `{id}`");
+        Ok(deleted)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn delete_all(&self, ctx: &Context<'_>) -> async_graphql::Result<u64, RustyError> {
+        log::debug!("handling `checkouts::deleteAll` request");
+        let deleted = service::delete_all(ctx.data::<DbClient>()?).await?;
+        log::debug!("`checkouts::deleteAll`: deleted {deleted} checkout settings");
+        Ok(deleted)
+    }
+}
@@ -0,0 +1,111 @@
+use async_graphql::{Context, Object};
+use serde_json::Value;
+
+use auth::{authenticate, authorize};
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::commons::search::SearchOptions;
+use domain::coverage::{CoverageDelta, FileCoverage, PagedCoverageReports, PipelineCoverage};
+use persist::db_client::DbClient;
+
+use crate::gql::{get_public_gql_endpoints, shared::paginate};
+use crate::services::coverage as service;
+
+pub struct CoverageQuery;
+
+#[Object]
+impl CoverageQuery {
+    #[auth_macro::authenticate(bearer)]
+    async fn get(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<Value>,
+        options: Option<SearchOptions>,
+    ) -> async_graphql::Result<PagedCoverageReports, RustyError> {
+        log::debug!("handling `coverage::get` request");
+        let entries = service::get_all(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &filter,
+            &options,
+        )
+        .await?;
+        let (total, page, page_size, entries) = paginate(&entries, options);
+        log::debug!("`coverage::get`: found {} entries", total);
+        Ok(PagedCoverageReports {
+            total,
+            page,
+            page_size,
+            entries,
+        })
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn get_trend(
+        &self,
+        ctx: &Context<'_>,
+        job_id: String,
+        branch: String,
+        limit: Option<usize>,
+    ) -> async_graphql::Result<Vec<PipelineCoverage>, RustyError> {
+        log::debug!("handling `coverage::getTrend` request");
+        let entries = service::get_trend(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &job_id,
+            &branch,
+            limit,
+        )
+        .await?;
+        log::debug!(
+            "`coverage::getTrend`: found {} pipelines for job `{job_id}` on `{branch}`",
+            entries.len()
+        );
+        Ok(entries)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn get_delta(
+        &self,
+        ctx: &Context<'_>,
+        pipeline_id: String,
+    ) -> async_graphql::Result<Option<CoverageDelta>, RustyError> {
+        log::debug!("handling `coverage::getDelta` request");
+        let entry = service::get_delta(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &pipeline_id,
+        )
+        .await?;
+        log::debug!("`coverage::getDelta`: computed delta for pipeline `{pipeline_id}`");
+        Ok(entry)
+    }
+}
+
+pub struct CoverageMutation;
+
+#[Object]
+impl CoverageMutation {
+    #[auth_macro::authenticate(bearer)]
+    async fn register(
+        &self,
+        ctx: &Context<'_>,
+        pipeline_id: String,
+        agent_id: String,
+        stage: String,
+        files: Vec<FileCoverage>,
+    ) -> async_graphql::Result<String, RustyError> {
+        log::debug!("handling `coverage::register` request");
+        let id = service::create(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &pipeline_id,
+            &agent_id,
+            &stage,
+            files,
+        )
+        .await?;
+        log::debug!("`coverage::register`: created coverage report with id `{id}`");
+        Ok(id)
+    }
+}
@@ -0,0 +1,93 @@
+use async_graphql::{Context, Object};
+use serde_json::Value;
+
+use auth::{authenticate, authorize};
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::commons::search::SearchOptions;
+use domain::deployments::{Deployment, PagedDeployments, ProjectEnvironment};
+use persist::db_client::DbClient;
+
+use crate::gql::{get_public_gql_endpoints, shared::paginate};
+use crate::services::deployments as service;
+
+pub struct DeploymentsQuery;
+
+#[Object]
+impl DeploymentsQuery {
+    #[auth_macro::authenticate(bearer)]
+    async fn get(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<Value>,
+        options: Option<SearchOptions>,
+    ) -> async_graphql::Result<PagedDeployments, RustyError> {
+        log::debug!("handling `deployments::get` request");
+        let entries = service::get_all(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &filter,
+            &options,
+        )
+        .await?;
+        let (total, page, page_size, entries) = paginate(&entries, options);
+        log::debug!("`deployments::get`: found {} entries", total);
+        Ok(PagedDeployments {
+            total,
+            page,
+            page_size,
+            entries,
+        })
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn get_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<Option<Deployment>, RustyError> {
+        log::debug!("handling `deployments::getById` request");
+        let entry =
+            service::get_by_id(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, &id).await?;
+        log::debug!("`deployments::getById`: found entry by id: `{}`", id);
+        Ok(entry)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn get_environments(
+        &self,
+        ctx: &Context<'_>,
+        project_id: String,
+    ) -> async_graphql::Result<Vec<ProjectEnvironment>, RustyError> {
+        log::debug!("handling `deployments::getEnvironments` request");
+        let entries = service::get_environments(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &project_id,
+        )
+        .await?;
+        log::debug!(
+            "`deployments::getEnvironments`: found {} environments for project `{project_id}`",
+            entries.len()
+        );
+        Ok(entries)
+    }
+}
+
+pub struct DeploymentsMutation;
+
+#[Object]
+impl DeploymentsMutation {
+    #[auth_macro::authenticate(bearer)]
+    async fn redeploy(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<String, RustyError> {
+        log::debug!("handling `deployments::redeploy` request");
+        let pipeline_id =
+            service::redeploy(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, &id).await?;
+        log::debug!("`deployments::redeploy`: created pipeline with id `{pipeline_id}`");
+        Ok(pipeline_id)
+    }
+}
@@ -0,0 +1,140 @@
+use async_graphql::{Context, Object};
+use serde_json::Value;
+
+use auth::{authenticate, authorize};
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::commons::search::SearchOptions;
+use domain::event_hooks::{
+    EventHook, EventHookDelivery, PagedEventHookDeliveries, PagedEventHooks, RegisterEventHook,
+};
+use persist::db_client::DbClient;
+
+use crate::gql::{get_public_gql_endpoints, shared::paginate};
+use crate::services::event_hooks as service;
+
+pub struct EventHooksQuery;
+
+#[Object]
+impl EventHooksQuery {
+    #[auth_macro::authenticate(bearer)]
+    async fn get(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<Value>,
+        options: Option<SearchOptions>,
+    ) -> async_graphql::Result<PagedEventHooks, RustyError> {
+        log::debug!("handling `eventHooks::get` request");
+        let entries = service::get_all(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &filter,
+            &options,
+        )
+        .await?;
+        let (total, page, page_size, entries) = paginate(&entries, options);
+        log::debug!("`eventHooks::get`: found {} entries", total);
+        Ok(PagedEventHooks {
+            total,
+            page,
+            page_size,
+            entries,
+        })
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn get_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<Option<EventHook>, RustyError> {
+        log::debug!("handling `eventHooks::getById` request");
+        let entry =
+            service::get_by_id(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, &id).await?;
+        log::debug!("`eventHooks::getById`: found entry by id: `{}`", id);
+        Ok(entry)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn get_deliveries(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        options: Option<SearchOptions>,
+    ) -> async_graphql::Result<PagedEventHookDeliveries, RustyError> {
+        log::debug!("handling `eventHooks::getDeliveries` request");
+        let entries = service::get_deliveries(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &id,
+            &options,
+        )
+        .await?;
+        let (total, page, page_size, entries) = paginate(&entries, options);
+        log::debug!("`eventHooks::getDeliveries`: found {} entries", total);
+        Ok(PagedEventHookDeliveries {
+            total,
+            page,
+            page_size,
+            entries,
+        })
+    }
+}
+
+pub struct EventHooksMutation;
+
+#[Object]
+impl EventHooksMutation {
+    #[auth_macro::authenticate(bearer)]
+    async fn register(
+        &self,
+        ctx: &Context<'_>,
+        hook: RegisterEventHook,
+    ) -> async_graphql::Result<String, RustyError> {
+        log::debug!("handling `eventHooks::register` request");
+        let id = service::create(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, hook).await?;
+        log::debug!("`eventHooks::register`: created event hook with id `{id}`");
+        Ok(id)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn redeliver(
+        &self,
+        ctx: &Context<'_>,
+        delivery_id: String,
+    ) -> async_graphql::Result<EventHookDelivery, RustyError> {
+        log::debug!("handling `eventHooks::redeliver` request");
+        let delivery = service::redeliver(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &delivery_id,
+        )
+        .await?;
+        log::debug!(
+            "`eventHooks::redeliver`: delivery `{delivery_id}` finished with status `{:?}`",
+            delivery.status
+        );
+        Ok(delivery)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn delete_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<u64, RustyError> {
+        log::debug!("handling `eventHooks::deleteById` request");
+        let deleted =
+            service::delete_by_id(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, &id).await?;
+        log::debug!("`eventHooks::deleteById`: deleted event hook with id `{id}`");
+        Ok(deleted)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn delete_all(&self, ctx: &Context<'_>) -> async_graphql::Result<u64, RustyError> {
+        log::debug!("handling `eventHooks::deleteAll` request");
+        let deleted = service::delete_all(ctx.data::<DbClient>()?).await?;
+        log::debug!("`eventHooks::deleteAll`: deleted {deleted} event hooks");
+        Ok(deleted)
+    }
+}
@@ -0,0 +1,86 @@
+use async_graphql::{Context, Object};
+use serde_json::Value;
+
+use auth::{authenticate, authorize};
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::commons::search::SearchOptions;
+use domain::job_triggers::{JobTrigger, PagedJobTriggers, RegisterJobTrigger};
+use persist::db_client::DbClient;
+
+use crate::gql::{get_public_gql_endpoints, shared::paginate};
+use crate::services::job_triggers as service;
+
+pub struct JobTriggersQuery;
+
+#[Object]
+impl JobTriggersQuery {
+    #[auth_macro::authenticate(bearer)]
+    async fn get(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<Value>,
+        options: Option<SearchOptions>,
+    ) -> async_graphql::Result<PagedJobTriggers, RustyError> {
+        log::debug!("handling `jobTriggers::get` request");
+        let entries = service::get_all(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &filter,
+            &options,
+        )
+        .await?;
+        let (total, page, page_size, entries) = paginate(&entries, options);
+        log::debug!("`jobTriggers::get`: found {} entries", total);
+        Ok(PagedJobTriggers {
+            total,
+            page,
+            page_size,
+            entries,
+        })
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn get_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<Option<JobTrigger>, RustyError> {
+        log::debug!("handling `jobTriggers::getById` request");
+        let entry =
+            service::get_by_id(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, &id).await?;
+        log::debug!("`jobTriggers::getById`: found entry by id: `{}`", id);
+        Ok(entry)
+    }
+}
+
+pub struct JobTriggersMutation;
+
+#[Object]
+impl JobTriggersMutation {
+    #[auth_macro::authenticate(bearer)]
+    async fn register(
+        &self,
+        ctx: &Context<'_>,
+        trigger: RegisterJobTrigger,
+    ) -> async_graphql::Result<String, RustyError> {
+        log::debug!("handling `jobTriggers::register` request");
+        let id =
+            service::create(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, trigger).await?;
+        log::debug!("`jobTriggers::register`: created job trigger with id `{id}`");
+        Ok(id)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn delete_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<u64, RustyError> {
+        log::debug!("handling `jobTriggers::deleteById` request");
+        let deleted =
+            service::delete_by_id(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, &id).await?;
+        log::debug!("`jobTriggers::deleteById`: deleted job trigger with id `{id}`");
+        Ok(deleted)
+    }
+}
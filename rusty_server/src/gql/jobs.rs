@@ -4,7 +4,7 @@ use serde_json::Value;
 use commons::errors::RustyError;
 use domain::auth::credentials::Credential;
 use domain::commons::search::SearchOptions;
-use domain::jobs::{JobModel, PagedJobs, RegisterJob};
+use domain::jobs::{JobModel, PagedJobs, RegisterJob, UpdateJob};
 use persist::db_client::DbClient;
 
 use crate::gql::{
@@ -80,6 +80,20 @@ impl JobsMutation {
         Ok(id)
     }
 
+    #[auth_macro::authenticate(bearer)]
+    async fn update(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        job: UpdateJob,
+    ) -> async_graphql::Result<String, RustyError> {
+        log::debug!("handling `jobs::update` request");
+        let id =
+            service::update(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, &id, job).await?;
+        log::debug!("`jobs::update`: updated job with id `{id}`");
+        Ok(id)
+    }
+
     #[auth_macro::authenticate(bearer)]
     async fn delete_by_id(
         &self,
@@ -10,10 +10,23 @@ use crate::gql::pipelines::PipelineSubscription;
 
 mod agents;
 mod auth;
+mod badges;
+mod checkouts;
+mod coverage;
+mod deployments;
+mod event_hooks;
+mod job_triggers;
 mod jobs;
+mod notifications;
 mod pipelines;
 mod project_groups;
 mod projects;
+mod registries;
+mod reporters;
+mod revisions;
+mod schedules;
+mod templates;
+mod test_reports;
 mod users;
 
 mod shared;
@@ -55,11 +68,46 @@ impl Query {
         auth::AuthQuery
     }
 
+    // job badge settings interface
+    async fn badges(&self) -> badges::BadgesQuery {
+        badges::BadgesQuery
+    }
+
+    // checkout settings interface
+    async fn checkouts(&self) -> checkouts::CheckoutsQuery {
+        checkouts::CheckoutsQuery
+    }
+
+    // coverage reports interface
+    async fn coverage(&self) -> coverage::CoverageQuery {
+        coverage::CoverageQuery
+    }
+
+    // deployments interface
+    async fn deployments(&self) -> deployments::DeploymentsQuery {
+        deployments::DeploymentsQuery
+    }
+
+    // event hooks interface
+    async fn event_hooks(&self) -> event_hooks::EventHooksQuery {
+        event_hooks::EventHooksQuery
+    }
+
+    // job triggers interface
+    async fn job_triggers(&self) -> job_triggers::JobTriggersQuery {
+        job_triggers::JobTriggersQuery
+    }
+
     // jobs interface
     async fn jobs(&self) -> jobs::JobsQuery {
         jobs::JobsQuery
     }
 
+    // notifications interface
+    async fn notifications(&self) -> notifications::NotificationsQuery {
+        notifications::NotificationsQuery
+    }
+
     // pipelines interface
     async fn pipelines(&self) -> pipelines::PipelinesQuery {
         pipelines::PipelinesQuery
@@ -75,6 +123,36 @@ impl Query {
         projects::ProjectsQuery
     }
 
+    // registries interface
+    async fn registries(&self) -> registries::RegistriesQuery {
+        registries::RegistriesQuery
+    }
+
+    // status reporters interface
+    async fn reporters(&self) -> reporters::ReportersQuery {
+        reporters::ReportersQuery
+    }
+
+    // job template revisions interface
+    async fn revisions(&self) -> revisions::RevisionsQuery {
+        revisions::RevisionsQuery
+    }
+
+    // schedules interface
+    async fn schedules(&self) -> schedules::SchedulesQuery {
+        schedules::SchedulesQuery
+    }
+
+    // pipeline templates interface
+    async fn templates(&self) -> templates::TemplatesQuery {
+        templates::TemplatesQuery
+    }
+
+    // test reports interface
+    async fn test_reports(&self) -> test_reports::TestReportsQuery {
+        test_reports::TestReportsQuery
+    }
+
     // projects interface
     async fn users(&self) -> users::UsersQuery {
         users::UsersQuery
@@ -90,11 +168,46 @@ impl Mutation {
         agents::AgentsMutation
     }
 
+    // job badge settings interface
+    async fn badges(&self) -> badges::BadgesMutation {
+        badges::BadgesMutation
+    }
+
+    // checkout settings interface
+    async fn checkouts(&self) -> checkouts::CheckoutsMutation {
+        checkouts::CheckoutsMutation
+    }
+
+    // coverage reports interface
+    async fn coverage(&self) -> coverage::CoverageMutation {
+        coverage::CoverageMutation
+    }
+
+    // deployments interface
+    async fn deployments(&self) -> deployments::DeploymentsMutation {
+        deployments::DeploymentsMutation
+    }
+
+    // event hooks interface
+    async fn event_hooks(&self) -> event_hooks::EventHooksMutation {
+        event_hooks::EventHooksMutation
+    }
+
+    // job triggers interface
+    async fn job_triggers(&self) -> job_triggers::JobTriggersMutation {
+        job_triggers::JobTriggersMutation
+    }
+
     // jobs interface
     async fn jobs(&self) -> jobs::JobsMutation {
         jobs::JobsMutation
     }
 
+    // notifications interface
+    async fn notifications(&self) -> notifications::NotificationsMutation {
+        notifications::NotificationsMutation
+    }
+
     // pipelines interface
     async fn pipelines(&self) -> pipelines::PipelinesMutation {
         pipelines::PipelinesMutation
@@ -110,6 +223,26 @@ impl Mutation {
         projects::ProjectsMutation
     }
 
+    // registries interface
+    async fn registries(&self) -> registries::RegistriesMutation {
+        registries::RegistriesMutation
+    }
+
+    // status reporters interface
+    async fn reporters(&self) -> reporters::ReportersMutation {
+        reporters::ReportersMutation
+    }
+
+    // schedules interface
+    async fn schedules(&self) -> schedules::SchedulesMutation {
+        schedules::SchedulesMutation
+    }
+
+    // test reports interface
+    async fn test_reports(&self) -> test_reports::TestReportsMutation {
+        test_reports::TestReportsMutation
+    }
+
     // projects interface
     async fn users(&self) -> users::UsersMutation {
         users::UsersMutation
@@ -0,0 +1,125 @@
+use async_graphql::{Context, Object};
+use serde_json::Value;
+
+use auth::{authenticate, authorize};
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::commons::search::SearchOptions;
+use domain::notifications::{
+    Notification, PagedNotificationDeliveries, PagedNotifications, RegisterNotification,
+};
+use persist::db_client::DbClient;
+
+use crate::gql::{get_public_gql_endpoints, shared::paginate};
+use crate::services::notifications as service;
+
+pub struct NotificationsQuery;
+
+#[Object]
+impl NotificationsQuery {
+    #[auth_macro::authenticate(bearer)]
+    async fn get(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<Value>,
+        options: Option<SearchOptions>,
+    ) -> async_graphql::Result<PagedNotifications, RustyError> {
+        log::debug!("handling `notifications::get` request");
+        let entries = service::get_all(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &filter,
+            &options,
+        )
+        .await?;
+        let (total, page, page_size, entries) = paginate(&entries, options);
+        log::debug!("`notifications::get`: found {} entries", total);
+        Ok(PagedNotifications {
+            total,
+            page,
+            page_size,
+            entries,
+        })
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn get_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<Option<Notification>, RustyError> {
+        log::debug!("handling `notifications::getById` request");
+        let entry =
+            service::get_by_id(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, &id).await?;
+        log::debug!("`notifications::getById`: found entry by id: `{}`", id);
+        Ok(entry)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn get_deliveries(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        options: Option<SearchOptions>,
+    ) -> async_graphql::Result<PagedNotificationDeliveries, RustyError> {
+        log::debug!("handling `notifications::getDeliveries` request");
+        let entries = service::get_deliveries(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &id,
+            &options,
+        )
+        .await?;
+        let (total, page, page_size, entries) = paginate(&entries, options);
+        log::debug!("`notifications::getDeliveries`: found {} entries", total);
+        Ok(PagedNotificationDeliveries {
+            total,
+            page,
+            page_size,
+            entries,
+        })
+    }
+}
+
+pub struct NotificationsMutation;
+
+#[Object]
+impl NotificationsMutation {
+    #[auth_macro::authenticate(bearer)]
+    async fn register(
+        &self,
+        ctx: &Context<'_>,
+        notification: RegisterNotification,
+    ) -> async_graphql::Result<String, RustyError> {
+        log::debug!("handling `notifications::register` request");
+        let id = service::create(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            notification,
+        )
+        .await?;
+        log::debug!("`notifications::register`: created notification with id `{id}`");
+        Ok(id)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn delete_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<u64, RustyError> {
+        log::debug!("handling `notifications::deleteById` request");
+        let deleted =
+            service::delete_by_id(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, &id).await?;
+        log::debug!("`notifications::deleteById`: deleted notification with id `{id}`");
+        Ok(deleted)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn delete_all(&self, ctx: &Context<'_>) -> async_graphql::Result<u64, RustyError> {
+        log::debug!("handling `notifications::deleteAll` request");
+        let deleted = service::delete_all(ctx.data::<DbClient>()?).await?;
+        log::debug!("`notifications::deleteAll`: deleted {deleted} notifications");
+        Ok(deleted)
+    }
+}
@@ -67,6 +67,22 @@ impl PipelinesQuery {
         log::debug!("`pipelines::logs`: fetched logs for id: `{}`", id);
         Ok(entry)
     }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn get_chain(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<Vec<Pipeline>, RustyError> {
+        log::debug!("handling `pipelines::getChain` request");
+        let entries =
+            service::get_chain(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, &id).await?;
+        log::debug!(
+            "`pipelines::getChain`: found {} pipelines in the chain of `{id}`",
+            entries.len()
+        );
+        Ok(entries)
+    }
 }
 
 pub struct PipelinesMutation;
@@ -4,7 +4,7 @@ use serde_json::Value;
 use commons::errors::RustyError;
 use domain::auth::credentials::Credential;
 use domain::commons::search::SearchOptions;
-use domain::projects::{GroupModel, PagedGroups, RegisterGroup};
+use domain::projects::{GroupModel, PagedGroups, RegisterGroup, UpdateGroup};
 use persist::db_client::DbClient;
 
 use crate::gql::{
@@ -80,6 +80,25 @@ impl ProjectGroupsMutation {
         Ok(id)
     }
 
+    #[auth_macro::authenticate(bearer)]
+    async fn update(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        group: UpdateGroup,
+    ) -> async_graphql::Result<String, RustyError> {
+        log::debug!("handling `project::groups::update` request");
+        let id = service::update(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &id,
+            group,
+        )
+        .await?;
+        log::debug!("`project::groups::update`: updated project group with id `{id}`");
+        Ok(id)
+    }
+
     #[auth_macro::authenticate(bearer)]
     async fn delete_by_id(
         &self,
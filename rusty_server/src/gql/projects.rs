@@ -4,7 +4,7 @@ use serde_json::Value;
 use commons::errors::RustyError;
 use domain::auth::credentials::Credential;
 use domain::commons::search::SearchOptions;
-use domain::projects::{PagedProjects, ProjectModel, RegisterProject};
+use domain::projects::{PagedProjects, ProjectModel, RegisterProject, UpdateProject};
 use persist::db_client::DbClient;
 
 use crate::gql::{
@@ -81,6 +81,25 @@ impl ProjectsMutation {
         Ok(id)
     }
 
+    #[auth_macro::authenticate(bearer)]
+    async fn update(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        project: UpdateProject,
+    ) -> async_graphql::Result<String, RustyError> {
+        log::debug!("handling `projects::update` request");
+        let id = service::update(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &id,
+            project,
+        )
+        .await?;
+        log::debug!("`projects::update`: updated project with id `{id}`");
+        Ok(id)
+    }
+
     #[auth_macro::authenticate(bearer)]
     async fn delete_by_id(
         &self,
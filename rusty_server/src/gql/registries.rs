@@ -0,0 +1,113 @@
+use async_graphql::{Context, Object};
+use serde_json::Value;
+
+use auth::{authenticate, authorize};
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::commons::search::SearchOptions;
+use domain::registries::{
+    PagedRegistryCredentials, RegisterRegistryCredential, RegistryAuth, RegistryCredential,
+};
+use persist::db_client::DbClient;
+
+use crate::gql::{get_public_gql_endpoints, shared::paginate};
+use crate::services::registries as service;
+
+pub struct RegistriesQuery;
+
+#[Object]
+impl RegistriesQuery {
+    #[auth_macro::authenticate(bearer)]
+    async fn get(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<Value>,
+        options: Option<SearchOptions>,
+    ) -> async_graphql::Result<PagedRegistryCredentials, RustyError> {
+        log::debug!("handling `registries::get` request");
+        let entries = service::get_all(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &filter,
+            &options,
+        )
+        .await?;
+        let (total, page, page_size, entries) = paginate(&entries, options);
+        log::debug!("`registries::get`: found {} entries", total);
+        Ok(PagedRegistryCredentials {
+            total,
+            page,
+            page_size,
+            entries,
+        })
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn get_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<Option<RegistryCredential>, RustyError> {
+        log::debug!("handling `registries::getById` request");
+        let entry =
+            service::get_by_id(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, &id).await?;
+        log::debug!("`registries::getById`: found entry by id: `{}`", id);
+        Ok(entry)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn get_auths(
+        &self,
+        ctx: &Context<'_>,
+        project_id: String,
+    ) -> async_graphql::Result<Vec<RegistryAuth>, RustyError> {
+        log::debug!("handling `registries::getAuths` request");
+        let entries = service::get_auths(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &project_id,
+        )
+        .await?;
+        log::debug!("`registries::getAuths`: found {} entries", entries.len());
+        Ok(entries)
+    }
+}
+
+pub struct RegistriesMutation;
+
+#[Object]
+impl RegistriesMutation {
+    #[auth_macro::authenticate(bearer)]
+    async fn register(
+        &self,
+        ctx: &Context<'_>,
+        registry: RegisterRegistryCredential,
+    ) -> async_graphql::Result<String, RustyError> {
+        log::debug!("handling `registries::register` request");
+        let id =
+            service::create(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, registry).await?;
+        log::debug!("`registries::register`: created registry credential with id `{id}`");
+        Ok(id)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn delete_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<u64, RustyError> {
+        log::debug!("handling `registries::deleteById` request");
+        let deleted =
+            service::delete_by_id(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, &id).await?;
+        log::debug!("`registries::deleteById`: deleted registry credential with id `{id}`");
+        Ok(deleted)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn delete_all(&self, ctx: &Context<'_>) -> async_graphql::Result<u64, RustyError> {
+        log::debug!("handling `registries::deleteAll` request");
+        let deleted = service::delete_all(ctx.data::<DbClient>()?).await?;
+        log::debug!("`registries::deleteAll`: deleted {deleted} registry credentials");
+        Ok(deleted)
+    }
+}
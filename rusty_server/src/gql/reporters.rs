@@ -0,0 +1,94 @@
+use async_graphql::{Context, Object};
+use serde_json::Value;
+
+use auth::{authenticate, authorize};
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::commons::search::SearchOptions;
+use domain::reporters::{PagedStatusReporters, RegisterStatusReporter, StatusReporter};
+use persist::db_client::DbClient;
+
+use crate::gql::{get_public_gql_endpoints, shared::paginate};
+use crate::services::reporters as service;
+
+pub struct ReportersQuery;
+
+#[Object]
+impl ReportersQuery {
+    #[auth_macro::authenticate(bearer)]
+    async fn get(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<Value>,
+        options: Option<SearchOptions>,
+    ) -> async_graphql::Result<PagedStatusReporters, RustyError> {
+        log::debug!("handling `reporters::get` request");
+        let entries = service::get_all(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &filter,
+            &options,
+        )
+        .await?;
+        let (total, page, page_size, entries) = paginate(&entries, options);
+        log::debug!("`reporters::get`: found {} entries", total);
+        Ok(PagedStatusReporters {
+            total,
+            page,
+            page_size,
+            entries,
+        })
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn get_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<Option<StatusReporter>, RustyError> {
+        log::debug!("handling `reporters::getById` request");
+        let entry =
+            service::get_by_id(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, &id).await?;
+        log::debug!("`reporters::getById`: found entry by id: `{}`", id);
+        Ok(entry)
+    }
+}
+
+pub struct ReportersMutation;
+
+#[Object]
+impl ReportersMutation {
+    #[auth_macro::authenticate(bearer)]
+    async fn register(
+        &self,
+        ctx: &Context<'_>,
+        reporter: RegisterStatusReporter,
+    ) -> async_graphql::Result<String, RustyError> {
+        log::debug!("handling `reporters::register` request");
+        let id =
+            service::create(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, reporter).await?;
+        log::debug!("`reporters::register`: created status reporter with id `{id}`");
+        Ok(id)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn delete_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<u64, RustyError> {
+        log::debug!("handling `reporters::deleteById` request");
+        let deleted =
+            service::delete_by_id(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, &id).await?;
+        log::debug!("`reporters::deleteById`: deleted status reporter with id `{id}`");
+        Ok(deleted)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn delete_all(&self, ctx: &Context<'_>) -> async_graphql::Result<u64, RustyError> {
+        log::debug!("handling `reporters::deleteAll` request");
+        let deleted = service::delete_all(ctx.data::<DbClient>()?).await?;
+        log::debug!("`reporters::deleteAll`: deleted {deleted} status reporters");
+        Ok(deleted)
+    }
+}
@@ -0,0 +1,73 @@
+use async_graphql::{Context, Object};
+
+use auth::{authenticate, authorize};
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::commons::search::SearchOptions;
+use domain::templates::revision::{DiffLine, PagedTemplateRevisions, TemplateRevision};
+use persist::db_client::DbClient;
+
+use crate::gql::{get_public_gql_endpoints, shared::paginate};
+use crate::services::revisions as service;
+
+pub struct RevisionsQuery;
+
+#[Object]
+impl RevisionsQuery {
+    #[auth_macro::authenticate(bearer)]
+    async fn get(
+        &self,
+        ctx: &Context<'_>,
+        job_id: String,
+        options: Option<SearchOptions>,
+    ) -> async_graphql::Result<PagedTemplateRevisions, RustyError> {
+        log::debug!("handling `revisions::get` request");
+        let entries = service::get_all(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &job_id,
+            &options,
+        )
+        .await?;
+        let (total, page, page_size, entries) = paginate(&entries, options);
+        log::debug!("`revisions::get`: found {} entries", total);
+        Ok(PagedTemplateRevisions {
+            total,
+            page,
+            page_size,
+            entries,
+        })
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn get_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<Option<TemplateRevision>, RustyError> {
+        log::debug!("handling `revisions::getById` request");
+        let entry =
+            service::get_by_id(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, &id).await?;
+        log::debug!("`revisions::getById`: found entry by id: `{}`", id);
+        Ok(entry)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn diff(
+        &self,
+        ctx: &Context<'_>,
+        from: String,
+        to: String,
+    ) -> async_graphql::Result<Vec<DiffLine>, RustyError> {
+        log::debug!("handling `revisions::diff` request");
+        let lines = service::get_diff(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &from,
+            &to,
+        )
+        .await?;
+        log::debug!("`revisions::diff`: compared `{from}` with `{to}`");
+        Ok(lines)
+    }
+}
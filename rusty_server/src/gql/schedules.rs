@@ -0,0 +1,113 @@
+use async_graphql::{Context, Object};
+use serde_json::Value;
+
+use auth::{authenticate, authorize};
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::commons::search::SearchOptions;
+use domain::schedules::{PagedSchedules, RegisterSchedule, Schedule, UpdateSchedule};
+use persist::db_client::DbClient;
+
+use crate::gql::{get_public_gql_endpoints, shared::paginate};
+use crate::services::schedules as service;
+
+pub struct SchedulesQuery;
+
+#[Object]
+impl SchedulesQuery {
+    #[auth_macro::authenticate(bearer)]
+    async fn get(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<Value>,
+        options: Option<SearchOptions>,
+    ) -> async_graphql::Result<PagedSchedules, RustyError> {
+        log::debug!("handling `schedules::get` request");
+        let entries = service::get_all(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &filter,
+            &options,
+        )
+        .await?;
+        let (total, page, page_size, entries) = paginate(&entries, options);
+        log::debug!("`schedules::get`: found {} entries", total);
+        Ok(PagedSchedules {
+            total,
+            page,
+            page_size,
+            entries,
+        })
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn get_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<Option<Schedule>, RustyError> {
+        log::debug!("handling `schedules::getById` request");
+        let entry =
+            service::get_by_id(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, &id).await?;
+        log::debug!("`schedules::getById`: found entry by id: `{}`", id);
+        Ok(entry)
+    }
+}
+
+pub struct SchedulesMutation;
+
+#[Object]
+impl SchedulesMutation {
+    #[auth_macro::authenticate(bearer)]
+    async fn register(
+        &self,
+        ctx: &Context<'_>,
+        schedule: RegisterSchedule,
+    ) -> async_graphql::Result<String, RustyError> {
+        log::debug!("handling `schedules::register` request");
+        let id =
+            service::create(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, schedule).await?;
+        log::debug!("`schedules::register`: created schedule with id `{id}`");
+        Ok(id)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn update(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        schedule: UpdateSchedule,
+    ) -> async_graphql::Result<String, RustyError> {
+        log::debug!("handling `schedules::update` request");
+        let id = service::update(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &id,
+            schedule,
+        )
+        .await?;
+        log::debug!("`schedules::update`: updated schedule with id `{id}`");
+        Ok(id)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn delete_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<u64, RustyError> {
+        log::debug!("handling `schedules::deleteById` request");
+        let deleted =
+            service::delete_by_id(ctx.data::<DbClient>()?, ctx.data::<Credential>()?, &id).await?;
+        log::debug!("`schedules::deleteById`: deleted schedule with id `{id}`");
+        Ok(deleted)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn delete_all(&self, ctx: &Context<'_>) -> async_graphql::Result<u64, RustyError> {
+        log::debug!("handling `schedules::deleteAll` request");
+        let deleted = service::delete_all(ctx.data::<DbClient>()?).await?;
+        log::debug!("`schedules::deleteAll`: deleted {deleted} schedules");
+        Ok(deleted)
+    }
+}
@@ -0,0 +1,38 @@
+use async_graphql::{Context, Object};
+
+use auth::{authenticate, authorize};
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::templates::pipeline::TemplateFormat;
+use domain::templates::schema::TemplateSchema;
+use domain::templates::validation::{validate, TemplateValidation};
+use persist::db_client::DbClient;
+
+use crate::gql::get_public_gql_endpoints;
+
+pub struct TemplatesQuery;
+
+#[Object]
+impl TemplatesQuery {
+    #[auth_macro::authenticate(bearer)]
+    async fn validate(
+        &self,
+        ctx: &Context<'_>,
+        template: String,
+        format: Option<TemplateFormat>,
+    ) -> async_graphql::Result<TemplateValidation, RustyError> {
+        log::debug!("handling `templates::validate` request");
+        let result = validate(&template, format);
+        log::debug!(
+            "`templates::validate`: found {} diagnostics",
+            result.diagnostics.len()
+        );
+        Ok(result)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn schema(&self, ctx: &Context<'_>) -> async_graphql::Result<TemplateSchema, RustyError> {
+        log::debug!("handling `templates::schema` request");
+        Ok(TemplateSchema::current())
+    }
+}
@@ -0,0 +1,134 @@
+use async_graphql::{Context, Object};
+use serde_json::Value;
+
+use auth::{authenticate, authorize};
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::commons::search::SearchOptions;
+use domain::test_reports::{FlakyTest, PagedTestReports, TestResult, TestSuite};
+use persist::db_client::DbClient;
+
+use crate::gql::{get_public_gql_endpoints, shared::paginate};
+use crate::services::test_reports as service;
+
+pub struct TestReportsQuery;
+
+#[Object]
+impl TestReportsQuery {
+    #[auth_macro::authenticate(bearer)]
+    async fn get(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<Value>,
+        options: Option<SearchOptions>,
+    ) -> async_graphql::Result<PagedTestReports, RustyError> {
+        log::debug!("handling `testReports::get` request");
+        let entries = service::get_all(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &filter,
+            &options,
+        )
+        .await?;
+        let (total, page, page_size, entries) = paginate(&entries, options);
+        log::debug!("`testReports::get`: found {} entries", total);
+        Ok(PagedTestReports {
+            total,
+            page,
+            page_size,
+            entries,
+        })
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn get_failures(
+        &self,
+        ctx: &Context<'_>,
+        pipeline_id: String,
+    ) -> async_graphql::Result<Vec<TestResult>, RustyError> {
+        log::debug!("handling `testReports::getFailures` request");
+        let entries = service::get_failures(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &pipeline_id,
+        )
+        .await?;
+        log::debug!(
+            "`testReports::getFailures`: found {} failed tests for pipeline `{pipeline_id}`",
+            entries.len()
+        );
+        Ok(entries)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn get_slowest(
+        &self,
+        ctx: &Context<'_>,
+        pipeline_id: String,
+        limit: Option<usize>,
+    ) -> async_graphql::Result<Vec<TestResult>, RustyError> {
+        log::debug!("handling `testReports::getSlowest` request");
+        let entries = service::get_slowest(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &pipeline_id,
+            limit,
+        )
+        .await?;
+        log::debug!(
+            "`testReports::getSlowest`: found {} tests for pipeline `{pipeline_id}`",
+            entries.len()
+        );
+        Ok(entries)
+    }
+
+    #[auth_macro::authenticate(bearer)]
+    async fn get_flaky(
+        &self,
+        ctx: &Context<'_>,
+        job_id: String,
+        runs: Option<u64>,
+    ) -> async_graphql::Result<Vec<FlakyTest>, RustyError> {
+        log::debug!("handling `testReports::getFlaky` request");
+        let entries = service::get_flaky(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &job_id,
+            runs,
+        )
+        .await?;
+        log::debug!(
+            "`testReports::getFlaky`: found {} flaky tests for job `{job_id}`",
+            entries.len()
+        );
+        Ok(entries)
+    }
+}
+
+pub struct TestReportsMutation;
+
+#[Object]
+impl TestReportsMutation {
+    #[auth_macro::authenticate(bearer)]
+    async fn register(
+        &self,
+        ctx: &Context<'_>,
+        pipeline_id: String,
+        agent_id: String,
+        stage: String,
+        suites: Vec<TestSuite>,
+    ) -> async_graphql::Result<String, RustyError> {
+        log::debug!("handling `testReports::register` request");
+        let id = service::create(
+            ctx.data::<DbClient>()?,
+            ctx.data::<Credential>()?,
+            &pipeline_id,
+            &agent_id,
+            &stage,
+            suites,
+        )
+        .await?;
+        log::debug!("`testReports::register`: created test report with id `{id}`");
+        Ok(id)
+    }
+}
@@ -1,5 +1,11 @@
+pub mod badges;
+pub mod event_hooks;
 pub mod gql;
 pub mod middleware;
+pub mod notifications;
+pub mod reporters;
 pub mod schedulers;
+pub mod schemas;
 pub mod server_ext;
 pub mod services;
+pub mod webhooks;
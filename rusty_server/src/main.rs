@@ -16,11 +16,11 @@
 #![allow(clippy::similar_names)]
 #![cfg_attr(test, deny(rust_2018_idioms))]
 
-use axum::{routing, Router};
+use axum::{routing, Extension, Router};
 use tokio::net::TcpListener;
 
 use commons::env::var_or_default;
-use rusty_server::{gql, middleware, schedulers, server_ext};
+use rusty_server::{badges, gql, middleware, schedulers, schemas, server_ext, webhooks};
 
 #[tokio::main]
 async fn main() {
@@ -36,6 +36,22 @@ async fn main() {
         .route("/health", routing::get(|| async { "ok" }))
         .route("/graphql", routing::post(server_ext::graphql_handler))
         .route("/ws", routing::get(server_ext::graphql_ws_handler))
+        .route("/webhooks/github", routing::post(webhooks::github_handler))
+        .route("/webhooks/gitlab", routing::post(webhooks::gitlab_handler))
+        .route("/webhooks/gitea", routing::post(webhooks::gitea_handler))
+        .route(
+            "/badges/:job_id/:badge",
+            routing::get(badges::badge_handler),
+        )
+        .route(
+            "/schemas/pipeline-template.json",
+            routing::get(schemas::pipeline_template_handler),
+        )
+        .route(
+            "/schemas/pipeline-template/:version",
+            routing::get(schemas::pipeline_template_version_handler),
+        )
+        .layer(Extension(db.clone()))
         .layer(middleware::cors::cors_layer())
         .with_state(schema);
 
@@ -0,0 +1,117 @@
+use std::future::Future;
+use std::time::Duration;
+
+use tokio::time::sleep;
+
+use commons::errors::RustyError;
+use domain::notifications::{Notification, NotificationChannel};
+use domain::pipelines::PipelineStatus;
+
+pub mod slack;
+pub mod smtp;
+pub mod webhook;
+
+/// Pipeline outcome message sent through a notification channel
+#[derive(Clone, Debug)]
+pub struct NotificationMessage {
+    /// project name
+    pub project: String,
+    /// job name
+    pub job: String,
+    /// pipeline id
+    pub pipeline_id: String,
+    /// pipeline order number
+    pub number: u64,
+    /// pipeline branch
+    pub branch: String,
+    /// pipeline status
+    pub status: PipelineStatus,
+    /// pipeline commit sha
+    pub commit: Option<String>,
+}
+
+impl NotificationMessage {
+    /// Short, human-readable subject of the message
+    pub fn subject(&self) -> String {
+        format!(
+            "[{}] {} #{} ({}): {:?}",
+            self.project, self.job, self.number, self.branch, self.status
+        )
+    }
+}
+
+/// Sender of notifications through a single channel
+pub trait Sender: Send + Sync {
+    /// Send a notification message
+    fn send(
+        &self,
+        message: &NotificationMessage,
+    ) -> impl Future<Output = Result<(), RustyError>> + Send;
+}
+
+/// Sender implementation for a configured notification channel
+#[derive(Clone, Debug)]
+pub enum ChannelSender {
+    Smtp(smtp::SmtpSender),
+    Slack(slack::SlackSender),
+    Webhook(webhook::WebhookSender),
+}
+
+impl ChannelSender {
+    /// Build a sender for a notification
+    pub fn new(notification: &Notification) -> Result<Self, RustyError> {
+        let target = &notification.target;
+        Ok(match notification.channel {
+            NotificationChannel::Smtp => Self::Smtp(smtp::SmtpSender::from_env(target)?),
+            NotificationChannel::Slack => Self::Slack(slack::SlackSender::new(target)),
+            NotificationChannel::Webhook => Self::Webhook(webhook::WebhookSender::new(target)),
+        })
+    }
+}
+
+impl Sender for ChannelSender {
+    async fn send(&self, message: &NotificationMessage) -> Result<(), RustyError> {
+        match self {
+            Self::Smtp(sender) => sender.send(message).await,
+            Self::Slack(sender) => sender.send(message).await,
+            Self::Webhook(sender) => sender.send(message).await,
+        }
+    }
+}
+
+/// Send a notification, retrying on failure
+///
+/// Returns the number of attempts made and the last error, if delivery failed.
+pub async fn send_with_retry<S: Sender>(
+    sender: &S,
+    message: &NotificationMessage,
+    retries: u32,
+    delay: Duration,
+) -> (u32, Option<RustyError>) {
+    let mut attempt = 1;
+    loop {
+        match sender.send(message).await {
+            Ok(()) => return (attempt, None),
+            Err(err) if attempt > retries => return (attempt, Some(err)),
+            Err(err) => {
+                log::debug!(
+                    "sending notification for `{}` failed: {err}. Retrying...",
+                    message.pipeline_id
+                );
+                attempt += 1;
+                sleep(delay).await;
+            }
+        }
+    }
+}
+
+pub(crate) async fn check_response(response: reqwest::Response) -> Result<(), RustyError> {
+    let status = response.status();
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(RustyError::RequestError(format!(
+            "notification target responded with `{status}`"
+        )))
+    }
+}
@@ -0,0 +1,30 @@
+use serde_json::json;
+
+use commons::errors::RustyError;
+
+use crate::notifications::{check_response, NotificationMessage, Sender};
+
+/// Sender posting messages to a Slack-compatible incoming webhook
+#[derive(Clone, Debug)]
+pub struct SlackSender {
+    url: String,
+}
+
+impl SlackSender {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+        }
+    }
+}
+
+impl Sender for SlackSender {
+    async fn send(&self, message: &NotificationMessage) -> Result<(), RustyError> {
+        let response = reqwest::Client::new()
+            .post(&self.url)
+            .json(&json!({ "text": message.subject() }))
+            .send()
+            .await?;
+        check_response(response).await
+    }
+}
@@ -0,0 +1,84 @@
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+use commons::env::{var, var_or_default};
+use commons::errors::RustyError;
+
+use crate::notifications::{NotificationMessage, Sender};
+
+/// Sender delivering e-mails through the configured SMTP server
+#[derive(Clone, Debug)]
+pub struct SmtpSender {
+    host: String,
+    port: u16,
+    tls: bool,
+    credentials: Option<(String, String)>,
+    from: String,
+    to: String,
+}
+
+impl SmtpSender {
+    /// Build a sender from `SMTP_*` environment variables
+    pub fn from_env(to: &str) -> Result<Self, RustyError> {
+        let credentials = match (var::<String>("SMTP_USER"), var::<String>("SMTP_PASSWORD")) {
+            (Ok(user), Ok(password)) => Some((user, password)),
+            _ => None,
+        };
+        Ok(Self {
+            host: var::<String>("SMTP_HOST")?,
+            port: var_or_default("SMTP_PORT", 587),
+            tls: var_or_default("SMTP_TLS", true),
+            credentials,
+            from: var_or_default("SMTP_FROM", "rusty-ops@localhost".to_string()),
+            to: to.to_string(),
+        })
+    }
+
+    fn transport(&self) -> Result<AsyncSmtpTransport<Tokio1Executor>, RustyError> {
+        let builder = if self.tls {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&self.host)
+                .map_err(|err| RustyError::RequestError(err.to_string()))?
+        } else {
+            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&self.host)
+        };
+        let builder = builder.port(self.port);
+        Ok(match self.credentials.clone() {
+            Some((user, password)) => builder.credentials(Credentials::new(user, password)),
+            None => builder,
+        }
+        .build())
+    }
+}
+
+fn mailbox(address: &str) -> Result<Mailbox, RustyError> {
+    address
+        .parse::<Mailbox>()
+        .map_err(|err| RustyError::ValidationError(format!("invalid address `{address}`: {err}")))
+}
+
+impl Sender for SmtpSender {
+    async fn send(&self, message: &NotificationMessage) -> Result<(), RustyError> {
+        let body = format!(
+            "Project: {}\nJob: {}\nPipeline: #{} ({})\nBranch: {}\nCommit: {}\nStatus: {:?}\n",
+            message.project,
+            message.job,
+            message.number,
+            message.pipeline_id,
+            message.branch,
+            message.commit.clone().unwrap_or_else(|| "-".to_string()),
+            message.status,
+        );
+        let email = Message::builder()
+            .from(mailbox(&self.from)?)
+            .to(mailbox(&self.to)?)
+            .subject(message.subject())
+            .body(body)
+            .map_err(|err| RustyError::RequestError(err.to_string()))?;
+        self.transport()?
+            .send(email)
+            .await
+            .map(|_| ())
+            .map_err(|err| RustyError::RequestError(err.to_string()))
+    }
+}
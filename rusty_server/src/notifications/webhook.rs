@@ -0,0 +1,40 @@
+use serde_json::json;
+
+use commons::errors::RustyError;
+
+use crate::notifications::{check_response, NotificationMessage, Sender};
+
+/// Sender posting pipeline outcomes as JSON to a generic webhook
+#[derive(Clone, Debug)]
+pub struct WebhookSender {
+    url: String,
+}
+
+impl WebhookSender {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+        }
+    }
+}
+
+impl Sender for WebhookSender {
+    async fn send(&self, message: &NotificationMessage) -> Result<(), RustyError> {
+        let response = reqwest::Client::new()
+            .post(&self.url)
+            .json(&json!({
+                "project": message.project,
+                "job": message.job,
+                "pipeline": {
+                    "id": message.pipeline_id,
+                    "number": message.number,
+                    "branch": message.branch,
+                    "status": message.status,
+                    "commit": message.commit,
+                },
+            }))
+            .send()
+            .await?;
+        check_response(response).await
+    }
+}
@@ -0,0 +1,61 @@
+use serde_json::{json, Value};
+
+use commons::errors::RustyError;
+
+use crate::reporters::{check_response, encode, CommitState, CommitStatus, Reporter};
+
+#[derive(Clone, Debug)]
+pub struct GiteaReporter {
+    api_url: String,
+    token: String,
+}
+
+impl GiteaReporter {
+    pub fn new(api_url: &str, token: &str) -> Self {
+        Self {
+            api_url: api_url.to_string(),
+            token: token.to_string(),
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        reqwest::Client::new()
+            .request(method, format!("{}{path}", self.api_url))
+            .header("Accept", "application/json")
+            .header("Authorization", format!("token {}", self.token))
+    }
+}
+
+const fn state(state: CommitState) -> &'static str {
+    match state {
+        CommitState::Pending | CommitState::Running => "pending",
+        CommitState::Success => "success",
+        CommitState::Failure => "failure",
+    }
+}
+
+impl Reporter for GiteaReporter {
+    async fn resolve_commit(&self, repository: &str, branch: &str) -> Result<String, RustyError> {
+        let path = format!("/repos/{repository}/branches/{}", encode(branch));
+        let response = self.request(reqwest::Method::GET, &path).send().await?;
+        let body: Value = serde_json::from_str(&check_response(response).await?)?;
+        body.pointer("/commit/id")
+            .and_then(Value::as_str)
+            .map(ToString::to_string)
+            .ok_or_else(|| RustyError::RequestError(format!("branch `{branch}` not found")))
+    }
+
+    async fn report(&self, status: &CommitStatus) -> Result<(), RustyError> {
+        let path = format!("/repos/{}/statuses/{}", status.repository, status.commit);
+        let response = self
+            .request(reqwest::Method::POST, &path)
+            .json(&json!({
+                "state": state(status.state),
+                "context": status.context,
+                "description": status.description,
+            }))
+            .send()
+            .await?;
+        check_response(response).await.map(|_| ())
+    }
+}
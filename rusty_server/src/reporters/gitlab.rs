@@ -0,0 +1,71 @@
+use serde_json::{json, Value};
+
+use commons::errors::RustyError;
+
+use crate::reporters::{check_response, encode, CommitState, CommitStatus, Reporter};
+
+#[derive(Clone, Debug)]
+pub struct GitLabReporter {
+    api_url: String,
+    token: String,
+}
+
+impl GitLabReporter {
+    pub fn new(api_url: &str, token: &str) -> Self {
+        Self {
+            api_url: api_url.to_string(),
+            token: token.to_string(),
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        reqwest::Client::new()
+            .request(method, format!("{}{path}", self.api_url))
+            .header("Accept", "application/json")
+            .header("PRIVATE-TOKEN", &self.token)
+    }
+}
+
+const fn state(state: CommitState) -> &'static str {
+    match state {
+        CommitState::Pending => "pending",
+        CommitState::Running => "running",
+        CommitState::Success => "success",
+        CommitState::Failure => "failed",
+    }
+}
+
+impl Reporter for GitLabReporter {
+    async fn resolve_commit(&self, repository: &str, branch: &str) -> Result<String, RustyError> {
+        let path = format!(
+            "/projects/{}/repository/branches/{}",
+            encode(repository),
+            encode(branch)
+        );
+        let response = self.request(reqwest::Method::GET, &path).send().await?;
+        let body: Value = serde_json::from_str(&check_response(response).await?)?;
+        body.pointer("/commit/id")
+            .and_then(Value::as_str)
+            .map(ToString::to_string)
+            .ok_or_else(|| RustyError::RequestError(format!("branch `{branch}` not found")))
+    }
+
+    async fn report(&self, status: &CommitStatus) -> Result<(), RustyError> {
+        let path = format!(
+            "/projects/{}/statuses/{}",
+            encode(&status.repository),
+            status.commit
+        );
+        let response = self
+            .request(reqwest::Method::POST, &path)
+            .json(&json!({
+                "state": state(status.state),
+                "ref": status.branch,
+                "name": status.context,
+                "description": status.description,
+            }))
+            .send()
+            .await?;
+        check_response(response).await.map(|_| ())
+    }
+}
@@ -0,0 +1,176 @@
+use std::future::Future;
+use std::time::Duration;
+
+use tokio::time::sleep;
+
+use commons::errors::RustyError;
+use domain::pipelines::PipelineStatus;
+use domain::reporters::StatusReporter;
+use domain::webhooks::Forge;
+
+pub mod gitea;
+pub mod github;
+pub mod gitlab;
+
+/// Commit state reported to a git forge
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CommitState {
+    Pending,
+    Running,
+    Success,
+    Failure,
+}
+
+impl From<PipelineStatus> for CommitState {
+    fn from(value: PipelineStatus) -> Self {
+        match value {
+            PipelineStatus::Defined | PipelineStatus::Assigned => Self::Pending,
+            PipelineStatus::InProgress => Self::Running,
+            PipelineStatus::Success => Self::Success,
+            PipelineStatus::Failure | PipelineStatus::Unstable => Self::Failure,
+        }
+    }
+}
+
+/// Commit status reported to a git forge
+#[derive(Clone, Debug)]
+pub struct CommitStatus {
+    /// repository path, e.g. `owner/repo`
+    pub repository: String,
+    /// commit sha
+    pub commit: String,
+    /// branch name
+    pub branch: String,
+    /// commit state
+    pub state: CommitState,
+    /// status context (name)
+    pub context: String,
+    /// status description
+    pub description: String,
+}
+
+/// Reporter of commit statuses to a git forge
+pub trait Reporter: Send + Sync {
+    /// Resolve the head commit of a branch
+    fn resolve_commit(
+        &self,
+        repository: &str,
+        branch: &str,
+    ) -> impl Future<Output = Result<String, RustyError>> + Send;
+
+    /// Post a commit status
+    fn report(&self, status: &CommitStatus) -> impl Future<Output = Result<(), RustyError>> + Send;
+}
+
+/// Reporter implementation for a configured git forge
+#[derive(Clone, Debug)]
+pub enum ForgeReporter {
+    GitHub(github::GitHubReporter),
+    GitLab(gitlab::GitLabReporter),
+    Gitea(gitea::GiteaReporter),
+}
+
+impl ForgeReporter {
+    /// Build a reporter for a project, based on its url
+    pub fn new(reporter: &StatusReporter, project_url: &str) -> Option<Self> {
+        let api_url = reporter
+            .clone()
+            .api_url
+            .or_else(|| default_api_url(reporter.forge, project_url))?;
+        let api_url = api_url.trim_end_matches('/');
+        let token = &reporter.token;
+        Some(match reporter.forge {
+            Forge::GitHub => Self::GitHub(github::GitHubReporter::new(api_url, token)),
+            Forge::GitLab => Self::GitLab(gitlab::GitLabReporter::new(api_url, token)),
+            Forge::Gitea => Self::Gitea(gitea::GiteaReporter::new(api_url, token)),
+        })
+    }
+}
+
+impl Reporter for ForgeReporter {
+    async fn resolve_commit(&self, repository: &str, branch: &str) -> Result<String, RustyError> {
+        match self {
+            Self::GitHub(reporter) => reporter.resolve_commit(repository, branch).await,
+            Self::GitLab(reporter) => reporter.resolve_commit(repository, branch).await,
+            Self::Gitea(reporter) => reporter.resolve_commit(repository, branch).await,
+        }
+    }
+
+    async fn report(&self, status: &CommitStatus) -> Result<(), RustyError> {
+        match self {
+            Self::GitHub(reporter) => reporter.report(status).await,
+            Self::GitLab(reporter) => reporter.report(status).await,
+            Self::Gitea(reporter) => reporter.report(status).await,
+        }
+    }
+}
+
+/// Post a commit status, retrying on failure
+pub async fn report_with_retry<R: Reporter>(
+    reporter: &R,
+    status: &CommitStatus,
+    retries: u32,
+    delay: Duration,
+) -> Result<(), RustyError> {
+    let mut attempt = 0;
+    loop {
+        match reporter.report(status).await {
+            Ok(()) => return Ok(()),
+            Err(err) if attempt >= retries => return Err(err),
+            Err(err) => {
+                log::debug!(
+                    "reporting status for `{}` failed: {err}. Retrying...",
+                    status.commit
+                );
+                attempt += 1;
+                sleep(delay).await;
+            }
+        }
+    }
+}
+
+/// Extract the repository path (e.g. `owner/repo`) from a project url
+pub fn repository_path(url: &str) -> Option<String> {
+    let path = match url::Url::parse(url) {
+        Ok(parsed) if parsed.has_host() => parsed.path().to_string(),
+        _ => url.split_once(':')?.1.to_string(),
+    };
+    let path = path.trim_matches('/').trim_end_matches(".git");
+    if path.is_empty() {
+        None
+    } else {
+        Some(path.to_string())
+    }
+}
+
+fn default_api_url(forge: Forge, project_url: &str) -> Option<String> {
+    match forge {
+        Forge::GitHub => Some("https://api.github.com".to_string()),
+        Forge::GitLab => Some("https://gitlab.com/api/v4".to_string()),
+        Forge::Gitea => match url::Url::parse(project_url) {
+            Ok(parsed) if ["http", "https"].contains(&parsed.scheme()) => {
+                Some(format!("{}/api/v1", parsed.origin().ascii_serialization()))
+            }
+            _ => {
+                let host = project_url.split_once('@')?.1.split_once(':')?.0;
+                Some(format!("https://{host}/api/v1"))
+            }
+        },
+    }
+}
+
+pub(crate) fn encode(segment: &str) -> String {
+    url::form_urlencoded::byte_serialize(segment.as_bytes()).collect()
+}
+
+pub(crate) async fn check_response(response: reqwest::Response) -> Result<String, RustyError> {
+    let status = response.status();
+    let body = response.text().await?;
+    if status.is_success() {
+        Ok(body)
+    } else {
+        Err(RustyError::RequestError(format!(
+            "forge responded with `{status}`: {body}"
+        )))
+    }
+}
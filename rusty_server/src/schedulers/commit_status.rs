@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+use commons::env::var_or_default;
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::pipelines::Pipeline;
+use persist::db_client::DbClient;
+
+use crate::reporters::{
+    report_with_retry, repository_path, CommitState, CommitStatus, ForgeReporter, Reporter,
+};
+use crate::services::{jobs, projects, reporters};
+
+// the statuses of a pipeline are reported by a single task, in the order of its events
+struct PipelineReports {
+    state: CommitState,
+    last_event: Instant,
+    events: UnboundedSender<Pipeline>,
+}
+
+impl PipelineReports {
+    fn spawn(db: &DbClient, state: CommitState) -> Self {
+        let (events, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(report_in_order(db.clone(), receiver));
+        Self {
+            state,
+            last_event: Instant::now(),
+            events,
+        }
+    }
+}
+
+pub async fn schedule(db: &DbClient) {
+    let mut receiver = messaging::internal::resubscribe().await;
+    let mut reports: HashMap<String, PipelineReports> = HashMap::new();
+    let ttl = Duration::from_secs(var_or_default("REPORTER_PIPELINE_TTL", 86_400));
+    loop {
+        let message = match receiver.recv().await {
+            Ok(message) => message,
+            Err(RecvError::Lagged(skipped)) => {
+                log::warn!("`commit_status`: skipped {skipped} pipeline events");
+                continue;
+            }
+            Err(RecvError::Closed) => return,
+        };
+
+        // pipelines which never finish are no longer tracked after a while
+        reports.retain(|_, entry| entry.last_event.elapsed() < ttl);
+
+        let Some(pipeline) = parse_pipeline(&message) else {
+            continue;
+        };
+        let state = CommitState::from(pipeline.status);
+        let id = pipeline.id.clone();
+        match reports.get_mut(&id) {
+            Some(entry) if entry.state == state => continue,
+            Some(entry) => {
+                entry.state = state;
+                entry.last_event = Instant::now();
+            }
+            None => {
+                reports.insert(id.clone(), PipelineReports::spawn(db, state));
+            }
+        }
+        if let Some(entry) = reports.get(&id) {
+            let _ = entry.events.send(pipeline);
+        }
+        // dropping the sender ends the task once the pending reports are sent
+        if matches!(state, CommitState::Success | CommitState::Failure) {
+            reports.remove(&id);
+        }
+    }
+}
+
+async fn report_in_order(db: DbClient, mut events: UnboundedReceiver<Pipeline>) {
+    // a pipeline without a commit reports all its statuses to the commit resolved first
+    let mut commit = None;
+    while let Some(mut pipeline) = events.recv().await {
+        if pipeline.commit.is_none() {
+            pipeline.commit.clone_from(&commit);
+        }
+        match report(&db, &pipeline).await {
+            Ok(reported) => commit = commit.or(reported),
+            Err(err) => log::warn!("`commit_status`: pipeline `{}`: {err}", pipeline.id),
+        }
+    }
+}
+
+fn parse_pipeline(message: &str) -> Option<Pipeline> {
+    let message = serde_json::from_str::<Value>(message).ok()?;
+    let index = message.get("index")?.as_str()?;
+    let operation = message.get("op")?.as_str()?;
+    let item = message.get("item")?.as_str()?;
+    if index == "pipelines" && ["create", "update"].contains(&operation) {
+        serde_json::from_str::<Pipeline>(item).ok()
+    } else {
+        None
+    }
+}
+
+/// Report the status of a pipeline to the forges of its project
+///
+/// Returns the reported commit - the head of the pipeline branch for pipelines without a commit.
+pub async fn report(db: &DbClient, pipeline: &Pipeline) -> Result<Option<String>, RustyError> {
+    let cred = Credential::System;
+    let Some(job) = jobs::get_by_id(db, &cred, &pipeline.job_id, &None, &[]).await? else {
+        return Ok(None);
+    };
+    let Some(project) = projects::get_by_id(db, &cred, &job.project_id, &None, &[]).await? else {
+        return Ok(None);
+    };
+    let Some(url) = project.url else {
+        return Ok(None);
+    };
+    let Some(repository) = repository_path(&url) else {
+        return Ok(None);
+    };
+
+    let filter = json!({ "project_id": { "equals": project.id } });
+    let retries = var_or_default("REPORTER_RETRIES", 3);
+    let delay = Duration::from_secs(var_or_default("REPORTER_RETRY_DELAY", 5));
+    let mut commit = pipeline.commit.clone();
+    for entry in reporters::get_all(db, &cred, &Some(filter), &None).await? {
+        let Some(reporter) = ForgeReporter::new(&entry, &url) else {
+            continue;
+        };
+        let sha = match &commit {
+            Some(commit) => commit.clone(),
+            None => match reporter.resolve_commit(&repository, &pipeline.branch).await {
+                Ok(resolved) => {
+                    commit = Some(resolved.clone());
+                    resolved
+                }
+                Err(err) => {
+                    log::error!(
+                        "`commit_status`: reporter `{}` failed to resolve the commit of pipeline `{}`: {err}",
+                        entry.id,
+                        pipeline.id
+                    );
+                    continue;
+                }
+            },
+        };
+        let status = CommitStatus {
+            repository: repository.clone(),
+            commit: sha,
+            branch: pipeline.branch.clone(),
+            state: CommitState::from(pipeline.status),
+            context: format!("rusty-ops/{}", job.name),
+            description: format!("Pipeline #{}: {:?}", pipeline.number, pipeline.status),
+        };
+        // a failing reporter does not prevent the other forges from being reported to
+        match report_with_retry(&reporter, &status, retries, delay).await {
+            Ok(()) => log::debug!(
+                "`commit_status`: reported `{:?}` for pipeline `{}`",
+                status.state,
+                pipeline.id
+            ),
+            Err(err) => log::error!(
+                "`commit_status`: reporter `{}` failed to report pipeline `{}`: {err}",
+                entry.id,
+                pipeline.id
+            ),
+        }
+    }
+    Ok(commit)
+}
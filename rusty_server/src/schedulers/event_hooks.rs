@@ -0,0 +1,144 @@
+use std::time::Duration;
+
+use serde_json::Value;
+use tokio::sync::broadcast::error::RecvError;
+
+use commons::env::var_or_default;
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::event_hooks::EventHookDelivery;
+use domain::jobs::Job;
+use persist::db_client::DbClient;
+
+use crate::event_hooks::PROTECTED_INDEXES;
+use crate::services::{event_hooks, shared};
+
+const LOCKS_INDEX: &str = "locks";
+
+pub async fn schedule(db: &DbClient) {
+    let mut receiver = messaging::internal::resubscribe().await;
+    loop {
+        let message = match receiver.recv().await {
+            Ok(message) => message,
+            Err(RecvError::Lagged(skipped)) => {
+                log::warn!("`event_hooks`: skipped {skipped} domain events");
+                continue;
+            }
+            Err(RecvError::Closed) => return,
+        };
+
+        let Some((index, op, item)) = parse_event(&message) else {
+            continue;
+        };
+        let db = db.clone();
+        tokio::spawn(async move {
+            if let Err(err) = dispatch(&db, &index, &op, &item).await {
+                log::warn!("`event_hooks`: `{index}.{op}`: {err}");
+            }
+        });
+    }
+}
+
+/// scheduler re-sending deliveries left pending, e.g. by a server restart
+pub async fn redeliver(db: &DbClient) {
+    let timer = var_or_default("SCHEDULER_EVENT_HOOKS_REDELIVERY", 60);
+    let mut task = tokio::time::interval(Duration::from_secs(timer));
+
+    loop {
+        task.tick().await;
+        log::trace!("running `event_hooks::redeliver` scheduled task");
+        let Ok(entries) = event_hooks::get_pending_deliveries(db).await else {
+            continue;
+        };
+        let threshold = chrono::Utc::now() - chrono::Duration::seconds(timer as i64);
+        for entry in entries {
+            let stale = chrono::DateTime::parse_from_rfc3339(&entry.date)
+                .map_or(true, |date| date < threshold);
+            if !stale {
+                continue;
+            }
+            // only one server replica may redeliver a given event
+            let lock = format!("event-delivery-{}", entry.id);
+            if db.lock(LOCKS_INDEX, &lock, timer).await.unwrap_or(false) {
+                send(db, &entry).await;
+            }
+        }
+    }
+}
+
+fn parse_event(message: &str) -> Option<(String, String, Value)> {
+    let message = serde_json::from_str::<Value>(message).ok()?;
+    let index = message.get("index")?.as_str()?;
+    let op = message.get("op")?.as_str()?;
+    let item = message.get("item")?.as_str()?;
+    if PROTECTED_INDEXES.contains(&index) {
+        return None;
+    }
+    let item = serde_json::from_str::<Value>(item).ok()?;
+    Some((index.to_string(), op.to_string(), item))
+}
+
+pub async fn dispatch(
+    db: &DbClient,
+    index: &str,
+    op: &str,
+    item: &Value,
+) -> Result<(), RustyError> {
+    let project_id = project_of(db, index, item).await?;
+    let hooks = event_hooks::get_all(db, &Credential::System, &None, &None).await?;
+    for hook in hooks {
+        if !hook.matches(index, op, project_id.as_deref()) {
+            continue;
+        }
+        let resource = project_id.as_ref().map_or_else(
+            || "PROJECTS:READ:ALL".to_string(),
+            |id| format!("PROJECTS:READ:ID[{id}]"),
+        );
+        if auth::authorize(db, &hook.owner, &resource).await.is_err() {
+            log::debug!("`event_hooks`: `{}` not authorized for `{index}`", hook.id);
+            continue;
+        }
+
+        // persisted before sending - pending deliveries are retried by the redelivery scheduler
+        let delivery = EventHookDelivery::new(&hook.id, index, op, item);
+        event_hooks::create_delivery(db, &delivery).await?;
+        send(db, &delivery).await;
+    }
+    Ok(())
+}
+
+async fn send(db: &DbClient, delivery: &EventHookDelivery) {
+    let Ok(Some(hook)) = event_hooks::get_by_id(db, &Credential::System, &delivery.hook_id).await
+    else {
+        return;
+    };
+    let retries = var_or_default("EVENT_HOOK_RETRIES", 3);
+    let delay = Duration::from_secs(var_or_default("EVENT_HOOK_RETRY_DELAY", 5));
+    if let Err(err) = event_hooks::deliver(db, &hook, delivery, retries, delay).await {
+        log::warn!("`event_hooks`: delivery `{}`: {err}", delivery.id);
+    }
+}
+
+async fn project_of(
+    db: &DbClient,
+    index: &str,
+    item: &Value,
+) -> Result<Option<String>, RustyError> {
+    let get = |key: &str| {
+        item.get(key)
+            .and_then(Value::as_str)
+            .map(ToString::to_string)
+    };
+    if index == "projects" {
+        return Ok(get("id"));
+    }
+    if let Some(project_id) = get("project_id") {
+        return Ok(Some(project_id));
+    }
+    match get("job_id") {
+        Some(job_id) => Ok(shared::get_by_id::<Job>(db, "jobs", &job_id)
+            .await?
+            .map(|job| job.project_id)),
+        None => Ok(None),
+    }
+}
@@ -2,8 +2,12 @@ use messaging::mq_client::MqClient;
 use persist::db_client::DbClient;
 
 pub mod agent_ttl;
+pub mod commit_status;
+pub mod event_hooks;
+pub mod notifications;
 pub mod pipeline_cleanup;
 pub mod pipeline_logs;
+pub mod pipeline_schedule;
 
 /// initialization of schedulers
 pub fn init(db: &DbClient, mq: &MqClient) {
@@ -19,6 +23,36 @@ pub fn init(db: &DbClient, mq: &MqClient) {
         pipeline_cleanup::schedule(&db_pipelines).await;
     });
 
+    // scheduler for pipeline schedules - register pipelines for due cron schedules
+    let db_schedules = db.clone();
+    tokio::spawn(async move {
+        pipeline_schedule::schedule(&db_schedules).await;
+    });
+
+    // listener for pipeline events - report commit statuses to git forges
+    let db_statuses = db.clone();
+    tokio::spawn(async move {
+        commit_status::schedule(&db_statuses).await;
+    });
+
+    // listener for pipeline events - send project notifications on finished pipelines
+    let db_notifications = db.clone();
+    tokio::spawn(async move {
+        notifications::schedule(&db_notifications).await;
+    });
+
+    // listener for domain events - deliver events to subscribed outgoing webhooks
+    let db_hooks = db.clone();
+    tokio::spawn(async move {
+        event_hooks::schedule(&db_hooks).await;
+    });
+
+    // scheduler for event hook deliveries - resend deliveries left pending
+    let db_deliveries = db.clone();
+    tokio::spawn(async move {
+        event_hooks::redeliver(&db_deliveries).await;
+    });
+
     // scheduler for pipeline logs - read from mq, push to db
     let db_pipelines = db.clone();
     let mut mq_pipelines = mq.clone();
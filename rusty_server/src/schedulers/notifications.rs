@@ -0,0 +1,144 @@
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tokio::sync::broadcast::error::RecvError;
+
+use commons::env::var_or_default;
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::notifications::NotificationDelivery;
+use domain::pipelines::{Pipeline, PipelineStatus};
+use persist::db_client::DbClient;
+
+use crate::notifications::{send_with_retry, ChannelSender, NotificationMessage};
+use crate::services::{jobs, notifications, pipelines, projects};
+
+const LOCKS_INDEX: &str = "locks";
+const LOCK_TTL: u64 = 3600;
+
+pub async fn schedule(db: &DbClient) {
+    let mut receiver = messaging::internal::resubscribe().await;
+    loop {
+        let message = match receiver.recv().await {
+            Ok(message) => message,
+            Err(RecvError::Lagged(skipped)) => {
+                log::warn!("`notifications`: skipped {skipped} pipeline events");
+                continue;
+            }
+            Err(RecvError::Closed) => return,
+        };
+
+        let Some(pipeline) = parse_finished_pipeline(&message) else {
+            continue;
+        };
+        let db = db.clone();
+        tokio::spawn(async move {
+            if let Err(err) = notify(&db, &pipeline).await {
+                log::warn!("`notifications`: pipeline `{}`: {err}", pipeline.id);
+            }
+        });
+    }
+}
+
+fn parse_finished_pipeline(message: &str) -> Option<Pipeline> {
+    let message = serde_json::from_str::<Value>(message).ok()?;
+    let index = message.get("index")?.as_str()?;
+    let operation = message.get("op")?.as_str()?;
+    let item = message.get("item")?.as_str()?;
+    if index != "pipelines" || operation != "update" {
+        return None;
+    }
+    serde_json::from_str::<Pipeline>(item)
+        .ok()
+        .filter(|pipeline| is_finished(pipeline.status))
+}
+
+const fn is_finished(status: PipelineStatus) -> bool {
+    matches!(
+        status,
+        PipelineStatus::Success | PipelineStatus::Failure | PipelineStatus::Unstable
+    )
+}
+
+pub async fn notify(db: &DbClient, pipeline: &Pipeline) -> Result<(), RustyError> {
+    let cred = Credential::System;
+    let Some(job) = jobs::get_by_id(db, &cred, &pipeline.job_id, &None, &[]).await? else {
+        return Ok(());
+    };
+    let Some(project) = projects::get_by_id(db, &cred, &job.project_id, &None, &[]).await? else {
+        return Ok(());
+    };
+
+    let filter = json!({ "project_id": { "equals": project.id } });
+    let entries = notifications::get_all(db, &cred, &Some(filter), &None).await?;
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let previous = previous_status(db, pipeline).await?;
+    let message = NotificationMessage {
+        project: project.name.clone(),
+        job: job.name.clone(),
+        pipeline_id: pipeline.id.clone(),
+        number: pipeline.number,
+        branch: pipeline.branch.clone(),
+        status: pipeline.status,
+        commit: pipeline.commit.clone(),
+    };
+    let retries = var_or_default("NOTIFICATION_RETRIES", 3);
+    let delay = Duration::from_secs(var_or_default("NOTIFICATION_RETRY_DELAY", 5));
+    for notification in entries {
+        if notification
+            .branch
+            .as_ref()
+            .is_some_and(|branch| branch != &pipeline.branch)
+            || !notification.rule.matches(pipeline.status, previous)
+        {
+            continue;
+        }
+
+        // only one server replica may deliver a given notification
+        let lock = format!("notification-{}-{}", notification.id, pipeline.id);
+        if !db.lock(LOCKS_INDEX, &lock, LOCK_TTL).await.unwrap_or(false) {
+            continue;
+        }
+
+        let (attempts, error) = match ChannelSender::new(&notification) {
+            Ok(sender) => send_with_retry(&sender, &message, retries, delay).await,
+            Err(err) => (0, Some(err)),
+        };
+        if let Some(err) = &error {
+            log::warn!(
+                "`notifications`: delivery of `{}` for pipeline `{}` failed: {err}",
+                notification.id,
+                pipeline.id
+            );
+        }
+        let delivery = NotificationDelivery::new(
+            &notification.id,
+            &pipeline.id,
+            attempts,
+            error.map(|err| err.to_string()),
+        );
+        notifications::record_delivery(db, &delivery).await?;
+    }
+    Ok(())
+}
+
+async fn previous_status(
+    db: &DbClient,
+    pipeline: &Pipeline,
+) -> Result<Option<PipelineStatus>, RustyError> {
+    let filter = json!({
+        "job_id": { "equals": pipeline.job_id },
+        "branch": { "equals": pipeline.branch },
+    });
+    Ok(
+        pipelines::get_all(db, &Credential::System, &Some(filter), &None)
+            .await?
+            .into_iter()
+            .filter(|entry| entry.number < pipeline.number && is_finished(entry.status))
+            .max_by_key(|entry| entry.number)
+            .map(|entry| entry.status),
+    )
+}
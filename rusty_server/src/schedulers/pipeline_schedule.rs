@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+use serde_json::json;
+
+use commons::env::var_or_default;
+use domain::auth::credentials::Credential;
+use domain::pipelines::{PipelineStatus, RegisterPipeline};
+use domain::schedules::Schedule;
+use persist::db_client::DbClient;
+
+use crate::services::{pipelines, schedules};
+
+const LOCKS_INDEX: &str = "locks";
+
+pub async fn schedule(db: &DbClient) {
+    let timer = var_or_default("SCHEDULER_PIPELINES_SCHEDULE", 30);
+    let mut task = tokio::time::interval(Duration::from_secs(timer));
+
+    loop {
+        task.tick().await;
+        log::trace!("running `pipelines::schedule` scheduled task");
+        if let Ok(entries) = schedules::get_all(db, &Credential::System, &None, &None).await {
+            for entry in entries {
+                if is_due(&entry) {
+                    run_schedule(db, &entry, timer).await;
+                }
+            }
+        }
+    }
+}
+
+fn is_due(schedule: &Schedule) -> bool {
+    schedule.is_due(&chrono::Utc::now()).unwrap_or_else(|err| {
+        log::warn!("schedule `{}` skipped: {err}", schedule.id);
+        false
+    })
+}
+
+async fn run_schedule(db: &DbClient, schedule: &Schedule, ttl: u64) {
+    // only one server replica may handle a given schedule run
+    let lock = format!("schedule-{}", schedule.id);
+    if !db.lock(LOCKS_INDEX, &lock, ttl).await.unwrap_or(false) {
+        return;
+    }
+
+    // re-read the schedule - it may have been triggered by another replica meanwhile
+    let schedule = match schedules::get_by_id(db, &Credential::System, &schedule.id).await {
+        Ok(Some(schedule)) if is_due(&schedule) => schedule,
+        _ => return,
+    };
+
+    if schedule.skip_if_running && is_running(db, &schedule).await {
+        log::debug!(
+            "schedule `{}` skipped: previous pipeline still in progress",
+            schedule.id
+        );
+    } else {
+        let register = RegisterPipeline {
+            branch: schedule.clone().branch,
+            job_id: schedule.clone().job_id,
+            commit: None,
+        };
+        match pipelines::create(db, &Credential::System, register).await {
+            Ok(id) => log::debug!("schedule `{}` registered pipeline `{id}`", schedule.id),
+            Err(err) => log::warn!("schedule `{}` failed: {err}", schedule.id),
+        }
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let _ = schedules::set_last_run(db, &Credential::System, &schedule.id, &now).await;
+}
+
+async fn is_running(db: &DbClient, schedule: &Schedule) -> bool {
+    let filter = json!({ "job_id": { "equals": schedule.job_id } });
+    pipelines::get_all(db, &Credential::System, &Some(filter), &None)
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|pipe| schedule.branch.is_none() || schedule.branch.as_ref() == Some(&pipe.branch))
+        .any(|pipe| {
+            [
+                PipelineStatus::Defined,
+                PipelineStatus::Assigned,
+                PipelineStatus::InProgress,
+            ]
+            .contains(&pipe.status)
+        })
+}
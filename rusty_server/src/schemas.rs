@@ -0,0 +1,25 @@
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::Json;
+use serde_json::Value;
+
+use domain::templates::schema::{json_schema, TEMPLATE_SCHEMA_VERSION};
+
+type SchemaResponse = Result<Json<Value>, (StatusCode, String)>;
+
+pub async fn pipeline_template_handler() -> Json<Value> {
+    log::debug!("handling `schemas::pipeline_template` request");
+    Json(json_schema())
+}
+
+pub async fn pipeline_template_version_handler(Path(version): Path<String>) -> SchemaResponse {
+    log::debug!("handling `schemas::pipeline_template` request for version `{version}`");
+    if version.trim_end_matches(".json") == TEMPLATE_SCHEMA_VERSION {
+        Ok(Json(json_schema()))
+    } else {
+        Err((
+            StatusCode::NOT_FOUND,
+            format!("unknown template schema version: {version}"),
+        ))
+    }
+}
@@ -12,7 +12,7 @@ use domain::commons::ws::ExtraWSData;
 
 use crate::gql::RustySchema;
 
-fn extract_auth_header(headers: &HeaderMap) -> Credential {
+pub(crate) fn extract_auth_header(headers: &HeaderMap) -> Credential {
     let Some(value) = headers.get("Authorization") else {
         return Credential::None;
     };
@@ -0,0 +1,184 @@
+use serde_json::{json, Value};
+
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::badges::{BadgeSettings, RegisterBadgeSettings};
+use domain::commons::search::SearchOptions;
+use domain::jobs::JobModel;
+use domain::pipelines::PipelineStatus;
+use persist::db_client::DbClient;
+
+use crate::services::shared::get_username_claim;
+use crate::services::{coverage, jobs, pipelines, projects, shared};
+
+const BADGES_INDEX: &str = "badge_settings";
+
+// query
+
+pub async fn get_all(
+    db: &DbClient,
+    cred: &Credential,
+    filter: &Option<Value>,
+    options: &Option<SearchOptions>,
+) -> Result<Vec<BadgeSettings>, RustyError> {
+    let entries = shared::get_all::<BadgeSettings>(db, BADGES_INDEX, filter, options).await?;
+    let mut filtered = vec![];
+    let username = get_username_claim(cred)?;
+    for entry in entries {
+        if auth::authorize(
+            db,
+            &username,
+            &format!("PROJECTS:READ:ID[{}]", entry.project_id),
+        )
+        .await
+        .is_ok()
+        {
+            filtered.push(entry);
+        }
+    }
+    Ok(filtered)
+}
+
+pub async fn get_by_id(
+    db: &DbClient,
+    cred: &Credential,
+    id: &str,
+) -> Result<Option<BadgeSettings>, RustyError> {
+    if let Some(settings) = shared::get_by_id::<BadgeSettings>(db, BADGES_INDEX, id).await? {
+        auth::authorize(
+            db,
+            &get_username_claim(cred)?,
+            &format!("PROJECTS:READ:ID[{}]", settings.project_id),
+        )
+        .await?;
+        Ok(Some(settings))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Check if the badges of a job are served without authentication.
+pub async fn is_public(db: &DbClient, job_id: &str) -> Result<bool, RustyError> {
+    let filter = json!({ "job_id": { "equals": job_id } });
+    Ok(get_all(db, &Credential::System, &Some(filter), &None)
+        .await?
+        .first()
+        .is_some_and(|settings| settings.public))
+}
+
+/// Status of the latest pipeline of a job on a branch - the project main branch by default.
+pub async fn get_status(
+    db: &DbClient,
+    cred: &Credential,
+    job_id: &str,
+    branch: Option<String>,
+) -> Result<Option<PipelineStatus>, RustyError> {
+    let job = get_job(db, cred, job_id).await?;
+    let branch = get_branch(db, cred, &job, branch).await?;
+    let filter = json!({
+        "job_id": { "equals": job.id },
+        "branch": { "equals": branch },
+    });
+    Ok(pipelines::get_all(db, cred, &Some(filter), &None)
+        .await?
+        .into_iter()
+        .max_by_key(|pipeline| pipeline.number)
+        .map(|pipeline| pipeline.status))
+}
+
+/// Coverage of the latest successful pipeline of a job on a branch with a coverage report.
+pub async fn get_coverage(
+    db: &DbClient,
+    cred: &Credential,
+    job_id: &str,
+    branch: Option<String>,
+) -> Result<Option<f64>, RustyError> {
+    let job = get_job(db, cred, job_id).await?;
+    let branch = get_branch(db, cred, &job, branch).await?;
+    let filter = json!({
+        "job_id": { "equals": job.id },
+        "branch": { "equals": branch },
+    });
+    let successful = pipelines::get_all(db, cred, &Some(filter), &None)
+        .await?
+        .into_iter()
+        .filter(|pipeline| pipeline.status == PipelineStatus::Success)
+        .map(|pipeline| pipeline.id)
+        .collect::<Vec<String>>();
+    let trend = coverage::get_trend(db, cred, &job.id, &branch, Some(usize::MAX)).await?;
+    Ok(trend
+        .into_iter()
+        .rev()
+        .find(|coverage| successful.contains(&coverage.pipeline_id))
+        .map(|coverage| coverage.percent))
+}
+
+async fn get_job(db: &DbClient, cred: &Credential, job_id: &str) -> Result<JobModel, RustyError> {
+    jobs::get_by_id(db, cred, job_id, &None, &[])
+        .await?
+        .ok_or_else(|| RustyError::ValidationError("job not found".to_string()))
+}
+
+async fn get_branch(
+    db: &DbClient,
+    cred: &Credential,
+    job: &JobModel,
+    branch: Option<String>,
+) -> Result<String, RustyError> {
+    match branch {
+        Some(branch) => Ok(branch),
+        None => projects::get_by_id(db, cred, &job.project_id, &None, &[])
+            .await?
+            .map(|project| project.main_branch)
+            .ok_or_else(|| RustyError::ValidationError("project not found".to_string())),
+    }
+}
+
+// mutate
+
+pub async fn create(
+    db: &DbClient,
+    cred: &Credential,
+    settings: RegisterBadgeSettings,
+) -> Result<String, RustyError> {
+    if let Some(job) = jobs::get_by_id(db, cred, &settings.job_id, &None, &[]).await? {
+        shared::check_project_write_permission(db, cred, &job.project_id).await?;
+        let filter = json!({ "job_id": { "equals": job.id } });
+        if !get_all(db, cred, &Some(filter), &None).await?.is_empty() {
+            return Err(RustyError::ValidationError(
+                "badge settings for the job already exist".to_string(),
+            ));
+        }
+        shared::create(db, BADGES_INDEX, settings, |s| {
+            BadgeSettings::new(&s, &job.project_id)
+        })
+        .await
+    } else {
+        Err(RustyError::ValidationError("job not found".to_string()))
+    }
+}
+
+pub async fn delete_by_id(db: &DbClient, cred: &Credential, id: &str) -> Result<u64, RustyError> {
+    if let Some(settings) = get_by_id(db, cred, id).await? {
+        shared::check_project_write_permission(db, cred, &settings.project_id).await?;
+        shared::delete_by_id(db, BADGES_INDEX, id).await
+    } else {
+        Ok(0)
+    }
+}
+
+pub async fn delete_many(
+    db: &DbClient,
+    cred: &Credential,
+    filter: &Value,
+) -> Result<u64, RustyError> {
+    let entries = get_all(db, cred, &Some(filter.clone()), &None).await?;
+    for settings in &entries {
+        delete_by_id(db, cred, &settings.id).await?;
+    }
+    Ok(entries.len() as u64)
+}
+
+pub async fn delete_all(db: &DbClient) -> Result<u64, RustyError> {
+    shared::delete_all(db, BADGES_INDEX).await
+}
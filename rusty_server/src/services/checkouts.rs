@@ -0,0 +1,124 @@
+use serde_json::{json, Value};
+
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::checkouts::{Checkout, CheckoutSettings, RegisterCheckoutSettings};
+use domain::commons::search::SearchOptions;
+use persist::db_client::DbClient;
+
+use crate::services::shared::get_username_claim;
+use crate::services::{projects, shared};
+
+const CHECKOUTS_INDEX: &str = "checkout_settings";
+
+// query
+
+pub async fn get_all(
+    db: &DbClient,
+    cred: &Credential,
+    filter: &Option<Value>,
+    options: &Option<SearchOptions>,
+) -> Result<Vec<CheckoutSettings>, RustyError> {
+    let entries = shared::get_all::<CheckoutSettings>(db, CHECKOUTS_INDEX, filter, options).await?;
+    let mut filtered = vec![];
+    let username = get_username_claim(cred)?;
+    for entry in entries {
+        if auth::authorize(
+            db,
+            &username,
+            &format!("PROJECTS:READ:ID[{}]", entry.project_id),
+        )
+        .await
+        .is_ok()
+        {
+            filtered.push(entry);
+        }
+    }
+    Ok(filtered)
+}
+
+pub async fn get_by_id(
+    db: &DbClient,
+    cred: &Credential,
+    id: &str,
+) -> Result<Option<CheckoutSettings>, RustyError> {
+    if let Some(settings) = shared::get_by_id::<CheckoutSettings>(db, CHECKOUTS_INDEX, id).await? {
+        auth::authorize(
+            db,
+            &get_username_claim(cred)?,
+            &format!("PROJECTS:READ:ID[{}]", settings.project_id),
+        )
+        .await?;
+        Ok(Some(settings))
+    } else {
+        Ok(None)
+    }
+}
+
+// secrets are handed out to agents only
+pub async fn get_checkout(
+    db: &DbClient,
+    cred: &Credential,
+    project_id: &str,
+) -> Result<Option<Checkout>, RustyError> {
+    auth::authorize(db, &get_username_claim(cred)?, "AGENTS:WRITE").await?;
+    let filter = json!({ "project_id": { "equals": project_id } });
+    Ok(get_all(db, cred, &Some(filter), &None)
+        .await?
+        .first()
+        .map(Checkout::from))
+}
+
+// mutate
+
+pub async fn create(
+    db: &DbClient,
+    cred: &Credential,
+    settings: RegisterCheckoutSettings,
+) -> Result<String, RustyError> {
+    if settings.ssh_key.is_some() && settings.token.is_some() {
+        return Err(RustyError::ValidationError(
+            "checkout credentials are either an ssh key or a token".to_string(),
+        ));
+    }
+    if let Some(project) = projects::get_by_id(db, cred, &settings.project_id, &None, &[]).await? {
+        shared::check_project_write_permission(db, cred, &project.id).await?;
+        let filter = json!({ "project_id": { "equals": project.id } });
+        if !get_all(db, cred, &Some(filter), &None).await?.is_empty() {
+            return Err(RustyError::ValidationError(
+                "checkout settings for the project already exist".to_string(),
+            ));
+        }
+        shared::create(db, CHECKOUTS_INDEX, settings, |s| {
+            CheckoutSettings::from(&s)
+        })
+        .await
+    } else {
+        Err(RustyError::ValidationError("project not found".to_string()))
+    }
+}
+
+pub async fn delete_by_id(db: &DbClient, cred: &Credential, id: &str) -> Result<u64, RustyError> {
+    if let Some(settings) = get_by_id(db, cred, id).await? {
+        shared::check_project_write_permission(db, cred, &settings.project_id).await?;
+        shared::delete_by_id(db, CHECKOUTS_INDEX, id).await
+    } else {
+        Ok(0)
+    }
+}
+
+pub async fn delete_many(
+    db: &DbClient,
+    cred: &Credential,
+    filter: &Value,
+) -> Result<u64, RustyError> {
+    let entries = get_all(db, cred, &Some(filter.clone()), &None).await?;
+    for settings in &entries {
+        delete_by_id(db, cred, &settings.id).await?;
+    }
+    Ok(entries.len() as u64)
+}
+
+pub async fn delete_all(db: &DbClient) -> Result<u64, RustyError> {
+    shared::delete_all(db, CHECKOUTS_INDEX).await
+}
@@ -0,0 +1,157 @@
+use serde_json::{json, Value};
+
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::commons::search::SearchOptions;
+use domain::coverage::{
+    delta, new_report, pipeline_coverage, CoverageDelta, CoverageReport, FileCoverage,
+    PipelineCoverage,
+};
+use domain::pipelines::PipelineStatus;
+use domain::RustyDomainItem;
+use persist::db_client::DbClient;
+
+use crate::services::shared::get_username_claim;
+use crate::services::{jobs, pipelines, projects, shared};
+
+const COVERAGE_INDEX: &str = "coverage_reports";
+
+// query
+
+pub async fn get_all(
+    db: &DbClient,
+    cred: &Credential,
+    filter: &Option<Value>,
+    options: &Option<SearchOptions>,
+) -> Result<Vec<CoverageReport>, RustyError> {
+    let entries = shared::get_all::<CoverageReport>(db, COVERAGE_INDEX, filter, options).await?;
+    let mut filtered = vec![];
+    let username = get_username_claim(cred)?;
+    for entry in entries {
+        if auth::authorize(
+            db,
+            &username,
+            &format!("PROJECTS:READ:ID[{}]", entry.project_id),
+        )
+        .await
+        .is_ok()
+        {
+            filtered.push(entry);
+        }
+    }
+    Ok(filtered)
+}
+
+/// Coverage of the latest pipelines of a job on a branch, the oldest first.
+pub async fn get_trend(
+    db: &DbClient,
+    cred: &Credential,
+    job_id: &str,
+    branch: &str,
+    limit: Option<usize>,
+) -> Result<Vec<PipelineCoverage>, RustyError> {
+    let filter = json!({
+        "job_id": { "equals": job_id },
+        "branch": { "equals": branch },
+    });
+    let entries = get_all(db, cred, &Some(filter), &None).await?;
+    let mut trend = pipeline_coverage(&entries);
+    let limit = limit.unwrap_or(20);
+    if trend.len() > limit {
+        trend.drain(..trend.len() - limit);
+    }
+    Ok(trend)
+}
+
+/// Coverage change of a pipeline against the last successful pipeline of the project main branch.
+pub async fn get_delta(
+    db: &DbClient,
+    cred: &Credential,
+    pipeline_id: &str,
+) -> Result<Option<CoverageDelta>, RustyError> {
+    let filter = json!({ "pipeline_id": { "equals": pipeline_id } });
+    let entries = get_all(db, cred, &Some(filter), &None).await?;
+    let Some(coverage) = pipeline_coverage(&entries).pop() else {
+        return Ok(None);
+    };
+    let Some(job) = jobs::get_by_id(db, cred, &entries[0].job_id, &None, &[]).await? else {
+        return Ok(Some(delta(coverage, None)));
+    };
+    let Some(project) = projects::get_by_id(db, cred, &job.project_id, &None, &[]).await? else {
+        return Ok(Some(delta(coverage, None)));
+    };
+
+    let filter = json!({
+        "job_id": { "equals": job.id },
+        "branch": { "equals": project.main_branch },
+    });
+    let successful = pipelines::get_all(db, cred, &Some(filter.clone()), &None)
+        .await?
+        .into_iter()
+        .filter(|pipeline| pipeline.status == PipelineStatus::Success && pipeline.id != pipeline_id)
+        .map(|pipeline| pipeline.id)
+        .collect::<Vec<String>>();
+    let base = pipeline_coverage(&get_all(db, cred, &Some(filter), &None).await?)
+        .into_iter()
+        .rev()
+        .find(|base| successful.contains(&base.pipeline_id));
+    Ok(Some(delta(coverage, base)))
+}
+
+// mutate
+
+/// Store the coverage report uploaded by the agent running a pipeline stage.
+pub async fn create(
+    db: &DbClient,
+    cred: &Credential,
+    pipeline_id: &str,
+    agent_id: &str,
+    stage: &str,
+    files: Vec<FileCoverage>,
+) -> Result<String, RustyError> {
+    let Some(pipeline) = pipelines::get_by_id(db, cred, pipeline_id).await? else {
+        let message = "`coverage::register` - pipeline not found".to_string();
+        log::debug!("{message}");
+        return Err(RustyError::AsyncGraphqlError(message));
+    };
+    let Some(job) = jobs::get_by_id(db, cred, &pipeline.job_id, &None, &[]).await? else {
+        return Err(RustyError::UnauthorizedError);
+    };
+    shared::check_project_write_permission(db, cred, &job.project_id).await?;
+    if pipeline.agent_id.as_deref() != Some(agent_id) {
+        let message = "`coverage::register` - cannot register".to_string();
+        log::debug!("{message}");
+        return Err(RustyError::AsyncGraphqlError(message));
+    }
+
+    let report = new_report(
+        &CoverageReport::generate_id(),
+        stage,
+        files,
+        &pipeline,
+        &job.project_id,
+    );
+    db.create(COVERAGE_INDEX, &report.to_value()?)
+        .await
+        .map_err(|err| {
+            log::error!("`{COVERAGE_INDEX}::create`: {err}");
+            err
+        })
+}
+
+pub async fn delete_many(
+    db: &DbClient,
+    cred: &Credential,
+    filter: &Value,
+) -> Result<u64, RustyError> {
+    let entries = get_all(db, cred, &Some(filter.clone()), &None).await?;
+    for report in &entries {
+        shared::check_project_write_permission(db, cred, &report.project_id).await?;
+        shared::delete_by_id(db, COVERAGE_INDEX, &report.id).await?;
+    }
+    Ok(entries.len() as u64)
+}
+
+pub async fn delete_all(db: &DbClient) -> Result<u64, RustyError> {
+    shared::delete_all(db, COVERAGE_INDEX).await
+}
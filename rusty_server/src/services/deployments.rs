@@ -0,0 +1,169 @@
+use serde_json::{json, Value};
+
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::commons::search::SearchOptions;
+use domain::deployments::{environments, Deployment, ProjectEnvironment};
+use domain::jobs::JobModel;
+use domain::pipelines::Pipeline;
+use domain::templates::pipeline::PipelineTemplate;
+use domain::templates::revision::TemplateRevision;
+use domain::RustyDomainItem;
+use persist::db_client::DbClient;
+
+use crate::services::shared::get_username_claim;
+use crate::services::{pipelines, shared};
+
+const DEPLOYMENTS_INDEX: &str = "deployments";
+
+// query
+
+pub async fn get_all(
+    db: &DbClient,
+    cred: &Credential,
+    filter: &Option<Value>,
+    options: &Option<SearchOptions>,
+) -> Result<Vec<Deployment>, RustyError> {
+    let entries = shared::get_all::<Deployment>(db, DEPLOYMENTS_INDEX, filter, options).await?;
+    let mut filtered = vec![];
+    let username = get_username_claim(cred)?;
+    for entry in entries {
+        if auth::authorize(
+            db,
+            &username,
+            &format!("PROJECTS:READ:ID[{}]", entry.project_id),
+        )
+        .await
+        .is_ok()
+        {
+            filtered.push(entry);
+        }
+    }
+    Ok(filtered)
+}
+
+pub async fn get_by_id(
+    db: &DbClient,
+    cred: &Credential,
+    id: &str,
+) -> Result<Option<Deployment>, RustyError> {
+    if let Some(deployment) = shared::get_by_id::<Deployment>(db, DEPLOYMENTS_INDEX, id).await? {
+        auth::authorize(
+            db,
+            &get_username_claim(cred)?,
+            &format!("PROJECTS:READ:ID[{}]", deployment.project_id),
+        )
+        .await?;
+        Ok(Some(deployment))
+    } else {
+        Ok(None)
+    }
+}
+
+pub async fn get_environments(
+    db: &DbClient,
+    cred: &Credential,
+    project_id: &str,
+) -> Result<Vec<ProjectEnvironment>, RustyError> {
+    auth::authorize(
+        db,
+        &get_username_claim(cred)?,
+        &format!("PROJECTS:READ:ID[{project_id}]"),
+    )
+    .await?;
+    let filter = json!({ "project_id": { "equals": project_id } });
+    let entries = get_all(db, cred, &Some(filter), &None).await?;
+    Ok(environments(&entries))
+}
+
+// mutate
+
+/// Record a deployment for a successful pipeline stage with an environment.
+///
+/// The stage is looked up in the template revision run by the pipeline.
+pub async fn record(
+    db: &DbClient,
+    pipeline: &Pipeline,
+    job: &JobModel,
+    stage: &str,
+) -> Result<Option<String>, RustyError> {
+    let template = match &pipeline.revision {
+        Some(revision) => {
+            shared::get_by_id::<TemplateRevision>(db, "job_template_revisions", revision)
+                .await?
+                .map_or_else(|| job.template.clone(), |revision| revision.template)
+        }
+        None => job.template.clone(),
+    };
+    let template = PipelineTemplate::from_yaml(&template)?;
+    let Some(environment) = template
+        .stages
+        .get(stage)
+        .and_then(|stage| stage.environment.clone())
+    else {
+        return Ok(None);
+    };
+
+    // stage statuses may be reported more than once
+    let filter = json!({
+        "pipeline_id": { "equals": pipeline.id },
+        "stage": { "equals": stage },
+    });
+    if shared::get_one::<Deployment>(db, DEPLOYMENTS_INDEX, &filter)
+        .await?
+        .is_some()
+    {
+        return Ok(None);
+    }
+
+    let deployment = Deployment {
+        id: Deployment::generate_id(),
+        environment: environment.name,
+        url: environment.url,
+        date: chrono::Utc::now().to_rfc3339(),
+        branch: pipeline.branch.clone(),
+        commit: pipeline.commit.clone(),
+        stage: stage.to_string(),
+        deployed_by: pipeline.triggered_by.clone(),
+        pipeline_id: pipeline.id.clone(),
+        pipeline_number: pipeline.number,
+        job_id: job.id.clone(),
+        project_id: job.project_id.clone(),
+    };
+    let id = db
+        .create(DEPLOYMENTS_INDEX, &deployment.to_value()?)
+        .await
+        .map_err(|err| {
+            log::error!("`{DEPLOYMENTS_INDEX}::create`: {err}");
+            err
+        })?;
+    Ok(Some(id))
+}
+
+/// Register a new pipeline running the template and commit of a deployment.
+pub async fn redeploy(db: &DbClient, cred: &Credential, id: &str) -> Result<String, RustyError> {
+    if let Some(deployment) = get_by_id(db, cred, id).await? {
+        pipelines::redeploy(db, cred, &deployment.pipeline_id).await
+    } else {
+        Err(RustyError::ValidationError(
+            "deployment not found".to_string(),
+        ))
+    }
+}
+
+pub async fn delete_many(
+    db: &DbClient,
+    cred: &Credential,
+    filter: &Value,
+) -> Result<u64, RustyError> {
+    let entries = get_all(db, cred, &Some(filter.clone()), &None).await?;
+    for deployment in &entries {
+        shared::check_project_write_permission(db, cred, &deployment.project_id).await?;
+        shared::delete_by_id(db, DEPLOYMENTS_INDEX, &deployment.id).await?;
+    }
+    Ok(entries.len() as u64)
+}
+
+pub async fn delete_all(db: &DbClient) -> Result<u64, RustyError> {
+    shared::delete_all(db, DEPLOYMENTS_INDEX).await
+}
@@ -0,0 +1,197 @@
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tokio::time::sleep;
+
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::commons::search::SearchOptions;
+use domain::event_hooks::{DeliveryStatus, EventHook, EventHookDelivery, RegisterEventHook};
+use domain::RustyDomainItem;
+use persist::db_client::DbClient;
+
+use crate::event_hooks;
+use crate::services::shared::get_username_claim;
+use crate::services::{projects, shared};
+
+const HOOKS_INDEX: &str = "event_hooks";
+const DELIVERIES_INDEX: &str = "event_hook_deliveries";
+
+// query
+
+pub async fn get_all(
+    db: &DbClient,
+    cred: &Credential,
+    filter: &Option<Value>,
+    options: &Option<SearchOptions>,
+) -> Result<Vec<EventHook>, RustyError> {
+    let entries = shared::get_all::<EventHook>(db, HOOKS_INDEX, filter, options).await?;
+    let username = get_username_claim(cred)?;
+    Ok(entries
+        .into_iter()
+        .filter(|entry| is_owner(&username, entry))
+        .collect())
+}
+
+pub async fn get_by_id(
+    db: &DbClient,
+    cred: &Credential,
+    id: &str,
+) -> Result<Option<EventHook>, RustyError> {
+    if let Some(hook) = shared::get_by_id::<EventHook>(db, HOOKS_INDEX, id).await? {
+        if is_owner(&get_username_claim(cred)?, &hook) {
+            Ok(Some(hook))
+        } else {
+            Err(RustyError::UnauthorizedError)
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+pub async fn get_deliveries(
+    db: &DbClient,
+    cred: &Credential,
+    hook_id: &str,
+    options: &Option<SearchOptions>,
+) -> Result<Vec<EventHookDelivery>, RustyError> {
+    if get_by_id(db, cred, hook_id).await?.is_some() {
+        let filter = json!({ "hook_id": { "equals": hook_id } });
+        shared::get_all::<EventHookDelivery>(db, DELIVERIES_INDEX, &Some(filter), options).await
+    } else {
+        Ok(vec![])
+    }
+}
+
+pub async fn get_pending_deliveries(db: &DbClient) -> Result<Vec<EventHookDelivery>, RustyError> {
+    let filter = json!({ "status": { "equals": "Pending" } });
+    shared::get_all::<EventHookDelivery>(db, DELIVERIES_INDEX, &Some(filter), &None).await
+}
+
+fn is_owner(username: &str, hook: &EventHook) -> bool {
+    username == "SYSTEM" || hook.owner == username
+}
+
+// mutate
+
+pub async fn create(
+    db: &DbClient,
+    cred: &Credential,
+    hook: RegisterEventHook,
+) -> Result<String, RustyError> {
+    if let Some(project_id) = &hook.project_id {
+        if projects::get_by_id(db, cred, project_id, &None, &[])
+            .await?
+            .is_none()
+        {
+            return Err(RustyError::ValidationError("project not found".to_string()));
+        }
+    }
+    let owner = get_username_claim(cred)?;
+    shared::create(db, HOOKS_INDEX, hook, |r| EventHook {
+        owner,
+        ..EventHook::from(&r)
+    })
+    .await
+}
+
+pub async fn create_delivery(
+    db: &DbClient,
+    delivery: &EventHookDelivery,
+) -> Result<String, RustyError> {
+    db.create(DELIVERIES_INDEX, &delivery.to_value()?)
+        .await
+        .map_err(|err| {
+            log::error!("`{DELIVERIES_INDEX}::create`: {err}");
+            err
+        })
+}
+
+pub async fn deliver(
+    db: &DbClient,
+    hook: &EventHook,
+    delivery: &EventHookDelivery,
+    retries: u32,
+    delay: Duration,
+) -> Result<EventHookDelivery, RustyError> {
+    let mut delivery = delivery.clone();
+    let mut attempt = 0;
+    loop {
+        delivery.attempts += 1;
+        match event_hooks::send(hook, &delivery).await {
+            Ok(()) => {
+                delivery.status = DeliveryStatus::Delivered;
+                delivery.error = None;
+                break;
+            }
+            Err(err) if attempt >= retries => {
+                log::warn!("`event_hooks`: delivery `{}` failed: {err}", delivery.id);
+                delivery.status = DeliveryStatus::Failed;
+                delivery.error = Some(err.to_string());
+                break;
+            }
+            Err(err) => {
+                log::debug!(
+                    "`event_hooks`: delivery `{}` failed: {err}. Retrying...",
+                    delivery.id
+                );
+                attempt += 1;
+                sleep(delay).await;
+            }
+        }
+    }
+    db.update(DELIVERIES_INDEX, &delivery.id, &delivery.to_value()?)
+        .await?;
+    Ok(delivery)
+}
+
+pub async fn redeliver(
+    db: &DbClient,
+    cred: &Credential,
+    delivery_id: &str,
+) -> Result<EventHookDelivery, RustyError> {
+    let Some(delivery) =
+        shared::get_by_id::<EventHookDelivery>(db, DELIVERIES_INDEX, delivery_id).await?
+    else {
+        return Err(RustyError::ValidationError(
+            "delivery not found".to_string(),
+        ));
+    };
+    let Some(hook) = get_by_id(db, cred, &delivery.hook_id).await? else {
+        return Err(RustyError::ValidationError(
+            "event hook not found".to_string(),
+        ));
+    };
+    deliver(db, &hook, &delivery, 0, Duration::ZERO).await
+}
+
+pub async fn delete_by_id(db: &DbClient, cred: &Credential, id: &str) -> Result<u64, RustyError> {
+    if get_by_id(db, cred, id).await?.is_some() {
+        let filter = json!({ "hook_id": { "equals": id } });
+        for delivery in
+            shared::get_all::<EventHookDelivery>(db, DELIVERIES_INDEX, &Some(filter), &None).await?
+        {
+            shared::delete_by_id(db, DELIVERIES_INDEX, &delivery.id).await?;
+        }
+        shared::delete_by_id(db, HOOKS_INDEX, id).await
+    } else {
+        Ok(0)
+    }
+}
+
+pub async fn delete_many(
+    db: &DbClient,
+    cred: &Credential,
+    filter: &Value,
+) -> Result<u64, RustyError> {
+    let hooks = get_all(db, cred, &Some(filter.clone()), &None).await?;
+    for hook in &hooks {
+        delete_by_id(db, cred, &hook.id).await?;
+    }
+    Ok(hooks.len() as u64)
+}
+
+pub async fn delete_all(db: &DbClient) -> Result<u64, RustyError> {
+    shared::delete_all(db, DELIVERIES_INDEX).await?;
+    shared::delete_all(db, HOOKS_INDEX).await
+}
@@ -0,0 +1,130 @@
+use serde_json::{json, Value};
+
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::commons::search::SearchOptions;
+use domain::job_triggers::{creates_cycle, JobTrigger, RegisterJobTrigger};
+use persist::db_client::DbClient;
+
+use crate::services::shared::get_username_claim;
+use crate::services::{jobs, shared};
+
+const JOB_TRIGGERS_INDEX: &str = "job_triggers";
+
+// query
+
+pub async fn get_all(
+    db: &DbClient,
+    cred: &Credential,
+    filter: &Option<Value>,
+    options: &Option<SearchOptions>,
+) -> Result<Vec<JobTrigger>, RustyError> {
+    let entries = shared::get_all::<JobTrigger>(db, JOB_TRIGGERS_INDEX, filter, options).await?;
+    let mut filtered = vec![];
+    let username = get_username_claim(cred)?;
+    for entry in entries {
+        if auth::authorize(
+            db,
+            &username,
+            &format!("PROJECTS:READ:ID[{}]", entry.project_id),
+        )
+        .await
+        .is_ok()
+        {
+            filtered.push(entry);
+        }
+    }
+    Ok(filtered)
+}
+
+pub async fn get_by_id(
+    db: &DbClient,
+    cred: &Credential,
+    id: &str,
+) -> Result<Option<JobTrigger>, RustyError> {
+    if let Some(trigger) = shared::get_by_id::<JobTrigger>(db, JOB_TRIGGERS_INDEX, id).await? {
+        auth::authorize(
+            db,
+            &get_username_claim(cred)?,
+            &format!("PROJECTS:READ:ID[{}]", trigger.project_id),
+        )
+        .await?;
+        Ok(Some(trigger))
+    } else {
+        Ok(None)
+    }
+}
+
+// mutate
+
+pub async fn create(
+    db: &DbClient,
+    cred: &Credential,
+    trigger: RegisterJobTrigger,
+) -> Result<String, RustyError> {
+    let Some(job) = jobs::get_by_id(db, cred, &trigger.job_id, &None, &[]).await? else {
+        return Err(RustyError::ValidationError("job not found".to_string()));
+    };
+    let Some(downstream) =
+        jobs::get_by_id(db, cred, &trigger.downstream_job_id, &None, &[]).await?
+    else {
+        return Err(RustyError::ValidationError(
+            "downstream job not found".to_string(),
+        ));
+    };
+    // the trigger registers pipelines of the downstream job
+    shared::check_project_write_permission(db, cred, &job.project_id).await?;
+    shared::check_project_write_permission(db, cred, &downstream.project_id).await?;
+
+    let triggers = shared::get_all::<JobTrigger>(db, JOB_TRIGGERS_INDEX, &None, &None).await?;
+    if creates_cycle(&triggers, &job.id, &downstream.id) {
+        return Err(RustyError::ValidationError(
+            "job trigger would start its upstream job again".to_string(),
+        ));
+    }
+    shared::create(db, JOB_TRIGGERS_INDEX, trigger, |t| {
+        JobTrigger::new(&t, &job.project_id)
+    })
+    .await
+}
+
+pub async fn delete_by_id(db: &DbClient, cred: &Credential, id: &str) -> Result<u64, RustyError> {
+    if let Some(trigger) = get_by_id(db, cred, id).await? {
+        shared::check_project_write_permission(db, cred, &trigger.project_id).await?;
+        shared::delete_by_id(db, JOB_TRIGGERS_INDEX, id).await
+    } else {
+        Ok(0)
+    }
+}
+
+pub async fn delete_many(
+    db: &DbClient,
+    cred: &Credential,
+    filter: &Value,
+) -> Result<u64, RustyError> {
+    let entries = get_all(db, cred, &Some(filter.clone()), &None).await?;
+    for trigger in &entries {
+        delete_by_id(db, cred, &trigger.id).await?;
+    }
+    Ok(entries.len() as u64)
+}
+
+/// Delete the triggers starting a job, or started by it.
+pub async fn delete_for_job(
+    db: &DbClient,
+    cred: &Credential,
+    job_id: &str,
+) -> Result<u64, RustyError> {
+    let upstream = delete_many(db, cred, &json!({ "job_id": { "equals": job_id } })).await?;
+    let downstream = delete_many(
+        db,
+        cred,
+        &json!({ "downstream_job_id": { "equals": job_id } }),
+    )
+    .await?;
+    Ok(upstream + downstream)
+}
+
+pub async fn delete_all(db: &DbClient) -> Result<u64, RustyError> {
+    shared::delete_all(db, JOB_TRIGGERS_INDEX).await
+}
@@ -4,12 +4,14 @@ use serde_json::{json, Value};
 use commons::errors::RustyError;
 use domain::auth::credentials::Credential;
 use domain::commons::search::{SearchOptions, SortOptions};
-use domain::jobs::{Job, JobModel, RegisterJob};
+use domain::jobs::{Job, JobModel, RegisterJob, UpdateJob};
 use domain::pipelines::Pipeline;
+use domain::schedules::Schedule;
+use domain::templates::pipeline::PipelineTemplate;
 use persist::db_client::DbClient;
 
 use crate::services::shared::{add_filter_field, get_username_claim, remove_filter_field};
-use crate::services::{pipelines, projects, shared};
+use crate::services::{badges, job_triggers, pipelines, projects, revisions, schedules, shared};
 
 const JOBS_INDEX: &str = "jobs";
 
@@ -49,6 +51,14 @@ pub async fn get_all(
             }
         }
     }
+
+    if inner.iter().map(|f| f.name()).any(|f| f == "schedules") {
+        for f in &mut filtered {
+            if let Ok(schedules) = get_schedules_for_job(db, cred, &f.id).await {
+                f.schedules = schedules;
+            }
+        }
+    }
     Ok(filtered)
 }
 
@@ -80,6 +90,11 @@ pub async fn get_by_id(
                 model.pipelines = pipelines;
             }
         }
+        if inner.iter().map(|f| f.name()).any(|f| f == "schedules") {
+            if let Ok(schedules) = get_schedules_for_job(db, cred, &model.id).await {
+                model.schedules = schedules;
+            }
+        }
         Ok(Some(model))
     } else {
         Ok(None)
@@ -105,26 +120,68 @@ async fn get_pipelines_for_job(
     .await
 }
 
+async fn get_schedules_for_job(
+    db: &DbClient,
+    cred: &Credential,
+    job_id: &str,
+) -> Result<Vec<Schedule>, RustyError> {
+    schedules::get_all(
+        db,
+        cred,
+        &Some(json!({ "job_id": { "equals": job_id } })),
+        &None,
+    )
+    .await
+}
+
 // mutate
 
 pub async fn create(
     db: &DbClient,
     cred: &Credential,
-    job: RegisterJob,
+    mut job: RegisterJob,
 ) -> Result<String, RustyError> {
+    job.template = PipelineTemplate::normalize(&job.template, None)?;
     if let Some(project) = projects::get_by_id(db, cred, &job.project_id, &None, &[]).await? {
         shared::check_project_write_permission(db, cred, &project.id).await?;
-        shared::create(db, JOBS_INDEX, job, |r| Job::from(&r)).await
+        let id = shared::create(db, JOBS_INDEX, job.clone(), |r| Job::from(&r)).await?;
+        revisions::record(db, cred, &id, &job.template).await?;
+        Ok(id)
     } else {
         Err(RustyError::ValidationError("project not found".to_string()))
     }
 }
 
+pub async fn update(
+    db: &DbClient,
+    cred: &Credential,
+    id: &str,
+    mut job: UpdateJob,
+) -> Result<String, RustyError> {
+    if let Some(template) = &job.template {
+        job.template = Some(PipelineTemplate::normalize(template, None)?);
+    }
+    if let Some(current) = get_by_id(db, cred, id, &None, &[]).await? {
+        shared::check_project_write_permission(db, cred, &current.project_id).await?;
+        let id = shared::update(db, JOBS_INDEX, id, job.clone(), |u, j: Job| u.apply(&j)).await?;
+        if let Some(template) = &job.template {
+            revisions::record(db, cred, &id, template).await?;
+        }
+        Ok(id)
+    } else {
+        Err(RustyError::ValidationError("job not found".to_string()))
+    }
+}
+
 pub async fn delete_by_id(db: &DbClient, cred: &Credential, id: &str) -> Result<u64, RustyError> {
     if let Some(job) = get_by_id(db, cred, id, &None, &[]).await? {
         shared::check_project_write_permission(db, cred, &job.project_id).await?;
     }
     pipelines::delete_many(db, cred, &json!({ "job_id": { "equals": id } })).await?;
+    schedules::delete_many(db, cred, &json!({ "job_id": { "equals": id } })).await?;
+    badges::delete_many(db, cred, &json!({ "job_id": { "equals": id } })).await?;
+    job_triggers::delete_for_job(db, cred, id).await?;
+    revisions::delete_many(db, id).await?;
     shared::delete_by_id(db, JOBS_INDEX, id).await
 }
 
@@ -148,6 +205,15 @@ pub async fn delete_all(db: &DbClient) -> Result<u64, RustyError> {
             &json!({ "job_id": { "equals": job.id } }),
         )
         .await?;
+        schedules::delete_many(
+            db,
+            &Credential::System,
+            &json!({ "job_id": { "equals": job.id } }),
+        )
+        .await?;
     }
+    badges::delete_all(db).await?;
+    job_triggers::delete_all(db).await?;
+    revisions::delete_all(db).await?;
     shared::delete_all(db, JOBS_INDEX).await
 }
@@ -1,8 +1,21 @@
 pub mod agents;
+pub mod badges;
+pub mod checkouts;
+pub mod coverage;
+pub mod deployments;
+pub mod event_hooks;
+pub mod job_triggers;
 pub mod jobs;
+pub mod notifications;
 pub mod pipelines;
 pub mod project_groups;
 pub mod projects;
+pub mod registries;
+pub mod reporters;
+pub mod revisions;
 pub mod roles;
+pub mod schedules;
 pub mod shared;
+pub mod test_reports;
 pub mod users;
+pub mod webhooks;
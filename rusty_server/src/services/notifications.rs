@@ -0,0 +1,140 @@
+use serde_json::{json, Value};
+
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::commons::search::SearchOptions;
+use domain::notifications::{Notification, NotificationDelivery, RegisterNotification};
+use domain::RustyDomainItem;
+use persist::db_client::DbClient;
+
+use crate::services::shared::get_username_claim;
+use crate::services::{projects, shared};
+
+const NOTIFICATIONS_INDEX: &str = "notifications";
+const DELIVERIES_INDEX: &str = "notification_deliveries";
+
+// query
+
+pub async fn get_all(
+    db: &DbClient,
+    cred: &Credential,
+    filter: &Option<Value>,
+    options: &Option<SearchOptions>,
+) -> Result<Vec<Notification>, RustyError> {
+    let entries = shared::get_all::<Notification>(db, NOTIFICATIONS_INDEX, filter, options).await?;
+    let mut filtered = vec![];
+    let username = get_username_claim(cred)?;
+    for entry in entries {
+        if auth::authorize(
+            db,
+            &username,
+            &format!("PROJECTS:READ:ID[{}]", entry.project_id),
+        )
+        .await
+        .is_ok()
+        {
+            filtered.push(entry);
+        }
+    }
+    Ok(filtered)
+}
+
+pub async fn get_by_id(
+    db: &DbClient,
+    cred: &Credential,
+    id: &str,
+) -> Result<Option<Notification>, RustyError> {
+    if let Some(notification) =
+        shared::get_by_id::<Notification>(db, NOTIFICATIONS_INDEX, id).await?
+    {
+        auth::authorize(
+            db,
+            &get_username_claim(cred)?,
+            &format!("PROJECTS:READ:ID[{}]", notification.project_id),
+        )
+        .await?;
+        Ok(Some(notification))
+    } else {
+        Ok(None)
+    }
+}
+
+pub async fn get_deliveries(
+    db: &DbClient,
+    cred: &Credential,
+    notification_id: &str,
+    options: &Option<SearchOptions>,
+) -> Result<Vec<NotificationDelivery>, RustyError> {
+    if get_by_id(db, cred, notification_id).await?.is_some() {
+        let filter = json!({ "notification_id": { "equals": notification_id } });
+        shared::get_all::<NotificationDelivery>(db, DELIVERIES_INDEX, &Some(filter), options).await
+    } else {
+        Ok(vec![])
+    }
+}
+
+// mutate
+
+pub async fn create(
+    db: &DbClient,
+    cred: &Credential,
+    notification: RegisterNotification,
+) -> Result<String, RustyError> {
+    notification.validate_target()?;
+    if let Some(project) =
+        projects::get_by_id(db, cred, &notification.project_id, &None, &[]).await?
+    {
+        shared::check_project_write_permission(db, cred, &project.id).await?;
+        shared::create(db, NOTIFICATIONS_INDEX, notification, |r| {
+            Notification::from(&r)
+        })
+        .await
+    } else {
+        Err(RustyError::ValidationError("project not found".to_string()))
+    }
+}
+
+pub async fn record_delivery(
+    db: &DbClient,
+    delivery: &NotificationDelivery,
+) -> Result<String, RustyError> {
+    db.create(DELIVERIES_INDEX, &delivery.to_value()?)
+        .await
+        .map_err(|err| {
+            log::error!("`{DELIVERIES_INDEX}::create`: {err}");
+            err
+        })
+}
+
+pub async fn delete_by_id(db: &DbClient, cred: &Credential, id: &str) -> Result<u64, RustyError> {
+    if let Some(notification) = get_by_id(db, cred, id).await? {
+        shared::check_project_write_permission(db, cred, &notification.project_id).await?;
+        let filter = json!({ "notification_id": { "equals": id } });
+        for delivery in
+            shared::get_all::<NotificationDelivery>(db, DELIVERIES_INDEX, &Some(filter), &None)
+                .await?
+        {
+            shared::delete_by_id(db, DELIVERIES_INDEX, &delivery.id).await?;
+        }
+        shared::delete_by_id(db, NOTIFICATIONS_INDEX, id).await
+    } else {
+        Ok(0)
+    }
+}
+
+pub async fn delete_many(
+    db: &DbClient,
+    cred: &Credential,
+    filter: &Value,
+) -> Result<u64, RustyError> {
+    let notifications = get_all(db, cred, &Some(filter.clone()), &None).await?;
+    for notification in &notifications {
+        delete_by_id(db, cred, &notification.id).await?;
+    }
+    Ok(notifications.len() as u64)
+}
+
+pub async fn delete_all(db: &DbClient) -> Result<u64, RustyError> {
+    shared::delete_all(db, DELIVERIES_INDEX).await?;
+    shared::delete_all(db, NOTIFICATIONS_INDEX).await
+}
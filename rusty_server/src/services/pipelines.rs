@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde_json::{json, Value};
 
 use commons::env::var_or_default;
@@ -10,11 +12,18 @@ use domain::RustyDomainItem;
 use persist::db_client::DbClient;
 
 use crate::services::shared::get_username_claim;
-use crate::services::{agents, jobs, projects, shared};
+use crate::services::{agents, deployments, job_triggers, jobs, projects, revisions, shared};
 
 const PIPELINES_INDEX: &str = "pipelines";
 const PIPELINE_LOGS_INDEX: &str = "pipelineLogs";
 
+// downstream pipelines are linked to the pipeline which triggered them
+struct Upstream {
+    id: String,
+    triggered_by: Option<String>,
+    variables: Option<HashMap<String, String>>,
+}
+
 // query
 
 pub async fn get_all(
@@ -87,12 +96,71 @@ pub async fn get_logs(
     }
 }
 
+/// Pipelines of the trigger chain of a pipeline - its upstream pipelines and all their downstream pipelines.
+pub async fn get_chain(
+    db: &DbClient,
+    cred: &Credential,
+    id: &str,
+) -> Result<Vec<Pipeline>, RustyError> {
+    let Some(mut root) = get_by_id(db, cred, id).await? else {
+        return Ok(vec![]);
+    };
+    while let Some(upstream_id) = root.upstream_id.clone() {
+        match get_by_id(db, cred, &upstream_id).await? {
+            Some(upstream) => root = upstream,
+            None => break,
+        }
+    }
+
+    // the root pipeline first, then the downstream pipelines level by level
+    let mut chain = vec![root];
+    let mut next = 0;
+    while let Some(pipeline) = chain.get(next) {
+        let filter = json!({ "upstream_id": { "equals": pipeline.id } });
+        let downstream = get_all(db, cred, &Some(filter), &None).await?;
+        chain.extend(downstream);
+        next += 1;
+    }
+    Ok(chain)
+}
+
 // mutate
 
 pub async fn create(
     db: &DbClient,
     cred: &Credential,
     pipeline: RegisterPipeline,
+) -> Result<String, RustyError> {
+    register(db, cred, pipeline, None, None).await
+}
+
+/// Register a new pipeline running the job template revision, branch and commit of a pipeline.
+pub async fn redeploy(
+    db: &DbClient,
+    cred: &Credential,
+    pipeline_id: &str,
+) -> Result<String, RustyError> {
+    if let Some(previous) = get_by_id(db, cred, pipeline_id).await? {
+        let pipeline = RegisterPipeline {
+            job_id: previous.job_id,
+            branch: Some(previous.branch),
+            commit: previous.commit,
+        };
+        register(db, cred, pipeline, previous.revision, None).await
+    } else {
+        Err(RustyError::ValidationError(
+            "pipeline not found".to_string(),
+        ))
+    }
+}
+
+// without a revision, the current job template is recorded
+async fn register(
+    db: &DbClient,
+    cred: &Credential,
+    pipeline: RegisterPipeline,
+    revision: Option<String>,
+    upstream: Option<Upstream>,
 ) -> Result<String, RustyError> {
     if let Some(job) = jobs::get_by_id(db, cred, &pipeline.job_id, &None, &[]).await? {
         if let Some(project) = projects::get_by_id(db, cred, &job.project_id, &None, &[]).await? {
@@ -113,6 +181,16 @@ pub async fn create(
             if pipeline.branch.is_empty() {
                 pipeline.branch = project.main_branch;
             }
+            pipeline.revision = match revision {
+                Some(revision) => Some(revision),
+                None => Some(revisions::record(db, cred, &job.id, &job.template).await?),
+            };
+            pipeline.triggered_by = Some(get_username_claim(cred)?);
+            if let Some(upstream) = upstream {
+                pipeline.triggered_by = upstream.triggered_by.or(pipeline.triggered_by);
+                pipeline.upstream_id = Some(upstream.id);
+                pipeline.variables = upstream.variables;
+            }
             shared::create(db, PIPELINES_INDEX, register, |_| pipeline).await
         } else {
             Err(RustyError::ValidationError("project not found".to_string()))
@@ -238,8 +316,18 @@ pub async fn update_stage(
             shared::check_project_write_permission(db, cred, &job.project_id).await?;
             if pipe.clone().agent_id.unwrap_or_else(String::new) == agent_id {
                 *pipe.stage_status.entry(stage.to_string()).or_insert(status) = status;
-                db.update(PIPELINES_INDEX, pipeline_id, &pipe.to_value()?)
-                    .await
+                let id = db
+                    .update(PIPELINES_INDEX, pipeline_id, &pipe.to_value()?)
+                    .await?;
+                if status == PipelineStatus::Success {
+                    // a failed record does not fail the stage
+                    if let Err(err) = deployments::record(db, &pipe, &job, stage).await {
+                        log::error!(
+                            "`pipelines::updateStage` - failed to record deployment: {err}"
+                        );
+                    }
+                }
+                Ok(id)
             } else {
                 let message = "`pipelines::updateStage` - cannot update".to_string();
                 log::debug!("{message}");
@@ -270,8 +358,16 @@ pub async fn finalize(
             {
                 pipe.status = status;
                 pipe.end_date = Some(chrono::Utc::now().to_rfc3339());
-                db.update(PIPELINES_INDEX, pipeline_id, &pipe.to_value()?)
-                    .await
+                let id = db
+                    .update(PIPELINES_INDEX, pipeline_id, &pipe.to_value()?)
+                    .await?;
+                let downstream = register_downstream(db, &pipe).await;
+                if !downstream.is_empty() {
+                    log::debug!(
+                        "`pipelines::finalize` - registered downstream pipelines: {downstream:?}"
+                    );
+                }
+                Ok(id)
             } else {
                 let message = "`pipelines::finalize` - cannot update".to_string();
                 log::debug!("{message}");
@@ -287,6 +383,49 @@ pub async fn finalize(
     }
 }
 
+// downstream pipelines are registered by the system, on behalf of the user who triggered the upstream one;
+// a failed registration does not fail the upstream pipeline
+async fn register_downstream(db: &DbClient, pipeline: &Pipeline) -> Vec<String> {
+    let filter = json!({ "job_id": { "equals": pipeline.job_id } });
+    let triggers = match job_triggers::get_all(db, &Credential::System, &Some(filter), &None).await
+    {
+        Ok(triggers) => triggers,
+        Err(err) => {
+            log::error!("`pipelines::finalize` - failed to fetch job triggers: {err}");
+            return vec![];
+        }
+    };
+
+    let mut ids = vec![];
+    for trigger in triggers.iter().filter(|trigger| trigger.matches(pipeline)) {
+        // the upstream branch and commit belong to the repository of the upstream project -
+        // pipelines of other projects run on their project main branch
+        let same_project = shared::get_by_id::<Job>(db, "jobs", &trigger.downstream_job_id)
+            .await
+            .ok()
+            .flatten()
+            .is_some_and(|job| job.project_id == trigger.project_id);
+        let downstream = RegisterPipeline {
+            job_id: trigger.downstream_job_id.clone(),
+            branch: Some(pipeline.branch.clone()).filter(|_| same_project),
+            commit: pipeline.commit.clone().filter(|_| same_project),
+        };
+        let upstream = Upstream {
+            id: pipeline.id.clone(),
+            triggered_by: pipeline.triggered_by.clone(),
+            variables: trigger.variables.clone(),
+        };
+        match register(db, &Credential::System, downstream, None, Some(upstream)).await {
+            Ok(id) => ids.push(id),
+            Err(err) => log::error!(
+                "`pipelines::finalize` - failed to trigger job `{}`: {err}",
+                trigger.downstream_job_id
+            ),
+        }
+    }
+    ids
+}
+
 pub async fn delete_by_id(db: &DbClient, cred: &Credential, id: &str) -> Result<u64, RustyError> {
     if let Some(pipe) = get_by_id(db, cred, id).await? {
         if let Some(job) = jobs::get_by_id(db, cred, &pipe.job_id, &None, &[]).await? {
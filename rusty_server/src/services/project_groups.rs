@@ -4,7 +4,7 @@ use serde_json::{json, Value};
 use commons::errors::RustyError;
 use domain::auth::credentials::Credential;
 use domain::commons::search::SearchOptions;
-use domain::projects::{Group, GroupModel, ProjectModel, RegisterGroup};
+use domain::projects::{Group, GroupModel, ProjectModel, RegisterGroup, UpdateGroup};
 use persist::db_client::DbClient;
 
 use crate::services::shared::{add_filter_field, get_username_claim, remove_filter_field};
@@ -105,6 +105,17 @@ pub async fn create(
     shared::create(db, GROUPS_INDEX, group, |r| Group::from(&r)).await
 }
 
+pub async fn update(
+    db: &DbClient,
+    cred: &Credential,
+    id: &str,
+    group: UpdateGroup,
+) -> Result<String, RustyError> {
+    let username = get_username_claim(cred)?;
+    auth::authorize(db, &username, &format!("PROJECT_GROUPS:WRITE:ID[{id}]")).await?;
+    shared::update(db, GROUPS_INDEX, id, group, |u, g: Group| u.apply(&g)).await
+}
+
 pub async fn delete_by_id(db: &DbClient, cred: &Credential, id: &str) -> Result<u64, RustyError> {
     let username = get_username_claim(cred)?;
     auth::authorize(db, &username, &format!("PROJECT_GROUPS:WRITE:ID[{id}]")).await?;
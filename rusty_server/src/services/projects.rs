@@ -5,11 +5,14 @@ use commons::errors::RustyError;
 use domain::auth::credentials::Credential;
 use domain::commons::search::SearchOptions;
 use domain::jobs::JobModel;
-use domain::projects::{Project, ProjectModel, RegisterProject};
+use domain::projects::{Project, ProjectModel, RegisterProject, UpdateProject};
 use persist::db_client::DbClient;
 
 use crate::services::shared::{add_filter_field, get_username_claim, remove_filter_field};
-use crate::services::{jobs, project_groups, shared};
+use crate::services::{
+    checkouts, coverage, deployments, event_hooks, jobs, notifications, project_groups, registries,
+    reporters, shared, test_reports,
+};
 
 const PROJECTS_INDEX: &str = "projects";
 
@@ -116,9 +119,53 @@ pub async fn create(
     }
 }
 
+pub async fn update(
+    db: &DbClient,
+    cred: &Credential,
+    id: &str,
+    project: UpdateProject,
+) -> Result<String, RustyError> {
+    shared::check_project_write_permission(db, cred, id).await?;
+    let group_id = project.clone().group_id.unwrap_or_default();
+    if !group_id.is_empty() {
+        if project_groups::get_by_id(db, cred, &group_id, &None, &[])
+            .await?
+            .is_none()
+        {
+            return Err(RustyError::ValidationError(
+                "project group not found".to_string(),
+            ));
+        }
+        // moving a project into a group changes the group
+        let current = shared::get_by_id::<Project>(db, PROJECTS_INDEX, id).await?;
+        if current.and_then(|p| p.group_id).as_deref() != Some(group_id.as_str()) {
+            auth::authorize(
+                db,
+                &get_username_claim(cred)?,
+                &format!("PROJECT_GROUPS:WRITE:ID[{group_id}]"),
+            )
+            .await?;
+        }
+    }
+    shared::update(db, PROJECTS_INDEX, id, project, |u, p: Project| u.apply(&p)).await
+}
+
 pub async fn delete_by_id(db: &DbClient, cred: &Credential, id: &str) -> Result<u64, RustyError> {
     shared::check_project_write_permission(db, cred, id).await?;
     jobs::delete_many(db, cred, &json!({ "project_id": { "equals": id } })).await?;
+    reporters::delete_many(db, cred, &json!({ "project_id": { "equals": id } })).await?;
+    registries::delete_many(db, cred, &json!({ "project_id": { "equals": id } })).await?;
+    checkouts::delete_many(db, cred, &json!({ "project_id": { "equals": id } })).await?;
+    deployments::delete_many(db, cred, &json!({ "project_id": { "equals": id } })).await?;
+    test_reports::delete_many(db, cred, &json!({ "project_id": { "equals": id } })).await?;
+    coverage::delete_many(db, cred, &json!({ "project_id": { "equals": id } })).await?;
+    notifications::delete_many(db, cred, &json!({ "project_id": { "equals": id } })).await?;
+    event_hooks::delete_many(
+        db,
+        &Credential::System,
+        &json!({ "project_id": { "equals": id } }),
+    )
+    .await?;
     shared::delete_by_id(db, PROJECTS_INDEX, id).await
 }
 
@@ -142,6 +189,54 @@ pub async fn delete_all(db: &DbClient) -> Result<u64, RustyError> {
             &json!({ "project_id": { "equals": project.id } }),
         )
         .await?;
+        reporters::delete_many(
+            db,
+            &Credential::System,
+            &json!({ "project_id": { "equals": project.id } }),
+        )
+        .await?;
+        registries::delete_many(
+            db,
+            &Credential::System,
+            &json!({ "project_id": { "equals": project.id } }),
+        )
+        .await?;
+        checkouts::delete_many(
+            db,
+            &Credential::System,
+            &json!({ "project_id": { "equals": project.id } }),
+        )
+        .await?;
+        deployments::delete_many(
+            db,
+            &Credential::System,
+            &json!({ "project_id": { "equals": project.id } }),
+        )
+        .await?;
+        test_reports::delete_many(
+            db,
+            &Credential::System,
+            &json!({ "project_id": { "equals": project.id } }),
+        )
+        .await?;
+        coverage::delete_many(
+            db,
+            &Credential::System,
+            &json!({ "project_id": { "equals": project.id } }),
+        )
+        .await?;
+        notifications::delete_many(
+            db,
+            &Credential::System,
+            &json!({ "project_id": { "equals": project.id } }),
+        )
+        .await?;
+        event_hooks::delete_many(
+            db,
+            &Credential::System,
+            &json!({ "project_id": { "equals": project.id } }),
+        )
+        .await?;
     }
     shared::delete_all(db, PROJECTS_INDEX).await
 }
@@ -0,0 +1,128 @@
+use serde_json::{json, Value};
+
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::commons::search::SearchOptions;
+use domain::registries::{RegisterRegistryCredential, RegistryAuth, RegistryCredential};
+use persist::db_client::DbClient;
+
+use crate::services::shared::get_username_claim;
+use crate::services::{projects, shared};
+
+const REGISTRIES_INDEX: &str = "registry_credentials";
+
+// query
+
+pub async fn get_all(
+    db: &DbClient,
+    cred: &Credential,
+    filter: &Option<Value>,
+    options: &Option<SearchOptions>,
+) -> Result<Vec<RegistryCredential>, RustyError> {
+    let entries =
+        shared::get_all::<RegistryCredential>(db, REGISTRIES_INDEX, filter, options).await?;
+    let mut filtered = vec![];
+    let username = get_username_claim(cred)?;
+    for entry in entries {
+        if auth::authorize(
+            db,
+            &username,
+            &format!("PROJECTS:READ:ID[{}]", entry.project_id),
+        )
+        .await
+        .is_ok()
+        {
+            filtered.push(entry);
+        }
+    }
+    Ok(filtered)
+}
+
+pub async fn get_by_id(
+    db: &DbClient,
+    cred: &Credential,
+    id: &str,
+) -> Result<Option<RegistryCredential>, RustyError> {
+    if let Some(registry) =
+        shared::get_by_id::<RegistryCredential>(db, REGISTRIES_INDEX, id).await?
+    {
+        auth::authorize(
+            db,
+            &get_username_claim(cred)?,
+            &format!("PROJECTS:READ:ID[{}]", registry.project_id),
+        )
+        .await?;
+        Ok(Some(registry))
+    } else {
+        Ok(None)
+    }
+}
+
+// secrets are handed out to agents only
+pub async fn get_auths(
+    db: &DbClient,
+    cred: &Credential,
+    project_id: &str,
+) -> Result<Vec<RegistryAuth>, RustyError> {
+    auth::authorize(db, &get_username_claim(cred)?, "AGENTS:WRITE").await?;
+    let filter = json!({ "project_id": { "equals": project_id } });
+    Ok(get_all(db, cred, &Some(filter), &None)
+        .await?
+        .iter()
+        .map(RegistryAuth::from)
+        .collect())
+}
+
+// mutate
+
+pub async fn create(
+    db: &DbClient,
+    cred: &Credential,
+    registry: RegisterRegistryCredential,
+) -> Result<String, RustyError> {
+    if let Some(project) = projects::get_by_id(db, cred, &registry.project_id, &None, &[]).await? {
+        shared::check_project_write_permission(db, cred, &project.id).await?;
+        let host = RegistryCredential::from(&registry).registry;
+        let filter = json!({ "project_id": { "equals": project.id } });
+        if get_all(db, cred, &Some(filter), &None)
+            .await?
+            .iter()
+            .any(|r| r.registry == host)
+        {
+            return Err(RustyError::ValidationError(format!(
+                "credential for registry `{host}` already exists"
+            )));
+        }
+        shared::create(db, REGISTRIES_INDEX, registry, |r| {
+            RegistryCredential::from(&r)
+        })
+        .await
+    } else {
+        Err(RustyError::ValidationError("project not found".to_string()))
+    }
+}
+
+pub async fn delete_by_id(db: &DbClient, cred: &Credential, id: &str) -> Result<u64, RustyError> {
+    if let Some(registry) = get_by_id(db, cred, id).await? {
+        shared::check_project_write_permission(db, cred, &registry.project_id).await?;
+        shared::delete_by_id(db, REGISTRIES_INDEX, id).await
+    } else {
+        Ok(0)
+    }
+}
+
+pub async fn delete_many(
+    db: &DbClient,
+    cred: &Credential,
+    filter: &Value,
+) -> Result<u64, RustyError> {
+    let registries = get_all(db, cred, &Some(filter.clone()), &None).await?;
+    for registry in &registries {
+        delete_by_id(db, cred, &registry.id).await?;
+    }
+    Ok(registries.len() as u64)
+}
+
+pub async fn delete_all(db: &DbClient) -> Result<u64, RustyError> {
+    shared::delete_all(db, REGISTRIES_INDEX).await
+}
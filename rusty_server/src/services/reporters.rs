@@ -0,0 +1,96 @@
+use serde_json::Value;
+
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::commons::search::SearchOptions;
+use domain::reporters::{RegisterStatusReporter, StatusReporter};
+use persist::db_client::DbClient;
+
+use crate::services::shared::get_username_claim;
+use crate::services::{projects, shared};
+
+const REPORTERS_INDEX: &str = "status_reporters";
+
+// query
+
+pub async fn get_all(
+    db: &DbClient,
+    cred: &Credential,
+    filter: &Option<Value>,
+    options: &Option<SearchOptions>,
+) -> Result<Vec<StatusReporter>, RustyError> {
+    let entries = shared::get_all::<StatusReporter>(db, REPORTERS_INDEX, filter, options).await?;
+    let mut filtered = vec![];
+    let username = get_username_claim(cred)?;
+    for entry in entries {
+        if auth::authorize(
+            db,
+            &username,
+            &format!("PROJECTS:READ:ID[{}]", entry.project_id),
+        )
+        .await
+        .is_ok()
+        {
+            filtered.push(entry);
+        }
+    }
+    Ok(filtered)
+}
+
+pub async fn get_by_id(
+    db: &DbClient,
+    cred: &Credential,
+    id: &str,
+) -> Result<Option<StatusReporter>, RustyError> {
+    if let Some(reporter) = shared::get_by_id::<StatusReporter>(db, REPORTERS_INDEX, id).await? {
+        auth::authorize(
+            db,
+            &get_username_claim(cred)?,
+            &format!("PROJECTS:READ:ID[{}]", reporter.project_id),
+        )
+        .await?;
+        Ok(Some(reporter))
+    } else {
+        Ok(None)
+    }
+}
+
+// mutate
+
+pub async fn create(
+    db: &DbClient,
+    cred: &Credential,
+    reporter: RegisterStatusReporter,
+) -> Result<String, RustyError> {
+    if let Some(project) = projects::get_by_id(db, cred, &reporter.project_id, &None, &[]).await? {
+        shared::check_project_write_permission(db, cred, &project.id).await?;
+        shared::create(db, REPORTERS_INDEX, reporter, |r| StatusReporter::from(&r)).await
+    } else {
+        Err(RustyError::ValidationError("project not found".to_string()))
+    }
+}
+
+pub async fn delete_by_id(db: &DbClient, cred: &Credential, id: &str) -> Result<u64, RustyError> {
+    if let Some(reporter) = get_by_id(db, cred, id).await? {
+        shared::check_project_write_permission(db, cred, &reporter.project_id).await?;
+        shared::delete_by_id(db, REPORTERS_INDEX, id).await
+    } else {
+        Ok(0)
+    }
+}
+
+pub async fn delete_many(
+    db: &DbClient,
+    cred: &Credential,
+    filter: &Value,
+) -> Result<u64, RustyError> {
+    let reporters = get_all(db, cred, &Some(filter.clone()), &None).await?;
+    for reporter in &reporters {
+        delete_by_id(db, cred, &reporter.id).await?;
+    }
+    Ok(reporters.len() as u64)
+}
+
+pub async fn delete_all(db: &DbClient) -> Result<u64, RustyError> {
+    shared::delete_all(db, REPORTERS_INDEX).await
+}
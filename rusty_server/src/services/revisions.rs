@@ -0,0 +1,131 @@
+use serde_json::json;
+
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::commons::search::SearchOptions;
+use domain::jobs::Job;
+use domain::templates::revision::{diff, DiffLine, TemplateRevision};
+use domain::RustyDomainItem;
+use persist::db_client::DbClient;
+
+use crate::services::shared;
+use crate::services::shared::get_username_claim;
+
+const REVISIONS_INDEX: &str = "job_template_revisions";
+
+// query
+
+pub async fn get_all(
+    db: &DbClient,
+    cred: &Credential,
+    job_id: &str,
+    options: &Option<SearchOptions>,
+) -> Result<Vec<TemplateRevision>, RustyError> {
+    check_job_read_permission(db, cred, job_id).await?;
+    let filter = json!({ "job_id": { "equals": job_id } });
+    let mut entries =
+        shared::get_all::<TemplateRevision>(db, REVISIONS_INDEX, &Some(filter), options).await?;
+    if options.as_ref().map_or(true, |o| o.sort_field.is_none()) {
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.number));
+    }
+    Ok(entries)
+}
+
+pub async fn get_by_id(
+    db: &DbClient,
+    cred: &Credential,
+    id: &str,
+) -> Result<Option<TemplateRevision>, RustyError> {
+    if let Some(revision) = shared::get_by_id::<TemplateRevision>(db, REVISIONS_INDEX, id).await? {
+        check_job_read_permission(db, cred, &revision.job_id).await?;
+        Ok(Some(revision))
+    } else {
+        Ok(None)
+    }
+}
+
+pub async fn get_latest(
+    db: &DbClient,
+    job_id: &str,
+) -> Result<Option<TemplateRevision>, RustyError> {
+    let filter = json!({ "job_id": { "equals": job_id } });
+    Ok(
+        shared::get_all::<TemplateRevision>(db, REVISIONS_INDEX, &Some(filter), &None)
+            .await?
+            .into_iter()
+            .max_by_key(|entry| entry.number),
+    )
+}
+
+pub async fn get_diff(
+    db: &DbClient,
+    cred: &Credential,
+    from: &str,
+    to: &str,
+) -> Result<Vec<DiffLine>, RustyError> {
+    let from = get_by_id(db, cred, from).await?;
+    let to = get_by_id(db, cred, to).await?;
+    match (from, to) {
+        (Some(from), Some(to)) => Ok(diff(&from.template, &to.template)),
+        _ => Err(RustyError::ValidationError(
+            "revision not found".to_string(),
+        )),
+    }
+}
+
+async fn check_job_read_permission(
+    db: &DbClient,
+    cred: &Credential,
+    job_id: &str,
+) -> Result<(), RustyError> {
+    let Some(job) = shared::get_by_id::<Job>(db, "jobs", job_id).await? else {
+        return Err(RustyError::ValidationError("job not found".to_string()));
+    };
+    auth::authorize(
+        db,
+        &get_username_claim(cred)?,
+        &format!("PROJECTS:READ:ID[{}]", job.project_id),
+    )
+    .await
+}
+
+// mutate
+
+/// Record the current template of a job, unless it matches the latest revision.
+///
+/// Returns the id of the revision holding the job template.
+pub async fn record(
+    db: &DbClient,
+    cred: &Credential,
+    job_id: &str,
+    template: &str,
+) -> Result<String, RustyError> {
+    let latest = get_latest(db, job_id).await?;
+    if let Some(latest) = &latest {
+        if latest.matches(template) {
+            return Ok(latest.id.clone());
+        }
+    }
+    let number = latest.map_or(1, |latest| latest.number + 1);
+    let revision = TemplateRevision::new(job_id, number, &get_username_claim(cred)?, template);
+    db.create(REVISIONS_INDEX, &revision.to_value()?)
+        .await
+        .map_err(|err| {
+            log::error!("`{REVISIONS_INDEX}::create`: {err}");
+            err
+        })
+}
+
+pub async fn delete_many(db: &DbClient, job_id: &str) -> Result<u64, RustyError> {
+    let filter = json!({ "job_id": { "equals": job_id } });
+    let revisions =
+        shared::get_all::<TemplateRevision>(db, REVISIONS_INDEX, &Some(filter), &None).await?;
+    for revision in &revisions {
+        shared::delete_by_id(db, REVISIONS_INDEX, &revision.id).await?;
+    }
+    Ok(revisions.len() as u64)
+}
+
+pub async fn delete_all(db: &DbClient) -> Result<u64, RustyError> {
+    shared::delete_all(db, REVISIONS_INDEX).await
+}
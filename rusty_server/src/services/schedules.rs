@@ -0,0 +1,153 @@
+use serde_json::Value;
+
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::commons::search::SearchOptions;
+use domain::jobs::Job;
+use domain::schedules::{RegisterSchedule, Schedule, UpdateSchedule};
+use domain::RustyDomainItem;
+use persist::db_client::DbClient;
+
+use crate::services::shared::get_username_claim;
+use crate::services::{jobs, shared};
+
+const SCHEDULES_INDEX: &str = "schedules";
+
+// query
+
+pub async fn get_all(
+    db: &DbClient,
+    cred: &Credential,
+    filter: &Option<Value>,
+    options: &Option<SearchOptions>,
+) -> Result<Vec<Schedule>, RustyError> {
+    let entries = shared::get_all::<Schedule>(db, SCHEDULES_INDEX, filter, options).await?;
+    let mut filtered = vec![];
+    let username = get_username_claim(cred)?;
+    for entry in entries {
+        if let Some(job) = shared::get_by_id::<Job>(db, "jobs", &entry.job_id).await? {
+            if auth::authorize(
+                db,
+                &username,
+                &format!("PROJECTS:READ:ID[{}]", job.project_id),
+            )
+            .await
+            .is_ok()
+            {
+                filtered.push(entry);
+            }
+        }
+    }
+    Ok(filtered)
+}
+
+pub async fn get_by_id(
+    db: &DbClient,
+    cred: &Credential,
+    id: &str,
+) -> Result<Option<Schedule>, RustyError> {
+    if let Some(schedule) = shared::get_by_id::<Schedule>(db, SCHEDULES_INDEX, id).await? {
+        if let Some(job) = shared::get_by_id::<Job>(db, "jobs", &schedule.job_id).await? {
+            auth::authorize(
+                db,
+                &get_username_claim(cred)?,
+                &format!("PROJECTS:READ:ID[{}]", job.project_id),
+            )
+            .await?;
+            Ok(Some(schedule))
+        } else {
+            Ok(None)
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+// mutate
+
+pub async fn create(
+    db: &DbClient,
+    cred: &Credential,
+    schedule: RegisterSchedule,
+) -> Result<String, RustyError> {
+    if let Some(job) = jobs::get_by_id(db, cred, &schedule.job_id, &None, &[]).await? {
+        shared::check_project_write_permission(db, cred, &job.project_id).await?;
+        shared::create(db, SCHEDULES_INDEX, schedule, |r| Schedule::from(&r)).await
+    } else {
+        Err(RustyError::ValidationError("job not found".to_string()))
+    }
+}
+
+pub async fn update(
+    db: &DbClient,
+    cred: &Credential,
+    id: &str,
+    schedule: UpdateSchedule,
+) -> Result<String, RustyError> {
+    if let Some(current) = get_by_id(db, cred, id).await? {
+        if let Some(job) = jobs::get_by_id(db, cred, &current.job_id, &None, &[]).await? {
+            shared::check_project_write_permission(db, cred, &job.project_id).await?;
+            shared::update(db, SCHEDULES_INDEX, id, schedule, |u, s: Schedule| {
+                u.apply(&s)
+            })
+            .await
+        } else {
+            Err(RustyError::UnauthorizedError)
+        }
+    } else {
+        let message = "`schedules::update` - schedule not found".to_string();
+        log::debug!("{message}");
+        Err(RustyError::AsyncGraphqlError(message))
+    }
+}
+
+pub async fn set_last_run(
+    db: &DbClient,
+    cred: &Credential,
+    schedule_id: &str,
+    last_run: &str,
+) -> Result<String, RustyError> {
+    if let Some(mut schedule) = get_by_id(db, cred, schedule_id).await? {
+        if let Some(job) = jobs::get_by_id(db, cred, &schedule.job_id, &None, &[]).await? {
+            shared::check_project_write_permission(db, cred, &job.project_id).await?;
+            schedule.last_run = Some(last_run.to_string());
+            db.update(SCHEDULES_INDEX, schedule_id, &schedule.to_value()?)
+                .await
+        } else {
+            Err(RustyError::UnauthorizedError)
+        }
+    } else {
+        let message = "`schedules::setLastRun` - schedule not found".to_string();
+        log::debug!("{message}");
+        Err(RustyError::AsyncGraphqlError(message))
+    }
+}
+
+pub async fn delete_by_id(db: &DbClient, cred: &Credential, id: &str) -> Result<u64, RustyError> {
+    if let Some(schedule) = get_by_id(db, cred, id).await? {
+        if let Some(job) = jobs::get_by_id(db, cred, &schedule.job_id, &None, &[]).await? {
+            shared::check_project_write_permission(db, cred, &job.project_id).await?;
+            shared::delete_by_id(db, SCHEDULES_INDEX, id).await
+        } else {
+            Ok(0)
+        }
+    } else {
+        Ok(0)
+    }
+}
+
+pub async fn delete_many(
+    db: &DbClient,
+    cred: &Credential,
+    filter: &Value,
+) -> Result<u64, RustyError> {
+    let schedules = get_all(db, cred, &Some(filter.clone()), &None).await?;
+    for schedule in &schedules {
+        delete_by_id(db, cred, &schedule.id).await?;
+    }
+    Ok(schedules.len() as u64)
+}
+
+pub async fn delete_all(db: &DbClient) -> Result<u64, RustyError> {
+    shared::delete_all(db, SCHEDULES_INDEX).await
+}
@@ -76,6 +76,36 @@ where
     Ok(id)
 }
 
+pub async fn update<S, T, F>(
+    db: &DbClient,
+    index: &str,
+    id: &str,
+    item: T,
+    apply: F,
+) -> Result<String, RustyError>
+where
+    S: RustyDomainItem,
+    T: Validate + Send,
+    F: FnOnce(T, S) -> S + Send,
+{
+    item.validate().map_err(|err| {
+        log::error!("`{index}::update`: {err}");
+        err
+    })?;
+
+    let Some(current) = get_by_id::<S>(db, index, id).await? else {
+        let message = format!("`{index}::update` - item not found");
+        log::debug!("{message}");
+        return Err(RustyError::AsyncGraphqlError(message));
+    };
+    db.update(index, id, &apply(item, current).to_value()?)
+        .await
+        .map_err(|err| {
+            log::error!("`{index}::update`: {err}");
+            err
+        })
+}
+
 pub async fn delete_by_id(db: &DbClient, index: &str, id: &str) -> Result<u64, RustyError> {
     db.delete_one(index, json!({ "id": id }))
         .await
@@ -0,0 +1,140 @@
+use serde_json::{json, Value};
+
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::commons::search::SearchOptions;
+use domain::test_reports::{
+    failures, flaky, new_report, slowest, FlakyTest, TestReport, TestResult, TestSuite,
+};
+use domain::RustyDomainItem;
+use persist::db_client::DbClient;
+
+use crate::services::shared::get_username_claim;
+use crate::services::{jobs, pipelines, shared};
+
+const TEST_REPORTS_INDEX: &str = "test_reports";
+
+// query
+
+pub async fn get_all(
+    db: &DbClient,
+    cred: &Credential,
+    filter: &Option<Value>,
+    options: &Option<SearchOptions>,
+) -> Result<Vec<TestReport>, RustyError> {
+    let entries = shared::get_all::<TestReport>(db, TEST_REPORTS_INDEX, filter, options).await?;
+    let mut filtered = vec![];
+    let username = get_username_claim(cred)?;
+    for entry in entries {
+        if auth::authorize(
+            db,
+            &username,
+            &format!("PROJECTS:READ:ID[{}]", entry.project_id),
+        )
+        .await
+        .is_ok()
+        {
+            filtered.push(entry);
+        }
+    }
+    Ok(filtered)
+}
+
+pub async fn get_failures(
+    db: &DbClient,
+    cred: &Credential,
+    pipeline_id: &str,
+) -> Result<Vec<TestResult>, RustyError> {
+    let filter = json!({ "pipeline_id": { "equals": pipeline_id } });
+    let entries = get_all(db, cred, &Some(filter), &None).await?;
+    Ok(failures(&entries))
+}
+
+pub async fn get_slowest(
+    db: &DbClient,
+    cred: &Credential,
+    pipeline_id: &str,
+    limit: Option<usize>,
+) -> Result<Vec<TestResult>, RustyError> {
+    let filter = json!({ "pipeline_id": { "equals": pipeline_id } });
+    let entries = get_all(db, cred, &Some(filter), &None).await?;
+    Ok(slowest(&entries, limit.unwrap_or(10)))
+}
+
+/// Tests both passing and failing across the latest pipelines of a job.
+pub async fn get_flaky(
+    db: &DbClient,
+    cred: &Credential,
+    job_id: &str,
+    runs: Option<u64>,
+) -> Result<Vec<FlakyTest>, RustyError> {
+    let filter = json!({ "job_id": { "equals": job_id } });
+    let entries = get_all(db, cred, &Some(filter), &None).await?;
+    let Some(last) = entries.iter().map(|report| report.pipeline_number).max() else {
+        return Ok(vec![]);
+    };
+    let first = last.saturating_sub(runs.unwrap_or(20).saturating_sub(1));
+    let entries = entries
+        .into_iter()
+        .filter(|report| report.pipeline_number >= first)
+        .collect::<Vec<TestReport>>();
+    Ok(flaky(&entries))
+}
+
+// mutate
+
+/// Store the test suites uploaded by the agent running a pipeline stage.
+pub async fn create(
+    db: &DbClient,
+    cred: &Credential,
+    pipeline_id: &str,
+    agent_id: &str,
+    stage: &str,
+    suites: Vec<TestSuite>,
+) -> Result<String, RustyError> {
+    let Some(pipeline) = pipelines::get_by_id(db, cred, pipeline_id).await? else {
+        let message = "`testReports::register` - pipeline not found".to_string();
+        log::debug!("{message}");
+        return Err(RustyError::AsyncGraphqlError(message));
+    };
+    let Some(job) = jobs::get_by_id(db, cred, &pipeline.job_id, &None, &[]).await? else {
+        return Err(RustyError::UnauthorizedError);
+    };
+    shared::check_project_write_permission(db, cred, &job.project_id).await?;
+    if pipeline.agent_id.as_deref() != Some(agent_id) {
+        let message = "`testReports::register` - cannot register".to_string();
+        log::debug!("{message}");
+        return Err(RustyError::AsyncGraphqlError(message));
+    }
+
+    let report = new_report(
+        &TestReport::generate_id(),
+        stage,
+        suites,
+        &pipeline,
+        &job.project_id,
+    );
+    db.create(TEST_REPORTS_INDEX, &report.to_value()?)
+        .await
+        .map_err(|err| {
+            log::error!("`{TEST_REPORTS_INDEX}::create`: {err}");
+            err
+        })
+}
+
+pub async fn delete_many(
+    db: &DbClient,
+    cred: &Credential,
+    filter: &Value,
+) -> Result<u64, RustyError> {
+    let entries = get_all(db, cred, &Some(filter.clone()), &None).await?;
+    for report in &entries {
+        shared::check_project_write_permission(db, cred, &report.project_id).await?;
+        shared::delete_by_id(db, TEST_REPORTS_INDEX, &report.id).await?;
+    }
+    Ok(entries.len() as u64)
+}
+
+pub async fn delete_all(db: &DbClient) -> Result<u64, RustyError> {
+    shared::delete_all(db, TEST_REPORTS_INDEX).await
+}
@@ -0,0 +1,56 @@
+use serde_json::json;
+
+use commons::errors::RustyError;
+use domain::auth::credentials::Credential;
+use domain::pipelines::RegisterPipeline;
+use domain::templates::pipeline::PipelineTemplate;
+use domain::webhooks::WebhookEvent;
+use persist::db_client::DbClient;
+
+use crate::services::{jobs, pipelines, projects};
+
+// mutate
+
+pub async fn trigger(db: &DbClient, event: &WebhookEvent) -> Result<Vec<String>, RustyError> {
+    let cred = Credential::System;
+    let mut registered = vec![];
+    for project in projects::get_all(db, &cred, &None, &None, &[]).await? {
+        if !project
+            .url
+            .as_ref()
+            .is_some_and(|url| event.matches_url(url))
+        {
+            continue;
+        }
+
+        let filter = json!({ "project_id": { "equals": project.id } });
+        for job in jobs::get_all(db, &cred, &Some(filter), &None, &[]).await? {
+            let triggered = PipelineTemplate::from_yaml(&job.template).map_or(false, |template| {
+                template.is_triggered(event.event, &event.branch, &project.main_branch)
+            });
+            if triggered {
+                let pipeline = RegisterPipeline {
+                    job_id: job.id.clone(),
+                    branch: Some(event.branch.clone()),
+                    commit: event.commit.clone(),
+                };
+                // a failed job does not fail the delivery - forges redeliver failed deliveries,
+                // registering the pipelines of the other jobs again
+                match pipelines::create(db, &cred, pipeline).await {
+                    Ok(id) => {
+                        log::debug!(
+                            "`webhooks::trigger`: registered pipeline `{id}` for job `{}`",
+                            job.id
+                        );
+                        registered.push(id);
+                    }
+                    Err(err) => log::error!(
+                        "`webhooks::trigger`: failed to register pipeline for job `{}`: {err}",
+                        job.id
+                    ),
+                }
+            }
+        }
+    }
+    Ok(registered)
+}
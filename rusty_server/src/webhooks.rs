@@ -0,0 +1,111 @@
+use axum::body::Bytes;
+use axum::http::{HeaderMap, StatusCode};
+use axum::Extension;
+use serde_json::{json, Value};
+
+use commons::env::var;
+use commons::hashing::sha::verify_hmac256;
+use domain::webhooks::{Forge, WebhookEvent};
+use persist::db_client::DbClient;
+
+use crate::services::webhooks as service;
+
+type WebhookResponse = (StatusCode, String);
+
+pub async fn github_handler(
+    Extension(db): Extension<DbClient>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> WebhookResponse {
+    handle(&db, Forge::GitHub, &headers, &body).await
+}
+
+pub async fn gitlab_handler(
+    Extension(db): Extension<DbClient>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> WebhookResponse {
+    handle(&db, Forge::GitLab, &headers, &body).await
+}
+
+pub async fn gitea_handler(
+    Extension(db): Extension<DbClient>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> WebhookResponse {
+    handle(&db, Forge::Gitea, &headers, &body).await
+}
+
+async fn handle(db: &DbClient, forge: Forge, headers: &HeaderMap, body: &[u8]) -> WebhookResponse {
+    log::debug!("handling `webhooks::{forge:?}` request");
+    let Ok(secret) = var::<String>("WEBHOOK_SECRET") else {
+        log::warn!("`webhooks::{forge:?}`: `WEBHOOK_SECRET` is not set - request rejected");
+        return (
+            StatusCode::UNAUTHORIZED,
+            "webhooks are disabled".to_string(),
+        );
+    };
+    if !verify_signature(forge, headers, body, &secret) {
+        log::debug!("`webhooks::{forge:?}`: invalid signature");
+        return (StatusCode::UNAUTHORIZED, "invalid signature".to_string());
+    }
+
+    let Ok(payload) = serde_json::from_slice::<Value>(body) else {
+        return (StatusCode::BAD_REQUEST, "invalid payload".to_string());
+    };
+    let event = get_header(headers, event_header(forge)).unwrap_or_default();
+    let Some(event) = WebhookEvent::parse(forge, event, &payload) else {
+        log::debug!("`webhooks::{forge:?}`: event `{event}` skipped");
+        return (StatusCode::OK, json!({ "pipelines": [] }).to_string());
+    };
+
+    match service::trigger(db, &event).await {
+        Ok(pipelines) => {
+            log::debug!(
+                "`webhooks::{forge:?}`: registered {} pipelines",
+                pipelines.len()
+            );
+            (
+                StatusCode::OK,
+                json!({ "pipelines": pipelines }).to_string(),
+            )
+        }
+        Err(err) => {
+            log::error!("`webhooks::{forge:?}`: {err}");
+            (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+        }
+    }
+}
+
+/// Verify a webhook request signature
+///
+/// `GitHub` and `Gitea` sign the payload with HMAC-SHA256, `GitLab` sends the secret token as is.
+pub fn verify_signature(forge: Forge, headers: &HeaderMap, body: &[u8], secret: &str) -> bool {
+    match forge {
+        Forge::GitHub => get_header(headers, "X-Hub-Signature-256")
+            .and_then(|signature| signature.strip_prefix("sha256="))
+            .is_some_and(|signature| verify_hmac256(secret, body, signature)),
+        Forge::Gitea => get_header(headers, "X-Gitea-Signature")
+            .is_some_and(|signature| verify_hmac256(secret, body, signature)),
+        Forge::GitLab => get_header(headers, "X-Gitlab-Token").is_some_and(|token| {
+            token.len() == secret.len()
+                && token
+                    .bytes()
+                    .zip(secret.bytes())
+                    .fold(0, |acc, (a, b)| acc | (a ^ b))
+                    == 0
+        }),
+    }
+}
+
+const fn event_header(forge: Forge) -> &'static str {
+    match forge {
+        Forge::GitHub => "X-GitHub-Event",
+        Forge::GitLab => "X-Gitlab-Event",
+        Forge::Gitea => "X-Gitea-Event",
+    }
+}
+
+fn get_header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
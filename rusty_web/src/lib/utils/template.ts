@@ -0,0 +1,12 @@
+// templates are stored as plain yaml - jobs registered before may still hold base64 encoded templates
+export const decodeTemplate = (template: string) => {
+	const encoded = template.trim();
+	if (!/^[A-Za-z0-9+/_-]+=*$/.test(encoded)) {
+		return template;
+	}
+	try {
+		return atob(encoded.replace(/-/g, '+').replace(/_/g, '/'));
+	} catch {
+		return template;
+	}
+};
@@ -1,6 +1,6 @@
 use rstest::rstest;
 
-use commons::hashing::sha::{hmac512, sha512};
+use commons::hashing::sha::{hmac256, hmac512, sha256, sha512, verify_hmac256};
 
 #[rstest]
 #[case("test")]
@@ -18,3 +18,40 @@ fn hmac512_test(#[case] key: &str) {
 fn sha512_test(#[case] input: &str, #[case] output: &str) {
     assert_eq!(output.to_string(), sha512(input))
 }
+
+#[rstest]
+#[case(
+    "test",
+    "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+)]
+#[case("", "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")]
+fn sha256_test(#[case] input: &str, #[case] output: &str) {
+    assert_eq!(output.to_string(), sha256(input))
+}
+
+#[test]
+fn hmac256_test() {
+    let signature = hmac256("key", b"The quick brown fox jumps over the lazy dog");
+    assert!(signature.is_ok());
+    assert_eq!(
+        "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8",
+        signature.unwrap()
+    );
+}
+
+#[rstest]
+#[case(
+    "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8",
+    true
+)]
+#[case(
+    "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd9",
+    false
+)]
+#[case("f7bc83f4", false)]
+#[case("not a signature", false)]
+#[case("", false)]
+fn verify_hmac256_test(#[case] signature: &str, #[case] expected: bool) {
+    let payload = b"The quick brown fox jumps over the lazy dog";
+    assert_eq!(expected, verify_hmac256("key", payload, signature));
+}
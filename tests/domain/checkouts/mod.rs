@@ -0,0 +1,66 @@
+use rstest::rstest;
+use serde_valid::Validate;
+
+use domain::checkouts::{Checkout, CheckoutSettings, RegisterCheckoutSettings, Submodules};
+
+const PROJECT_ID: &str = "57c38e8b-1845-49f1-874a-1eefe9923456";
+
+fn settings() -> RegisterCheckoutSettings {
+    RegisterCheckoutSettings {
+        depth: Some(1),
+        submodules: Some(Submodules::Recursive),
+        lfs: Some(true),
+        sparse_paths: Some(vec!["src".to_string(), "docs/api".to_string()]),
+        token: Some("secret".to_string()),
+        ..RegisterCheckoutSettings::new(PROJECT_ID)
+    }
+}
+
+#[test]
+fn from_register_checkout_settings_test() {
+    let settings = CheckoutSettings::from(&settings());
+    assert_eq!(36, settings.id.len());
+    assert_eq!(Some(1), settings.depth);
+    assert_eq!(Some(Submodules::Recursive), settings.submodules);
+    assert_eq!(Some(true), settings.lfs);
+    assert_eq!(
+        Some(vec!["src".to_string(), "docs/api".to_string()]),
+        settings.sparse_paths
+    );
+    assert_eq!(Some("secret".to_string()), settings.token);
+    assert_eq!(PROJECT_ID, settings.project_id);
+}
+
+#[test]
+fn checkout_from_settings_test() {
+    let checkout = Checkout::from(&CheckoutSettings::from(&settings()));
+    assert_eq!(Some(1), checkout.depth);
+    assert_eq!(Some(Submodules::Recursive), checkout.submodules);
+    assert_eq!(Some("secret".to_string()), checkout.token);
+    assert_eq!(None, checkout.ssh_key);
+}
+
+#[rstest]
+#[case(settings(), true)]
+#[case(RegisterCheckoutSettings::new(PROJECT_ID), true)]
+#[case(RegisterCheckoutSettings::new("project"), false)]
+#[case(RegisterCheckoutSettings { depth: Some(0), ..settings() }, false)]
+#[case(RegisterCheckoutSettings { sparse_paths: Some(vec![]), ..settings() }, false)]
+#[case(RegisterCheckoutSettings { sparse_paths: Some(vec![" ".to_string()]), ..settings() }, false)]
+#[case(RegisterCheckoutSettings { sparse_paths: Some(vec!["/etc".to_string()]), ..settings() }, false)]
+#[case(RegisterCheckoutSettings { sparse_paths: Some(vec!["src/../..".to_string()]), ..settings() }, false)]
+#[case(RegisterCheckoutSettings { token: Some(String::new()), ..settings() }, false)]
+fn validate_checkout_settings_test(
+    #[case] settings: RegisterCheckoutSettings,
+    #[case] expected: bool,
+) {
+    assert_eq!(expected, settings.validate().is_ok());
+}
+
+#[rstest]
+#[case("\"recursive\"", Submodules::Recursive)]
+#[case("\"RECURSIVE\"", Submodules::Recursive)]
+#[case("\"none\"", Submodules::None)]
+fn submodules_deserialize_test(#[case] value: &str, #[case] expected: Submodules) {
+    assert_eq!(expected, serde_json::from_str::<Submodules>(value).unwrap());
+}
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use domain::coverage::{delta, new_report, percent, pipeline_coverage, FileCoverage};
+use domain::pipelines::{Pipeline, PipelineStatus};
+
+fn pipeline(number: u64) -> Pipeline {
+    Pipeline {
+        id: format!("pipeline-{number}"),
+        number,
+        branch: "master".to_string(),
+        register_date: "now".to_string(),
+        start_date: None,
+        end_date: None,
+        stage_status: HashMap::new(),
+        status: PipelineStatus::InProgress,
+        job_id: "job".to_string(),
+        agent_id: None,
+        commit: Some("0123abc".to_string()),
+        revision: None,
+        triggered_by: None,
+        upstream_id: None,
+        variables: None,
+    }
+}
+
+fn file(path: &str, lines: u64, covered: u64) -> FileCoverage {
+    FileCoverage {
+        path: path.to_string(),
+        lines,
+        covered,
+    }
+}
+
+#[test]
+fn percent_test() {
+    assert!((percent(1, 4) - 25.0).abs() < f64::EPSILON);
+    assert!(percent(0, 0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn new_report_test() {
+    let report = new_report(
+        "id",
+        "test",
+        vec![file("src/lib.rs", 10, 5), file("src/main.rs", 10, 10)],
+        &pipeline(2),
+        "project",
+    );
+    assert_eq!(20, report.lines);
+    assert_eq!(15, report.covered);
+    assert!((report.percent - 75.0).abs() < f64::EPSILON);
+    assert_eq!("pipeline-2", report.pipeline_id);
+    assert_eq!(Some("0123abc".to_string()), report.commit);
+    assert_eq!("job", report.job_id);
+    assert_eq!("project", report.project_id);
+}
+
+#[test]
+fn pipeline_coverage_test() {
+    let reports = vec![
+        new_report(
+            "a",
+            "unit",
+            vec![file("src/lib.rs", 10, 5)],
+            &pipeline(2),
+            "p",
+        ),
+        new_report(
+            "b",
+            "integration",
+            vec![file("src/lib.rs", 10, 8), file("src/main.rs", 10, 2)],
+            &pipeline(2),
+            "p",
+        ),
+        new_report(
+            "c",
+            "unit",
+            vec![file("src/lib.rs", 10, 1)],
+            &pipeline(1),
+            "p",
+        ),
+    ];
+    let coverage = pipeline_coverage(&reports);
+    assert_eq!(2, coverage.len());
+    assert_eq!(1, coverage[0].pipeline_number);
+    assert_eq!(10, coverage[0].lines);
+    assert_eq!(1, coverage[0].covered);
+    assert_eq!(2, coverage[1].pipeline_number);
+    assert_eq!(20, coverage[1].lines);
+    assert_eq!(10, coverage[1].covered);
+    assert!((coverage[1].percent - 50.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn delta_test() {
+    let reports = vec![
+        new_report(
+            "a",
+            "unit",
+            vec![file("src/lib.rs", 10, 8)],
+            &pipeline(1),
+            "p",
+        ),
+        new_report(
+            "b",
+            "unit",
+            vec![file("src/lib.rs", 10, 6)],
+            &pipeline(2),
+            "p",
+        ),
+    ];
+    let coverage = pipeline_coverage(&reports);
+    let result = delta(coverage[1].clone(), Some(coverage[0].clone()));
+    assert!((result.delta.unwrap() + 20.0).abs() < 1e-9);
+    assert_eq!(Some(coverage[0].clone()), result.base);
+
+    let result = delta(coverage[1].clone(), None);
+    assert_eq!(None, result.delta);
+}
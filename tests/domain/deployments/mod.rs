@@ -0,0 +1,54 @@
+use domain::deployments::{environments, Deployment};
+
+fn deployment(environment: &str, date: &str, pipeline_number: u64) -> Deployment {
+    Deployment {
+        id: uuid::Uuid::new_v4().to_string(),
+        environment: environment.to_string(),
+        url: Some(format!("https://{environment}.example.com")),
+        date: date.to_string(),
+        branch: "master".to_string(),
+        commit: None,
+        stage: "deploy".to_string(),
+        deployed_by: Some("user".to_string()),
+        pipeline_id: uuid::Uuid::new_v4().to_string(),
+        pipeline_number,
+        job_id: "job".to_string(),
+        project_id: "project".to_string(),
+    }
+}
+
+#[test]
+fn environments_test() {
+    let deployments = vec![
+        deployment("staging", "2024-05-01T10:00:00+00:00", 1),
+        deployment("production", "2024-05-02T10:00:00+00:00", 2),
+        deployment("staging", "2024-05-03T10:00:00+00:00", 3),
+        deployment("staging", "2024-05-02T10:00:00+00:00", 2),
+    ];
+    let environments = environments(&deployments);
+    assert_eq!(2, environments.len());
+
+    assert_eq!("production", environments[0].name);
+    assert_eq!(2, environments[0].current.pipeline_number);
+    assert!(environments[0].previous.is_empty());
+
+    assert_eq!("staging", environments[1].name);
+    assert_eq!(
+        Some("https://staging.example.com".to_string()),
+        environments[1].url
+    );
+    assert_eq!(3, environments[1].current.pipeline_number);
+    assert_eq!(
+        vec![2, 1],
+        environments[1]
+            .previous
+            .iter()
+            .map(|deployment| deployment.pipeline_number)
+            .collect::<Vec<u64>>()
+    );
+}
+
+#[test]
+fn environments_empty_test() {
+    assert!(environments(&[]).is_empty());
+}
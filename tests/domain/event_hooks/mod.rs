@@ -0,0 +1,79 @@
+use rstest::rstest;
+use serde_json::{json, Value};
+use serde_valid::Validate;
+
+use domain::event_hooks::{DeliveryStatus, EventHook, EventHookDelivery, RegisterEventHook};
+
+const PROJECT_ID: &str = "871188c7-6a26-41a0-b7a2-1cb97dcdb01a";
+const SECRET: &str = "0123456789abcdef";
+
+#[test]
+fn from_register_event_hook_test() {
+    let input = RegisterEventHook::new("https://hooks.rusty.ops", SECRET);
+    let hook = EventHook::from(&input);
+    assert_eq!(36, hook.id.len());
+    assert_eq!("https://hooks.rusty.ops", hook.url);
+    assert_eq!(SECRET, hook.secret);
+    assert!(hook.indexes.is_empty());
+    assert!(hook.operations.is_empty());
+    assert_eq!(None, hook.project_id);
+}
+
+#[rstest]
+#[case("https://hooks.rusty.ops", SECRET, vec![], None, true)]
+#[case("https://hooks.rusty.ops", SECRET, vec!["create"], Some(PROJECT_ID), true)]
+#[case("ftp://hooks.rusty.ops", SECRET, vec![], None, false)]
+#[case("https://hooks.rusty.ops", "short", vec![], None, false)]
+#[case("https://hooks.rusty.ops", SECRET, vec!["delete"], None, false)]
+#[case("https://hooks.rusty.ops", SECRET, vec![], Some("project"), false)]
+fn validate_event_hook_test(
+    #[case] url: &str,
+    #[case] secret: &str,
+    #[case] operations: Vec<&str>,
+    #[case] project_id: Option<&str>,
+    #[case] expected: bool,
+) {
+    let mut input = RegisterEventHook::new(url, secret);
+    input.operations = operations.into_iter().map(ToString::to_string).collect();
+    input.project_id = project_id.map(ToString::to_string);
+    assert_eq!(expected, input.validate().is_ok())
+}
+
+#[rstest]
+#[case(vec![], vec![], None, "jobs", "create", None, true)]
+#[case(vec!["jobs"], vec![], None, "jobs", "update", None, true)]
+#[case(vec!["pipelines"], vec![], None, "jobs", "update", None, false)]
+#[case(vec![], vec!["create"], None, "jobs", "update", None, false)]
+#[case(vec![], vec![], Some(PROJECT_ID), "jobs", "create", Some(PROJECT_ID), true)]
+#[case(vec![], vec![], Some(PROJECT_ID), "jobs", "create", Some("other"), false)]
+#[case(vec![], vec![], Some(PROJECT_ID), "agents", "create", None, false)]
+fn matches_test(
+    #[case] indexes: Vec<&str>,
+    #[case] operations: Vec<&str>,
+    #[case] filter: Option<&str>,
+    #[case] index: &str,
+    #[case] op: &str,
+    #[case] project_id: Option<&str>,
+    #[case] expected: bool,
+) {
+    let mut input = RegisterEventHook::new("https://hooks.rusty.ops", SECRET);
+    input.indexes = indexes.into_iter().map(ToString::to_string).collect();
+    input.operations = operations.into_iter().map(ToString::to_string).collect();
+    input.project_id = filter.map(ToString::to_string);
+    let hook = EventHook::from(&input);
+    assert_eq!(expected, hook.matches(index, op, project_id));
+}
+
+#[test]
+fn delivery_test() {
+    let item = json!({ "id": "job", "name": "sample" });
+    let delivery = EventHookDelivery::new("hook", "jobs", "create", &item);
+    assert_eq!(36, delivery.id.len());
+    assert_eq!(DeliveryStatus::Pending, delivery.status);
+    assert_eq!(0, delivery.attempts);
+    let payload = serde_json::from_str::<Value>(&delivery.payload).unwrap();
+    assert_eq!(json!(delivery.id), payload["id"]);
+    assert_eq!(json!("jobs"), payload["index"]);
+    assert_eq!(json!("create"), payload["op"]);
+    assert_eq!(item, payload["item"]);
+}
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use rstest::rstest;
+use serde_valid::Validate;
+
+use domain::job_triggers::{creates_cycle, JobTrigger, RegisterJobTrigger, TriggerCondition};
+use domain::pipelines::{Pipeline, PipelineStatus};
+
+const JOB_ID: &str = "871188c7-6a26-41a0-b7a2-1cb97dcdb01a";
+const DOWNSTREAM_JOB_ID: &str = "4d2ae8b9-0a1f-4bb4-8f3c-92f5d0e2a6c7";
+const LAST_JOB_ID: &str = "e5b7d3a9-62c4-4f1e-a8d0-3c9f7b2e6d14";
+const PROJECT_ID: &str = "a2c1b6e0-3f6d-4c8e-9d52-0b7f1e4c9a31";
+
+fn pipeline(branch: &str, status: PipelineStatus) -> Pipeline {
+    Pipeline {
+        id: uuid::Uuid::new_v4().to_string(),
+        number: 1,
+        branch: branch.to_string(),
+        register_date: "now".to_string(),
+        start_date: None,
+        end_date: None,
+        stage_status: HashMap::new(),
+        status,
+        job_id: JOB_ID.to_string(),
+        agent_id: None,
+        commit: None,
+        revision: None,
+        triggered_by: None,
+        upstream_id: None,
+        variables: None,
+    }
+}
+
+fn trigger(job_id: &str, downstream_job_id: &str) -> JobTrigger {
+    JobTrigger::new(
+        &RegisterJobTrigger::new(job_id, downstream_job_id, TriggerCondition::Always),
+        PROJECT_ID,
+    )
+}
+
+#[test]
+fn from_register_job_trigger_test() {
+    let mut input = RegisterJobTrigger::new(JOB_ID, DOWNSTREAM_JOB_ID, TriggerCondition::OnSuccess);
+    input.variables = Some(HashMap::from([("ENV".to_string(), "staging".to_string())]));
+    let trigger = JobTrigger::new(&input, PROJECT_ID);
+    assert_eq!(36, trigger.id.len());
+    assert_eq!(TriggerCondition::OnSuccess, trigger.condition);
+    assert_eq!(None, trigger.branch);
+    assert_eq!(input.variables, trigger.variables);
+    assert_eq!(JOB_ID, trigger.job_id);
+    assert_eq!(DOWNSTREAM_JOB_ID, trigger.downstream_job_id);
+    assert_eq!(PROJECT_ID, trigger.project_id);
+}
+
+#[rstest]
+#[case(
+    RegisterJobTrigger::new(JOB_ID, DOWNSTREAM_JOB_ID, TriggerCondition::Always),
+    true
+)]
+#[case(RegisterJobTrigger::new(JOB_ID, "", TriggerCondition::Always), false)]
+#[case(
+    RegisterJobTrigger::new("", DOWNSTREAM_JOB_ID, TriggerCondition::Always),
+    false
+)]
+fn validate_job_trigger_test(#[case] trigger: RegisterJobTrigger, #[case] expected: bool) {
+    assert_eq!(expected, trigger.validate().is_ok());
+}
+
+#[rstest]
+#[case(TriggerCondition::OnSuccess, PipelineStatus::Success, true)]
+#[case(TriggerCondition::OnSuccess, PipelineStatus::Failure, false)]
+#[case(TriggerCondition::OnFailure, PipelineStatus::Failure, true)]
+#[case(TriggerCondition::OnFailure, PipelineStatus::Unstable, true)]
+#[case(TriggerCondition::OnFailure, PipelineStatus::Success, false)]
+#[case(TriggerCondition::Always, PipelineStatus::Success, true)]
+#[case(TriggerCondition::Always, PipelineStatus::Failure, true)]
+fn condition_matches_test(
+    #[case] condition: TriggerCondition,
+    #[case] status: PipelineStatus,
+    #[case] expected: bool,
+) {
+    assert_eq!(expected, condition.matches(status));
+}
+
+#[rstest]
+#[case(None, "feature/login", true)]
+#[case(Some("master"), "master", true)]
+#[case(Some("master"), "develop", false)]
+#[case(Some("release/*"), "release/1.0", true)]
+#[case(Some("release/*"), "hotfix/1.0", false)]
+fn trigger_matches_branch_test(
+    #[case] branch: Option<&str>,
+    #[case] pipeline_branch: &str,
+    #[case] expected: bool,
+) {
+    let mut trigger = trigger(JOB_ID, DOWNSTREAM_JOB_ID);
+    trigger.branch = branch.map(ToString::to_string);
+    assert_eq!(
+        expected,
+        trigger.matches(&pipeline(pipeline_branch, PipelineStatus::Success))
+    );
+}
+
+#[test]
+fn trigger_matches_other_job_test() {
+    let trigger = trigger(DOWNSTREAM_JOB_ID, JOB_ID);
+    assert!(!trigger.matches(&pipeline("master", PipelineStatus::Success)));
+}
+
+#[rstest]
+#[case(JOB_ID, DOWNSTREAM_JOB_ID, false)]
+#[case(DOWNSTREAM_JOB_ID, JOB_ID, true)]
+#[case(JOB_ID, JOB_ID, true)]
+#[case(LAST_JOB_ID, JOB_ID, true)]
+fn creates_cycle_test(
+    #[case] job_id: &str,
+    #[case] downstream_job_id: &str,
+    #[case] expected: bool,
+) {
+    // JOB_ID -> DOWNSTREAM_JOB_ID -> LAST_JOB_ID
+    let triggers = vec![
+        trigger(JOB_ID, DOWNSTREAM_JOB_ID),
+        trigger(DOWNSTREAM_JOB_ID, LAST_JOB_ID),
+    ];
+    assert_eq!(
+        expected,
+        creates_cycle(&triggers, job_id, downstream_job_id)
+    );
+}
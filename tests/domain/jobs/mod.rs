@@ -1,7 +1,7 @@
 use rstest::rstest;
 use serde_valid::Validate;
 
-use domain::jobs::{Job, RegisterJob};
+use domain::jobs::{Job, RegisterJob, UpdateJob};
 
 #[test]
 fn from_register_job_test() {
@@ -37,3 +37,33 @@ const TEMPLATE_MINIMAL: &str =
 fn validate_user_test(#[case] job: RegisterJob, #[case] expected: bool) {
     assert_eq!(expected, job.validate().is_ok())
 }
+
+#[test]
+fn update_job_test() {
+    let job = Job::from(&RegisterJob::new(
+        "test_01",
+        "desc",
+        TEMPLATE_MINIMAL,
+        PROJECT_ID,
+    ));
+    let update = UpdateJob {
+        name: Some("test_02".to_string()),
+        description: Some(String::new()),
+        template: None,
+    };
+    let updated = update.apply(&job);
+    assert_eq!(job.id, updated.id);
+    assert_eq!("test_02", updated.name);
+    assert_eq!(None, updated.description);
+    assert_eq!(job.template, updated.template);
+    assert_eq!(job.project_id, updated.project_id);
+}
+
+#[rstest]
+#[case(UpdateJob::default(), true)]
+#[case(UpdateJob { template: Some(TEMPLATE_MINIMAL.to_string()), ..Default::default() }, true)]
+#[case(UpdateJob { template: Some("dfghfhfghf".to_string()), ..Default::default() }, false)]
+#[case(UpdateJob { name: Some(String::new()), ..Default::default() }, false)]
+fn validate_update_job_test(#[case] job: UpdateJob, #[case] expected: bool) {
+    assert_eq!(expected, job.validate().is_ok())
+}
@@ -4,17 +4,47 @@ mod agents;
 #[cfg(test)]
 mod auth;
 
+#[cfg(test)]
+mod checkouts;
+
 #[cfg(test)]
 mod commons;
 
+#[cfg(test)]
+mod coverage;
+
+#[cfg(test)]
+mod deployments;
+
+#[cfg(test)]
+mod event_hooks;
+
+#[cfg(test)]
+mod job_triggers;
+
 #[cfg(test)]
 mod jobs;
 
+#[cfg(test)]
+mod notifications;
+
 #[cfg(test)]
 mod pipelines;
 
 #[cfg(test)]
 mod projects;
 
+#[cfg(test)]
+mod registries;
+
+#[cfg(test)]
+mod schedules;
+
 #[cfg(test)]
 mod templates;
+
+#[cfg(test)]
+mod test_reports;
+
+#[cfg(test)]
+mod webhooks;
@@ -0,0 +1,105 @@
+use rstest::rstest;
+use serde_valid::Validate;
+
+use domain::notifications::{
+    Notification, NotificationChannel, NotificationDelivery, NotificationRule, RegisterNotification,
+};
+use domain::pipelines::PipelineStatus;
+
+const PROJECT_ID: &str = "871188c7-6a26-41a0-b7a2-1cb97dcdb01a";
+
+#[test]
+fn from_register_notification_test() {
+    let input = RegisterNotification::new(
+        NotificationChannel::Smtp,
+        "dev@rusty.ops",
+        NotificationRule::OnFailure,
+        PROJECT_ID,
+    );
+    let notification = Notification::from(&input);
+    assert_eq!(36, notification.id.len());
+    assert_eq!(NotificationChannel::Smtp, notification.channel);
+    assert_eq!("dev@rusty.ops", notification.target);
+    assert_eq!(NotificationRule::OnFailure, notification.rule);
+    assert_eq!(None, notification.branch);
+    assert_eq!(PROJECT_ID, notification.project_id);
+}
+
+#[rstest]
+#[case(NotificationChannel::Smtp, "dev@rusty.ops", PROJECT_ID, true)]
+#[case(
+    NotificationChannel::Slack,
+    "https://hooks.slack.com/x",
+    PROJECT_ID,
+    true
+)]
+#[case(NotificationChannel::Webhook, "", PROJECT_ID, false)]
+#[case(NotificationChannel::Webhook, "http://localhost", "", false)]
+fn validate_notification_test(
+    #[case] channel: NotificationChannel,
+    #[case] target: &str,
+    #[case] project_id: &str,
+    #[case] expected: bool,
+) {
+    let input = RegisterNotification::new(channel, target, NotificationRule::EveryRun, project_id);
+    assert_eq!(expected, input.validate().is_ok())
+}
+
+#[rstest]
+#[case(NotificationChannel::Smtp, "dev@rusty.ops", true)]
+#[case(NotificationChannel::Smtp, "https://rusty.ops", false)]
+#[case(NotificationChannel::Slack, "https://hooks.slack.com/services/x", true)]
+#[case(NotificationChannel::Slack, "dev@rusty.ops", false)]
+#[case(NotificationChannel::Webhook, "http://localhost:8080/hook", true)]
+#[case(NotificationChannel::Webhook, "ftp://localhost/hook", false)]
+fn validate_target_test(
+    #[case] channel: NotificationChannel,
+    #[case] target: &str,
+    #[case] expected: bool,
+) {
+    let input = RegisterNotification::new(channel, target, NotificationRule::EveryRun, PROJECT_ID);
+    assert_eq!(expected, input.validate_target().is_ok())
+}
+
+#[rstest]
+#[case(NotificationRule::EveryRun, PipelineStatus::Success, None, true)]
+#[case(NotificationRule::EveryRun, PipelineStatus::Failure, None, true)]
+#[case(NotificationRule::OnFailure, PipelineStatus::Failure, None, true)]
+#[case(NotificationRule::OnFailure, PipelineStatus::Unstable, None, true)]
+#[case(NotificationRule::OnFailure, PipelineStatus::Success, None, false)]
+#[case(
+    NotificationRule::OnFixed,
+    PipelineStatus::Success,
+    Some(PipelineStatus::Failure),
+    true
+)]
+#[case(
+    NotificationRule::OnFixed,
+    PipelineStatus::Success,
+    Some(PipelineStatus::Success),
+    false
+)]
+#[case(NotificationRule::OnFixed, PipelineStatus::Success, None, false)]
+#[case(
+    NotificationRule::OnFixed,
+    PipelineStatus::Failure,
+    Some(PipelineStatus::Failure),
+    false
+)]
+fn rule_matches_test(
+    #[case] rule: NotificationRule,
+    #[case] status: PipelineStatus,
+    #[case] previous: Option<PipelineStatus>,
+    #[case] expected: bool,
+) {
+    assert_eq!(expected, rule.matches(status, previous));
+}
+
+#[rstest]
+#[case(None, true)]
+#[case(Some("timeout".to_string()), false)]
+fn delivery_test(#[case] error: Option<String>, #[case] delivered: bool) {
+    let delivery = NotificationDelivery::new("notification", "pipeline", 1, error);
+    assert_eq!(36, delivery.id.len());
+    assert_eq!(delivered, delivery.delivered);
+}
@@ -1,4 +1,6 @@
-use domain::projects::{Group, RegisterGroup};
+use serde_valid::Validate;
+
+use domain::projects::{Group, RegisterGroup, UpdateGroup};
 
 #[test]
 fn from_register_project_test() {
@@ -8,3 +10,25 @@ fn from_register_project_test() {
     assert_eq!(36, group.id.len());
     assert_eq!(name.to_string(), group.name);
 }
+
+#[test]
+fn update_group_test() {
+    let group = Group::from(&RegisterGroup::new("test_group_01"));
+    let updated = UpdateGroup {
+        name: Some("test_group_02".to_string()),
+    }
+    .apply(&group);
+    assert_eq!(group.id, updated.id);
+    assert_eq!("test_group_02", updated.name);
+    assert_eq!(group.name, UpdateGroup::default().apply(&group).name);
+}
+
+#[test]
+fn validate_update_group_test() {
+    assert!(UpdateGroup::default().validate().is_ok());
+    assert!(UpdateGroup {
+        name: Some(String::new())
+    }
+    .validate()
+    .is_err());
+}
@@ -1,7 +1,7 @@
 use rstest::rstest;
 use serde_valid::Validate;
 
-use domain::projects::{Project, RegisterProject};
+use domain::projects::{Project, RegisterProject, UpdateProject};
 
 #[test]
 fn from_register_project_test() {
@@ -24,3 +24,37 @@ fn from_register_project_test() {
 fn validate_project_test(#[case] project: RegisterProject, #[case] expected: bool) {
     assert_eq!(expected, project.validate().is_ok())
 }
+
+#[test]
+fn update_project_test() {
+    let project = Project::from(&RegisterProject::new("test_01", "http://dummy"));
+    let update = UpdateProject {
+        name: Some("test_02".to_string()),
+        group_id: Some("871188c7-6a26-41a0-b7a2-1cb97dcdb01a".to_string()),
+        ..Default::default()
+    };
+    let updated = update.apply(&project);
+    assert_eq!(project.id, updated.id);
+    assert_eq!("test_02", updated.name);
+    assert_eq!(project.url, updated.url);
+    assert_eq!(project.main_branch, updated.main_branch);
+    assert_eq!(update.group_id, updated.group_id);
+
+    let update = UpdateProject {
+        group_id: Some(String::new()),
+        ..Default::default()
+    };
+    assert_eq!(None, update.apply(&updated).group_id);
+}
+
+#[rstest]
+#[case(UpdateProject::default(), true)]
+#[case(UpdateProject { name: Some("name".to_string()), ..Default::default() }, true)]
+#[case(UpdateProject { name: Some(String::new()), ..Default::default() }, false)]
+#[case(UpdateProject { url: Some("http".to_string()), ..Default::default() }, false)]
+#[case(UpdateProject { main_branch: Some(String::new()), ..Default::default() }, false)]
+#[case(UpdateProject { group_id: Some(String::new()), ..Default::default() }, true)]
+#[case(UpdateProject { group_id: Some("uuid".to_string()), ..Default::default() }, false)]
+fn validate_update_project_test(#[case] project: UpdateProject, #[case] expected: bool) {
+    assert_eq!(expected, project.validate().is_ok())
+}
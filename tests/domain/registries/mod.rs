@@ -0,0 +1,105 @@
+use rstest::rstest;
+use serde_valid::Validate;
+
+use domain::registries::{
+    image_registry, split_tag, RegisterRegistryCredential, RegistryAuth, RegistryCredential,
+};
+
+const PROJECT_ID: &str = "57c38e8b-1845-49f1-874a-1eefe9923456";
+
+#[rstest]
+#[case("alpine", "docker.io")]
+#[case("postgres:16", "docker.io")]
+#[case("bitnami/redis:7.2", "docker.io")]
+#[case("docker.io/library/alpine:3.20", "docker.io")]
+#[case("index.docker.io/library/alpine", "docker.io")]
+#[case("ghcr.io/org/image:latest", "ghcr.io")]
+#[case("GHCR.io/org/image", "ghcr.io")]
+#[case("localhost/image", "localhost")]
+#[case("localhost:5000/image:1.0", "localhost:5000")]
+#[case(
+    "registry.example.com:8443/team/app@sha256:abc",
+    "registry.example.com:8443"
+)]
+fn image_registry_test(#[case] image: &str, #[case] expected: &str) {
+    assert_eq!(expected, image_registry(image));
+}
+
+#[rstest]
+#[case("ghcr.io", "ghcr.io")]
+#[case("https://ghcr.io/", "ghcr.io")]
+#[case("https://index.docker.io/v1/", "docker.io")]
+#[case("registry-1.docker.io", "docker.io")]
+#[case(" Registry.Example.com:8443 ", "registry.example.com:8443")]
+fn from_register_registry_credential_test(#[case] registry: &str, #[case] expected: &str) {
+    let input = RegisterRegistryCredential::new(registry, "user", "secret", PROJECT_ID);
+    let credential = RegistryCredential::from(&input);
+    assert_eq!(36, credential.id.len());
+    assert_eq!(expected, credential.registry);
+    assert_eq!("user", credential.username);
+    assert_eq!("secret", credential.password);
+    assert_eq!(PROJECT_ID, credential.project_id);
+}
+
+#[rstest]
+#[case("ghcr.io", "ghcr.io/org/image", true)]
+#[case("ghcr.io", "alpine", false)]
+#[case("docker.io", "alpine", true)]
+#[case("https://index.docker.io/v1/", "bitnami/redis", true)]
+#[case("localhost:5000", "localhost:5000/image", true)]
+#[case("localhost:5000", "localhost/image", false)]
+fn matches_test(#[case] registry: &str, #[case] image: &str, #[case] expected: bool) {
+    let credential = RegistryCredential::from(&RegisterRegistryCredential::new(
+        registry, "user", "secret", PROJECT_ID,
+    ));
+    assert_eq!(expected, credential.matches(image));
+}
+
+#[rstest]
+#[case(
+    RegisterRegistryCredential::new("ghcr.io", "user", "secret", PROJECT_ID),
+    true
+)]
+#[case(
+    RegisterRegistryCredential::new("", "user", "secret", PROJECT_ID),
+    false
+)]
+#[case(
+    RegisterRegistryCredential::new("ghcr.io", "", "secret", PROJECT_ID),
+    false
+)]
+#[case(
+    RegisterRegistryCredential::new("ghcr.io", "user", "", PROJECT_ID),
+    false
+)]
+#[case(
+    RegisterRegistryCredential::new("ghcr.io", "user", "secret", "project"),
+    false
+)]
+fn validate_registry_credential_test(
+    #[case] credential: RegisterRegistryCredential,
+    #[case] expected: bool,
+) {
+    assert_eq!(expected, credential.validate().is_ok());
+}
+
+#[test]
+fn registry_auth_from_credential_test() {
+    let credential = RegistryCredential::from(&RegisterRegistryCredential::new(
+        "ghcr.io", "user", "secret", PROJECT_ID,
+    ));
+    let auth = RegistryAuth::from(&credential);
+    assert_eq!("ghcr.io", auth.registry);
+    assert_eq!("user", auth.username);
+    assert_eq!("secret", auth.password);
+}
+
+#[rstest]
+#[case("app", "app", "latest")]
+#[case("app:1.0", "app", "1.0")]
+#[case("ghcr.io/org/app:1.0", "ghcr.io/org/app", "1.0")]
+#[case("localhost:5000/app", "localhost:5000/app", "latest")]
+#[case("localhost:5000/team/app:dev", "localhost:5000/team/app", "dev")]
+fn split_tag_test(#[case] image: &str, #[case] repository: &str, #[case] tag: &str) {
+    assert_eq!((repository, tag), split_tag(image));
+}
@@ -0,0 +1,94 @@
+use chrono::{TimeZone, Utc};
+use rstest::rstest;
+use serde_valid::Validate;
+
+use domain::schedules::{parse_cron, RegisterSchedule, Schedule, UpdateSchedule};
+
+const JOB_ID: &str = "871188c7-6a26-41a0-b7a2-1cb97dcdb01a";
+
+#[test]
+fn from_register_schedule_test() {
+    let input = RegisterSchedule::new("0 2 * * *", "main", JOB_ID);
+    let schedule = Schedule::from(&input);
+    assert_eq!(36, schedule.id.len());
+    assert_eq!("0 2 * * *", schedule.cron);
+    assert_eq!(Some("main".to_string()), schedule.branch);
+    assert!(schedule.skip_if_running);
+    assert_eq!(None, schedule.last_run);
+    assert_eq!(JOB_ID, schedule.job_id);
+}
+
+#[rstest]
+#[case(RegisterSchedule::new("0 2 * * *", "main", JOB_ID), true)]
+#[case(RegisterSchedule::new("*/5 * * * *", "", JOB_ID), true)]
+#[case(RegisterSchedule::new("0 0 2 * * * *", "", JOB_ID), true)]
+#[case(RegisterSchedule::new("0 25 * * *", "main", JOB_ID), false)]
+#[case(RegisterSchedule::new("every day", "main", JOB_ID), false)]
+#[case(RegisterSchedule::new("", "main", JOB_ID), false)]
+#[case(RegisterSchedule::new("0 2 * * *", "main", ""), false)]
+fn validate_schedule_test(#[case] schedule: RegisterSchedule, #[case] expected: bool) {
+    assert_eq!(expected, schedule.validate().is_ok())
+}
+
+#[test]
+fn update_schedule_test() {
+    let schedule = Schedule::from(&RegisterSchedule::new("0 2 * * *", "main", JOB_ID));
+    let update = UpdateSchedule {
+        cron: Some("*/5 * * * *".to_string()),
+        skip_if_running: Some(false),
+        ..Default::default()
+    };
+    let updated = update.apply(&schedule);
+    assert_eq!(schedule.id, updated.id);
+    assert_eq!("*/5 * * * *", updated.cron);
+    assert_eq!(schedule.branch, updated.branch);
+    assert!(!updated.skip_if_running);
+    assert_eq!(schedule.job_id, updated.job_id);
+
+    let update = UpdateSchedule {
+        branch: Some(String::new()),
+        ..Default::default()
+    };
+    assert_eq!(None, update.apply(&updated).branch);
+}
+
+#[rstest]
+#[case(UpdateSchedule::default(), true)]
+#[case(UpdateSchedule { cron: Some("0 2 * * *".to_string()), ..Default::default() }, true)]
+#[case(UpdateSchedule { cron: Some("0 25 * * *".to_string()), ..Default::default() }, false)]
+#[case(UpdateSchedule { branch: Some(String::new()), ..Default::default() }, true)]
+fn validate_update_schedule_test(#[case] schedule: UpdateSchedule, #[case] expected: bool) {
+    assert_eq!(expected, schedule.validate().is_ok())
+}
+
+#[test]
+fn parse_cron_test() {
+    let since = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+    let next = parse_cron("0 2 * * *").unwrap().after(&since).next();
+    assert_eq!(
+        Some(Utc.with_ymd_and_hms(2024, 1, 2, 2, 0, 0).unwrap()),
+        next
+    );
+}
+
+#[rstest]
+#[case(None, "2024-01-01T01:00:00+00:00", false)]
+#[case(None, "2024-01-01T02:00:00+00:00", true)]
+#[case(Some("2024-01-01T02:00:00+00:00"), "2024-01-01T03:00:00+00:00", false)]
+#[case(Some("2024-01-01T02:00:00+00:00"), "2024-01-02T02:00:00+00:00", true)]
+fn is_due_test(#[case] last_run: Option<&str>, #[case] now: &str, #[case] expected: bool) {
+    let mut schedule = Schedule::from(&RegisterSchedule::new("0 2 * * *", "", JOB_ID));
+    schedule.register_date = "2024-01-01T00:00:00+00:00".to_string();
+    schedule.last_run = last_run.map(ToString::to_string);
+    let now = chrono::DateTime::parse_from_rfc3339(now)
+        .unwrap()
+        .with_timezone(&Utc);
+    assert_eq!(expected, schedule.is_due(&now).unwrap());
+}
+
+#[test]
+fn is_due_invalid_date_test() {
+    let mut schedule = Schedule::from(&RegisterSchedule::new("0 2 * * *", "", JOB_ID));
+    schedule.register_date = "invalid".to_string();
+    assert!(schedule.is_due(&Utc::now()).is_err());
+}
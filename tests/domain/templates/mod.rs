@@ -1 +1,10 @@
 mod pipelines;
+
+#[cfg(test)]
+mod revisions;
+
+#[cfg(test)]
+mod schema;
+
+#[cfg(test)]
+mod validation;
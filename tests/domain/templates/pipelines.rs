@@ -1,5 +1,10 @@
+use rstest::rstest;
+
 use commons::errors::RustyError;
-use domain::templates::pipeline::PipelineTemplate;
+use domain::templates::pipeline::{
+    parse_memory, Environment, PipelineTemplate, PullPolicy, Reports, Resources, RunnerKind,
+    Script, Security, Service, TemplateFormat, TriggerEvent,
+};
 
 #[test]
 fn validate_from_yaml_minimal_test() {
@@ -187,6 +192,33 @@ fn validate_from_yaml_error_depends_on_unknown_stage_test() {
     );
 }
 
+#[test]
+fn validate_from_yaml_error_circular_dependencies_test() {
+    let yaml = r#"
+    stages:
+      test_1:
+        script:
+          - echo "hello"
+        depends_on:
+          - test_2
+      test_2:
+        script:
+          - echo "hello"
+        depends_on:
+          - test_1
+    "#;
+
+    let encoded = base64_url::encode(&yaml);
+    let pipeline = PipelineTemplate::from_yaml(&encoded);
+    assert!(pipeline.is_err());
+    assert_eq!(
+        RustyError::SerializationError(
+            "Pipeline template: [stages have circular dependencies]".to_string()
+        ),
+        pipeline.unwrap_err()
+    );
+}
+
 #[test]
 fn build_dependency_tree() {
     let yaml = r#"
@@ -218,3 +250,607 @@ fn build_dependency_tree() {
     assert_eq!(vec!["test_2"], dependency_tree[1]);
     assert_eq!(vec!["test_3"], dependency_tree[2]);
 }
+
+#[rstest]
+#[case(TriggerEvent::Push, "master", true)]
+#[case(TriggerEvent::Push, "feature/test", false)]
+#[case(TriggerEvent::PullRequest, "master", false)]
+fn is_triggered_default_test(
+    #[case] event: TriggerEvent,
+    #[case] branch: &str,
+    #[case] expected: bool,
+) {
+    let yaml = r#"
+    stages:
+       test:
+          script:
+            - echo "hello"
+    "#;
+    let encoded = base64_url::encode(&yaml);
+    let pipeline = PipelineTemplate::from_yaml(&encoded).unwrap();
+    assert_eq!(expected, pipeline.is_triggered(event, branch, "master"));
+}
+
+#[rstest]
+#[case(TriggerEvent::Push, "master", false)]
+#[case(TriggerEvent::Push, "develop", true)]
+#[case(TriggerEvent::Push, "release/1.0", true)]
+#[case(TriggerEvent::Push, "release", false)]
+#[case(TriggerEvent::PullRequest, "develop", false)]
+fn is_triggered_filter_test(
+    #[case] event: TriggerEvent,
+    #[case] branch: &str,
+    #[case] expected: bool,
+) {
+    let yaml = r#"
+    trigger:
+      events:
+        - push
+      branches:
+        - develop
+        - release/*
+    stages:
+       test:
+          script:
+            - echo "hello"
+    "#;
+    let encoded = base64_url::encode(&yaml);
+    let pipeline = PipelineTemplate::from_yaml(&encoded).unwrap();
+    assert_eq!(expected, pipeline.is_triggered(event, branch, "master"));
+}
+
+const TEMPLATE_YAML: &str = r#"
+env:
+  b: "2"
+  a: "1"
+stages:
+  build:
+    script:
+      - echo "build"
+  test:
+    script:
+      - echo "test"
+    dependsOn:
+      - build
+"#;
+
+const TEMPLATE_JSON: &str = r#"{
+  "env": { "a": "1", "b": "2" },
+  "stages": {
+    "build": { "script": ["echo \"build\""] },
+    "test": { "script": ["echo \"test\""], "depends_on": ["build"] }
+  }
+}"#;
+
+const TEMPLATE_TOML: &str = r#"
+[env]
+a = "1"
+b = "2"
+
+[stages.build]
+script = ["echo \"build\""]
+
+[stages.test]
+script = ["echo \"test\""]
+depends_on = ["build"]
+"#;
+
+#[rstest]
+#[case(TEMPLATE_YAML, TemplateFormat::Yaml)]
+#[case(TEMPLATE_JSON, TemplateFormat::Json)]
+#[case(TEMPLATE_TOML, TemplateFormat::Toml)]
+#[case(
+    "c3RhZ2VzOgogICB0ZXN0OgogICAgICBzY3JpcHQ6CiAgICAgICAgLSBlY2hvICJoZWxsbyI",
+    TemplateFormat::Base64
+)]
+fn detect_format_test(#[case] template: &str, #[case] expected: TemplateFormat) {
+    assert_eq!(expected, TemplateFormat::detect(template));
+}
+
+#[rstest]
+#[case(TEMPLATE_YAML.to_string(), None)]
+#[case(TEMPLATE_JSON.to_string(), None)]
+#[case(TEMPLATE_TOML.to_string(), None)]
+#[case(base64_url::encode(TEMPLATE_TOML), None)]
+#[case(TEMPLATE_YAML.to_string(), Some(TemplateFormat::Yaml))]
+fn parse_formats_test(#[case] template: String, #[case] format: Option<TemplateFormat>) {
+    let pipeline = PipelineTemplate::parse(&template, format);
+    assert!(pipeline.is_ok());
+    let pipeline = pipeline.unwrap();
+    assert_eq!(
+        vec![vec!["build"], vec!["test"]],
+        pipeline.dependency_tree()
+    );
+    assert_eq!("1", pipeline.env.unwrap()["a"]);
+}
+
+#[test]
+fn parse_wrong_format_test() {
+    let pipeline = PipelineTemplate::parse(TEMPLATE_YAML, Some(TemplateFormat::Json));
+    assert!(pipeline.is_err());
+}
+
+#[test]
+fn normalize_test() {
+    let yaml = PipelineTemplate::normalize(TEMPLATE_YAML, None).unwrap();
+    let json = PipelineTemplate::normalize(TEMPLATE_JSON, None).unwrap();
+    let toml = PipelineTemplate::normalize(&base64_url::encode(TEMPLATE_TOML), None).unwrap();
+    assert_eq!(yaml, json);
+    assert_eq!(yaml, toml);
+    assert!(yaml.starts_with("env:\n  a: "));
+    assert!(PipelineTemplate::from_yaml(&yaml).is_ok());
+}
+
+#[rstest]
+#[case("postgres", None, "postgres")]
+#[case("postgres:16-alpine", None, "postgres")]
+#[case("docker.io/bitnami/rabbitmq:3.13", None, "rabbitmq")]
+#[case("redis@sha256:0123", None, "redis")]
+#[case("postgres:16", Some("db"), "db")]
+fn service_hostname_test(#[case] image: &str, #[case] alias: Option<&str>, #[case] expected: &str) {
+    let service = Service {
+        image: image.to_string(),
+        alias: alias.map(ToString::to_string),
+        env: None,
+        ports: None,
+    };
+    assert_eq!(expected, service.hostname());
+}
+
+#[test]
+fn validate_from_yaml_services_test() {
+    let yaml = r#"
+    image: rust:alpine
+    services:
+      - image: postgres:16
+        env:
+          POSTGRES_PASSWORD: secret
+        ports:
+          - 5432
+    stages:
+      test:
+        services:
+          - image: rabbitmq:3
+            alias: broker
+        script:
+          - cargo test
+    "#;
+
+    let pipeline = PipelineTemplate::from_yaml(yaml);
+    assert!(pipeline.is_ok());
+    let pipeline = pipeline.unwrap();
+    assert_eq!(1, pipeline.services.unwrap().len());
+    assert_eq!(
+        "broker",
+        pipeline.stages[0].clone().services.unwrap()[0].hostname()
+    );
+}
+
+#[rstest]
+#[case(
+    "stages:\n  test:\n    services:\n      - image: postgres\n    script:\n      - echo\n",
+    "services require a pipeline docker image"
+)]
+#[case(
+    "image: alpine\nservices:\n  - image: postgres\nstages:\n  test:\n    services:\n      - image: postgres:16\n    script:\n      - echo\n",
+    "services.alias must be unique"
+)]
+#[case(
+    "image: alpine\nservices:\n  - image: postgres\n    alias: not a hostname\nstages:\n  test:\n    script:\n      - echo\n",
+    "services.alias must be a valid hostname"
+)]
+#[case(
+    "image: alpine\nservices:\n  - image: ''\nstages:\n  test:\n    script:\n      - echo\n",
+    "services.image cannot be empty, services.alias must be a valid hostname"
+)]
+fn validate_from_yaml_error_services_test(#[case] yaml: &str, #[case] error: &str) {
+    let pipeline = PipelineTemplate::from_yaml(yaml);
+    assert!(pipeline.is_err());
+    assert_eq!(
+        RustyError::SerializationError(format!("Pipeline template: [{error}]")),
+        pipeline.unwrap_err()
+    );
+}
+
+fn run_script(lines: &[&str]) -> (i32, String) {
+    let script = Script::new(
+        &lines
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>(),
+    );
+    let output = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(script.to_shell())
+        .output()
+        .unwrap();
+    (
+        output.status.code().unwrap(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+#[rstest]
+#[case(&["cd /tmp", "pwd"], 0, "/tmp\n")]
+#[case(&["export KEY=value", "echo $KEY | tr a-z A-Z"], 0, "VALUE\n")]
+#[case(&["true && echo ok"], 0, "ok\n")]
+#[case(&["echo first", "exit 3", "echo never"], 3, "first\n")]
+#[case(&["false | true", "echo piped"], 0, "piped\n")]
+#[case(&["echo multi\necho line"], 0, "multi\nline\n")]
+fn script_to_shell_test(
+    #[case] lines: &[&str],
+    #[case] expected_code: i32,
+    #[case] expected_output: &str,
+) {
+    let (code, output) = run_script(lines);
+    assert_eq!(expected_code, code);
+    assert_eq!(expected_output, output);
+}
+
+#[test]
+fn validate_from_yaml_shell_test() {
+    let yaml = r#"
+    image: alpine
+    shell: bash
+    stages:
+      test:
+        shell: zsh
+        script:
+          - echo "hello"
+    "#;
+
+    let pipeline = PipelineTemplate::from_yaml(yaml).unwrap();
+    assert_eq!(Some("bash".to_string()), pipeline.shell);
+    assert_eq!(Some("zsh".to_string()), pipeline.stages[0].clone().shell);
+}
+
+#[rstest]
+#[case("", None)]
+#[case("pull_policy: always", Some(PullPolicy::Always))]
+#[case("pull_policy: if-not-present", Some(PullPolicy::IfNotPresent))]
+#[case("pull_policy: never", Some(PullPolicy::Never))]
+fn validate_from_yaml_pull_policy_test(#[case] policy: &str, #[case] expected: Option<PullPolicy>) {
+    let yaml =
+        format!("image: alpine\n{policy}\nstages:\n  test:\n    script:\n      - echo \"hello\"\n");
+    let pipeline = PipelineTemplate::from_yaml(&yaml).unwrap();
+    assert_eq!(expected, pipeline.pull_policy);
+    assert_eq!(
+        expected.unwrap_or(PullPolicy::IfNotPresent),
+        pipeline.pull_policy.unwrap_or_default()
+    );
+}
+
+#[test]
+fn validate_from_yaml_error_pull_policy_test() {
+    let yaml = "image: alpine\npull_policy: sometimes\nstages:\n  test:\n    script:\n      - echo \"hello\"\n";
+    assert!(PipelineTemplate::from_yaml(yaml).is_err());
+}
+
+#[rstest]
+#[case("1048576", Some(1_048_576))]
+#[case("512k", Some(512 * 1024))]
+#[case("512m", Some(512 * 1024 * 1024))]
+#[case("512MB", Some(512 * 1024 * 1024))]
+#[case("2g", Some(2 * 1024 * 1024 * 1024))]
+#[case("1t", Some(1024 * 1024 * 1024 * 1024))]
+#[case("0m", None)]
+#[case("-1g", None)]
+#[case("1.5g", None)]
+#[case("2x", None)]
+#[case("", None)]
+fn parse_memory_test(#[case] value: &str, #[case] expected: Option<i64>) {
+    assert_eq!(expected, parse_memory(value));
+}
+
+#[test]
+fn validate_from_yaml_resources_test() {
+    let yaml = r#"
+    image: alpine
+    stages:
+      test:
+        resources:
+          cpus: 1.5
+          memory: 512m
+          pids: 100
+        security:
+          user: "1000:1000"
+          cap_drop:
+            - ALL
+          network: false
+        script:
+          - echo "hello"
+    "#;
+
+    let pipeline = PipelineTemplate::from_yaml(yaml).unwrap();
+    let stage = pipeline.stages[0].clone();
+    assert_eq!(
+        Some(Resources {
+            cpus: Some(1.5),
+            memory: Some("512m".to_string()),
+            pids: Some(100),
+        }),
+        stage.resources
+    );
+    assert_eq!(
+        Some(512 * 1024 * 1024),
+        stage.resources.unwrap().memory_bytes()
+    );
+    assert_eq!(
+        Some(Security {
+            user: Some("1000:1000".to_string()),
+            cap_drop: Some(vec!["ALL".to_string()]),
+            network: Some(false),
+        }),
+        stage.security
+    );
+}
+
+#[rstest]
+#[case(
+    "image: alpine\nstages:\n  test:\n    resources:\n      cpus: 0\n    script:\n      - echo\n",
+    "resources.cpus must be positive"
+)]
+#[case(
+    "image: alpine\nstages:\n  test:\n    resources:\n      memory: lots\n    script:\n      - echo\n",
+    "resources.memory must be a size, e.g. `512m`"
+)]
+#[case(
+    "image: alpine\nstages:\n  test:\n    resources:\n      pids: -1\n    script:\n      - echo\n",
+    "resources.pids must be positive"
+)]
+#[case(
+    "image: alpine\nstages:\n  test:\n    security:\n      user: ''\n    script:\n      - echo\n",
+    "security.user cannot be empty"
+)]
+#[case(
+    "image: alpine\nstages:\n  test:\n    security:\n      network: false\n    services:\n      - image: postgres\n    script:\n      - echo\n",
+    "stage services require network access"
+)]
+fn validate_from_yaml_error_resources_test(#[case] yaml: &str, #[case] error: &str) {
+    let pipeline = PipelineTemplate::from_yaml(yaml);
+    assert!(pipeline.is_err());
+    assert_eq!(
+        RustyError::SerializationError(format!("Pipeline template: [{error}]")),
+        pipeline.unwrap_err()
+    );
+}
+
+#[test]
+fn resources_or_test() {
+    let stage = Resources {
+        cpus: Some(2.0),
+        memory: None,
+        pids: None,
+    };
+    let defaults = Resources {
+        cpus: Some(1.0),
+        memory: Some("1g".to_string()),
+        pids: None,
+    };
+    assert_eq!(
+        Resources {
+            cpus: Some(2.0),
+            memory: Some("1g".to_string()),
+            pids: None,
+        },
+        stage.or(&defaults)
+    );
+}
+
+#[rstest]
+#[case(Resources::default(), Resources::default(), Resources::default(), vec![])]
+#[case(
+    Resources { cpus: Some(8.0), memory: Some("4g".to_string()), pids: Some(1000) },
+    Resources { cpus: Some(2.0), memory: Some("1g".to_string()), pids: Some(500) },
+    Resources { cpus: Some(2.0), memory: Some("1g".to_string()), pids: Some(500) },
+    vec!["cpus", "memory", "pids"],
+)]
+#[case(
+    Resources { cpus: Some(1.0), memory: Some("512m".to_string()), pids: None },
+    Resources { cpus: Some(2.0), memory: Some("1g".to_string()), pids: Some(500) },
+    Resources { cpus: Some(1.0), memory: Some("512m".to_string()), pids: Some(500) },
+    vec![],
+)]
+fn resources_capped_test(
+    #[case] resources: Resources,
+    #[case] max: Resources,
+    #[case] expected: Resources,
+    #[case] lowered: Vec<&str>,
+) {
+    assert_eq!((expected, lowered), resources.capped(&max));
+}
+
+#[test]
+fn security_or_test() {
+    let stage = Security {
+        user: None,
+        cap_drop: Some(vec![]),
+        network: Some(true),
+    };
+    let defaults = Security {
+        user: Some("nobody".to_string()),
+        cap_drop: Some(vec!["ALL".to_string()]),
+        network: Some(false),
+    };
+    assert_eq!(
+        Security {
+            user: Some("nobody".to_string()),
+            cap_drop: Some(vec![]),
+            network: Some(true),
+        },
+        stage.or(&defaults)
+    );
+}
+
+#[test]
+fn validate_from_yaml_build_test() {
+    let yaml = r#"
+    image: alpine
+    stages:
+      image:
+        env:
+          VERSION: "1.0"
+        build:
+          context: app
+          tags:
+            - ghcr.io/org/app:1.0
+            - ghcr.io/org/app:latest
+          push: true
+    "#;
+
+    let pipeline = PipelineTemplate::from_yaml(yaml).unwrap();
+    let stage = pipeline.stages[0].clone();
+    assert!(stage.script.is_empty());
+    let build = stage.build.unwrap();
+    assert_eq!("app", build.context());
+    assert_eq!("Dockerfile", build.dockerfile());
+    assert_eq!(2, build.tags.len());
+    assert_eq!(Some(true), build.push);
+}
+
+#[test]
+fn build_defaults_test() {
+    let yaml = "image: alpine\nstages:\n  image:\n    build:\n      tags:\n        - app\n";
+    let build = PipelineTemplate::from_yaml(yaml).unwrap().stages[0]
+        .clone()
+        .build
+        .unwrap();
+    assert_eq!(".", build.context());
+    assert_eq!("Dockerfile", build.dockerfile());
+    assert_eq!(None, build.push);
+}
+
+#[rstest]
+#[case(
+    "image: alpine\nstages:\n  image:\n    build:\n      tags:\n        - app\n    script:\n      - echo\n",
+    "build stages cannot have a script"
+)]
+#[case(
+    "stages:\n  image:\n    build:\n      tags:\n        - app\n",
+    "build stages require a pipeline docker image"
+)]
+#[case(
+    "image: alpine\nstages:\n  image:\n    build:\n      tags: []\n",
+    "build.tags cannot be empty"
+)]
+#[case(
+    "image: alpine\nstages:\n  image:\n    build:\n      context: ../other\n      tags:\n        - app\n",
+    "build paths must be relative and stay in the repository"
+)]
+#[case(
+    "image: alpine\nstages:\n  image:\n    build:\n      dockerfile: /etc/Dockerfile\n      tags:\n        - app\n",
+    "build paths must be relative and stay in the repository"
+)]
+#[case(
+    "image: alpine\nstages:\n  image:\n    env:\n      A: b\n",
+    "stages.script cannot be empty"
+)]
+fn validate_from_yaml_error_build_test(#[case] yaml: &str, #[case] error: &str) {
+    let pipeline = PipelineTemplate::from_yaml(yaml);
+    assert!(pipeline.is_err());
+    assert_eq!(
+        RustyError::SerializationError(format!("Pipeline template: [{error}]")),
+        pipeline.unwrap_err()
+    );
+}
+
+#[rstest]
+#[case("image: alpine\n", None)]
+#[case("image: alpine\nrunner: docker\n", Some(RunnerKind::Docker))]
+#[case("image: alpine\nrunner: kubernetes\n", Some(RunnerKind::Kubernetes))]
+#[case("runner: shell\n", Some(RunnerKind::Shell))]
+#[case("image: alpine\nrunner: shell\n", Some(RunnerKind::Shell))]
+fn validate_from_yaml_runner_test(#[case] runner: &str, #[case] expected: Option<RunnerKind>) {
+    let yaml = format!("{runner}stages:\n  test:\n    script:\n      - echo \"hello\"\n");
+    let pipeline = PipelineTemplate::from_yaml(&yaml).unwrap();
+    assert_eq!(expected, pipeline.runner);
+}
+
+#[rstest]
+#[case(
+    "runner: docker\nstages:\n  test:\n    script:\n      - echo\n",
+    "container runners require a pipeline docker image"
+)]
+#[case(
+    "runner: kubernetes\nstages:\n  test:\n    script:\n      - echo\n",
+    "container runners require a pipeline docker image"
+)]
+#[case(
+    "image: alpine\nrunner: shell\nservices:\n  - image: redis\nstages:\n  test:\n    script:\n      - echo\n",
+    "services and build stages require the docker runner"
+)]
+#[case(
+    "image: alpine\nrunner: kubernetes\nstages:\n  image:\n    build:\n      tags:\n        - app\n",
+    "services and build stages require the docker runner"
+)]
+fn validate_from_yaml_error_runner_test(#[case] yaml: &str, #[case] error: &str) {
+    let pipeline = PipelineTemplate::from_yaml(yaml);
+    assert!(pipeline.is_err());
+    assert_eq!(
+        RustyError::SerializationError(format!("Pipeline template: [{error}]")),
+        pipeline.unwrap_err()
+    );
+}
+
+#[test]
+fn validate_from_yaml_error_runner_kind_test() {
+    let yaml = "runner: vm\nstages:\n  test:\n    script:\n      - echo \"hello\"\n";
+    assert!(PipelineTemplate::from_yaml(yaml).is_err());
+}
+
+#[test]
+fn validate_from_yaml_environment_test() {
+    let yaml = "stages:\n  deploy:\n    environment:\n      name: staging\n      url: https://staging.example.com\n    script:\n      - ./deploy.sh\n";
+    let pipeline = PipelineTemplate::from_yaml(yaml).unwrap();
+    assert_eq!(
+        Some(Environment {
+            name: "staging".to_string(),
+            url: Some("https://staging.example.com".to_string()),
+        }),
+        pipeline.stages["deploy"].environment
+    );
+}
+
+#[test]
+fn validate_from_yaml_error_environment_test() {
+    let yaml =
+        "stages:\n  deploy:\n    environment:\n      name: ''\n    script:\n      - ./deploy.sh\n";
+    assert_eq!(
+        RustyError::SerializationError(
+            "Pipeline template: [environment.name cannot be empty]".to_string()
+        ),
+        PipelineTemplate::from_yaml(yaml).unwrap_err()
+    );
+}
+
+#[test]
+fn validate_from_yaml_reports_test() {
+    let yaml = "stages:\n  test:\n    reports:\n      junit: target/*.xml\n      coverage: lcov.info\n    script:\n      - cargo test\n";
+    let pipeline = PipelineTemplate::from_yaml(yaml).unwrap();
+    assert_eq!(
+        Some(Reports {
+            junit: Some("target/*.xml".to_string()),
+            coverage: Some("lcov.info".to_string()),
+        }),
+        pipeline.stages["test"].reports
+    );
+}
+
+#[rstest]
+#[case("junit: /tmp/*.xml")]
+#[case("junit: ../*.xml")]
+#[case("junit: ''")]
+#[case("coverage: /tmp/lcov.info")]
+#[case("coverage: ../lcov.info")]
+fn validate_from_yaml_error_reports_test(#[case] reports: &str) {
+    let yaml = format!(
+        "stages:\n  test:\n    reports:\n      {reports}\n    script:\n      - cargo test\n"
+    );
+    assert_eq!(
+        RustyError::SerializationError(
+            "Pipeline template: [report paths must be relative and stay in the repository]"
+                .to_string()
+        ),
+        PipelineTemplate::from_yaml(&yaml).unwrap_err()
+    );
+}
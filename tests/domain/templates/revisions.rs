@@ -0,0 +1,51 @@
+use rstest::rstest;
+
+use domain::templates::revision::{diff, DiffKind, DiffLine, TemplateRevision};
+
+const TEMPLATE: &str = "stages:\n  build:\n    script:\n      - cargo build\n";
+
+#[test]
+fn new_revision_test() {
+    let revision = TemplateRevision::new("job", 2, "user", TEMPLATE);
+    assert_eq!(36, revision.id.len());
+    assert_eq!(2, revision.number);
+    assert_eq!("user", revision.author);
+    assert_eq!(64, revision.hash.len());
+    assert_eq!(TEMPLATE, revision.template);
+    assert_eq!("job", revision.job_id);
+    assert!(revision.matches(TEMPLATE));
+    assert!(!revision.matches("stages: {}"));
+}
+
+fn lines(entries: &[(DiffKind, &str)]) -> Vec<DiffLine> {
+    entries
+        .iter()
+        .map(|(kind, line)| DiffLine {
+            kind: *kind,
+            line: line.to_string(),
+        })
+        .collect()
+}
+
+#[rstest]
+#[case("a\nb\nc", "a\nb\nc", vec![(DiffKind::Unchanged, "a"), (DiffKind::Unchanged, "b"), (DiffKind::Unchanged, "c")])]
+#[case("a\nb\nc", "a\nx\nc", vec![(DiffKind::Unchanged, "a"), (DiffKind::Removed, "b"), (DiffKind::Added, "x"), (DiffKind::Unchanged, "c")])]
+#[case("a\nc", "a\nb\nc", vec![(DiffKind::Unchanged, "a"), (DiffKind::Added, "b"), (DiffKind::Unchanged, "c")])]
+#[case("a\nb", "", vec![(DiffKind::Removed, "a"), (DiffKind::Removed, "b")])]
+fn diff_test(#[case] old: &str, #[case] new: &str, #[case] expected: Vec<(DiffKind, &str)>) {
+    assert_eq!(lines(&expected), diff(old, new));
+}
+
+#[test]
+fn diff_base64_test() {
+    let old = base64_url::encode("stages:\n  build:\n");
+    let new = base64_url::encode("stages:\n  test:\n");
+    assert_eq!(
+        lines(&[
+            (DiffKind::Unchanged, "stages:"),
+            (DiffKind::Removed, "  build:"),
+            (DiffKind::Added, "  test:"),
+        ]),
+        diff(&old, &new)
+    );
+}
@@ -0,0 +1,77 @@
+use serde_json::json;
+
+use domain::templates::schema::{json_schema, TemplateSchema, TEMPLATE_SCHEMA_VERSION};
+
+#[test]
+fn json_schema_test() {
+    let schema = json_schema();
+    assert_eq!(
+        json!(format!(
+            "/schemas/pipeline-template/{TEMPLATE_SCHEMA_VERSION}.json"
+        )),
+        schema["$id"]
+    );
+    assert_eq!(json!(TEMPLATE_SCHEMA_VERSION), schema["version"]);
+    assert_eq!(json!(["stages"]), schema["required"]);
+    assert_eq!("v2", TEMPLATE_SCHEMA_VERSION);
+    for property in [
+        "image",
+        "env",
+        "shell",
+        "runner",
+        "pull_policy",
+        "before",
+        "after",
+        "stages",
+        "trigger",
+        "services",
+    ] {
+        assert!(schema["properties"].get(property).is_some());
+    }
+}
+
+#[test]
+fn json_schema_stage_test() {
+    let schema = json_schema();
+    let stage = &schema["definitions"]["Stage"];
+    // build stages have no script
+    assert!(stage["required"].is_null());
+    for property in [
+        "image",
+        "env",
+        "script",
+        "shell",
+        "dependsOn",
+        "services",
+        "resources",
+        "security",
+        "build",
+        "environment",
+        "reports",
+    ] {
+        assert!(stage["properties"].get(property).is_some());
+    }
+    assert_eq!(
+        stage["properties"]["dependsOn"],
+        stage["properties"]["depends_on"]
+    );
+    assert_eq!(
+        json!(["script"]),
+        schema["definitions"]["Script"]["required"]
+    );
+}
+
+#[test]
+fn json_schema_trigger_events_test() {
+    let schema = json_schema();
+    let events = schema["definitions"]["TriggerEvent"].to_string();
+    assert!(events.contains("\"push\""));
+    assert!(events.contains("\"pull_request\""));
+}
+
+#[test]
+fn template_schema_current_test() {
+    let schema = TemplateSchema::current();
+    assert_eq!(TEMPLATE_SCHEMA_VERSION, schema.version);
+    assert_eq!(json_schema(), schema.schema);
+}
@@ -0,0 +1,132 @@
+use domain::templates::pipeline::TemplateFormat;
+use domain::templates::validation::{validate, EnvVariable, TemplateDiagnostic};
+
+#[test]
+fn validate_valid_test() {
+    let yaml = r#"
+    image: alpine
+    env:
+      key: value
+      shared: pipeline
+    stages:
+      build:
+        script:
+          - echo "build"
+      test:
+        image: rust:alpine
+        env:
+          shared: stage
+        script:
+          - echo "test"
+        dependsOn:
+          - build
+    "#;
+
+    let result = validate(&base64_url::encode(&yaml), None);
+    assert!(result.valid);
+    assert!(result.diagnostics.is_empty());
+    assert_eq!(2, result.stages.len());
+    assert_eq!("build", result.stages[0][0].name);
+    assert_eq!(Some("alpine".to_string()), result.stages[0][0].image);
+    let test = &result.stages[1][0];
+    assert_eq!("test", test.name);
+    assert_eq!(Some("rust:alpine".to_string()), test.image);
+    assert_eq!(vec!["build".to_string()], test.depends_on);
+    assert_eq!(
+        vec![
+            EnvVariable {
+                name: "key".to_string(),
+                value: "value".to_string(),
+            },
+            EnvVariable {
+                name: "shared".to_string(),
+                value: "stage".to_string(),
+            },
+        ],
+        test.env
+    );
+}
+
+#[test]
+fn validate_invalid_base64_test() {
+    let result = validate("not base64!", Some(TemplateFormat::Base64));
+    assert!(!result.valid);
+    assert_eq!(1, result.diagnostics.len());
+    assert_eq!("", result.diagnostics[0].path);
+}
+
+#[test]
+fn validate_parse_error_test() {
+    let yaml = "stages:\n  test:\n    image: alpine\n    script: echo\n";
+
+    let result = validate(&base64_url::encode(&yaml), None);
+    assert!(!result.valid);
+    assert_eq!(1, result.diagnostics.len());
+    assert_eq!("stages.test.script", result.diagnostics[0].path);
+    assert!(result.diagnostics[0]
+        .message
+        .starts_with("invalid type: string \"echo\""));
+    assert!(result.diagnostics[0].line.is_some());
+    assert!(result.stages.is_empty());
+}
+
+#[test]
+fn validate_diagnostics_test() {
+    let yaml = "stages:\n  test:\n    script: []\n  deploy:\n    script:\n      - echo \"deploy\"\n    depends_on:\n      - unknown\nafter:\n  script: []\n";
+
+    let result = validate(&base64_url::encode(&yaml), None);
+    assert!(!result.valid);
+    assert_eq!(
+        vec![
+            TemplateDiagnostic {
+                path: "stages.test.script".to_string(),
+                message: "stages.script cannot be empty".to_string(),
+                line: Some(3),
+                column: Some(5),
+            },
+            TemplateDiagnostic {
+                path: "stages.deploy.dependsOn".to_string(),
+                message: "stage depends on an unknown stage".to_string(),
+                line: Some(4),
+                column: Some(3),
+            },
+            TemplateDiagnostic {
+                path: "after.script".to_string(),
+                message: "after.script cannot be empty".to_string(),
+                line: Some(10),
+                column: Some(3),
+            },
+        ],
+        result.diagnostics
+    );
+}
+
+#[test]
+fn validate_circular_dependencies_test() {
+    let yaml = "stages:\n  a:\n    script:\n      - echo\n    dependsOn:\n      - b\n  b:\n    script:\n      - echo\n    dependsOn:\n      - a\n";
+
+    let result = validate(&base64_url::encode(&yaml), None);
+    assert!(!result.valid);
+    assert_eq!(1, result.diagnostics.len());
+    assert_eq!("stages", result.diagnostics[0].path);
+    assert_eq!(Some(1), result.diagnostics[0].line);
+}
+
+#[test]
+fn validate_json_test() {
+    let json = r#"{ "stages": { "test": { "script": ["echo"] } } }"#;
+
+    let result = validate(json, None);
+    assert!(result.valid);
+    assert_eq!("test", result.stages[0][0].name);
+}
+
+#[test]
+fn validate_toml_parse_error_test() {
+    let toml = "[stages.test]\nimage = \"alpine\"\n";
+
+    let result = validate(toml, Some(TemplateFormat::Toml));
+    assert!(!result.valid);
+    assert_eq!(1, result.diagnostics.len());
+    assert!(result.diagnostics[0].message.contains("script"));
+}
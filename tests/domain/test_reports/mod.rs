@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use domain::pipelines::{Pipeline, PipelineStatus};
+use domain::test_reports::{
+    failures, flaky, new_report, slowest, TestCase, TestReport, TestStatus, TestSuite,
+};
+
+fn pipeline(number: u64) -> Pipeline {
+    Pipeline {
+        id: format!("pipeline-{number}"),
+        number,
+        branch: "master".to_string(),
+        register_date: "now".to_string(),
+        start_date: None,
+        end_date: None,
+        stage_status: HashMap::new(),
+        status: PipelineStatus::InProgress,
+        job_id: "job".to_string(),
+        agent_id: None,
+        commit: None,
+        revision: None,
+        triggered_by: None,
+        upstream_id: None,
+        variables: None,
+    }
+}
+
+fn case(name: &str, duration: f64, status: TestStatus) -> TestCase {
+    TestCase {
+        name: name.to_string(),
+        classname: Some("tests".to_string()),
+        duration,
+        status,
+        message: None,
+    }
+}
+
+fn report(number: u64, cases: Vec<TestCase>) -> TestReport {
+    let suites = vec![TestSuite {
+        name: "suite".to_string(),
+        duration: cases.iter().map(|case| case.duration).sum(),
+        cases,
+    }];
+    new_report("id", "test", suites, &pipeline(number), "project")
+}
+
+#[test]
+fn new_report_test() {
+    let report = report(
+        3,
+        vec![
+            case("a", 0.1, TestStatus::Passed),
+            case("b", 0.2, TestStatus::Failed),
+            case("c", 0.3, TestStatus::Error),
+            case("d", 0.0, TestStatus::Skipped),
+        ],
+    );
+    assert_eq!(4, report.tests);
+    assert_eq!(2, report.failures);
+    assert_eq!(1, report.skipped);
+    assert_eq!("pipeline-3", report.pipeline_id);
+    assert_eq!(3, report.pipeline_number);
+    assert_eq!("job", report.job_id);
+    assert_eq!("project", report.project_id);
+    assert_eq!("master", report.branch);
+}
+
+#[test]
+fn failures_test() {
+    let reports = vec![report(
+        1,
+        vec![
+            case("a", 0.1, TestStatus::Passed),
+            case("b", 0.2, TestStatus::Failed),
+            case("c", 0.3, TestStatus::Error),
+            case("d", 0.0, TestStatus::Skipped),
+        ],
+    )];
+    let failures = failures(&reports);
+    assert_eq!(
+        vec!["b", "c"],
+        failures
+            .iter()
+            .map(|result| result.name.as_str())
+            .collect::<Vec<&str>>()
+    );
+    assert_eq!("suite", failures[0].suite);
+    assert_eq!("test", failures[0].stage);
+    assert_eq!("pipeline-1", failures[0].pipeline_id);
+}
+
+#[test]
+fn slowest_test() {
+    let reports = vec![report(
+        1,
+        vec![
+            case("a", 0.1, TestStatus::Passed),
+            case("b", 2.5, TestStatus::Passed),
+            case("c", 1.0, TestStatus::Failed),
+        ],
+    )];
+    assert_eq!(
+        vec!["b", "c"],
+        slowest(&reports, 2)
+            .iter()
+            .map(|result| result.name.as_str())
+            .collect::<Vec<&str>>()
+    );
+}
+
+#[test]
+fn flaky_test() {
+    let reports = vec![
+        report(
+            1,
+            vec![
+                case("stable", 0.1, TestStatus::Passed),
+                case("flaky", 0.1, TestStatus::Failed),
+                case("broken", 0.1, TestStatus::Failed),
+            ],
+        ),
+        report(
+            2,
+            vec![
+                case("stable", 0.1, TestStatus::Passed),
+                case("flaky", 0.1, TestStatus::Passed),
+                case("broken", 0.1, TestStatus::Failed),
+            ],
+        ),
+        report(
+            3,
+            vec![
+                case("stable", 0.1, TestStatus::Passed),
+                case("flaky", 0.1, TestStatus::Error),
+                case("broken", 0.1, TestStatus::Failed),
+            ],
+        ),
+    ];
+    let flaky = flaky(&reports);
+    assert_eq!(1, flaky.len());
+    assert_eq!("flaky", flaky[0].name);
+    assert_eq!(1, flaky[0].passes);
+    assert_eq!(2, flaky[0].failures);
+    assert_eq!("pipeline-3", flaky[0].last_failure);
+}
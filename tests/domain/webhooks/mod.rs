@@ -0,0 +1,117 @@
+use rstest::rstest;
+use serde_json::{json, Value};
+
+use domain::templates::pipeline::TriggerEvent;
+use domain::webhooks::{normalize_url, Forge, WebhookEvent};
+
+fn payload(name: &str) -> Value {
+    let path = format!(
+        "{}/resources/webhooks/{name}.json",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[rstest]
+#[case(Forge::GitHub, "push", "github_push", TriggerEvent::Push, "master")]
+#[case(
+    Forge::GitHub,
+    "pull_request",
+    "github_pull_request",
+    TriggerEvent::PullRequest,
+    "feature/test"
+)]
+#[case(
+    Forge::GitLab,
+    "Push Hook",
+    "gitlab_push",
+    TriggerEvent::Push,
+    "master"
+)]
+#[case(
+    Forge::GitLab,
+    "Merge Request Hook",
+    "gitlab_merge_request",
+    TriggerEvent::PullRequest,
+    "feature/test"
+)]
+#[case(Forge::Gitea, "push", "gitea_push", TriggerEvent::Push, "master")]
+#[case(
+    Forge::Gitea,
+    "pull_request",
+    "gitea_pull_request",
+    TriggerEvent::PullRequest,
+    "feature/test"
+)]
+fn parse_test(
+    #[case] forge: Forge,
+    #[case] event: &str,
+    #[case] name: &str,
+    #[case] expected_event: TriggerEvent,
+    #[case] expected_branch: &str,
+) {
+    let event = WebhookEvent::parse(forge, event, &payload(name));
+    assert!(event.is_some());
+    let event = event.unwrap();
+    assert_eq!(expected_event, event.event);
+    assert_eq!(expected_branch, event.branch);
+    assert!(event.commit.is_some());
+    assert_eq!(3, event.urls.len());
+}
+
+#[rstest]
+#[case(Forge::GitHub, "issues", "github_push")]
+#[case(Forge::GitHub, "push", "gitlab_merge_request")]
+#[case(Forge::GitLab, "push", "gitlab_push")]
+#[case(Forge::Gitea, "Push Hook", "gitea_push")]
+fn parse_unsupported_test(#[case] forge: Forge, #[case] event: &str, #[case] name: &str) {
+    assert!(WebhookEvent::parse(forge, event, &payload(name)).is_none());
+}
+
+#[test]
+fn parse_tag_push_test() {
+    let mut payload = payload("github_push");
+    payload["ref"] = json!("refs/tags/v1.0.0");
+    assert!(WebhookEvent::parse(Forge::GitHub, "push", &payload).is_none());
+}
+
+#[test]
+fn parse_deleted_branch_test() {
+    let mut payload = payload("gitlab_push");
+    payload["after"] = json!("0000000000000000000000000000000000000000");
+    assert!(WebhookEvent::parse(Forge::GitLab, "Push Hook", &payload).is_none());
+}
+
+#[test]
+fn parse_closed_pull_request_test() {
+    let mut payload = payload("github_pull_request");
+    payload["action"] = json!("closed");
+    assert!(WebhookEvent::parse(Forge::GitHub, "pull_request", &payload).is_none());
+}
+
+#[rstest]
+#[case("https://github.com/rusty-ops/sample", true)]
+#[case("https://github.com/rusty-ops/sample.git", true)]
+#[case("https://GitHub.com/rusty-ops/sample/", true)]
+#[case("git@github.com:rusty-ops/sample.git", true)]
+#[case("ssh://git@github.com/rusty-ops/sample", true)]
+#[case("https://github.com/rusty-ops/other", false)]
+#[case("https://gitlab.com/rusty-ops/sample", false)]
+fn matches_url_test(#[case] url: &str, #[case] expected: bool) {
+    let event = WebhookEvent::parse(Forge::GitHub, "push", &payload("github_push")).unwrap();
+    assert_eq!(expected, event.matches_url(url));
+}
+
+#[rstest]
+#[case(
+    "https://github.com/rusty-ops/sample.git",
+    "github.com/rusty-ops/sample"
+)]
+#[case("git@github.com:rusty-ops/sample.git", "github.com/rusty-ops/sample")]
+#[case(
+    "http://localhost:3000/rusty-ops/sample",
+    "localhost/3000/rusty-ops/sample"
+)]
+fn normalize_url_test(#[case] url: &str, #[case] expected: &str) {
+    assert_eq!(expected, normalize_url(url));
+}
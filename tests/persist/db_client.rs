@@ -10,9 +10,12 @@ use testcontainers::Image;
 use testcontainers_modules::{mongo::Mongo, postgres::Postgres, redis::Redis};
 
 use commons::errors::RustyError;
+use domain::checkouts::CheckoutSettings;
+use domain::coverage::{CoverageReport, FileCoverage};
 use domain::jobs::Job;
 use domain::pipelines::{Pipeline, PipelineStatus};
 use domain::projects::Project;
+use domain::test_reports::{TestCase, TestReport, TestStatus, TestSuite};
 use domain::RustyDomainItem;
 use persist::db_client::DbClient;
 
@@ -21,6 +24,9 @@ use crate::utils::db_connect;
 const PROJECTS_INDEX: &str = "projects";
 const JOBS_INDEX: &str = "jobs";
 const PIPELINES_INDEX: &str = "pipelines";
+const CHECKOUT_SETTINGS_INDEX: &str = "checkout_settings";
+const TEST_REPORTS_INDEX: &str = "test_reports";
+const COVERAGE_REPORTS_INDEX: &str = "coverage_reports";
 
 #[rstest]
 #[case(Redis, "internal", 0)]
@@ -186,6 +192,226 @@ where
     assert!(uuid::Uuid::from_str(&updated).is_ok());
 }
 
+#[rstest]
+#[case(Redis, "internal", 0)]
+#[case(Mongo::default(), "mongodb", 27017)]
+#[case(Postgres::default(), "postgres", 5432)]
+#[case(Redis, "redis", 6379)]
+#[tokio::test]
+async fn create_quoted_text_test<I: Image + Default>(
+    #[case] image: I,
+    #[case] db_type: &str,
+    #[case] port: u16,
+) where
+    I: Image,
+{
+    let db = image
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, db_type, port).await;
+    let project_id = create_project(&db_client, "project_1").await.unwrap();
+    let template =
+        "stages:\n  build:\n    script:\n    - echo 'it''s built'); drop table rusty.jobs; --\n";
+    let job = Job {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: "quoted".to_string(),
+        description: Some("a 'quoted' description".to_string()),
+        template: template.to_string(),
+        project_id,
+    };
+    let created = db_client.create(JOBS_INDEX, &job.to_value().unwrap()).await;
+    let found = db_client
+        .get_one(JOBS_INDEX, json!({ "id": { "equals": job.id } }))
+        .await;
+    let _ = db.stop().await;
+    assert!(created.is_ok());
+    let found = found.unwrap().unwrap();
+    assert_eq!(template, found.get("template").unwrap().as_str().unwrap());
+    assert_eq!(
+        "a 'quoted' description",
+        found.get("description").unwrap().as_str().unwrap()
+    );
+}
+
+#[rstest]
+#[case(Redis, "internal", 0)]
+#[case(Mongo::default(), "mongodb", 27017)]
+#[case(Postgres::default(), "postgres", 5432)]
+#[case(Redis, "redis", 6379)]
+#[tokio::test]
+async fn optional_and_array_values_test<I: Image + Default>(
+    #[case] image: I,
+    #[case] db_type: &str,
+    #[case] port: u16,
+) where
+    I: Image,
+{
+    let db = image
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, db_type, port).await;
+    let project_id = create_project(&db_client, "project_1").await.unwrap();
+    let sparse_paths = vec![
+        "services/payments/src/main/resources/config".to_string(),
+        "docs".to_string(),
+    ];
+    let settings = CheckoutSettings {
+        id: uuid::Uuid::new_v4().to_string(),
+        depth: None,
+        submodules: None,
+        lfs: Some(true),
+        sparse_paths: Some(sparse_paths.clone()),
+        ssh_key: None,
+        username: None,
+        token: None,
+        project_id,
+    };
+    let created = db_client
+        .create(CHECKOUT_SETTINGS_INDEX, &settings.to_value().unwrap())
+        .await;
+    let found = db_client
+        .get_one(
+            CHECKOUT_SETTINGS_INDEX,
+            json!({ "id": { "equals": settings.id } }),
+        )
+        .await;
+    let _ = db.stop().await;
+    assert!(created.is_ok());
+    let found = serde_json::from_value::<CheckoutSettings>(found.unwrap().unwrap()).unwrap();
+    assert_eq!(None, found.depth);
+    assert_eq!(Some(true), found.lfs);
+    assert_eq!(Some(sparse_paths), found.sparse_paths);
+}
+
+#[rstest]
+#[case(Redis, "internal", 0)]
+#[case(Mongo::default(), "mongodb", 27017)]
+#[case(Postgres::default(), "postgres", 5432)]
+#[case(Redis, "redis", 6379)]
+#[tokio::test]
+async fn test_report_round_trip_test<I: Image + Default>(
+    #[case] image: I,
+    #[case] db_type: &str,
+    #[case] port: u16,
+) where
+    I: Image,
+{
+    let db = image
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, db_type, port).await;
+    let project_id = create_project(&db_client, "project_1").await.unwrap();
+    let suites = vec![TestSuite {
+        name: "suite".to_string(),
+        duration: 1.5,
+        cases: vec![
+            TestCase {
+                name: "passing".to_string(),
+                classname: Some("sample".to_string()),
+                duration: 0.5,
+                status: TestStatus::Passed,
+                message: None,
+            },
+            TestCase {
+                name: "failing".to_string(),
+                classname: Some("sample".to_string()),
+                duration: 1.0,
+                status: TestStatus::Failed,
+                message: Some("expected 'a', found 'b'".to_string()),
+            },
+        ],
+    }];
+    let report = TestReport {
+        id: uuid::Uuid::new_v4().to_string(),
+        date: chrono::Utc::now().to_rfc3339(),
+        stage: "test".to_string(),
+        branch: "master".to_string(),
+        tests: 2,
+        failures: 1,
+        skipped: 0,
+        suites: suites.clone(),
+        pipeline_id: uuid::Uuid::new_v4().to_string(),
+        pipeline_number: 1,
+        job_id: uuid::Uuid::new_v4().to_string(),
+        project_id,
+    };
+    let created = db_client
+        .create(TEST_REPORTS_INDEX, &report.to_value().unwrap())
+        .await;
+    let found = db_client
+        .get_one(TEST_REPORTS_INDEX, json!({ "id": { "equals": report.id } }))
+        .await;
+    let _ = db.stop().await;
+    assert!(created.is_ok());
+    let found = serde_json::from_value::<TestReport>(found.unwrap().unwrap()).unwrap();
+    assert_eq!(2, found.tests);
+    assert_eq!(suites, found.suites);
+}
+
+#[rstest]
+#[case(Redis, "internal", 0)]
+#[case(Mongo::default(), "mongodb", 27017)]
+#[case(Postgres::default(), "postgres", 5432)]
+#[case(Redis, "redis", 6379)]
+#[tokio::test]
+async fn coverage_report_round_trip_test<I: Image + Default>(
+    #[case] image: I,
+    #[case] db_type: &str,
+    #[case] port: u16,
+) where
+    I: Image,
+{
+    let db = image
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, db_type, port).await;
+    let project_id = create_project(&db_client, "project_1").await.unwrap();
+    let files = vec![
+        FileCoverage {
+            path: "src/lib.rs".to_string(),
+            lines: 6,
+            covered: 5,
+        },
+        FileCoverage {
+            path: "src/main.rs".to_string(),
+            lines: 2,
+            covered: 2,
+        },
+    ];
+    let report = CoverageReport {
+        id: uuid::Uuid::new_v4().to_string(),
+        date: chrono::Utc::now().to_rfc3339(),
+        stage: "test".to_string(),
+        branch: "master".to_string(),
+        commit: None,
+        lines: 8,
+        covered: 7,
+        percent: 87.5,
+        files: files.clone(),
+        pipeline_id: uuid::Uuid::new_v4().to_string(),
+        pipeline_number: 1,
+        job_id: uuid::Uuid::new_v4().to_string(),
+        project_id,
+    };
+    let created = db_client
+        .create(COVERAGE_REPORTS_INDEX, &report.to_value().unwrap())
+        .await;
+    let found = db_client
+        .get_all(COVERAGE_REPORTS_INDEX, &None, &None)
+        .await;
+    let _ = db.stop().await;
+    assert!(created.is_ok());
+    let found = found.unwrap();
+    assert_eq!(1, found.len());
+    let found = serde_json::from_value::<CoverageReport>(found[0].clone()).unwrap();
+    assert!((found.percent - 87.5).abs() < f64::EPSILON);
+    assert_eq!(files, found.files);
+}
+
 #[rstest]
 #[case(Redis, "internal", 0)]
 #[case(Mongo::default(), "mongodb", 27017)]
@@ -210,6 +436,33 @@ where
     assert!(result.is_ok());
 }
 
+#[rstest]
+#[case(Redis, "internal", 0)]
+#[case(Mongo::default(), "mongodb", 27017)]
+#[case(Postgres::default(), "postgres", 5432)]
+#[case(Redis, "redis", 6379)]
+#[tokio::test]
+async fn lock_test<I: Image + Default>(#[case] image: I, #[case] db_type: &str, #[case] port: u16)
+where
+    I: Image,
+{
+    let db = image
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, db_type, port).await;
+    let first = db_client.lock("locks", "lock-1", 60).await;
+    let second = db_client.lock("locks", "lock-1", 60).await;
+    let other = db_client.lock("locks", "lock-2", 60).await;
+    let _ = db.stop().await;
+    assert!(first.is_ok());
+    assert!(first.unwrap());
+    assert!(second.is_ok());
+    assert!(!second.unwrap());
+    assert!(other.is_ok());
+    assert!(other.unwrap());
+}
+
 #[rstest]
 #[case(Redis, "internal", 0)]
 #[case(Mongo::default(), "mongodb", 27017)]
@@ -385,6 +638,11 @@ async fn create_pipeline(db_client: &DbClient, id: &str) -> Result<String, Rusty
                 status: PipelineStatus::Defined,
                 job_id: id.to_string(),
                 agent_id: None,
+                commit: None,
+                revision: None,
+                triggered_by: None,
+                upstream_id: None,
+                variables: None,
             }
             .to_value()?,
         )
@@ -0,0 +1,296 @@
+use mockito::Matcher;
+use rstest::rstest;
+use serde_json::{json, Value};
+
+use domain::checkouts::Checkout;
+use domain::pipelines::PipelineStatus;
+use domain::templates::pipeline::PipelineTemplate;
+use rusty_agent::runners::pipelines::kubernetes::{
+    container_state, pod_manifest, pod_name, pod_steps, termination_status, KubernetesClient,
+    PodStep,
+};
+
+const PIPELINE_ID: &str = "57c38e8b-1845-49f1-874a-1eefe9923456";
+
+fn template() -> PipelineTemplate {
+    let yaml = r#"
+    image: rust:alpine
+    before:
+      script:
+        - echo "before"
+    stages:
+      deploy:
+        depends_on:
+          - test
+        script:
+          - echo "deploy"
+      test:
+        image: rust:slim
+        env:
+          MODE: test
+        resources:
+          cpus: 1.5
+          memory: 1g
+        security:
+          user: "1000:1000"
+          cap_drop:
+            - ALL
+        script:
+          - cargo test
+    "#;
+    PipelineTemplate::from_yaml(yaml).unwrap()
+}
+
+fn step(stage: &str, container: Option<&str>) -> PodStep {
+    PodStep {
+        stage: stage.to_string(),
+        container: container.map(ToString::to_string),
+    }
+}
+
+#[test]
+fn pod_steps_test() {
+    assert_eq!(
+        vec![
+            step("rusty-before", Some("rusty-before")),
+            step("test", Some("stage-0")),
+            step("deploy", Some("stage-1")),
+            step("rusty-after", None),
+        ],
+        pod_steps(&template())
+    );
+}
+
+#[test]
+fn pod_manifest_test() {
+    let manifest = pod_manifest(
+        PIPELINE_ID,
+        &template(),
+        "https://git/repo",
+        "main",
+        None,
+        &Checkout::default(),
+    );
+    assert_eq!(pod_name(PIPELINE_ID), manifest["metadata"]["name"]);
+    assert_eq!(
+        PIPELINE_ID,
+        manifest["metadata"]["labels"]["rusty.pipeline"]
+    );
+
+    let spec = &manifest["spec"];
+    assert_eq!("Never", spec["restartPolicy"]);
+    assert_eq!(json!({}), spec["volumes"][0]["emptyDir"]);
+    let names = spec["initContainers"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|container| container["name"].as_str().unwrap())
+        .collect::<Vec<&str>>();
+    assert_eq!(
+        vec!["rusty-clone", "rusty-before", "stage-0", "stage-1"],
+        names
+    );
+    assert!(spec["initContainers"][0]["command"][2]
+        .as_str()
+        .unwrap()
+        .contains("git clone -b main https://git/repo /workspace"));
+
+    let test = &spec["initContainers"][2];
+    assert_eq!("rust:slim", test["image"]);
+    assert_eq!("IfNotPresent", test["imagePullPolicy"]);
+    assert_eq!("/workspace", test["workingDir"]);
+    assert_eq!("sh", test["command"][0]);
+    assert_eq!(json!([{ "name": "MODE", "value": "test" }]), test["env"]);
+    assert_eq!("1.5", test["resources"]["limits"]["cpu"]);
+    assert_eq!("1073741824", test["resources"]["limits"]["memory"]);
+    assert_eq!(1000, test["securityContext"]["runAsUser"]);
+    assert_eq!(1000, test["securityContext"]["runAsGroup"]);
+    assert_eq!(
+        json!(["ALL"]),
+        test["securityContext"]["capabilities"]["drop"]
+    );
+    assert_eq!("rust:alpine", spec["initContainers"][3]["image"]);
+
+    assert_eq!("rusty-after", spec["containers"][0]["name"]);
+    assert_eq!(json!(["true"]), spec["containers"][0]["command"]);
+}
+
+#[test]
+fn pod_manifest_checkout_test() {
+    let checkout = Checkout {
+        depth: Some(1),
+        token: Some("secret".to_string()),
+        ..Default::default()
+    };
+    let manifest = pod_manifest(
+        PIPELINE_ID,
+        &template(),
+        "https://git/repo",
+        "main",
+        None,
+        &checkout,
+    );
+    let clone = &manifest["spec"]["initContainers"][0];
+    let command = clone["command"][2].as_str().unwrap();
+    assert!(command.contains("git clone --depth 1 -b main https://git/repo /workspace"));
+    assert!(!command.contains("secret"));
+    let env = clone["env"].as_array().unwrap();
+    assert!(env
+        .iter()
+        .any(|var| var["name"] == "GIT_CONFIG_KEY_0"
+            && var["value"] == "http.https://git/.extraHeader"));
+}
+
+#[rstest]
+#[case(json!({ "running": {} }), None)]
+#[case(json!({ "terminated": { "exitCode": 0 } }), Some((PipelineStatus::Success, None)))]
+#[case(
+    json!({ "terminated": { "exitCode": 137, "reason": "OOMKilled" } }),
+    Some((PipelineStatus::Failure, Some("killed: out of memory".to_string())))
+)]
+#[case(
+    json!({ "terminated": { "exitCode": 2, "reason": "Error" } }),
+    Some((PipelineStatus::Failure, Some("failed: Error (exit status: 2)".to_string())))
+)]
+fn termination_status_test(
+    #[case] state: Value,
+    #[case] expected: Option<(PipelineStatus, Option<String>)>,
+) {
+    assert_eq!(expected, termination_status(&state));
+}
+
+#[test]
+fn container_state_test() {
+    let pod = json!({
+        "status": {
+            "initContainerStatuses": [
+                { "name": "rusty-clone", "state": { "terminated": { "exitCode": 0 } } },
+                { "name": "stage-0", "state": { "running": {} } },
+            ],
+            "containerStatuses": [
+                { "name": "rusty-after", "state": { "waiting": { "reason": "PodInitializing" } } },
+            ],
+        }
+    });
+    assert_eq!(
+        Some(json!({ "running": {} })),
+        container_state(&pod, "stage-0")
+    );
+    assert_eq!(
+        Some(json!({ "waiting": { "reason": "PodInitializing" } })),
+        container_state(&pod, "rusty-after")
+    );
+    assert_eq!(None, container_state(&pod, "stage-1"));
+}
+
+#[tokio::test]
+async fn create_pod_test() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/v1/namespaces/ci/pods")
+        .match_header("authorization", "Bearer token")
+        .match_body(Matcher::PartialJson(
+            json!({ "metadata": { "name": pod_name(PIPELINE_ID) } }),
+        ))
+        .with_status(201)
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let client = KubernetesClient::new(&server.url(), Some("token".to_string()), "ci");
+    let manifest = pod_manifest(
+        PIPELINE_ID,
+        &template(),
+        "https://git/repo",
+        "main",
+        None,
+        &Checkout::default(),
+    );
+    let result = client.create_pod(&manifest).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn create_pod_error_test() {
+    let mut server = mockito::Server::new_async().await;
+    let _ = server
+        .mock("POST", "/api/v1/namespaces/ci/pods")
+        .with_status(403)
+        .with_body(r#"{"kind": "Status", "message": "pods is forbidden"}"#)
+        .create_async()
+        .await;
+
+    let client = KubernetesClient::new(&server.url(), None, "ci");
+    let result = client.create_pod(&json!({})).await;
+    assert!(result.is_err());
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("pods is forbidden"));
+}
+
+#[tokio::test]
+async fn get_pod_test() {
+    let mut server = mockito::Server::new_async().await;
+    let name = pod_name(PIPELINE_ID);
+    let _ = server
+        .mock("GET", format!("/api/v1/namespaces/ci/pods/{name}").as_str())
+        .with_status(200)
+        .with_body(r#"{"status": {"phase": "Running"}}"#)
+        .create_async()
+        .await;
+
+    let client = KubernetesClient::new(&server.url(), None, "ci");
+    let result = client.get_pod(&name).await;
+    assert!(result.is_ok());
+    assert_eq!("Running", result.unwrap()["status"]["phase"]);
+}
+
+#[tokio::test]
+async fn logs_test() {
+    let mut server = mockito::Server::new_async().await;
+    let name = pod_name(PIPELINE_ID);
+    let mock = server
+        .mock(
+            "GET",
+            format!("/api/v1/namespaces/ci/pods/{name}/log").as_str(),
+        )
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("container".to_string(), "stage-0".to_string()),
+            Matcher::UrlEncoded("follow".to_string(), "true".to_string()),
+        ]))
+        .with_status(200)
+        .with_body("line 1\nline 2\n")
+        .create_async()
+        .await;
+
+    let client = KubernetesClient::new(&server.url(), None, "ci");
+    let result = client.logs(&name, "stage-0").await;
+    assert!(result.is_ok());
+    assert_eq!("line 1\nline 2\n", result.unwrap().text().await.unwrap());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn delete_pod_test() {
+    let mut server = mockito::Server::new_async().await;
+    let name = pod_name(PIPELINE_ID);
+    let mock = server
+        .mock(
+            "DELETE",
+            format!("/api/v1/namespaces/ci/pods/{name}").as_str(),
+        )
+        .match_query(Matcher::UrlEncoded(
+            "gracePeriodSeconds".to_string(),
+            "0".to_string(),
+        ))
+        .with_status(200)
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let client = KubernetesClient::new(&server.url(), None, "ci");
+    assert!(client.delete_pod(&name).await.is_ok());
+    mock.assert_async().await;
+}
@@ -0,0 +1,80 @@
+use std::time::{Duration, SystemTime};
+
+use rstest::rstest;
+
+use domain::checkouts::Checkout;
+use rusty_agent::runners::pipelines::mirror::{
+    gc_candidates, update_script, MirrorCache, MirrorUsage,
+};
+
+#[rstest]
+#[case("https://github.com/org/repo.git", "/mirrors/github.com_org_repo.git")]
+#[case("https://github.com/org/repo/", "/mirrors/github.com_org_repo.git")]
+#[case(
+    "https://user@gitlab.com/group/sub/repo",
+    "/mirrors/gitlab.com_group_sub_repo.git"
+)]
+#[case("git@github.com:org/repo.git", "/mirrors/github.com_org_repo.git")]
+fn path_test(#[case] repo_url: &str, #[case] expected: &str) {
+    let mirrors = MirrorCache {
+        dir: "/mirrors".to_string(),
+        limit: None,
+    };
+    assert_eq!(expected, mirrors.path(repo_url));
+}
+
+#[test]
+fn update_script_test() {
+    let script = update_script(
+        &Checkout::default(),
+        "https://github.com/org/repo.git",
+        "/mirrors/repo.git",
+    );
+    assert_eq!(
+        vec![
+            "set -e",
+            "if [ -d /mirrors/repo.git ]; then",
+            "  git -C /mirrors/repo.git fetch --prune origin",
+            "else",
+            "  rm -rf /mirrors/repo.git.tmp",
+            "  git clone --mirror https://github.com/org/repo.git /mirrors/repo.git.tmp",
+            "  mv /mirrors/repo.git.tmp /mirrors/repo.git",
+            "fi",
+        ],
+        script.lines().collect::<Vec<&str>>()
+    );
+}
+
+#[test]
+fn update_script_ssh_key_test() {
+    let checkout = Checkout {
+        ssh_key: Some("PRIVATE".to_string()),
+        ..Default::default()
+    };
+    let script = update_script(
+        &checkout,
+        "git@github.com:org/repo.git",
+        "/mirrors/repo.git",
+    );
+    assert!(script.contains("GIT_SSH_COMMAND"));
+    assert!(!script.contains("PRIVATE"));
+}
+
+#[rstest]
+#[case(600, vec![])]
+#[case(500, vec!["/mirrors/old.git"])]
+#[case(250, vec!["/mirrors/old.git", "/mirrors/recent.git"])]
+#[case(0, vec!["/mirrors/old.git", "/mirrors/recent.git", "/mirrors/latest.git"])]
+fn gc_candidates_test(#[case] limit: u64, #[case] expected: Vec<&str>) {
+    let usage = |path: &str, size: u64, age: u64| MirrorUsage {
+        path: path.to_string(),
+        size,
+        last_used: SystemTime::UNIX_EPOCH + Duration::from_secs(1000 - age),
+    };
+    let mirrors = vec![
+        usage("/mirrors/recent.git", 200, 10),
+        usage("/mirrors/latest.git", 100, 1),
+        usage("/mirrors/old.git", 300, 100),
+    ];
+    assert_eq!(expected, gc_candidates(&mirrors, limit));
+}
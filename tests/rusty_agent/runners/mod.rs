@@ -1 +1,6 @@
+mod checkout;
+mod kubernetes;
+mod mirror;
 mod pipelines;
+mod reports;
+mod sandbox;
@@ -1,7 +1,11 @@
 use mockito::{Mock, ServerGuard};
 
+use rstest::rstest;
+
 use domain::pipelines::{Pipeline, RegisterPipeline};
+use domain::templates::pipeline::{PipelineTemplate, RunnerKind};
 use rusty_agent::runners;
+use rusty_agent::runners::pipelines::{select_runner, StageRun};
 
 use crate::utils::mockito_start_server;
 
@@ -11,6 +15,7 @@ async fn execute_test() {
     let pipeline = Pipeline::from(&RegisterPipeline {
         job_id: "dummy".to_string(),
         branch: Some("master".to_string()),
+        commit: None,
     });
     let mut server = mockito_start_server().await;
     let _ = mock_server_request(&mut server).await;
@@ -18,6 +23,48 @@ async fn execute_test() {
     assert!(result.is_ok());
 }
 
+#[rstest]
+#[case("", RunnerKind::Shell)]
+#[case("image: alpine\n", RunnerKind::Docker)]
+#[case("image: alpine\nrunner: shell\n", RunnerKind::Shell)]
+#[case("image: alpine\nrunner: kubernetes\n", RunnerKind::Kubernetes)]
+fn select_runner_test(#[case] yaml: &str, #[case] expected: RunnerKind) {
+    let yaml = format!("{yaml}stages:\n  test:\n    script:\n      - echo \"hello\"\n");
+    let template = PipelineTemplate::from_yaml(&yaml).unwrap();
+    assert_eq!(expected, select_runner(&template));
+}
+
+// docker is selected whatever the `CONTAINER_RUNNER` backend
+#[rstest]
+#[case("image: alpine\nservices:\n  - image: redis\nstages:\n  test:\n    script:\n      - echo \"hello\"\n")]
+#[case("image: docker:27\nstages:\n  image:\n    build:\n      tags:\n        - app:latest\n")]
+fn select_runner_requires_docker_test(#[case] yaml: &str) {
+    let template = PipelineTemplate::from_yaml(yaml).unwrap();
+    assert!(template.requires_docker());
+    assert_eq!(RunnerKind::Docker, select_runner(&template));
+}
+
+#[test]
+fn stage_run_test() {
+    let yaml =
+        "before:\n  script:\n    - echo before\nstages:\n  test:\n    script:\n      - echo test\n";
+    let template = PipelineTemplate::from_yaml(yaml).unwrap();
+
+    let before = StageRun::before(&template);
+    assert_eq!("rusty-before", before.name);
+    assert_eq!(vec!["echo before"], before.script.unwrap().script);
+    assert!(before.stage.is_none());
+
+    let after = StageRun::after(&template);
+    assert_eq!("rusty-after", after.name);
+    assert!(after.script.is_none());
+
+    let stage = StageRun::stage("test", &template.stages["test"]);
+    assert_eq!("test", stage.name);
+    assert_eq!(vec!["echo test"], stage.script.unwrap().script);
+    assert!(stage.stage.is_some());
+}
+
 async fn mock_server_request(server: &mut ServerGuard) -> Mock {
     server
         .mock("POST", "/graphql")
@@ -0,0 +1,182 @@
+use rstest::rstest;
+
+use domain::coverage::FileCoverage;
+use domain::test_reports::TestStatus;
+use rusty_agent::runners::pipelines::reports::{find, merge_coverage, parse_coverage, parse_junit};
+
+const JUNIT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="all" tests="4" failures="1" errors="1" skipped="1">
+  <testsuite name="api" tests="4" time="1,234.5">
+    <testcase name="get" classname="api.users" time="0.5"/>
+    <testcase name="create" classname="api.users" time="1.25">
+      <failure message="expected 201" type="AssertionError">expected 201
+  at users.rs:12</failure>
+    </testcase>
+    <testcase name="delete" classname="api.users" time="0.1">
+      <error message="connection refused"/>
+    </testcase>
+    <testcase name="update" classname="api.users">
+      <skipped/>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#;
+
+#[test]
+fn parse_junit_test() {
+    let suites = parse_junit(JUNIT).unwrap();
+    assert_eq!(1, suites.len());
+    assert_eq!("api", suites[0].name);
+    assert!((suites[0].duration - 1234.5).abs() < f64::EPSILON);
+
+    let cases = &suites[0].cases;
+    assert_eq!(4, cases.len());
+    assert_eq!("get", cases[0].name);
+    assert_eq!(Some("api.users".to_string()), cases[0].classname);
+    assert_eq!(TestStatus::Passed, cases[0].status);
+    assert_eq!(None, cases[0].message);
+    assert_eq!(TestStatus::Failed, cases[1].status);
+    assert_eq!(
+        Some("expected 201\nexpected 201\n  at users.rs:12".to_string()),
+        cases[1].message
+    );
+    assert_eq!(TestStatus::Error, cases[2].status);
+    assert_eq!(Some("connection refused".to_string()), cases[2].message);
+    assert_eq!(TestStatus::Skipped, cases[3].status);
+    assert!(cases[3].duration.abs() < f64::EPSILON);
+}
+
+#[rstest]
+#[case(
+    r#"<testsuite name="single" time="0.5"><testcase name="a" time="0.5"/></testsuite>"#,
+    1
+)]
+#[case(r#"<testsuites><testsuite name="a"><testcase name="a"/></testsuite><testsuite name="b"><testcase name="b"/></testsuite></testsuites>"#, 2)]
+#[case(r#"<testsuites><testsuite name="empty"/></testsuites>"#, 0)]
+fn parse_junit_suites_test(#[case] xml: &str, #[case] expected: usize) {
+    assert_eq!(expected, parse_junit(xml).unwrap().len());
+}
+
+#[test]
+fn parse_junit_duration_from_cases_test() {
+    let xml = r#"<testsuite name="a"><testcase name="a" time="0.5"/><testcase name="b" time="1.5"/></testsuite>"#;
+    let suites = parse_junit(xml).unwrap();
+    assert!((suites[0].duration - 2.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn parse_junit_invalid_test() {
+    assert!(parse_junit("<testsuite name=\"a\">").is_err());
+}
+
+#[test]
+fn find_test() {
+    let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+    std::fs::create_dir_all(dir.join("target/reports")).unwrap();
+    std::fs::write(dir.join("target/reports/a.xml"), JUNIT).unwrap();
+    std::fs::write(dir.join("target/reports/b.xml"), JUNIT).unwrap();
+    std::fs::write(dir.join("target/reports/c.txt"), "").unwrap();
+
+    let found = find(&dir.to_string_lossy(), "target/**/*.xml");
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!(2, found.len());
+    assert!(found.iter().all(|path| path.extension().unwrap() == "xml"));
+}
+
+const LCOV: &str = "TN:
+SF:src/lib.rs
+FN:1,main
+DA:1,1
+DA:2,0
+DA:3,5,checksum
+LF:3
+LH:2
+end_of_record
+SF:src/main.rs
+DA:1,0
+end_of_record
+SF:src/lib.rs
+DA:2,3
+DA:4,0
+end_of_record
+";
+
+const COBERTURA: &str = r#"<?xml version="1.0" ?>
+<coverage line-rate="0.5" version="1.9">
+  <packages>
+    <package name="app">
+      <classes>
+        <class name="lib" filename="src/lib.rs" line-rate="0.5">
+          <methods>
+            <method name="main"><lines><line number="1" hits="1"/></lines></method>
+          </methods>
+          <lines>
+            <line number="1" hits="1"/>
+            <line number="2" hits="0"/>
+          </lines>
+        </class>
+        <class name="lib_tests" filename="src/lib.rs" line-rate="1">
+          <lines>
+            <line number="2" hits="4"/>
+            <line number="3" hits="0"/>
+          </lines>
+        </class>
+      </classes>
+    </package>
+  </packages>
+</coverage>
+"#;
+
+#[test]
+fn parse_lcov_test() {
+    let files = parse_coverage(LCOV).unwrap();
+    assert_eq!(
+        vec![
+            FileCoverage {
+                path: "src/lib.rs".to_string(),
+                lines: 4,
+                covered: 3,
+            },
+            FileCoverage {
+                path: "src/main.rs".to_string(),
+                lines: 1,
+                covered: 0,
+            },
+        ],
+        files
+    );
+}
+
+#[test]
+fn parse_cobertura_test() {
+    let files = parse_coverage(COBERTURA).unwrap();
+    assert_eq!(
+        vec![FileCoverage {
+            path: "src/lib.rs".to_string(),
+            lines: 3,
+            covered: 2,
+        }],
+        files
+    );
+}
+
+#[rstest]
+#[case("TN:\n")]
+#[case("SF:src/lib.rs\nDA:one,1\nend_of_record\n")]
+#[case("<coverage><packages>")]
+fn parse_coverage_invalid_test(#[case] report: &str) {
+    assert!(parse_coverage(report).is_err());
+}
+
+#[test]
+fn merge_coverage_test() {
+    let file = |path: &str, covered| FileCoverage {
+        path: path.to_string(),
+        lines: 10,
+        covered,
+    };
+    assert_eq!(
+        vec![file("a.rs", 7), file("b.rs", 1)],
+        merge_coverage(vec![file("b.rs", 1), file("a.rs", 2), file("a.rs", 7)])
+    );
+}
@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::os::unix::fs::PermissionsExt;
+
+use rstest::rstest;
+
+use rusty_agent::runners::pipelines::sandbox::{resolve_user, Sandbox};
+
+const PASSWD: &str = "root:x:0:0:root:/root:/bin/sh\nrunner:x:1001:1002::/home/runner:/bin/sh\n";
+const GROUP: &str = "root:x:0:\nbuilders:x:2000:runner\n";
+
+#[test]
+fn env_test() {
+    let sandbox = Sandbox {
+        allowed_env: vec!["PATH".to_string()],
+        user: None,
+        allowed_users: vec![],
+    };
+    let agent_env = vec![
+        ("PATH".to_string(), "/usr/bin".to_string()),
+        ("AGENT_PASSWORD".to_string(), "secret".to_string()),
+        ("HOME".to_string(), "/root".to_string()),
+    ];
+    let pipeline_env = HashMap::from([("KEY".to_string(), "value".to_string())]);
+    let env = sandbox.env(agent_env, &pipeline_env, "/tmp/rusty/id");
+    assert_eq!(
+        HashMap::from([
+            ("PATH".to_string(), "/usr/bin".to_string()),
+            ("HOME".to_string(), "/tmp/rusty/id".to_string()),
+            ("KEY".to_string(), "value".to_string()),
+        ]),
+        env
+    );
+}
+
+#[test]
+fn env_pipeline_override_test() {
+    let sandbox = Sandbox {
+        allowed_env: vec!["PATH".to_string()],
+        user: None,
+        allowed_users: vec![],
+    };
+    let agent_env = vec![("PATH".to_string(), "/usr/bin".to_string())];
+    let pipeline_env = HashMap::from([("PATH".to_string(), "/opt/bin".to_string())]);
+    let env = sandbox.env(agent_env, &pipeline_env, "/tmp/rusty/id");
+    assert_eq!(Some(&"/opt/bin".to_string()), env.get("PATH"));
+}
+
+#[rstest]
+#[case("runner", Some((1001, 1002)))]
+#[case("1001", Some((1001, 1002)))]
+#[case("1500", Some((1500, 1500)))]
+#[case("runner:builders", Some((1001, 2000)))]
+#[case("runner:3000", Some((1001, 3000)))]
+#[case("1500:1600", Some((1500, 1600)))]
+#[case("unknown", None)]
+#[case("runner:unknown", None)]
+fn resolve_user_test(#[case] user: &str, #[case] expected: Option<(u32, u32)>) {
+    assert_eq!(expected, resolve_user(user, PASSWD, GROUP));
+}
+
+#[rstest]
+#[case(None, Ok((1001, 1002)))]
+#[case(Some("runner"), Ok((1001, 1002)))]
+#[case(Some("1500:1600"), Ok((1500, 1600)))]
+#[case(Some("1700"), Err("stage user `1700` is not allowed by the agent"))]
+#[case(Some("root"), Err("stage user `root` is not allowed by the agent"))]
+fn stage_user_test(#[case] requested: Option<&str>, #[case] expected: Result<(u32, u32), &str>) {
+    let sandbox = Sandbox {
+        allowed_env: vec![],
+        user: Some("runner".to_string()),
+        allowed_users: vec!["1500:1600".to_string()],
+    };
+    let user = sandbox
+        .stage_user(requested, PASSWD, GROUP)
+        .map_err(|err| err.to_string());
+    assert_eq!(expected.map_err(|err| format!("IO error: {err}")), user);
+}
+
+#[rstest]
+#[case(None, "sandboxed stages need a `SANDBOX_USER`")]
+#[case(Some("root"), "stage user `root` cannot be root")]
+#[case(Some("0:1002"), "stage user `0:1002` cannot be root")]
+#[case(Some("unknown"), "unknown stage user `unknown`")]
+fn stage_user_error_test(#[case] user: Option<&str>, #[case] expected: &str) {
+    let sandbox = Sandbox {
+        allowed_env: vec![],
+        user: user.map(ToString::to_string),
+        allowed_users: vec![],
+    };
+    let err = sandbox.stage_user(None, PASSWD, GROUP).unwrap_err();
+    assert!(err.to_string().contains(expected));
+}
+
+#[test]
+fn create_workspace_test() {
+    let dir = format!("/tmp/rusty/{}", uuid::Uuid::new_v4());
+    let sandbox = Sandbox {
+        allowed_env: vec![],
+        user: None,
+        allowed_users: vec![],
+    };
+    assert!(sandbox.create_workspace(&dir).is_ok());
+    let mode = std::fs::metadata(&dir).unwrap().permissions().mode();
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!(0o700, mode & 0o777);
+}
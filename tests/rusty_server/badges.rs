@@ -0,0 +1,103 @@
+use axum::extract::{Path, Query};
+use axum::http::{HeaderMap, StatusCode};
+use axum::Extension;
+use rstest::rstest;
+use testcontainers::runners::AsyncRunner;
+use testcontainers_modules::redis::Redis;
+
+use domain::auth::credentials::Credential;
+use domain::badges::RegisterBadgeSettings;
+use domain::pipelines::PipelineStatus;
+use rusty_server::badges::{badge_handler, coverage_badge, render, status_badge, BadgeQuery};
+use rusty_server::services::badges as service;
+
+use crate::rusty_server::services::shared;
+use crate::utils::db_connect;
+
+#[test]
+fn render_test() {
+    let svg = render("pipeline", "<passing>", "#4c1");
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains(r#"aria-label="pipeline: &lt;passing&gt;""#));
+    assert!(svg.contains(r##"fill="#4c1""##));
+}
+
+#[rstest]
+#[case(None, "unknown")]
+#[case(Some(PipelineStatus::Assigned), "pending")]
+#[case(Some(PipelineStatus::InProgress), "running")]
+#[case(Some(PipelineStatus::Success), "passing")]
+#[case(Some(PipelineStatus::Failure), "failing")]
+fn status_badge_test(#[case] status: Option<PipelineStatus>, #[case] expected: &str) {
+    assert!(status_badge(status).contains(&format!("<title>pipeline: {expected}</title>")));
+}
+
+#[rstest]
+#[case(None, "unknown", "#9f9f9f")]
+#[case(Some(92.4), "92%", "#4c1")]
+#[case(Some(64.0), "64%", "#dfb317")]
+#[case(Some(12.0), "12%", "#e05d44")]
+fn coverage_badge_test(#[case] percent: Option<f64>, #[case] expected: &str, #[case] color: &str) {
+    let svg = coverage_badge(percent);
+    assert!(svg.contains(&format!("<title>coverage: {expected}</title>")));
+    assert!(svg.contains(&format!(r#"fill="{color}""#)));
+}
+
+#[tokio::test]
+async fn badge_handler_public_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+    let _ = shared::create_pipeline(&db_client, &job_id).await;
+    let _ = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterBadgeSettings::new(&job_id, true),
+    )
+    .await;
+
+    let status = badge_handler(
+        Extension(db_client.clone()),
+        Path((job_id.clone(), "status.svg".to_string())),
+        Query(BadgeQuery::default()),
+        HeaderMap::new(),
+    )
+    .await;
+    let unknown = badge_handler(
+        Extension(db_client.clone()),
+        Path((job_id, "duration.svg".to_string())),
+        Query(BadgeQuery::default()),
+        HeaderMap::new(),
+    )
+    .await;
+    let _ = db.stop().await;
+    let (headers, svg) = status.unwrap();
+    assert_eq!("image/svg+xml", headers[0].1);
+    assert!(svg.contains("<title>pipeline: pending</title>"));
+    assert_eq!(StatusCode::NOT_FOUND, unknown.unwrap_err().0);
+}
+
+#[tokio::test]
+async fn badge_handler_private_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+
+    let result = badge_handler(
+        Extension(db_client.clone()),
+        Path((job_id, "status.svg".to_string())),
+        Query(BadgeQuery::default()),
+        HeaderMap::new(),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert_eq!(StatusCode::UNAUTHORIZED, result.unwrap_err().0);
+}
@@ -0,0 +1,59 @@
+use domain::event_hooks::{EventHook, EventHookDelivery, RegisterEventHook};
+use rusty_server::event_hooks::{send, signature};
+use serde_json::json;
+
+const SECRET: &str = "0123456789abcdef";
+
+#[test]
+fn signature_test() {
+    let result = signature(SECRET, "{}");
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    assert!(result.starts_with("sha256="));
+    assert_eq!(71, result.len());
+    assert_eq!(result, signature(SECRET, "{}").unwrap());
+    assert_ne!(result, signature(SECRET, "[]").unwrap());
+}
+
+#[tokio::test]
+async fn send_test() {
+    let mut server = mockito::Server::new_async().await;
+    let hook = EventHook::from(&RegisterEventHook::new(
+        &format!("{}/hook", server.url()),
+        SECRET,
+    ));
+    let delivery = EventHookDelivery::new(&hook.id, "jobs", "create", &json!({ "id": "job" }));
+    let mock = server
+        .mock("POST", "/hook")
+        .match_header("x-rusty-event", "jobs.create")
+        .match_header("x-rusty-delivery", delivery.id.as_str())
+        .match_header(
+            "x-rusty-signature-256",
+            signature(SECRET, &delivery.payload).unwrap().as_str(),
+        )
+        .match_body(delivery.payload.as_str())
+        .with_status(200)
+        .create_async()
+        .await;
+    let result = send(&hook, &delivery).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn send_error_test() {
+    let mut server = mockito::Server::new_async().await;
+    let hook = EventHook::from(&RegisterEventHook::new(
+        &format!("{}/hook", server.url()),
+        SECRET,
+    ));
+    let delivery = EventHookDelivery::new(&hook.id, "jobs", "create", &json!({}));
+    let mock = server
+        .mock("POST", "/hook")
+        .with_status(500)
+        .create_async()
+        .await;
+    let result = send(&hook, &delivery).await;
+    assert!(result.is_err());
+    mock.assert_async().await;
+}
@@ -1,3 +1,9 @@
+mod badges;
+mod event_hooks;
 mod middleware;
+mod notifications;
+mod reporters;
 mod schedulers;
+mod schemas;
 mod services;
+mod webhooks;
@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use mockito::Matcher;
+use serde_json::json;
+
+use domain::notifications::{Notification, NotificationChannel, NotificationRule};
+use domain::pipelines::PipelineStatus;
+use rusty_server::notifications::slack::SlackSender;
+use rusty_server::notifications::webhook::WebhookSender;
+use rusty_server::notifications::{send_with_retry, ChannelSender, NotificationMessage, Sender};
+
+fn message() -> NotificationMessage {
+    NotificationMessage {
+        project: "sample".to_string(),
+        job: "build".to_string(),
+        pipeline_id: "uuid".to_string(),
+        number: 3,
+        branch: "master".to_string(),
+        status: PipelineStatus::Failure,
+        commit: Some("sha".to_string()),
+    }
+}
+
+#[test]
+fn subject_test() {
+    assert_eq!("[sample] build #3 (master): Failure", message().subject());
+}
+
+#[test]
+fn channel_sender_test() {
+    let notification = Notification {
+        id: "uuid".to_string(),
+        channel: NotificationChannel::Webhook,
+        target: "http://localhost/hook".to_string(),
+        rule: NotificationRule::EveryRun,
+        branch: None,
+        project_id: "project".to_string(),
+    };
+    assert!(matches!(
+        ChannelSender::new(&notification),
+        Ok(ChannelSender::Webhook(_))
+    ));
+}
+
+#[tokio::test]
+async fn slack_send_test() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/hook")
+        .match_body(Matcher::PartialJson(json!({
+            "text": "[sample] build #3 (master): Failure",
+        })))
+        .with_status(200)
+        .create_async()
+        .await;
+    let sender = SlackSender::new(&format!("{}/hook", server.url()));
+    let result = sender.send(&message()).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn webhook_send_test() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/hook")
+        .match_header("content-type", "application/json")
+        .match_body(Matcher::PartialJson(json!({
+            "project": "sample",
+            "job": "build",
+            "pipeline": { "id": "uuid", "number": 3, "status": "Failure", "commit": "sha" },
+        })))
+        .with_status(204)
+        .create_async()
+        .await;
+    let sender = WebhookSender::new(&format!("{}/hook", server.url()));
+    let result = sender.send(&message()).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn send_with_retry_test() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/hook")
+        .with_status(500)
+        .expect(3)
+        .create_async()
+        .await;
+    let sender = WebhookSender::new(&format!("{}/hook", server.url()));
+    let (attempts, error) =
+        send_with_retry(&sender, &message(), 2, Duration::from_millis(10)).await;
+    assert_eq!(3, attempts);
+    assert!(error.is_some());
+    mock.assert_async().await;
+}
@@ -0,0 +1,214 @@
+use std::time::Duration;
+
+use mockito::Matcher;
+use rstest::rstest;
+use serde_json::json;
+
+use domain::pipelines::PipelineStatus;
+use domain::reporters::{RegisterStatusReporter, StatusReporter};
+use domain::webhooks::Forge;
+use rusty_server::reporters::gitea::GiteaReporter;
+use rusty_server::reporters::github::GitHubReporter;
+use rusty_server::reporters::gitlab::GitLabReporter;
+use rusty_server::reporters::{
+    report_with_retry, repository_path, CommitState, CommitStatus, ForgeReporter, Reporter,
+};
+
+const SHA: &str = "59b20b8d5c6ff8d09518454d4dd8b7a30f095ab5";
+
+fn status(state: CommitState) -> CommitStatus {
+    CommitStatus {
+        repository: "rusty-ops/sample".to_string(),
+        commit: SHA.to_string(),
+        branch: "feature/test".to_string(),
+        state,
+        context: "rusty-ops/sample".to_string(),
+        description: "Pipeline #1".to_string(),
+    }
+}
+
+#[rstest]
+#[case(PipelineStatus::Defined, CommitState::Pending)]
+#[case(PipelineStatus::Assigned, CommitState::Pending)]
+#[case(PipelineStatus::InProgress, CommitState::Running)]
+#[case(PipelineStatus::Success, CommitState::Success)]
+#[case(PipelineStatus::Failure, CommitState::Failure)]
+#[case(PipelineStatus::Unstable, CommitState::Failure)]
+fn commit_state_test(#[case] status: PipelineStatus, #[case] expected: CommitState) {
+    assert_eq!(expected, CommitState::from(status));
+}
+
+#[rstest]
+#[case("https://github.com/rusty-ops/sample", Some("rusty-ops/sample"))]
+#[case("https://github.com/rusty-ops/sample.git", Some("rusty-ops/sample"))]
+#[case(
+    "git@gitlab.com:rusty-ops/group/sample.git",
+    Some("rusty-ops/group/sample")
+)]
+#[case(
+    "ssh://git@localhost:2222/rusty-ops/sample.git",
+    Some("rusty-ops/sample")
+)]
+#[case("https://github.com", None)]
+fn repository_path_test(#[case] url: &str, #[case] expected: Option<&str>) {
+    assert_eq!(expected.map(ToString::to_string), repository_path(url));
+}
+
+#[rstest]
+#[case(Forge::GitHub, "https://github.com/rusty-ops/sample", true)]
+#[case(Forge::GitLab, "https://gitlab.com/rusty-ops/sample", true)]
+#[case(Forge::Gitea, "http://localhost:3000/rusty-ops/sample", true)]
+#[case(Forge::Gitea, "git@localhost:rusty-ops/sample.git", true)]
+#[case(Forge::Gitea, "rusty-ops/sample", false)]
+fn forge_reporter_new_test(#[case] forge: Forge, #[case] url: &str, #[case] expected: bool) {
+    let reporter = StatusReporter::from(&RegisterStatusReporter::new(
+        forge,
+        "token",
+        "871188c7-6a26-41a0-b7a2-1cb97dcdb01a",
+    ));
+    assert_eq!(expected, ForgeReporter::new(&reporter, url).is_some());
+}
+
+#[tokio::test]
+async fn github_report_test() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock(
+            "POST",
+            format!("/repos/rusty-ops/sample/statuses/{SHA}").as_str(),
+        )
+        .match_header("authorization", "Bearer token")
+        .match_body(Matcher::PartialJson(json!({
+            "state": "pending",
+            "context": "rusty-ops/sample",
+        })))
+        .with_status(201)
+        .create_async()
+        .await;
+    let reporter = GitHubReporter::new(&server.url(), "token");
+    let result = reporter.report(&status(CommitState::Running)).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn github_resolve_commit_test() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/repos/rusty-ops/sample/branches/feature%2Ftest")
+        .with_status(200)
+        .with_body(json!({ "name": "feature/test", "commit": { "sha": SHA } }).to_string())
+        .create_async()
+        .await;
+    let reporter = GitHubReporter::new(&server.url(), "token");
+    let result = reporter
+        .resolve_commit("rusty-ops/sample", "feature/test")
+        .await;
+    assert!(result.is_ok());
+    assert_eq!(SHA, result.unwrap());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn gitlab_report_test() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock(
+            "POST",
+            format!("/projects/rusty-ops%2Fsample/statuses/{SHA}").as_str(),
+        )
+        .match_header("private-token", "token")
+        .match_body(Matcher::PartialJson(json!({
+            "state": "failed",
+            "ref": "feature/test",
+            "name": "rusty-ops/sample",
+        })))
+        .with_status(201)
+        .create_async()
+        .await;
+    let reporter = GitLabReporter::new(&server.url(), "token");
+    let result = reporter.report(&status(CommitState::Failure)).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn gitlab_resolve_commit_test() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock(
+            "GET",
+            "/projects/rusty-ops%2Fsample/repository/branches/feature%2Ftest",
+        )
+        .with_status(200)
+        .with_body(json!({ "name": "feature/test", "commit": { "id": SHA } }).to_string())
+        .create_async()
+        .await;
+    let reporter = GitLabReporter::new(&server.url(), "token");
+    let result = reporter
+        .resolve_commit("rusty-ops/sample", "feature/test")
+        .await;
+    assert!(result.is_ok());
+    assert_eq!(SHA, result.unwrap());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn gitea_report_test() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock(
+            "POST",
+            format!("/repos/rusty-ops/sample/statuses/{SHA}").as_str(),
+        )
+        .match_header("authorization", "token token")
+        .match_body(Matcher::PartialJson(json!({ "state": "success" })))
+        .with_status(201)
+        .create_async()
+        .await;
+    let reporter = GiteaReporter::new(&server.url(), "token");
+    let result = reporter.report(&status(CommitState::Success)).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn report_error_test() {
+    let mut server = mockito::Server::new_async().await;
+    let _ = server
+        .mock(
+            "POST",
+            format!("/repos/rusty-ops/sample/statuses/{SHA}").as_str(),
+        )
+        .with_status(401)
+        .with_body("Bad credentials")
+        .create_async()
+        .await;
+    let reporter = GiteaReporter::new(&server.url(), "token");
+    let result = reporter.report(&status(CommitState::Success)).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn report_with_retry_test() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock(
+            "POST",
+            format!("/repos/rusty-ops/sample/statuses/{SHA}").as_str(),
+        )
+        .with_status(500)
+        .expect(3)
+        .create_async()
+        .await;
+    let reporter = GitHubReporter::new(&server.url(), "token");
+    let result = report_with_retry(
+        &reporter,
+        &status(CommitState::Success),
+        2,
+        Duration::from_millis(10),
+    )
+    .await;
+    assert!(result.is_err());
+    mock.assert_async().await;
+}
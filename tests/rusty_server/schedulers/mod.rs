@@ -7,8 +7,16 @@ use testcontainers_modules::redis::Redis;
 use tokio::time::timeout;
 
 use domain::agents::Agent;
+use domain::event_hooks::{DeliveryStatus, EventHook};
+use domain::jobs::Job;
+use domain::notifications::{Notification, NotificationChannel, NotificationRule};
 use domain::pipelines::{Pipeline, PipelineStatus};
+use domain::projects::Project;
+use domain::reporters::StatusReporter;
+use domain::schedules::Schedule;
+use domain::webhooks::Forge;
 use domain::RustyDomainItem;
+use persist::db_client::DbClient;
 use rusty_server::schedulers;
 
 use crate::utils::{db_connect, mq_connect};
@@ -75,6 +83,11 @@ async fn scheduler_pipelines_cleanup_test() {
                 status: PipelineStatus::Assigned,
                 job_id: "uuid".to_string(),
                 agent_id: Some("uuid".to_string()),
+                commit: None,
+                revision: None,
+                triggered_by: None,
+                upstream_id: None,
+                variables: None,
             }
             .to_value()
             .unwrap(),
@@ -88,3 +101,369 @@ async fn scheduler_pipelines_cleanup_test() {
     let result = timeout(Duration::from_secs(1), handle).await;
     assert!(result.is_err());
 }
+
+#[tokio::test]
+async fn scheduler_pipeline_schedule_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let _ = db_client
+        .create(
+            "projects",
+            &Project {
+                id: "project".to_string(),
+                name: "sample".to_string(),
+                url: None,
+                main_branch: "master".to_string(),
+                group_id: None,
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await;
+    let _ = db_client
+        .create(
+            "jobs",
+            &Job {
+                id: "job".to_string(),
+                name: "sample".to_string(),
+                description: None,
+                template: "".to_string(),
+                project_id: "project".to_string(),
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await;
+    let _ = db_client
+        .create(
+            "schedules",
+            &Schedule {
+                id: "schedule".to_string(),
+                cron: "* * * * *".to_string(),
+                branch: None,
+                skip_if_running: true,
+                register_date: "2024-01-01T00:00:00+00:00".to_string(),
+                last_run: None,
+                job_id: "job".to_string(),
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await;
+
+    let db_scheduler = db_client.clone();
+    let handle =
+        tokio::spawn(async move { schedulers::pipeline_schedule::schedule(&db_scheduler).await });
+    let result = timeout(Duration::from_secs(1), handle).await;
+    let pipelines = db_client.get_all("pipelines", &None, &None).await;
+    let schedule = db_client
+        .get_one(
+            "schedules",
+            serde_json::json!({ "id": { "equals": "schedule" } }),
+        )
+        .await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+    assert_eq!(1, pipelines.unwrap().len());
+    assert!(schedule
+        .unwrap()
+        .unwrap()
+        .get("last_run")
+        .unwrap()
+        .is_string());
+}
+
+async fn create_commit_status_fixtures(db_client: &DbClient, api_url: &str) {
+    let _ = db_client
+        .create(
+            "projects",
+            &Project {
+                id: "project".to_string(),
+                name: "sample".to_string(),
+                url: Some("https://github.com/rusty-ops/sample".to_string()),
+                main_branch: "master".to_string(),
+                group_id: None,
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await;
+    let _ = db_client
+        .create(
+            "jobs",
+            &Job {
+                id: "job".to_string(),
+                name: "sample".to_string(),
+                description: None,
+                template: "".to_string(),
+                project_id: "project".to_string(),
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await;
+    let _ = db_client
+        .create(
+            "status_reporters",
+            &StatusReporter {
+                id: "reporter".to_string(),
+                forge: Forge::GitHub,
+                api_url: Some(api_url.to_string()),
+                token: "token".to_string(),
+                project_id: "project".to_string(),
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await;
+}
+
+fn commit_status_pipeline(commit: Option<&str>) -> Pipeline {
+    Pipeline {
+        id: "uuid".to_string(),
+        number: 1,
+        branch: "master".to_string(),
+        register_date: "now".to_string(),
+        start_date: Some("now".to_string()),
+        end_date: Some("now".to_string()),
+        stage_status: HashMap::new(),
+        status: PipelineStatus::Success,
+        job_id: "job".to_string(),
+        agent_id: Some("uuid".to_string()),
+        commit: commit.map(ToString::to_string),
+        revision: None,
+        triggered_by: None,
+        upstream_id: None,
+        variables: None,
+    }
+}
+
+#[tokio::test]
+async fn commit_status_report_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/repos/rusty-ops/sample/statuses/sha")
+        .match_header("authorization", "Bearer token")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "state": "success",
+            "context": "rusty-ops/sample",
+        })))
+        .with_status(201)
+        .create_async()
+        .await;
+    create_commit_status_fixtures(&db_client, &server.url()).await;
+
+    let result =
+        schedulers::commit_status::report(&db_client, &commit_status_pipeline(Some("sha"))).await;
+    let _ = db.stop().await;
+    assert_eq!(Some("sha".to_string()), result.unwrap());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn commit_status_report_resolve_commit_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let mut server = mockito::Server::new_async().await;
+    let branch_mock = server
+        .mock("GET", "/repos/rusty-ops/sample/branches/master")
+        .with_status(200)
+        .with_body(r#"{ "commit": { "sha": "head" } }"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let status_mock = server
+        .mock("POST", "/repos/rusty-ops/sample/statuses/head")
+        .with_status(201)
+        .create_async()
+        .await;
+    create_commit_status_fixtures(&db_client, &server.url()).await;
+
+    let result = schedulers::commit_status::report(&db_client, &commit_status_pipeline(None)).await;
+    let _ = db.stop().await;
+    assert_eq!(Some("head".to_string()), result.unwrap());
+    branch_mock.assert_async().await;
+    status_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn notifications_notify_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/hook")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "text": "[sample] sample #2 (master): Success",
+        })))
+        .with_status(200)
+        .expect(1)
+        .create_async()
+        .await;
+    let _ = db_client
+        .create(
+            "projects",
+            &Project {
+                id: "project".to_string(),
+                name: "sample".to_string(),
+                url: None,
+                main_branch: "master".to_string(),
+                group_id: None,
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await;
+    let _ = db_client
+        .create(
+            "jobs",
+            &Job {
+                id: "job".to_string(),
+                name: "sample".to_string(),
+                description: None,
+                template: "".to_string(),
+                project_id: "project".to_string(),
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await;
+    for (id, rule) in [
+        ("fixed", NotificationRule::OnFixed),
+        ("failure", NotificationRule::OnFailure),
+    ] {
+        let _ = db_client
+            .create(
+                "notifications",
+                &Notification {
+                    id: id.to_string(),
+                    channel: NotificationChannel::Slack,
+                    target: format!("{}/hook", server.url()),
+                    rule,
+                    branch: None,
+                    project_id: "project".to_string(),
+                }
+                .to_value()
+                .unwrap(),
+            )
+            .await;
+    }
+    let mut pipeline = Pipeline {
+        id: "previous".to_string(),
+        number: 1,
+        branch: "master".to_string(),
+        register_date: "now".to_string(),
+        start_date: Some("now".to_string()),
+        end_date: Some("now".to_string()),
+        stage_status: HashMap::new(),
+        status: PipelineStatus::Failure,
+        job_id: "job".to_string(),
+        agent_id: None,
+        commit: None,
+        revision: None,
+        triggered_by: None,
+        upstream_id: None,
+        variables: None,
+    };
+    let _ = db_client
+        .create("pipelines", &pipeline.to_value().unwrap())
+        .await;
+    pipeline.id = "current".to_string();
+    pipeline.number = 2;
+    pipeline.status = PipelineStatus::Success;
+
+    let result = schedulers::notifications::notify(&db_client, &pipeline).await;
+    let second = schedulers::notifications::notify(&db_client, &pipeline).await;
+    let deliveries = db_client
+        .get_all("notification_deliveries", &None, &None)
+        .await
+        .unwrap();
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert!(second.is_ok());
+    assert_eq!(1, deliveries.len());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn event_hooks_dispatch_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/hook")
+        .match_header("x-rusty-event", "pipelines.update")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "index": "pipelines",
+            "op": "update",
+            "item": { "id": "uuid" },
+        })))
+        .with_status(200)
+        .expect(1)
+        .create_async()
+        .await;
+    let _ = db_client
+        .create(
+            "jobs",
+            &Job {
+                id: "job".to_string(),
+                name: "sample".to_string(),
+                description: None,
+                template: "".to_string(),
+                project_id: "project".to_string(),
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await;
+    for (id, project_id) in [("matching", "project"), ("other", "other")] {
+        let _ = db_client
+            .create(
+                "event_hooks",
+                &EventHook {
+                    id: id.to_string(),
+                    url: format!("{}/hook", server.url()),
+                    secret: "0123456789abcdef".to_string(),
+                    indexes: vec!["pipelines".to_string()],
+                    operations: vec![],
+                    project_id: Some(project_id.to_string()),
+                    owner: "SYSTEM".to_string(),
+                }
+                .to_value()
+                .unwrap(),
+            )
+            .await;
+    }
+    let item = serde_json::json!({ "id": "uuid", "job_id": "job" });
+
+    let result = schedulers::event_hooks::dispatch(&db_client, "pipelines", "update", &item).await;
+    let deliveries = db_client
+        .get_all("event_hook_deliveries", &None, &None)
+        .await
+        .unwrap();
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(1, deliveries.len());
+    assert_eq!(
+        serde_json::json!(DeliveryStatus::Delivered),
+        deliveries[0]["status"]
+    );
+    mock.assert_async().await;
+}
@@ -0,0 +1,26 @@
+use axum::extract::Path;
+use axum::http::StatusCode;
+
+use domain::templates::schema::{json_schema, TEMPLATE_SCHEMA_VERSION};
+use rusty_server::schemas::{pipeline_template_handler, pipeline_template_version_handler};
+
+#[tokio::test]
+async fn pipeline_template_handler_test() {
+    let result = pipeline_template_handler().await;
+    assert_eq!(json_schema(), result.0);
+}
+
+#[tokio::test]
+async fn pipeline_template_version_handler_test() {
+    let result =
+        pipeline_template_version_handler(Path(format!("{TEMPLATE_SCHEMA_VERSION}.json"))).await;
+    assert!(result.is_ok());
+    assert_eq!(json_schema(), result.unwrap().0);
+}
+
+#[tokio::test]
+async fn pipeline_template_version_handler_unknown_test() {
+    let result = pipeline_template_version_handler(Path("v0.json".to_string())).await;
+    assert!(result.is_err());
+    assert_eq!(StatusCode::NOT_FOUND, result.unwrap_err().0);
+}
@@ -0,0 +1,193 @@
+use serde_json::json;
+use std::collections::HashMap;
+use testcontainers::runners::AsyncRunner;
+use testcontainers_modules::redis::Redis;
+
+use domain::auth::credentials::Credential;
+use domain::badges::RegisterBadgeSettings;
+use domain::pipelines::{Pipeline, PipelineStatus};
+use domain::RustyDomainItem;
+use persist::db_client::DbClient;
+use rusty_server::services::badges as service;
+
+use crate::rusty_server::services::shared;
+use crate::utils::db_connect;
+
+async fn create_pipeline(
+    db_client: &DbClient,
+    job_id: &str,
+    number: u64,
+    status: PipelineStatus,
+) -> String {
+    db_client
+        .create(
+            "pipelines",
+            &Pipeline {
+                id: uuid::Uuid::new_v4().to_string(),
+                number,
+                branch: "master".to_string(),
+                register_date: "now".to_string(),
+                start_date: None,
+                end_date: None,
+                stage_status: HashMap::new(),
+                status,
+                job_id: job_id.to_string(),
+                agent_id: None,
+                commit: None,
+                revision: None,
+                triggered_by: None,
+                upstream_id: None,
+                variables: None,
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn create_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+
+    let result = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterBadgeSettings::new(&job_id, true),
+    )
+    .await;
+    let duplicate = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterBadgeSettings::new(&job_id, false),
+    )
+    .await;
+    let public = service::is_public(&db_client, &job_id).await;
+    let entry = service::get_by_id(&db_client, &Credential::System, &result.clone().unwrap()).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert!(duplicate.is_err());
+    assert_eq!(Ok(true), public);
+    assert_eq!(project_id, entry.unwrap().unwrap().project_id);
+}
+
+#[tokio::test]
+async fn create_job_not_found_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+
+    let result = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterBadgeSettings::new("57c38e8b-1845-49f1-874a-1eefe9923456", true),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn is_public_without_settings_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+
+    let result = service::is_public(&db_client, &job_id).await;
+    let _ = db.stop().await;
+    assert_eq!(Ok(false), result);
+}
+
+#[tokio::test]
+async fn get_status_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+    let _ = shared::create_pipeline(&db_client, &job_id).await;
+
+    let main = service::get_status(&db_client, &Credential::System, &job_id, None).await;
+    let other = service::get_status(
+        &db_client,
+        &Credential::System,
+        &job_id,
+        Some("feature".to_string()),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert_eq!(Ok(Some(PipelineStatus::Defined)), main);
+    assert_eq!(Ok(None), other);
+}
+
+#[tokio::test]
+async fn get_coverage_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+    for (number, status, covered) in [
+        (1, PipelineStatus::Success, 50),
+        (2, PipelineStatus::Success, 80),
+        (3, PipelineStatus::Failure, 30),
+    ] {
+        let pipeline = create_pipeline(&db_client, &job_id, number, status).await;
+        let _ = shared::create_coverage_report(
+            &db_client,
+            &project_id,
+            &job_id,
+            &pipeline,
+            number,
+            "master",
+            covered,
+        )
+        .await;
+    }
+
+    let result = service::get_coverage(&db_client, &Credential::System, &job_id, None).await;
+    let _ = db.stop().await;
+    assert_eq!(Ok(Some(80.0)), result);
+}
+
+#[tokio::test]
+async fn delete_many_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+    let _ = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterBadgeSettings::new(&job_id, true),
+    )
+    .await;
+
+    let result = service::delete_many(
+        &db_client,
+        &Credential::System,
+        &json!({ "job_id": { "equals": &job_id } }),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert_eq!(Ok(1), result);
+}
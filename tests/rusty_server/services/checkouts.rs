@@ -0,0 +1,160 @@
+use testcontainers::runners::AsyncRunner;
+use testcontainers_modules::redis::Redis;
+
+use domain::auth::credentials::Credential;
+use domain::checkouts::RegisterCheckoutSettings;
+use rusty_server::services::checkouts as service;
+
+use crate::rusty_server::services::shared;
+use crate::utils::db_connect;
+
+#[tokio::test]
+async fn get_all_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let _ = shared::create_checkout_settings(&db_client, &id).await;
+
+    let result = service::get_all(&db_client, &Credential::System, &None, &None).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(1, result.unwrap().len());
+}
+
+#[tokio::test]
+async fn get_by_id_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let id = shared::create_checkout_settings(&db_client, &id).await;
+
+    let result = service::get_by_id(&db_client, &Credential::System, &id).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert!(result.clone().unwrap().is_some());
+    assert_eq!(id, result.unwrap().unwrap().id);
+}
+
+#[tokio::test]
+async fn get_checkout_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let _ = shared::create_checkout_settings(&db_client, &id).await;
+    let other = shared::create_project(&db_client).await;
+
+    let result = service::get_checkout(&db_client, &Credential::System, &id).await;
+    let other = service::get_checkout(&db_client, &Credential::System, &other).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    let result = result.unwrap().unwrap();
+    assert_eq!(Some(1), result.depth);
+    assert_eq!(Some("secret".to_string()), result.token);
+    assert!(other.is_ok());
+    assert!(other.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn create_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+
+    let settings = RegisterCheckoutSettings {
+        depth: Some(10),
+        ..RegisterCheckoutSettings::new(&id)
+    };
+    let result = service::create(&db_client, &Credential::System, settings).await;
+    let created = service::get_by_id(&db_client, &Credential::System, &result.clone().unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(Some(10), created.depth);
+}
+
+#[tokio::test]
+async fn create_duplicate_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let _ = shared::create_checkout_settings(&db_client, &id).await;
+
+    let result = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterCheckoutSettings::new(&id),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn create_key_and_token_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+
+    let settings = RegisterCheckoutSettings {
+        ssh_key: Some("key".to_string()),
+        token: Some("secret".to_string()),
+        ..RegisterCheckoutSettings::new(&id)
+    };
+    let result = service::create(&db_client, &Credential::System, settings).await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn create_no_project_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+
+    let result = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterCheckoutSettings::new("57c38e8b-1845-49f1-874a-1eefe9923456"),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn delete_by_id_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let id = shared::create_checkout_settings(&db_client, &id).await;
+
+    let result = service::delete_by_id(&db_client, &Credential::System, &id).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(1, result.unwrap());
+}
@@ -0,0 +1,238 @@
+use serde_json::json;
+use std::collections::HashMap;
+use testcontainers::runners::AsyncRunner;
+use testcontainers_modules::redis::Redis;
+
+use domain::auth::credentials::Credential;
+use domain::coverage::FileCoverage;
+use domain::pipelines::{Pipeline, PipelineStatus};
+use domain::RustyDomainItem;
+use persist::db_client::DbClient;
+use rusty_server::services::coverage as service;
+
+use crate::rusty_server::services::shared;
+use crate::utils::db_connect;
+
+async fn create_pipeline(
+    db_client: &DbClient,
+    job_id: &str,
+    number: u64,
+    branch: &str,
+    status: PipelineStatus,
+    agent_id: Option<String>,
+) -> String {
+    db_client
+        .create(
+            "pipelines",
+            &Pipeline {
+                id: uuid::Uuid::new_v4().to_string(),
+                number,
+                branch: branch.to_string(),
+                register_date: "now".to_string(),
+                start_date: None,
+                end_date: None,
+                stage_status: HashMap::new(),
+                status,
+                job_id: job_id.to_string(),
+                agent_id,
+                commit: None,
+                revision: None,
+                triggered_by: None,
+                upstream_id: None,
+                variables: None,
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn create_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let agent_id = shared::create_agent(&db_client).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+    let pipeline_id = create_pipeline(
+        &db_client,
+        &job_id,
+        1,
+        "master",
+        PipelineStatus::InProgress,
+        Some(agent_id.clone()),
+    )
+    .await;
+
+    let files = vec![FileCoverage {
+        path: "src/lib.rs".to_string(),
+        lines: 4,
+        covered: 3,
+    }];
+    let wrong_agent = service::create(
+        &db_client,
+        &Credential::System,
+        &pipeline_id,
+        "other-agent",
+        "test",
+        files.clone(),
+    )
+    .await;
+    let result = service::create(
+        &db_client,
+        &Credential::System,
+        &pipeline_id,
+        &agent_id,
+        "test",
+        files,
+    )
+    .await;
+    let entries = service::get_all(&db_client, &Credential::System, &None, &None).await;
+    let _ = db.stop().await;
+    assert!(wrong_agent.is_err());
+    assert!(result.is_ok());
+    let entries = entries.unwrap();
+    assert_eq!(1, entries.len());
+    assert_eq!(pipeline_id, entries[0].pipeline_id);
+    assert!((entries[0].percent - 75.0).abs() < f64::EPSILON);
+}
+
+#[tokio::test]
+async fn get_trend_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+    for (pipeline, number, branch, covered) in [
+        ("p1", 1, "master", 50),
+        ("p2", 2, "feature", 40),
+        ("p3", 3, "master", 60),
+        ("p4", 4, "master", 70),
+    ] {
+        let _ = shared::create_coverage_report(
+            &db_client,
+            &project_id,
+            &job_id,
+            pipeline,
+            number,
+            branch,
+            covered,
+        )
+        .await;
+    }
+
+    let result =
+        service::get_trend(&db_client, &Credential::System, &job_id, "master", Some(2)).await;
+    let _ = db.stop().await;
+    let trend = result.unwrap();
+    assert_eq!(
+        vec![(3, 60), (4, 70)],
+        trend
+            .iter()
+            .map(|point| (point.pipeline_number, point.covered))
+            .collect::<Vec<(u64, u64)>>()
+    );
+}
+
+#[tokio::test]
+async fn get_delta_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+    let mut pipelines = vec![];
+    for (number, branch, status, covered) in [
+        (1, "master", PipelineStatus::Success, 60),
+        (2, "master", PipelineStatus::Failure, 80),
+        (3, "feature", PipelineStatus::Success, 55),
+    ] {
+        let id = create_pipeline(&db_client, &job_id, number, branch, status, None).await;
+        let _ = shared::create_coverage_report(
+            &db_client,
+            &project_id,
+            &job_id,
+            &id,
+            number,
+            branch,
+            covered,
+        )
+        .await;
+        pipelines.push(id);
+    }
+
+    let result = service::get_delta(&db_client, &Credential::System, &pipelines[2]).await;
+    let _ = db.stop().await;
+    let delta = result.unwrap().unwrap();
+    assert_eq!(pipelines[2], delta.coverage.pipeline_id);
+    assert_eq!(pipelines[0], delta.base.unwrap().pipeline_id);
+    assert!((delta.delta.unwrap() + 5.0).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn get_delta_no_base_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+    let pipeline_id = create_pipeline(
+        &db_client,
+        &job_id,
+        1,
+        "feature",
+        PipelineStatus::Success,
+        None,
+    )
+    .await;
+    let _ = shared::create_coverage_report(
+        &db_client,
+        &project_id,
+        &job_id,
+        &pipeline_id,
+        1,
+        "feature",
+        55,
+    )
+    .await;
+
+    let result = service::get_delta(&db_client, &Credential::System, &pipeline_id).await;
+    let _ = db.stop().await;
+    let delta = result.unwrap().unwrap();
+    assert_eq!(None, delta.base);
+    assert_eq!(None, delta.delta);
+}
+
+#[tokio::test]
+async fn delete_many_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+    let _ = shared::create_coverage_report(&db_client, &project_id, &job_id, "p1", 1, "master", 50)
+        .await;
+
+    let result = service::delete_many(
+        &db_client,
+        &Credential::System,
+        &json!({ "project_id": { "equals": &project_id } }),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(1, result.unwrap());
+}
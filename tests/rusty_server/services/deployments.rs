@@ -0,0 +1,231 @@
+use serde_json::json;
+use std::collections::HashMap;
+use testcontainers::runners::AsyncRunner;
+use testcontainers_modules::redis::Redis;
+
+use domain::auth::credentials::Credential;
+use domain::jobs::Job;
+use domain::pipelines::{Pipeline, PipelineStatus};
+use domain::RustyDomainItem;
+use persist::db_client::DbClient;
+use rusty_server::services::{deployments as service, pipelines};
+
+use crate::rusty_server::services::shared;
+use crate::utils::db_connect;
+
+const TEMPLATE: &str = r#"
+stages:
+  build:
+    script:
+      - cargo build
+  deploy:
+    environment:
+      name: staging
+      url: https://staging.example.com
+    script:
+      - ./deploy.sh
+"#;
+
+async fn create_deploy_job(db_client: &DbClient, project_id: &str) -> String {
+    db_client
+        .create(
+            "jobs",
+            &Job {
+                id: uuid::Uuid::new_v4().to_string(),
+                name: "deploy".to_string(),
+                description: None,
+                template: TEMPLATE.to_string(),
+                project_id: project_id.to_string(),
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+async fn create_running_pipeline(db_client: &DbClient, job_id: &str, agent_id: &str) -> String {
+    db_client
+        .create(
+            "pipelines",
+            &Pipeline {
+                id: uuid::Uuid::new_v4().to_string(),
+                number: 1,
+                branch: "master".to_string(),
+                register_date: "now".to_string(),
+                start_date: None,
+                end_date: None,
+                stage_status: HashMap::new(),
+                status: PipelineStatus::InProgress,
+                job_id: job_id.to_string(),
+                agent_id: Some(agent_id.to_string()),
+                commit: Some("0123abc".to_string()),
+                revision: None,
+                triggered_by: Some("user".to_string()),
+                upstream_id: None,
+                variables: None,
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn get_all_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+    let pipeline_id = shared::create_pipeline(&db_client, &job_id).await;
+    let _ = shared::create_deployment(&db_client, &project_id, &job_id, &pipeline_id).await;
+
+    let result = service::get_all(&db_client, &Credential::System, &None, &None).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(1, result.unwrap().len());
+}
+
+#[tokio::test]
+async fn record_on_stage_success_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let agent_id = shared::create_agent(&db_client).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = create_deploy_job(&db_client, &project_id).await;
+    let pipeline_id = create_running_pipeline(&db_client, &job_id, &agent_id).await;
+
+    for stage in ["build", "deploy", "deploy"] {
+        let result = pipelines::update_stage(
+            &db_client,
+            &Credential::System,
+            &pipeline_id,
+            &agent_id,
+            stage,
+            PipelineStatus::Success,
+        )
+        .await;
+        assert!(result.is_ok());
+    }
+
+    let result = service::get_environments(&db_client, &Credential::System, &project_id).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    let environments = result.unwrap();
+    assert_eq!(1, environments.len());
+    assert_eq!("staging", environments[0].name);
+    assert_eq!(
+        Some("https://staging.example.com".to_string()),
+        environments[0].url
+    );
+    let current = &environments[0].current;
+    assert_eq!(pipeline_id, current.pipeline_id);
+    assert_eq!("deploy", current.stage);
+    assert_eq!(Some("0123abc".to_string()), current.commit);
+    assert_eq!(Some("user".to_string()), current.deployed_by);
+    assert!(environments[0].previous.is_empty());
+}
+
+#[tokio::test]
+async fn record_on_stage_failure_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let agent_id = shared::create_agent(&db_client).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = create_deploy_job(&db_client, &project_id).await;
+    let pipeline_id = create_running_pipeline(&db_client, &job_id, &agent_id).await;
+
+    let _ = pipelines::update_stage(
+        &db_client,
+        &Credential::System,
+        &pipeline_id,
+        &agent_id,
+        "deploy",
+        PipelineStatus::Failure,
+    )
+    .await;
+
+    let result = service::get_environments(&db_client, &Credential::System, &project_id).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert!(result.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn redeploy_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let agent_id = shared::create_agent(&db_client).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = create_deploy_job(&db_client, &project_id).await;
+    let pipeline_id = create_running_pipeline(&db_client, &job_id, &agent_id).await;
+    let id = shared::create_deployment(&db_client, &project_id, &job_id, &pipeline_id).await;
+
+    let result = service::redeploy(&db_client, &Credential::System, &id).await;
+    assert!(result.is_ok());
+    let pipeline = pipelines::get_by_id(&db_client, &Credential::System, &result.unwrap()).await;
+    let _ = db.stop().await;
+    let pipeline = pipeline.unwrap().unwrap();
+    assert_ne!(pipeline_id, pipeline.id);
+    assert_eq!(job_id, pipeline.job_id);
+    assert_eq!(2, pipeline.number);
+    assert_eq!("master", pipeline.branch);
+    assert_eq!(Some("0123abc".to_string()), pipeline.commit);
+    assert_eq!(PipelineStatus::Defined, pipeline.status);
+    assert_eq!(Some("SYSTEM".to_string()), pipeline.triggered_by);
+}
+
+#[tokio::test]
+async fn redeploy_no_deployment_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+
+    let result = service::redeploy(
+        &db_client,
+        &Credential::System,
+        "57c38e8b-1845-49f1-874a-1eefe9923456",
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn delete_many_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+    let pipeline_id = shared::create_pipeline(&db_client, &job_id).await;
+    let _ = shared::create_deployment(&db_client, &project_id, &job_id, &pipeline_id).await;
+    let _ = shared::create_deployment(&db_client, &project_id, &job_id, &pipeline_id).await;
+
+    let result = service::delete_many(
+        &db_client,
+        &Credential::System,
+        &json!({ "project_id": { "equals": &project_id } }),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(2, result.unwrap());
+}
@@ -0,0 +1,152 @@
+use serde_json::json;
+use testcontainers::runners::AsyncRunner;
+use testcontainers_modules::redis::Redis;
+
+use domain::auth::credentials::Credential;
+use domain::event_hooks::{DeliveryStatus, EventHookDelivery, RegisterEventHook};
+use rusty_server::services::event_hooks as service;
+
+use crate::rusty_server::services::shared;
+use crate::utils::db_connect;
+
+const SECRET: &str = "0123456789abcdef";
+
+#[tokio::test]
+async fn get_all_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let _ = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterEventHook::new("http://localhost/hook", SECRET),
+    )
+    .await;
+
+    let result = service::get_all(&db_client, &Credential::System, &None, &None).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    assert_eq!(1, result.len());
+    assert_eq!("SYSTEM", result[0].owner);
+}
+
+#[tokio::test]
+async fn create_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+
+    let mut hook = RegisterEventHook::new("http://localhost/hook", SECRET);
+    hook.project_id = Some(id);
+    let result = service::create(&db_client, &Credential::System, hook).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn create_no_project_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+
+    let mut hook = RegisterEventHook::new("http://localhost/hook", SECRET);
+    hook.project_id = Some("57c38e8b-1845-49f1-874a-1eefe9923456".to_string());
+    let result = service::create(&db_client, &Credential::System, hook).await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn create_invalid_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+
+    let result = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterEventHook::new("http://localhost/hook", "secret"),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn redeliver_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/hook")
+        .with_status(200)
+        .create_async()
+        .await;
+    let id = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterEventHook::new(&format!("{}/hook", server.url()), SECRET),
+    )
+    .await
+    .unwrap();
+    let delivery = EventHookDelivery::new(&id, "jobs", "create", &json!({ "id": "job" }));
+    let _ = service::create_delivery(&db_client, &delivery).await;
+
+    let result = service::redeliver(&db_client, &Credential::System, &delivery.id).await;
+    let deliveries = service::get_deliveries(&db_client, &Credential::System, &id, &None).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(DeliveryStatus::Delivered, result.unwrap().status);
+    let deliveries = deliveries.unwrap();
+    assert_eq!(1, deliveries.len());
+    assert_eq!(DeliveryStatus::Delivered, deliveries[0].status);
+    assert_eq!(1, deliveries[0].attempts);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn redeliver_not_found_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+
+    let result = service::redeliver(&db_client, &Credential::System, "delivery").await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn delete_by_id_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterEventHook::new("http://localhost/hook", SECRET),
+    )
+    .await
+    .unwrap();
+
+    let result = service::delete_by_id(&db_client, &Credential::System, &id).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(1, result.unwrap());
+}
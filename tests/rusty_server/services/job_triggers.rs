@@ -0,0 +1,261 @@
+use std::collections::HashMap;
+use testcontainers::runners::AsyncRunner;
+use testcontainers_modules::redis::Redis;
+
+use domain::auth::credentials::Credential;
+use domain::job_triggers::{RegisterJobTrigger, TriggerCondition};
+use domain::pipelines::{Pipeline, PipelineStatus};
+use domain::RustyDomainItem;
+use persist::db_client::DbClient;
+use rusty_server::services::{job_triggers as service, pipelines};
+
+use crate::rusty_server::services::shared;
+use crate::utils::db_connect;
+
+async fn create_running_pipeline(db_client: &DbClient, job_id: &str, agent_id: &str) -> String {
+    db_client
+        .create(
+            "pipelines",
+            &Pipeline {
+                id: uuid::Uuid::new_v4().to_string(),
+                number: 1,
+                branch: "develop".to_string(),
+                register_date: "now".to_string(),
+                start_date: None,
+                end_date: None,
+                stage_status: HashMap::new(),
+                status: PipelineStatus::InProgress,
+                job_id: job_id.to_string(),
+                agent_id: Some(agent_id.to_string()),
+                commit: Some("0123abc".to_string()),
+                revision: None,
+                triggered_by: Some("user".to_string()),
+                upstream_id: None,
+                variables: None,
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn create_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+    let downstream_job_id = shared::create_job(&db_client, &project_id).await;
+
+    let result = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterJobTrigger::new(&job_id, &downstream_job_id, TriggerCondition::OnSuccess),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn create_no_downstream_job_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+
+    let result = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterJobTrigger::new(
+            &job_id,
+            "57c38e8b-1845-49f1-874a-1eefe9923456",
+            TriggerCondition::OnSuccess,
+        ),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn create_cycle_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+    let downstream_job_id = shared::create_job(&db_client, &project_id).await;
+
+    let result = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterJobTrigger::new(&job_id, &downstream_job_id, TriggerCondition::Always),
+    )
+    .await;
+    assert!(result.is_ok());
+    let result = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterJobTrigger::new(&downstream_job_id, &job_id, TriggerCondition::Always),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn finalize_registers_downstream_test() {
+    std::env::set_var("AGENT_MAX_ASSIGNED_JOBS", "1");
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let agent_id = shared::create_agent(&db_client).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+    let downstream_job_id = shared::create_job(&db_client, &project_id).await;
+    let mut trigger =
+        RegisterJobTrigger::new(&job_id, &downstream_job_id, TriggerCondition::OnSuccess);
+    trigger.variables = Some(HashMap::from([("ENV".to_string(), "staging".to_string())]));
+    let _ = service::create(&db_client, &Credential::System, trigger)
+        .await
+        .unwrap();
+    let pipeline_id = create_running_pipeline(&db_client, &job_id, &agent_id).await;
+
+    let result = pipelines::finalize(
+        &db_client,
+        &Credential::System,
+        &pipeline_id,
+        &agent_id,
+        PipelineStatus::Success,
+    )
+    .await;
+    assert!(result.is_ok());
+    let chain = pipelines::get_chain(&db_client, &Credential::System, &pipeline_id).await;
+    let _ = db.stop().await;
+    let chain = chain.unwrap();
+    assert_eq!(2, chain.len());
+    assert_eq!(pipeline_id, chain[0].id);
+    let downstream = &chain[1];
+    assert_eq!(downstream_job_id, downstream.job_id);
+    assert_eq!(Some(pipeline_id), downstream.upstream_id);
+    assert_eq!("develop", downstream.branch);
+    assert_eq!(Some("0123abc".to_string()), downstream.commit);
+    assert_eq!(Some("user".to_string()), downstream.triggered_by);
+    assert_eq!(
+        Some(HashMap::from([("ENV".to_string(), "staging".to_string())])),
+        downstream.variables
+    );
+    assert_eq!(PipelineStatus::Defined, downstream.status);
+}
+
+#[tokio::test]
+async fn finalize_registers_downstream_other_project_test() {
+    std::env::set_var("AGENT_MAX_ASSIGNED_JOBS", "1");
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let agent_id = shared::create_agent(&db_client).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+    let other_project_id = shared::create_project(&db_client).await;
+    let downstream_job_id = shared::create_job(&db_client, &other_project_id).await;
+    let _ = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterJobTrigger::new(&job_id, &downstream_job_id, TriggerCondition::OnSuccess),
+    )
+    .await
+    .unwrap();
+    let pipeline_id = create_running_pipeline(&db_client, &job_id, &agent_id).await;
+
+    let result = pipelines::finalize(
+        &db_client,
+        &Credential::System,
+        &pipeline_id,
+        &agent_id,
+        PipelineStatus::Success,
+    )
+    .await;
+    assert!(result.is_ok());
+    let chain = pipelines::get_chain(&db_client, &Credential::System, &pipeline_id).await;
+    let _ = db.stop().await;
+    let chain = chain.unwrap();
+    assert_eq!(2, chain.len());
+    let downstream = &chain[1];
+    assert_eq!(downstream_job_id, downstream.job_id);
+    assert_eq!("master", downstream.branch);
+    assert_eq!(None, downstream.commit);
+}
+
+#[tokio::test]
+async fn finalize_condition_not_met_test() {
+    std::env::set_var("AGENT_MAX_ASSIGNED_JOBS", "1");
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let agent_id = shared::create_agent(&db_client).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+    let downstream_job_id = shared::create_job(&db_client, &project_id).await;
+    let _ = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterJobTrigger::new(&job_id, &downstream_job_id, TriggerCondition::OnSuccess),
+    )
+    .await
+    .unwrap();
+    let pipeline_id = create_running_pipeline(&db_client, &job_id, &agent_id).await;
+
+    let result = pipelines::finalize(
+        &db_client,
+        &Credential::System,
+        &pipeline_id,
+        &agent_id,
+        PipelineStatus::Failure,
+    )
+    .await;
+    assert!(result.is_ok());
+    let chain = pipelines::get_chain(&db_client, &Credential::System, &pipeline_id).await;
+    let _ = db.stop().await;
+    assert_eq!(1, chain.unwrap().len());
+}
+
+#[tokio::test]
+async fn delete_for_job_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+    let downstream_job_id = shared::create_job(&db_client, &project_id).await;
+    let _ = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterJobTrigger::new(&job_id, &downstream_job_id, TriggerCondition::Always),
+    )
+    .await
+    .unwrap();
+
+    let result = service::delete_for_job(&db_client, &Credential::System, &downstream_job_id).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(1, result.unwrap());
+}
@@ -3,7 +3,7 @@ use testcontainers::runners::AsyncRunner;
 use testcontainers_modules::redis::Redis;
 
 use domain::auth::credentials::Credential;
-use domain::jobs::RegisterJob;
+use domain::jobs::{RegisterJob, UpdateJob};
 use rusty_server::services::jobs as service;
 
 use crate::rusty_server::services::shared;
@@ -147,3 +147,75 @@ async fn delete_all_test() {
     assert!(result.is_ok());
     assert_eq!(1, result.unwrap());
 }
+
+#[tokio::test]
+async fn update_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let id = shared::create_job(&db_client, &id).await;
+
+    let result = service::update(
+        &db_client,
+        &Credential::System,
+        &id,
+        UpdateJob {
+            description: Some("updated".to_string()),
+            ..Default::default()
+        },
+    )
+    .await;
+    let job = service::get_by_id(&db_client, &Credential::System, &id, &None, &[]).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(
+        Some("updated".to_string()),
+        job.unwrap().unwrap().description
+    );
+}
+
+#[tokio::test]
+async fn update_invalid_template_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let id = shared::create_job(&db_client, &id).await;
+
+    let result = service::update(
+        &db_client,
+        &Credential::System,
+        &id,
+        UpdateJob {
+            template: Some("invalid".to_string()),
+            ..Default::default()
+        },
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn update_not_found_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+
+    let result = service::update(
+        &db_client,
+        &Credential::System,
+        "57c38e8b-1845-49f1-874a-1eefe9923456",
+        UpdateJob::default(),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
@@ -1,9 +1,22 @@
 mod agents;
+mod badges;
+mod checkouts;
+mod coverage;
+mod deployments;
+mod event_hooks;
+mod job_triggers;
 mod jobs;
+mod notifications;
 mod pipelines;
 mod project_groups;
 mod projects;
+mod registries;
+mod reporters;
+mod revisions;
 mod roles;
+mod schedules;
+mod test_reports;
 mod users;
+mod webhooks;
 
 mod shared;
@@ -0,0 +1,149 @@
+use testcontainers::runners::AsyncRunner;
+use testcontainers_modules::redis::Redis;
+
+use domain::auth::credentials::Credential;
+use domain::notifications::{
+    NotificationChannel, NotificationDelivery, NotificationRule, RegisterNotification,
+};
+use rusty_server::services::notifications as service;
+
+use crate::rusty_server::services::shared;
+use crate::utils::db_connect;
+
+#[tokio::test]
+async fn get_all_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let _ = shared::create_notification(&db_client, &id).await;
+
+    let result = service::get_all(&db_client, &Credential::System, &None, &None).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(1, result.unwrap().len());
+}
+
+#[tokio::test]
+async fn get_by_id_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let id = shared::create_notification(&db_client, &id).await;
+
+    let result = service::get_by_id(&db_client, &Credential::System, &id).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert!(result.clone().unwrap().is_some());
+    assert_eq!(id, result.unwrap().unwrap().id);
+}
+
+#[tokio::test]
+async fn get_deliveries_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let id = shared::create_notification(&db_client, &id).await;
+    let delivery = NotificationDelivery::new(&id, "pipeline", 1, None);
+    let _ = service::record_delivery(&db_client, &delivery).await;
+
+    let result = service::get_deliveries(&db_client, &Credential::System, &id, &None).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(1, result.unwrap().len());
+}
+
+#[tokio::test]
+async fn create_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+
+    let result = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterNotification::new(
+            NotificationChannel::Smtp,
+            "dev@rusty.ops",
+            NotificationRule::OnFailure,
+            &id,
+        ),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn create_invalid_target_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+
+    let result = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterNotification::new(
+            NotificationChannel::Slack,
+            "dev@rusty.ops",
+            NotificationRule::OnFailure,
+            &id,
+        ),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn create_no_project_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+
+    let result = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterNotification::new(
+            NotificationChannel::Webhook,
+            "http://localhost/hook",
+            NotificationRule::EveryRun,
+            "57c38e8b-1845-49f1-874a-1eefe9923456",
+        ),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn delete_by_id_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let id = shared::create_notification(&db_client, &id).await;
+
+    let result = service::delete_by_id(&db_client, &Credential::System, &id).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(1, result.unwrap());
+}
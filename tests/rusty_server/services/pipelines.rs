@@ -62,6 +62,7 @@ async fn create_test() {
         RegisterPipeline {
             job_id: id.to_string(),
             branch: None,
+            commit: None,
         },
     )
     .await;
@@ -83,6 +84,7 @@ async fn create_no_job_test() {
         RegisterPipeline {
             job_id: "57c38e8b-1845-49f1-874a-1eefe9923456".to_string(),
             branch: None,
+            commit: None,
         },
     )
     .await;
@@ -257,6 +259,11 @@ async fn set_running_positive_test() {
                 status: PipelineStatus::Assigned,
                 job_id: id.to_string(),
                 agent_id: Some(agent_id.clone()),
+                commit: None,
+                revision: None,
+                triggered_by: None,
+                upstream_id: None,
+                variables: None,
             }
             .to_value()
             .unwrap(),
@@ -343,6 +350,11 @@ async fn finalize_positive_test() {
                 status: PipelineStatus::InProgress,
                 job_id: id.to_string(),
                 agent_id: Some(agent_id.clone()),
+                commit: None,
+                revision: None,
+                triggered_by: None,
+                upstream_id: None,
+                variables: None,
             }
             .to_value()
             .unwrap(),
@@ -2,7 +2,7 @@ use testcontainers::runners::AsyncRunner;
 use testcontainers_modules::redis::Redis;
 
 use domain::auth::credentials::Credential;
-use domain::projects::RegisterGroup;
+use domain::projects::{RegisterGroup, UpdateGroup};
 use rusty_server::services::project_groups as service;
 
 use crate::rusty_server::services::shared;
@@ -90,3 +90,27 @@ async fn delete_all_test() {
     assert!(result.is_ok());
     assert_eq!(1, result.unwrap());
 }
+
+#[tokio::test]
+async fn update_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project_group(&db_client).await;
+
+    let result = service::update(
+        &db_client,
+        &Credential::System,
+        &id,
+        UpdateGroup {
+            name: Some("renamed".to_string()),
+        },
+    )
+    .await;
+    let group = service::get_by_id(&db_client, &Credential::System, &id, &None, &[]).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!("renamed", group.unwrap().unwrap().name);
+}
@@ -3,7 +3,7 @@ use testcontainers::runners::AsyncRunner;
 use testcontainers_modules::redis::Redis;
 
 use domain::auth::credentials::Credential;
-use domain::projects::RegisterProject;
+use domain::projects::{RegisterProject, UpdateProject};
 use rusty_server::services::projects as service;
 
 use crate::rusty_server::services::shared;
@@ -140,3 +140,74 @@ async fn delete_all_test() {
     assert!(result.is_ok());
     assert_eq!(1, result.unwrap());
 }
+
+#[tokio::test]
+async fn update_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let group_id = shared::create_project_group(&db_client).await;
+    let id = shared::create_project(&db_client).await;
+
+    let result = service::update(
+        &db_client,
+        &Credential::System,
+        &id,
+        UpdateProject {
+            name: Some("renamed".to_string()),
+            group_id: Some(group_id.clone()),
+            ..Default::default()
+        },
+    )
+    .await;
+    let project = service::get_by_id(&db_client, &Credential::System, &id, &None, &[]).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    let project = project.unwrap().unwrap();
+    assert_eq!("renamed", project.name);
+    assert_eq!(Some(group_id), project.group_id);
+}
+
+#[tokio::test]
+async fn update_no_group_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+
+    let result = service::update(
+        &db_client,
+        &Credential::System,
+        &id,
+        UpdateProject {
+            group_id: Some("57c38e8b-1845-49f1-874a-1eefe9923456".to_string()),
+            ..Default::default()
+        },
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn update_not_found_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+
+    let result = service::update(
+        &db_client,
+        &Credential::System,
+        "57c38e8b-1845-49f1-874a-1eefe9923456",
+        UpdateProject::default(),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,149 @@
+use testcontainers::runners::AsyncRunner;
+use testcontainers_modules::redis::Redis;
+
+use domain::auth::credentials::Credential;
+use domain::registries::RegisterRegistryCredential;
+use rusty_server::services::registries as service;
+
+use crate::rusty_server::services::shared;
+use crate::utils::db_connect;
+
+#[tokio::test]
+async fn get_all_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let _ = shared::create_registry_credential(&db_client, &id).await;
+
+    let result = service::get_all(&db_client, &Credential::System, &None, &None).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(1, result.unwrap().len());
+}
+
+#[tokio::test]
+async fn get_by_id_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let id = shared::create_registry_credential(&db_client, &id).await;
+
+    let result = service::get_by_id(&db_client, &Credential::System, &id).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert!(result.clone().unwrap().is_some());
+    assert_eq!(id, result.unwrap().unwrap().id);
+}
+
+#[tokio::test]
+async fn get_auths_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let _ = shared::create_registry_credential(&db_client, &id).await;
+    let other = shared::create_project(&db_client).await;
+
+    let result = service::get_auths(&db_client, &Credential::System, &id).await;
+    let other = service::get_auths(&db_client, &Credential::System, &other).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    assert_eq!(1, result.len());
+    assert_eq!("ghcr.io", result[0].registry);
+    assert_eq!("user", result[0].username);
+    assert_eq!("secret", result[0].password);
+    assert!(other.is_ok());
+    assert!(other.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn create_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+
+    let result = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterRegistryCredential::new("https://GHCR.io/", "user", "secret", &id),
+    )
+    .await;
+    let created = service::get_by_id(&db_client, &Credential::System, &result.clone().unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!("ghcr.io", created.registry);
+}
+
+#[tokio::test]
+async fn create_duplicate_registry_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let _ = shared::create_registry_credential(&db_client, &id).await;
+
+    let result = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterRegistryCredential::new("ghcr.io", "other", "secret", &id),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn create_no_project_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+
+    let result = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterRegistryCredential::new(
+            "ghcr.io",
+            "user",
+            "secret",
+            "57c38e8b-1845-49f1-874a-1eefe9923456",
+        ),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn delete_by_id_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let id = shared::create_registry_credential(&db_client, &id).await;
+
+    let result = service::delete_by_id(&db_client, &Credential::System, &id).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(1, result.unwrap());
+}
@@ -0,0 +1,160 @@
+use testcontainers::runners::AsyncRunner;
+use testcontainers_modules::redis::Redis;
+
+use domain::auth::credentials::Credential;
+use domain::jobs::{RegisterJob, UpdateJob};
+use domain::pipelines::RegisterPipeline;
+use domain::templates::pipeline::PipelineTemplate;
+use domain::templates::revision::DiffKind;
+use rusty_server::services::{jobs, pipelines, revisions as service};
+
+use crate::rusty_server::services::shared;
+use crate::utils::db_connect;
+
+const TEMPLATE_V1: &str = "stages:\n  test:\n    script:\n      - echo \"hello\"\n";
+const TEMPLATE_V2: &str = "stages:\n  test:\n    script:\n      - echo \"world\"\n";
+
+async fn create_job(db_client: &persist::db_client::DbClient) -> String {
+    let id = shared::create_project(db_client).await;
+    jobs::create(
+        db_client,
+        &Credential::System,
+        RegisterJob::new("sample", "", &base64_url::encode(TEMPLATE_V1), &id),
+    )
+    .await
+    .unwrap()
+}
+
+async fn update_template(db_client: &persist::db_client::DbClient, id: &str, template: &str) {
+    let _ = jobs::update(
+        db_client,
+        &Credential::System,
+        id,
+        UpdateJob {
+            template: Some(base64_url::encode(template)),
+            ..Default::default()
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn get_all_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = create_job(&db_client).await;
+    update_template(&db_client, &id, TEMPLATE_V2).await;
+    update_template(&db_client, &id, TEMPLATE_V2).await;
+
+    let result = service::get_all(&db_client, &Credential::System, &id, &None).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    assert_eq!(2, result.len());
+    assert_eq!(2, result[0].number);
+    assert_eq!(1, result[1].number);
+    assert_eq!("SYSTEM", result[0].author);
+}
+
+#[tokio::test]
+async fn get_by_id_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = create_job(&db_client).await;
+    let latest = service::get_latest(&db_client, &id).await.unwrap().unwrap();
+
+    let result = service::get_by_id(&db_client, &Credential::System, &latest.id).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(
+        PipelineTemplate::normalize(TEMPLATE_V1, None).unwrap(),
+        result.unwrap().unwrap().template
+    );
+}
+
+#[tokio::test]
+async fn pipeline_revision_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = create_job(&db_client).await;
+    let first = pipelines::create(&db_client, &Credential::System, RegisterPipeline::new(&id))
+        .await
+        .unwrap();
+    update_template(&db_client, &id, TEMPLATE_V2).await;
+    let second = pipelines::create(&db_client, &Credential::System, RegisterPipeline::new(&id))
+        .await
+        .unwrap();
+
+    let first = pipelines::get_by_id(&db_client, &Credential::System, &first).await;
+    let second = pipelines::get_by_id(&db_client, &Credential::System, &second).await;
+    let revisions = service::get_all(&db_client, &Credential::System, &id, &None).await;
+    let _ = db.stop().await;
+    let revisions = revisions.unwrap();
+    assert_eq!(
+        Some(revisions[1].id.clone()),
+        first.unwrap().unwrap().revision
+    );
+    assert_eq!(
+        Some(revisions[0].id.clone()),
+        second.unwrap().unwrap().revision
+    );
+}
+
+#[tokio::test]
+async fn get_diff_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = create_job(&db_client).await;
+    update_template(&db_client, &id, TEMPLATE_V2).await;
+    let revisions = service::get_all(&db_client, &Credential::System, &id, &None)
+        .await
+        .unwrap();
+
+    let result = service::get_diff(
+        &db_client,
+        &Credential::System,
+        &revisions[1].id,
+        &revisions[0].id,
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    assert_eq!(5, result.len());
+    assert_eq!(
+        1,
+        result
+            .iter()
+            .filter(|l| l.kind == DiffKind::Removed)
+            .count()
+    );
+    assert_eq!(
+        1,
+        result.iter().filter(|l| l.kind == DiffKind::Added).count()
+    );
+}
+
+#[tokio::test]
+async fn get_diff_not_found_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+
+    let result = service::get_diff(&db_client, &Credential::System, "from", "to").await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
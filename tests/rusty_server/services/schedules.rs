@@ -0,0 +1,213 @@
+use testcontainers::runners::AsyncRunner;
+use testcontainers_modules::redis::Redis;
+
+use domain::auth::credentials::Credential;
+use domain::schedules::{RegisterSchedule, UpdateSchedule};
+use rusty_server::services::schedules as service;
+
+use crate::rusty_server::services::shared;
+use crate::utils::db_connect;
+
+#[tokio::test]
+async fn get_all_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let id = shared::create_job(&db_client, &id).await;
+    let _ = shared::create_schedule(&db_client, &id).await;
+
+    let result = service::get_all(&db_client, &Credential::System, &None, &None).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(1, result.unwrap().len());
+}
+
+#[tokio::test]
+async fn get_by_id_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let id = shared::create_job(&db_client, &id).await;
+    let id = shared::create_schedule(&db_client, &id).await;
+
+    let result = service::get_by_id(&db_client, &Credential::System, &id).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert!(result.clone().unwrap().is_some());
+    assert_eq!(id, result.unwrap().unwrap().id);
+}
+
+#[tokio::test]
+async fn create_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let id = shared::create_job(&db_client, &id).await;
+
+    let result = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterSchedule::new("0 2 * * *", "main", &id),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn create_invalid_cron_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let id = shared::create_job(&db_client, &id).await;
+
+    let result = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterSchedule::new("every night", "main", &id),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn create_no_job_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+
+    let result = service::create(
+        &db_client,
+        &Credential::System,
+        RegisterSchedule::new("0 2 * * *", "main", "57c38e8b-1845-49f1-874a-1eefe9923456"),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn update_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let id = shared::create_job(&db_client, &id).await;
+    let id = shared::create_schedule(&db_client, &id).await;
+
+    let result = service::update(
+        &db_client,
+        &Credential::System,
+        &id,
+        UpdateSchedule {
+            cron: Some("*/5 * * * *".to_string()),
+            branch: Some("develop".to_string()),
+            ..Default::default()
+        },
+    )
+    .await;
+    let entry = service::get_by_id(&db_client, &Credential::System, &id).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    let entry = entry.unwrap().unwrap();
+    assert_eq!("*/5 * * * *", entry.cron);
+    assert_eq!(Some("develop".to_string()), entry.branch);
+    assert!(entry.skip_if_running);
+}
+
+#[tokio::test]
+async fn update_invalid_cron_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let id = shared::create_job(&db_client, &id).await;
+    let id = shared::create_schedule(&db_client, &id).await;
+
+    let result = service::update(
+        &db_client,
+        &Credential::System,
+        &id,
+        UpdateSchedule {
+            cron: Some("every day".to_string()),
+            ..Default::default()
+        },
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn update_not_found_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+
+    let result = service::update(
+        &db_client,
+        &Credential::System,
+        "57c38e8b-1845-49f1-874a-1eefe9923456",
+        UpdateSchedule::default(),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn set_last_run_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let id = shared::create_job(&db_client, &id).await;
+    let id = shared::create_schedule(&db_client, &id).await;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let result = service::set_last_run(&db_client, &Credential::System, &id, &now).await;
+    let entry = service::get_by_id(&db_client, &Credential::System, &id).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(Some(now), entry.unwrap().unwrap().last_run);
+}
+
+#[tokio::test]
+async fn delete_by_id_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = shared::create_project(&db_client).await;
+    let id = shared::create_job(&db_client, &id).await;
+    let id = shared::create_schedule(&db_client, &id).await;
+
+    let result = service::delete_by_id(&db_client, &Credential::System, &id).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(1, result.unwrap());
+}
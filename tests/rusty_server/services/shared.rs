@@ -1,7 +1,16 @@
 use domain::agents::Agent;
+use domain::checkouts::CheckoutSettings;
+use domain::coverage::{CoverageReport, FileCoverage};
+use domain::deployments::Deployment;
 use domain::jobs::Job;
+use domain::notifications::{Notification, NotificationChannel, NotificationRule};
 use domain::pipelines::{Pipeline, PipelineStatus};
 use domain::projects::{Group, Project};
+use domain::registries::RegistryCredential;
+use domain::reporters::StatusReporter;
+use domain::schedules::Schedule;
+use domain::test_reports::{TestCase, TestReport, TestStatus, TestSuite};
+use domain::webhooks::Forge;
 use domain::RustyDomainItem;
 use persist::db_client::DbClient;
 use std::collections::HashMap;
@@ -105,6 +114,217 @@ pub(crate) async fn create_pipeline(db_client: &DbClient, id: &str) -> String {
                 status: PipelineStatus::Defined,
                 job_id: id.to_string(),
                 agent_id: None,
+                commit: None,
+                revision: None,
+                triggered_by: None,
+                upstream_id: None,
+                variables: None,
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+pub(crate) async fn create_coverage_report(
+    db_client: &DbClient,
+    project_id: &str,
+    job_id: &str,
+    pipeline_id: &str,
+    pipeline_number: u64,
+    branch: &str,
+    covered: u64,
+) -> String {
+    db_client
+        .create(
+            "coverage_reports",
+            &CoverageReport {
+                id: uuid::Uuid::new_v4().to_string(),
+                date: chrono::Utc::now().to_rfc3339(),
+                stage: "test".to_string(),
+                branch: branch.to_string(),
+                commit: None,
+                lines: 100,
+                covered,
+                percent: covered as f64,
+                files: vec![FileCoverage {
+                    path: "src/lib.rs".to_string(),
+                    lines: 100,
+                    covered,
+                }],
+                pipeline_id: pipeline_id.to_string(),
+                pipeline_number,
+                job_id: job_id.to_string(),
+                project_id: project_id.to_string(),
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+pub(crate) async fn create_deployment(
+    db_client: &DbClient,
+    project_id: &str,
+    job_id: &str,
+    pipeline_id: &str,
+) -> String {
+    db_client
+        .create(
+            "deployments",
+            &Deployment {
+                id: uuid::Uuid::new_v4().to_string(),
+                environment: "staging".to_string(),
+                url: None,
+                date: chrono::Utc::now().to_rfc3339(),
+                branch: "master".to_string(),
+                commit: Some("0123abc".to_string()),
+                stage: "deploy".to_string(),
+                deployed_by: Some("SYSTEM".to_string()),
+                pipeline_id: pipeline_id.to_string(),
+                pipeline_number: 1,
+                job_id: job_id.to_string(),
+                project_id: project_id.to_string(),
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+pub(crate) async fn create_test_report(
+    db_client: &DbClient,
+    project_id: &str,
+    job_id: &str,
+    pipeline_number: u64,
+    status: TestStatus,
+) -> String {
+    db_client
+        .create(
+            "test_reports",
+            &TestReport {
+                id: uuid::Uuid::new_v4().to_string(),
+                date: chrono::Utc::now().to_rfc3339(),
+                stage: "test".to_string(),
+                branch: "master".to_string(),
+                tests: 1,
+                failures: u64::from(status.is_failure()),
+                skipped: 0,
+                suites: vec![TestSuite {
+                    name: "suite".to_string(),
+                    duration: 0.5,
+                    cases: vec![TestCase {
+                        name: "test".to_string(),
+                        classname: None,
+                        duration: 0.5,
+                        status,
+                        message: None,
+                    }],
+                }],
+                pipeline_id: format!("pipeline-{pipeline_number}"),
+                pipeline_number,
+                job_id: job_id.to_string(),
+                project_id: project_id.to_string(),
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+pub(crate) async fn create_schedule(db_client: &DbClient, id: &str) -> String {
+    db_client
+        .create(
+            "schedules",
+            &Schedule {
+                id: uuid::Uuid::new_v4().to_string(),
+                cron: "0 2 * * *".to_string(),
+                branch: None,
+                skip_if_running: true,
+                register_date: "2024-01-01T00:00:00+00:00".to_string(),
+                last_run: None,
+                job_id: id.to_string(),
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+pub(crate) async fn create_status_reporter(db_client: &DbClient, id: &str) -> String {
+    db_client
+        .create(
+            "status_reporters",
+            &StatusReporter {
+                id: uuid::Uuid::new_v4().to_string(),
+                forge: Forge::GitHub,
+                api_url: None,
+                token: "token".to_string(),
+                project_id: id.to_string(),
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+pub(crate) async fn create_registry_credential(db_client: &DbClient, id: &str) -> String {
+    db_client
+        .create(
+            "registry_credentials",
+            &RegistryCredential {
+                id: uuid::Uuid::new_v4().to_string(),
+                registry: "ghcr.io".to_string(),
+                username: "user".to_string(),
+                password: "secret".to_string(),
+                project_id: id.to_string(),
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+pub(crate) async fn create_notification(db_client: &DbClient, id: &str) -> String {
+    db_client
+        .create(
+            "notifications",
+            &Notification {
+                id: uuid::Uuid::new_v4().to_string(),
+                channel: NotificationChannel::Webhook,
+                target: "http://localhost/hook".to_string(),
+                rule: NotificationRule::EveryRun,
+                branch: None,
+                project_id: id.to_string(),
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+pub(crate) async fn create_checkout_settings(db_client: &DbClient, id: &str) -> String {
+    db_client
+        .create(
+            "checkout_settings",
+            &CheckoutSettings {
+                id: uuid::Uuid::new_v4().to_string(),
+                depth: Some(1),
+                submodules: None,
+                lfs: None,
+                sparse_paths: None,
+                ssh_key: None,
+                username: None,
+                token: Some("secret".to_string()),
+                project_id: id.to_string(),
             }
             .to_value()
             .unwrap(),
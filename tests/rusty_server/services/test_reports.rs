@@ -0,0 +1,203 @@
+use serde_json::json;
+use std::collections::HashMap;
+use testcontainers::runners::AsyncRunner;
+use testcontainers_modules::redis::Redis;
+
+use domain::auth::credentials::Credential;
+use domain::pipelines::{Pipeline, PipelineStatus};
+use domain::test_reports::{TestCase, TestStatus, TestSuite};
+use domain::RustyDomainItem;
+use persist::db_client::DbClient;
+use rusty_server::services::test_reports as service;
+
+use crate::rusty_server::services::shared;
+use crate::utils::db_connect;
+
+async fn create_running_pipeline(db_client: &DbClient, job_id: &str, agent_id: &str) -> String {
+    db_client
+        .create(
+            "pipelines",
+            &Pipeline {
+                id: uuid::Uuid::new_v4().to_string(),
+                number: 1,
+                branch: "master".to_string(),
+                register_date: "now".to_string(),
+                start_date: None,
+                end_date: None,
+                stage_status: HashMap::new(),
+                status: PipelineStatus::InProgress,
+                job_id: job_id.to_string(),
+                agent_id: Some(agent_id.to_string()),
+                commit: None,
+                revision: None,
+                triggered_by: None,
+                upstream_id: None,
+                variables: None,
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+fn suites() -> Vec<TestSuite> {
+    vec![TestSuite {
+        name: "api".to_string(),
+        duration: 1.5,
+        cases: vec![
+            TestCase {
+                name: "get".to_string(),
+                classname: None,
+                duration: 0.5,
+                status: TestStatus::Passed,
+                message: None,
+            },
+            TestCase {
+                name: "create".to_string(),
+                classname: None,
+                duration: 1.0,
+                status: TestStatus::Failed,
+                message: Some("expected 201".to_string()),
+            },
+        ],
+    }]
+}
+
+#[tokio::test]
+async fn get_all_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+    let _ =
+        shared::create_test_report(&db_client, &project_id, &job_id, 1, TestStatus::Passed).await;
+
+    let result = service::get_all(&db_client, &Credential::System, &None, &None).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(1, result.unwrap().len());
+}
+
+#[tokio::test]
+async fn create_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let agent_id = shared::create_agent(&db_client).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+    let pipeline_id = create_running_pipeline(&db_client, &job_id, &agent_id).await;
+
+    let result = service::create(
+        &db_client,
+        &Credential::System,
+        &pipeline_id,
+        &agent_id,
+        "test",
+        suites(),
+    )
+    .await;
+    assert!(result.is_ok());
+    let failures = service::get_failures(&db_client, &Credential::System, &pipeline_id).await;
+    let slowest =
+        service::get_slowest(&db_client, &Credential::System, &pipeline_id, Some(1)).await;
+    let _ = db.stop().await;
+    let failures = failures.unwrap();
+    assert_eq!(1, failures.len());
+    assert_eq!("create", failures[0].name);
+    assert_eq!(Some("expected 201".to_string()), failures[0].message);
+    assert_eq!("test", failures[0].stage);
+    let slowest = slowest.unwrap();
+    assert_eq!(1, slowest.len());
+    assert_eq!("create", slowest[0].name);
+}
+
+#[tokio::test]
+async fn create_wrong_agent_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let agent_id = shared::create_agent(&db_client).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+    let pipeline_id = create_running_pipeline(&db_client, &job_id, &agent_id).await;
+
+    let result = service::create(
+        &db_client,
+        &Credential::System,
+        &pipeline_id,
+        "other-agent",
+        "test",
+        suites(),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn get_flaky_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+    for (number, status) in [
+        (1, TestStatus::Failed),
+        (2, TestStatus::Passed),
+        (3, TestStatus::Passed),
+        (4, TestStatus::Failed),
+    ] {
+        let _ = shared::create_test_report(&db_client, &project_id, &job_id, number, status).await;
+    }
+
+    let all = service::get_flaky(&db_client, &Credential::System, &job_id, None).await;
+    let latest = service::get_flaky(&db_client, &Credential::System, &job_id, Some(2)).await;
+    let passing = service::get_flaky(&db_client, &Credential::System, &job_id, Some(1)).await;
+    let _ = db.stop().await;
+    let all = all.unwrap();
+    assert_eq!(1, all.len());
+    assert_eq!(2, all[0].passes);
+    assert_eq!(2, all[0].failures);
+    assert_eq!("pipeline-4", all[0].last_failure);
+    let latest = latest.unwrap();
+    assert_eq!(1, latest.len());
+    assert_eq!(1, latest[0].passes);
+    assert_eq!(1, latest[0].failures);
+    assert!(passing.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn delete_many_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let project_id = shared::create_project(&db_client).await;
+    let job_id = shared::create_job(&db_client, &project_id).await;
+    let _ =
+        shared::create_test_report(&db_client, &project_id, &job_id, 1, TestStatus::Passed).await;
+    let _ =
+        shared::create_test_report(&db_client, &project_id, &job_id, 2, TestStatus::Failed).await;
+
+    let result = service::delete_many(
+        &db_client,
+        &Credential::System,
+        &json!({ "project_id": { "equals": &project_id } }),
+    )
+    .await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(2, result.unwrap());
+}
@@ -0,0 +1,146 @@
+use serde_json::Value;
+use testcontainers::runners::AsyncRunner;
+use testcontainers_modules::redis::Redis;
+
+use domain::auth::credentials::Credential;
+use domain::jobs::Job;
+use domain::projects::Project;
+use domain::webhooks::{Forge, WebhookEvent};
+use domain::RustyDomainItem;
+use persist::db_client::DbClient;
+use rusty_server::services::{pipelines, webhooks as service};
+
+use crate::utils::db_connect;
+
+const TEMPLATE: &str = r#"
+trigger:
+  events:
+    - push
+    - pull_request
+  branches:
+    - master
+    - feature/*
+stages:
+  test:
+    script:
+      - echo "hello"
+"#;
+
+const TEMPLATE_MAIN_ONLY: &str = r#"
+stages:
+  test:
+    script:
+      - echo "hello"
+"#;
+
+fn payload(name: &str) -> Value {
+    let path = format!(
+        "{}/resources/webhooks/{name}.json",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+async fn create_project(db_client: &DbClient, url: &str) -> String {
+    db_client
+        .create(
+            "projects",
+            &Project {
+                id: uuid::Uuid::new_v4().to_string(),
+                name: "sample".to_string(),
+                url: Some(url.to_string()),
+                main_branch: "master".to_string(),
+                group_id: None,
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+async fn create_job(db_client: &DbClient, id: &str, template: &str) -> String {
+    db_client
+        .create(
+            "jobs",
+            &Job {
+                id: uuid::Uuid::new_v4().to_string(),
+                name: "sample".to_string(),
+                description: None,
+                template: base64_url::encode(template),
+                project_id: id.to_string(),
+            }
+            .to_value()
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn trigger_push_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = create_project(&db_client, "git@github.com:rusty-ops/sample.git").await;
+    let _ = create_job(&db_client, &id, TEMPLATE).await;
+    let _ = create_job(&db_client, &id, TEMPLATE_MAIN_ONLY).await;
+    let other = create_project(&db_client, "https://github.com/rusty-ops/other").await;
+    let _ = create_job(&db_client, &other, TEMPLATE).await;
+
+    let event = WebhookEvent::parse(Forge::GitHub, "push", &payload("github_push")).unwrap();
+    let result = service::trigger(&db_client, &event).await;
+    let entries = pipelines::get_all(&db_client, &Credential::System, &None, &None).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(2, result.unwrap().len());
+    let entries = entries.unwrap();
+    assert_eq!(2, entries.len());
+    assert!(entries.iter().all(|p| p.branch == "master"));
+}
+
+#[tokio::test]
+async fn trigger_pull_request_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = create_project(&db_client, "https://gitlab.com/rusty-ops/sample").await;
+    let _ = create_job(&db_client, &id, TEMPLATE).await;
+    let _ = create_job(&db_client, &id, TEMPLATE_MAIN_ONLY).await;
+
+    let event = WebhookEvent::parse(
+        Forge::GitLab,
+        "Merge Request Hook",
+        &payload("gitlab_merge_request"),
+    )
+    .unwrap();
+    let result = service::trigger(&db_client, &event).await;
+    let entries = pipelines::get_all(&db_client, &Credential::System, &None, &None).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert_eq!(1, result.unwrap().len());
+    let entries = entries.unwrap();
+    assert_eq!(1, entries.len());
+    assert_eq!("feature/test", entries[0].branch);
+}
+
+#[tokio::test]
+async fn trigger_no_project_test() {
+    let db = Redis
+        .start()
+        .await
+        .expect("initializing test container failed");
+    let db_client = db_connect(&db, "redis", 6379).await;
+    let id = create_project(&db_client, "https://github.com/rusty-ops/other").await;
+    let _ = create_job(&db_client, &id, TEMPLATE).await;
+
+    let event = WebhookEvent::parse(Forge::Gitea, "push", &payload("gitea_push")).unwrap();
+    let result = service::trigger(&db_client, &event).await;
+    let _ = db.stop().await;
+    assert!(result.is_ok());
+    assert!(result.unwrap().is_empty());
+}
@@ -0,0 +1,45 @@
+use axum::http::{HeaderMap, HeaderValue};
+use rstest::rstest;
+
+use commons::hashing::sha::hmac256;
+use domain::webhooks::Forge;
+use rusty_server::webhooks::verify_signature;
+
+const SECRET: &str = "webhook_secret";
+const BODY: &[u8] = br#"{"ref":"refs/heads/master"}"#;
+
+fn headers(name: &'static str, value: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(name, HeaderValue::from_str(value).unwrap());
+    headers
+}
+
+#[rstest]
+#[case(Forge::GitHub, "X-Hub-Signature-256", format!("sha256={}", hmac256(SECRET, BODY).unwrap()), true)]
+#[case(Forge::GitHub, "X-Hub-Signature-256", hmac256(SECRET, BODY).unwrap(), false)]
+#[case(Forge::GitHub, "X-Hub-Signature-256", format!("sha256={}", hmac256("other", BODY).unwrap()), false)]
+#[case(Forge::GitHub, "X-Gitea-Signature", format!("sha256={}", hmac256(SECRET, BODY).unwrap()), false)]
+#[case(Forge::Gitea, "X-Gitea-Signature", hmac256(SECRET, BODY).unwrap(), true)]
+#[case(Forge::Gitea, "X-Gitea-Signature", hmac256("other", BODY).unwrap(), false)]
+#[case(Forge::GitLab, "X-Gitlab-Token", SECRET.to_string(), true)]
+#[case(Forge::GitLab, "X-Gitlab-Token", "webhook_secreT".to_string(), false)]
+#[case(Forge::GitLab, "X-Gitlab-Token", "other".to_string(), false)]
+fn verify_signature_test(
+    #[case] forge: Forge,
+    #[case] header: &'static str,
+    #[case] value: String,
+    #[case] expected: bool,
+) {
+    let headers = headers(header, &value);
+    assert_eq!(expected, verify_signature(forge, &headers, BODY, SECRET));
+}
+
+#[test]
+fn verify_signature_missing_header_test() {
+    assert!(!verify_signature(
+        Forge::GitHub,
+        &HeaderMap::new(),
+        BODY,
+        SECRET
+    ));
+}